@@ -0,0 +1,29 @@
+use super::Ident;
+use lc_lex::TokenGroup;
+use lc_span::{sym, Span, Symbol};
+
+/// an outer attribute `#[<ident>]`, `#[<ident>(<token-trees>)]` or `#[<ident> = "<value>"]`
+#[derive(Debug, PartialEq, Clone)]
+pub struct Attribute {
+    pub span: Span,
+    pub ident: Ident,
+    pub args: AttrArgs,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum AttrArgs {
+    Empty,
+    /// the delimited arguments to the attribute
+    Delimited(TokenGroup),
+    /// the string value assigned to the attribute (without its quotes)
+    Eq(Ident),
+}
+
+/// the attributes understood by the compiler, any other attribute is an error
+pub const BUILTIN_ATTRIBUTES: &[Symbol] = &[sym::cfg, sym::derive, sym::path];
+
+impl Attribute {
+    pub fn is_builtin(&self) -> bool {
+        BUILTIN_ATTRIBUTES.contains(&self.ident.symbol)
+    }
+}
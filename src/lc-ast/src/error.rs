@@ -1,7 +1,12 @@
+use crate::Ident;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum AstError {
     #[error("functions must have a body")]
     FunctionWithoutBody,
+    #[error("type aliases must have a type")]
+    TypeAliasWithoutType,
+    #[error("cannot find attribute `{0}` in this scope")]
+    UnknownAttribute(Ident),
 }
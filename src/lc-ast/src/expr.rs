@@ -13,7 +13,8 @@ pub struct Expr {
 /// this is only used to make expr `takeable`
 impl Default for Expr {
     fn default() -> Self {
-        Self { span: Span::default(), id: NodeId::new(0), kind: ExprKind::Lit(Lit::Int(0)) }
+        let kind = ExprKind::Lit(Lit::Int(0, LitIntTy::Unsuffixed));
+        Self { span: Span::default(), id: NodeId::new(0), kind }
     }
 }
 
@@ -34,12 +35,17 @@ impl Expr {
             | ExprKind::Paren(..)
             | ExprKind::Path(..)
             | ExprKind::Tuple(..)
+            | ExprKind::Array(..)
+            | ExprKind::Repeat(..)
             | ExprKind::Ret(..)
             | ExprKind::Assign(..)
+            | ExprKind::AssignOp(..)
             | ExprKind::Closure(..)
             | ExprKind::Call(..)
+            | ExprKind::MethodCall(..)
             | ExprKind::Struct(..)
             | ExprKind::Field(..)
+            | ExprKind::Index(..)
             | ExprKind::Macro(..)
             | ExprKind::Err
             | ExprKind::Break
@@ -77,13 +83,23 @@ pub enum ExprKind {
     While(P<Expr>, P<Block>),
     Path(Path),
     Tuple(Vec<P<Expr>>),
+    /// [x, y, z]
+    Array(Vec<P<Expr>>),
+    /// [x; n]
+    Repeat(P<Expr>, usize),
     Ret(Option<P<Expr>>),
     Assign(P<Expr>, P<Expr>),
+    /// compound assignment `l op= r`
+    AssignOp(BinOp, P<Expr>, P<Expr>),
     Closure(Option<Ident>, FnSig, P<Expr>),
     Call(P<Expr>, Vec<P<Expr>>),
+    /// `receiver.method(args)`
+    MethodCall(P<Expr>, Ident, Vec<P<Expr>>),
     If(P<Expr>, P<Block>, Option<P<Expr>>),
     Struct(Path, Vec<Field>),
     Field(P<Expr>, Ident),
+    /// `base[index]`
+    Index(P<Expr>, P<Expr>),
     Match(P<Expr>, Vec<Arm>),
     Macro(Path, TokenGroup),
     Break,
@@ -100,12 +116,18 @@ impl Display for ExprKind {
             Self::Unary(op, expr) => write!(fmt, "({}{})", op, expr),
             Self::Paren(expr) => write!(fmt, "({})", expr),
             Self::Assign(l, r) => write!(fmt, "{} = {}", l, r),
+            Self::AssignOp(op, l, r) => write!(fmt, "{} {}= {}", l, op, r),
             Self::Block(block) => write!(fmt, "{}", block),
             Self::Path(path) => write!(fmt, "{}", path),
             Self::Tuple(xs) => write!(fmt, "({})", lc_util::join(xs, ",")),
+            Self::Array(xs) => write!(fmt, "[{}]", lc_util::join(xs, ",")),
+            Self::Repeat(x, n) => write!(fmt, "[{}; {}]", x, n),
             Self::Call(f, args) => write!(fmt, "({} {})", f, lc_util::join(args, " ")),
+            Self::MethodCall(receiver, ident, args) =>
+                write!(fmt, "({}.{} {})", receiver, ident, lc_util::join(args, " ")),
             Self::Struct(_path, _fields) => todo!(),
             Self::Field(expr, ident) => write!(fmt, "{}.{}", expr, ident),
+            Self::Index(base, index) => write!(fmt, "{}[{}]", base, index),
             Self::While(expr, block) => write!(fmt, "while {} {}", expr, block),
             Self::Loop(block) => write!(fmt, "loop {}", block),
             Self::Closure(name, sig, body) => match name {
@@ -8,6 +8,7 @@ use std::fmt::{self, Display, Formatter};
 pub struct Item<K = ItemKind> {
     pub span: Span,
     pub id: NodeId,
+    pub attrs: Vec<Attribute>,
     pub vis: Visibility,
     pub ident: Ident,
     pub kind: K,
@@ -41,11 +42,12 @@ pub enum ItemKind {
     /// extern "<abi>" {}
     Extern(Abi, Vec<P<ForeignItem>>),
     /// type T = S;
-    TypeAlias(Generics, P<Ty>),
+    /// (the type may only be omitted for associated types in traits)
+    TypeAlias(Generics, Option<P<Ty>>),
     /// mod foo;
     Mod(Module),
     /// use some::path;
-    Use(Path),
+    Use(UseTree),
     Trait {
         generics: Generics,
         items: Vec<P<TraitItem>>,
@@ -91,6 +93,9 @@ impl ItemKind {
 #[derive(Debug, PartialEq, Clone)]
 pub enum AssocItemKind {
     Fn(FnSig, Generics, Option<P<Expr>>),
+    /// type T;
+    /// type T = S;
+    TyAlias(Generics, Option<P<Ty>>),
 }
 
 impl TryFrom<ItemKind> for AssocItemKind {
@@ -99,7 +104,7 @@ impl TryFrom<ItemKind> for AssocItemKind {
     fn try_from(kind: ItemKind) -> Result<Self, Self::Error> {
         match kind {
             ItemKind::Fn(sig, generics, expr) => Ok(Self::Fn(sig, generics, expr)),
-            ItemKind::TypeAlias(..) => todo!("assoc types not impl"),
+            ItemKind::TypeAlias(generics, ty) => Ok(Self::TyAlias(generics, ty)),
             ItemKind::Use(..)
             | ItemKind::Mod(..)
             | ItemKind::Extern(..)
@@ -148,12 +153,17 @@ impl Display for Item {
                 body.as_ref().unwrap()
             ),
             ItemKind::Macro(m) => write!(f, "{} macro {}, {}", self.vis.node, self.ident, m),
-            ItemKind::TypeAlias(generics, ty) =>
-                write!(f, "{} type {}<{}> = {}", self.vis.node, self.ident, generics, ty),
+            ItemKind::TypeAlias(generics, ty) => {
+                write!(f, "{} type {}<{}>", self.vis.node, self.ident, generics)?;
+                match ty {
+                    Some(ty) => write!(f, " = {}", ty),
+                    None => Ok(()),
+                }
+            }
             ItemKind::Enum(_generics, _variants) => todo!(),
             ItemKind::Struct(_generics, _variant_kind) => todo!(),
             ItemKind::Extern(..) => todo!(),
-            ItemKind::Use(path) => write!(f, "use {}", path),
+            ItemKind::Use(tree) => write!(f, "use {}", tree),
             ItemKind::Mod(..) => todo!(),
             ItemKind::Impl { .. } => todo!(),
             ItemKind::Trait { .. } => todo!(),
@@ -218,3 +228,44 @@ impl Display for MacroRule {
         todo!()
     }
 }
+
+/// `a::b`, `a::b as c`, `a::*` or `a::{<use-tree>, ...}`
+/// the prefix of a glob or nested use tree may be empty (e.g. within `a::{*, b}`)
+#[derive(Debug, PartialEq, Clone)]
+pub struct UseTree {
+    pub span: Span,
+    pub prefix: Path,
+    pub kind: UseTreeKind,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum UseTreeKind {
+    /// imports the last segment of the prefix, optionally renaming it
+    Simple(Option<Ident>),
+    Glob,
+    Nested(Vec<UseTree>),
+}
+
+impl UseTree {
+    /// the name a simple import binds
+    pub fn ident(&self) -> Option<Ident> {
+        match self.kind {
+            UseTreeKind::Simple(rename) =>
+                rename.or_else(|| self.prefix.segments.last().map(|segment| segment.ident)),
+            UseTreeKind::Glob | UseTreeKind::Nested(..) => None,
+        }
+    }
+}
+
+impl Display for UseTree {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.prefix)?;
+        let sep = if self.prefix.segments.is_empty() { "" } else { "::" };
+        match &self.kind {
+            UseTreeKind::Simple(Some(rename)) => write!(f, " as {}", rename),
+            UseTreeKind::Simple(None) => Ok(()),
+            UseTreeKind::Glob => write!(f, "{}*", sep),
+            UseTreeKind::Nested(trees) => write!(f, "{}{{{}}}", sep, lc_util::join(trees, ", ")),
+        }
+    }
+}
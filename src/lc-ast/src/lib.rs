@@ -1,18 +1,22 @@
 #[macro_use]
 extern crate serde;
 
+mod attr;
 mod error;
 mod expr;
 mod item;
+mod mut_visit;
 mod pattern;
 mod prog;
 mod stmt;
 mod ty;
 mod visit;
 
+pub use attr::*;
 pub use error::*;
 pub use expr::*;
 pub use item::*;
+pub use mut_visit::*;
 pub use pattern::*;
 pub use prog::Ast;
 pub use stmt::*;
@@ -38,6 +42,7 @@ newtype_index!(
 pub struct Arm {
     pub id: NodeId,
     pub span: Span,
+    pub attrs: Vec<Attribute>,
     pub pat: P<Pattern>,
     pub body: P<Expr>,
     pub guard: Option<P<Expr>>,
@@ -73,6 +78,7 @@ pub struct Field {
 pub struct FieldDecl {
     pub id: NodeId,
     pub span: Span,
+    pub attrs: Vec<Attribute>,
     pub vis: Visibility,
     pub ident: Option<Ident>,
     pub ty: P<Ty>,
@@ -183,11 +189,17 @@ impl Display for Block {
 pub struct Generics {
     pub span: Span,
     pub params: Vec<TyParam>,
+    /// the predicates of the `where` clause
+    pub predicates: Vec<WherePredicate>,
 }
 
 impl Display for Generics {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", lc_util::join(&self.params, ","))
+        write!(f, "{}", lc_util::join(&self.params, ","))?;
+        if !self.predicates.is_empty() {
+            write!(f, " where {}", lc_util::join(&self.predicates, ", "))?;
+        }
+        Ok(())
     }
 }
 
@@ -196,12 +208,33 @@ pub struct TyParam {
     pub span: Span,
     pub id: NodeId,
     pub ident: Ident,
+    /// the trait bounds on the parameter
+    /// (i.e. the `Trait`s in `T: Trait + Trait`)
+    pub bounds: Vec<Path>,
     pub default: Option<P<Ty>>,
 }
 
 impl Display for TyParam {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.ident)
+        write!(f, "{}", self.ident)?;
+        if !self.bounds.is_empty() {
+            write!(f, ": {}", lc_util::join(&self.bounds, " + "))?;
+        }
+        Ok(())
+    }
+}
+
+/// `<ty>: <bound> + <bound>` within a `where` clause
+#[derive(Debug, PartialEq, Clone)]
+pub struct WherePredicate {
+    pub span: Span,
+    pub ty: P<Ty>,
+    pub bounds: Vec<Path>,
+}
+
+impl Display for WherePredicate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.ty, lc_util::join(&self.bounds, " + "))
     }
 }
 
@@ -310,17 +343,58 @@ impl Display for PathSegment {
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Lit {
-    Float(f64),
-    Int(i64),
+    Float(f64, LitFloatTy),
+    /// integer literals are never negative (negation is a unary operator)
+    Int(u64, LitIntTy),
     Bool(bool),
+    Char(char),
+    /// the (unescaped) contents of a string literal
+    Str(Symbol),
+}
+
+/// the type of an integer literal as specified by its suffix
+/// `12u8` => `Unsigned(U8)`
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum LitIntTy {
+    Signed(IntTy),
+    Unsigned(UintTy),
+    Unsuffixed,
+}
+
+/// the type of a float literal as specified by its suffix
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum LitFloatTy {
+    Suffixed(FloatTy),
+    Unsuffixed,
+}
+
+impl Display for LitIntTy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LitIntTy::Signed(int_ty) => write!(f, "{}", int_ty),
+            LitIntTy::Unsigned(uint_ty) => write!(f, "{}", uint_ty),
+            LitIntTy::Unsuffixed => Ok(()),
+        }
+    }
+}
+
+impl Display for LitFloatTy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LitFloatTy::Suffixed(float_ty) => write!(f, "{}", float_ty),
+            LitFloatTy::Unsuffixed => Ok(()),
+        }
+    }
 }
 
 impl Display for Lit {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Float(d) => write!(f, "{}", d),
-            Self::Int(i) => write!(f, "{}", i),
+            Self::Float(d, suffix) => write!(f, "{}{}", d, suffix),
+            Self::Int(i, suffix) => write!(f, "{}{}", i, suffix),
             Self::Bool(b) => write!(f, "{}", b),
+            Self::Char(c) => write!(f, "{:?}", c),
+            Self::Str(s) => write!(f, "{:?}", s.as_str()),
         }
     }
 }
@@ -329,16 +403,45 @@ impl Display for Lit {
 pub enum BinOp {
     Mul,
     Div,
+    /// remainder
+    Rem,
     Add,
     Sub,
+    /// shift left
+    Shl,
+    /// shift right (arithmetic for signed integers, logical for unsigned integers)
+    Shr,
     Lt,
     Gt,
+    Le,
+    Ge,
     Eq,
     Neq,
     /// bitwise and
     And,
     /// bitwise or
     Or,
+    /// bitwise xor
+    Xor,
+    /// short-circuiting logical and
+    AndAnd,
+    /// short-circuiting logical or
+    OrOr,
+}
+
+impl BinOp {
+    /// whether the right operand is only evaluated depending on the value of the left
+    pub fn is_short_circuiting(self) -> bool {
+        matches!(self, BinOp::AndAnd | BinOp::OrOr)
+    }
+
+    pub fn is_comparison(self) -> bool {
+        matches!(self, BinOp::Lt | BinOp::Gt | BinOp::Le | BinOp::Ge | BinOp::Eq | BinOp::Neq)
+    }
+
+    pub fn is_shift(self) -> bool {
+        matches!(self, BinOp::Shl | BinOp::Shr)
+    }
 }
 
 impl Display for BinOp {
@@ -346,28 +449,22 @@ impl Display for BinOp {
         match self {
             BinOp::Mul => write!(f, "*"),
             BinOp::Div => write!(f, "/"),
+            BinOp::Rem => write!(f, "%"),
             BinOp::Add => write!(f, "+"),
             BinOp::Sub => write!(f, "-"),
+            BinOp::Shl => write!(f, "<<"),
+            BinOp::Shr => write!(f, ">>"),
             BinOp::Lt => write!(f, "<"),
             BinOp::Gt => write!(f, ">"),
+            BinOp::Le => write!(f, "<="),
+            BinOp::Ge => write!(f, ">="),
             BinOp::Or => write!(f, "|"),
             BinOp::And => write!(f, "&"),
+            BinOp::Xor => write!(f, "^"),
             BinOp::Eq => write!(f, "=="),
             BinOp::Neq => write!(f, "!="),
-        }
-    }
-}
-
-impl From<Token> for BinOp {
-    fn from(t: Token) -> Self {
-        match t.kind {
-            TokenKind::Plus => Self::Add,
-            TokenKind::Minus => Self::Sub,
-            TokenKind::Star => Self::Mul,
-            TokenKind::Slash => Self::Div,
-            TokenKind::Gt => Self::Gt,
-            TokenKind::Lt => Self::Lt,
-            k => panic!("invalid binary operator `{:?}`", k),
+            BinOp::AndAnd => write!(f, "&&"),
+            BinOp::OrOr => write!(f, "||"),
         }
     }
 }
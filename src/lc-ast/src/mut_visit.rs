@@ -0,0 +1,188 @@
+use super::*;
+
+/// mutable traversal of the ast; each function can be overridden.
+/// by default, just recursively visits each substructure.
+/// this is used by passes that rewrite the ast in place (i.e. macro expansion)
+pub trait MutVisitor: Sized {
+    fn visit_ast(&mut self, ast: &mut Ast) {
+        walk_ast_mut(self, ast);
+    }
+
+    fn visit_module(&mut self, module: &mut Module) {
+        walk_module_mut(self, module);
+    }
+
+    fn visit_item(&mut self, item: &mut Item) {
+        walk_item_mut(self, item);
+    }
+
+    fn visit_assoc_item(&mut self, item: &mut AssocItem) {
+        walk_assoc_item_mut(self, item);
+    }
+
+    fn visit_fn(&mut self, sig: &mut FnSig, body: Option<&mut Expr>) {
+        walk_fn_mut(self, sig, body);
+    }
+
+    fn visit_fn_sig(&mut self, sig: &mut FnSig) {
+        walk_fn_sig_mut(self, sig);
+    }
+
+    fn visit_block(&mut self, block: &mut Block) {
+        walk_block_mut(self, block);
+    }
+
+    fn visit_stmt(&mut self, stmt: &mut Stmt) {
+        walk_stmt_mut(self, stmt);
+    }
+
+    fn visit_let(&mut self, l: &mut Let) {
+        walk_let_mut(self, l);
+    }
+
+    fn visit_expr(&mut self, expr: &mut Expr) {
+        walk_expr_mut(self, expr);
+    }
+
+    fn visit_arm(&mut self, arm: &mut Arm) {
+        walk_arm_mut(self, arm);
+    }
+
+    fn visit_pattern(&mut self, pattern: &mut Pattern) {
+        walk_pat_mut(self, pattern);
+    }
+
+    fn visit_ty(&mut self, _ty: &mut Ty) {
+    }
+}
+
+pub fn walk_ast_mut(visitor: &mut impl MutVisitor, ast: &mut Ast) {
+    visitor.visit_module(&mut ast.module);
+}
+
+pub fn walk_module_mut(visitor: &mut impl MutVisitor, module: &mut Module) {
+    module.items.iter_mut().for_each(|item| visitor.visit_item(item));
+}
+
+pub fn walk_item_mut(visitor: &mut impl MutVisitor, item: &mut Item) {
+    match &mut item.kind {
+        ItemKind::Fn(sig, _, body) => visitor.visit_fn(sig, body.as_deref_mut()),
+        ItemKind::TypeAlias(_, ty) => ty.iter_mut().for_each(|ty| visitor.visit_ty(ty)),
+        ItemKind::Mod(module) => visitor.visit_module(module),
+        ItemKind::Trait { items, .. } | ItemKind::Impl { items, .. } =>
+            items.iter_mut().for_each(|item| visitor.visit_assoc_item(item)),
+        ItemKind::Enum(..)
+        | ItemKind::Struct(..)
+        | ItemKind::Extern(..)
+        | ItemKind::Use(..)
+        | ItemKind::Macro(..) => {}
+    }
+}
+
+pub fn walk_assoc_item_mut(visitor: &mut impl MutVisitor, item: &mut AssocItem) {
+    match &mut item.kind {
+        AssocItemKind::Fn(sig, _, body) => visitor.visit_fn(sig, body.as_deref_mut()),
+        AssocItemKind::TyAlias(_, ty) => ty.iter_mut().for_each(|ty| visitor.visit_ty(ty)),
+    }
+}
+
+pub fn walk_fn_mut(visitor: &mut impl MutVisitor, sig: &mut FnSig, body: Option<&mut Expr>) {
+    visitor.visit_fn_sig(sig);
+    if let Some(body) = body {
+        visitor.visit_expr(body);
+    }
+}
+
+pub fn walk_fn_sig_mut(visitor: &mut impl MutVisitor, sig: &mut FnSig) {
+    for param in &mut sig.params {
+        visitor.visit_pattern(&mut param.pattern);
+        visitor.visit_ty(&mut param.ty);
+    }
+    sig.ret_ty.iter_mut().for_each(|ty| visitor.visit_ty(ty));
+}
+
+pub fn walk_block_mut(visitor: &mut impl MutVisitor, block: &mut Block) {
+    block.stmts.iter_mut().for_each(|stmt| visitor.visit_stmt(stmt));
+}
+
+pub fn walk_stmt_mut(visitor: &mut impl MutVisitor, stmt: &mut Stmt) {
+    match &mut stmt.kind {
+        StmtKind::Let(l) => visitor.visit_let(l),
+        StmtKind::Expr(expr) | StmtKind::Semi(expr) => visitor.visit_expr(expr),
+    }
+}
+
+pub fn walk_let_mut(visitor: &mut impl MutVisitor, l: &mut Let) {
+    l.init.iter_mut().for_each(|expr| visitor.visit_expr(expr));
+    visitor.visit_pattern(&mut l.pat);
+    l.ty.iter_mut().for_each(|ty| visitor.visit_ty(ty));
+}
+
+pub fn walk_arm_mut(visitor: &mut impl MutVisitor, arm: &mut Arm) {
+    visitor.visit_pattern(&mut arm.pat);
+    arm.guard.iter_mut().for_each(|expr| visitor.visit_expr(expr));
+    visitor.visit_expr(&mut arm.body);
+}
+
+pub fn walk_expr_mut(visitor: &mut impl MutVisitor, expr: &mut Expr) {
+    match &mut expr.kind {
+        ExprKind::Err
+        | ExprKind::Lit(..)
+        | ExprKind::Path(..)
+        | ExprKind::Continue
+        | ExprKind::Break
+        | ExprKind::Macro(..) => {}
+        ExprKind::Ret(expr) => expr.iter_mut().for_each(|expr| visitor.visit_expr(expr)),
+        ExprKind::Unary(_, expr) | ExprKind::Paren(expr) | ExprKind::Box(expr) =>
+            visitor.visit_expr(expr),
+        ExprKind::Field(expr, _) => visitor.visit_expr(expr),
+        ExprKind::Block(block) | ExprKind::Loop(block) => visitor.visit_block(block),
+        ExprKind::Tuple(xs) | ExprKind::Array(xs) =>
+            xs.iter_mut().for_each(|expr| visitor.visit_expr(expr)),
+        ExprKind::Repeat(expr, _) => visitor.visit_expr(expr),
+        ExprKind::Closure(_, sig, body) => visitor.visit_fn(sig, Some(body)),
+        ExprKind::Assign(l, r)
+        | ExprKind::AssignOp(_, l, r)
+        | ExprKind::Bin(_, l, r)
+        | ExprKind::Index(l, r) => {
+            visitor.visit_expr(l);
+            visitor.visit_expr(r);
+        }
+        ExprKind::Struct(_, fields) =>
+            fields.iter_mut().for_each(|field| visitor.visit_expr(&mut field.expr)),
+        ExprKind::Call(f, args) => {
+            visitor.visit_expr(f);
+            args.iter_mut().for_each(|expr| visitor.visit_expr(expr));
+        }
+        ExprKind::MethodCall(receiver, _, args) => {
+            visitor.visit_expr(receiver);
+            args.iter_mut().for_each(|expr| visitor.visit_expr(expr));
+        }
+        ExprKind::If(c, l, r) => {
+            visitor.visit_expr(c);
+            visitor.visit_block(l);
+            r.iter_mut().for_each(|expr| visitor.visit_expr(expr));
+        }
+        ExprKind::Match(expr, arms) => {
+            visitor.visit_expr(expr);
+            arms.iter_mut().for_each(|arm| visitor.visit_arm(arm));
+        }
+        ExprKind::While(expr, block) => {
+            visitor.visit_expr(expr);
+            visitor.visit_block(block);
+        }
+    }
+}
+
+pub fn walk_pat_mut(visitor: &mut impl MutVisitor, pat: &mut Pattern) {
+    match &mut pat.kind {
+        PatternKind::Wildcard | PatternKind::Path(..) => {}
+        PatternKind::Box(pat) | PatternKind::Paren(pat) => visitor.visit_pattern(pat),
+        PatternKind::Tuple(pats) | PatternKind::Variant(_, pats) =>
+            pats.iter_mut().for_each(|pat| visitor.visit_pattern(pat)),
+        PatternKind::Ident(_, pat, _) => pat.iter_mut().for_each(|pat| visitor.visit_pattern(pat)),
+        PatternKind::Lit(expr) => visitor.visit_expr(expr),
+        PatternKind::Struct(_, fields) =>
+            fields.iter_mut().for_each(|field| visitor.visit_pattern(&mut field.pat)),
+    }
+}
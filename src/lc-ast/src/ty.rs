@@ -1,4 +1,4 @@
-use super::{NodeId, Path, P};
+use super::{Ident, NodeId, Path, P};
 use lc_span::Span;
 use std::fmt::{self, Display, Formatter};
 
@@ -11,11 +11,16 @@ pub struct Ty {
 
 #[derive(Debug, PartialEq, Clone, Eq, Hash)]
 pub enum TyKind {
-    Array(P<Ty>),
+    /// [<ty>; <len>]
+    Array(P<Ty>, usize),
     Tuple(Vec<P<Ty>>),
     /// (<ty>)
     Paren(P<Ty>),
     Path(Path),
+    /// <<ty> as <path>>::<ident>
+    Projection(P<Ty>, Path, Ident),
+    /// dyn <path>
+    Dyn(Path),
     /// &<ty>
     Box(P<Ty>),
     /// fn(<ty>...) (-> <ty>)?
@@ -34,3 +39,104 @@ impl Display for Ty {
         todo!()
     }
 }
+
+/// the sized signed integer types
+/// `int` is an alias for `i64`
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy, Serialize, Deserialize)]
+pub enum IntTy {
+    I8,
+    I16,
+    I32,
+    I64,
+}
+
+impl IntTy {
+    pub fn bit_width(self) -> u32 {
+        match self {
+            IntTy::I8 => 8,
+            IntTy::I16 => 16,
+            IntTy::I32 => 32,
+            IntTy::I64 => 64,
+        }
+    }
+
+    pub fn name_str(self) -> &'static str {
+        match self {
+            IntTy::I8 => "i8",
+            IntTy::I16 => "i16",
+            IntTy::I32 => "i32",
+            IntTy::I64 => "i64",
+        }
+    }
+}
+
+/// the sized unsigned integer types
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy, Serialize, Deserialize)]
+pub enum UintTy {
+    U8,
+    U16,
+    U32,
+    U64,
+}
+
+impl UintTy {
+    pub fn bit_width(self) -> u32 {
+        match self {
+            UintTy::U8 => 8,
+            UintTy::U16 => 16,
+            UintTy::U32 => 32,
+            UintTy::U64 => 64,
+        }
+    }
+
+    pub fn name_str(self) -> &'static str {
+        match self {
+            UintTy::U8 => "u8",
+            UintTy::U16 => "u16",
+            UintTy::U32 => "u32",
+            UintTy::U64 => "u64",
+        }
+    }
+}
+
+/// the floating point types
+/// `float` is an alias for `f64`
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy, Serialize, Deserialize)]
+pub enum FloatTy {
+    F32,
+    F64,
+}
+
+impl FloatTy {
+    pub fn bit_width(self) -> u32 {
+        match self {
+            FloatTy::F32 => 32,
+            FloatTy::F64 => 64,
+        }
+    }
+
+    pub fn name_str(self) -> &'static str {
+        match self {
+            FloatTy::F32 => "f32",
+            FloatTy::F64 => "f64",
+        }
+    }
+}
+
+impl Display for IntTy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name_str())
+    }
+}
+
+impl Display for UintTy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name_str())
+    }
+}
+
+impl Display for FloatTy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name_str())
+    }
+}
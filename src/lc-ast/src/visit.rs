@@ -109,6 +109,10 @@ pub trait Visitor<'ast>: Sized {
     fn visit_ident(&mut self, _ident: Ident) {
     }
 
+    fn visit_use_tree(&mut self, tree: &'ast UseTree) {
+        walk_use_tree(self, tree);
+    }
+
     fn visit_arm(&mut self, arm: &'ast Arm) {
         walk_arm(self, arm);
     }
@@ -137,6 +141,10 @@ pub fn walk_assoc_item<'ast>(visitor: &mut impl Visitor<'ast>, item: &'ast Assoc
             visitor.visit_generics(generics);
             visitor.visit_fn(sig, body.as_deref());
         }
+        AssocItemKind::TyAlias(generics, ty) => {
+            visitor.visit_generics(generics);
+            ty.iter().for_each(|ty| visitor.visit_ty(ty));
+        }
     }
 }
 
@@ -198,6 +206,15 @@ pub fn walk_path_segment<'ast>(visitor: &mut impl Visitor<'ast>, segment: &'ast
     segment.args.iter().for_each(|args| visitor.visit_generic_args(args));
 }
 
+pub fn walk_use_tree<'ast>(visitor: &mut impl Visitor<'ast>, tree: &'ast UseTree) {
+    visitor.visit_path(&tree.prefix);
+    match &tree.kind {
+        UseTreeKind::Simple(rename) => rename.iter().for_each(|&ident| visitor.visit_ident(ident)),
+        UseTreeKind::Glob => {}
+        UseTreeKind::Nested(trees) => trees.iter().for_each(|tree| visitor.visit_use_tree(tree)),
+    }
+}
+
 pub fn walk_arm<'ast>(visitor: &mut impl Visitor<'ast>, arm: &'ast Arm) {
     visitor.visit_id(arm.id);
     visitor.visit_pattern(&arm.pat);
@@ -214,10 +231,12 @@ pub fn walk_expr<'ast>(visitor: &mut impl Visitor<'ast>, expr: &'ast Expr) {
         ExprKind::Paren(expr) => visitor.visit_expr(expr),
         ExprKind::Block(block) => visitor.visit_block(block),
         ExprKind::Path(path) => visitor.visit_path(path),
-        ExprKind::Tuple(xs) => xs.iter().for_each(|expr| visitor.visit_expr(expr)),
+        ExprKind::Tuple(xs) | ExprKind::Array(xs) =>
+            xs.iter().for_each(|expr| visitor.visit_expr(expr)),
+        ExprKind::Repeat(expr, _) => visitor.visit_expr(expr),
         ExprKind::Closure(name, sig, expr) => visitor.visit_closure(*name, sig, expr),
         ExprKind::Box(expr) => visitor.visit_expr(expr),
-        ExprKind::Assign(l, r) => {
+        ExprKind::Assign(l, r) | ExprKind::AssignOp(_, l, r) => {
             visitor.visit_expr(l);
             visitor.visit_expr(r);
         }
@@ -229,12 +248,17 @@ pub fn walk_expr<'ast>(visitor: &mut impl Visitor<'ast>, expr: &'ast Expr) {
             visitor.visit_expr(f);
             args.iter().for_each(|expr| visitor.visit_expr(expr));
         }
+        ExprKind::MethodCall(receiver, ident, args) => {
+            visitor.visit_expr(receiver);
+            visitor.visit_ident(*ident);
+            args.iter().for_each(|expr| visitor.visit_expr(expr));
+        }
         ExprKind::If(c, l, r) => {
             visitor.visit_expr(c);
             visitor.visit_block(l);
             r.iter().for_each(|expr| visitor.visit_expr(expr));
         }
-        ExprKind::Bin(_, l, r) => {
+        ExprKind::Bin(_, l, r) | ExprKind::Index(l, r) => {
             visitor.visit_expr(l);
             visitor.visit_expr(r);
         }
@@ -301,10 +325,15 @@ pub fn walk_pat<'ast>(visitor: &mut impl Visitor<'ast>, pat: &'ast Pattern) {
 pub fn walk_ty<'ast>(visitor: &mut impl Visitor<'ast>, ty: &'ast Ty) {
     visitor.visit_id(ty.id);
     match &ty.kind {
-        TyKind::Box(ty) | TyKind::Array(ty) | TyKind::Ptr(ty) | TyKind::Paren(ty) =>
+        TyKind::Box(ty) | TyKind::Array(ty, _) | TyKind::Ptr(ty) | TyKind::Paren(ty) =>
             visitor.visit_ty(ty),
         TyKind::Tuple(tys) => tys.iter().for_each(|ty| visitor.visit_ty(ty)),
-        TyKind::Path(path) => visitor.visit_path(path),
+        TyKind::Path(path) | TyKind::Dyn(path) => visitor.visit_path(path),
+        TyKind::Projection(ty, path, ident) => {
+            visitor.visit_ty(ty);
+            visitor.visit_path(path);
+            visitor.visit_ident(*ident);
+        }
         TyKind::Fn(params, ret) => {
             params.iter().for_each(|ty| visitor.visit_ty(ty));
             ret.iter().for_each(|ty| visitor.visit_ty(ty));
@@ -355,7 +384,7 @@ pub fn walk_item<'ast>(visitor: &mut impl Visitor<'ast>, item: &'ast Item) {
 
         ItemKind::TypeAlias(generics, ty) => {
             visitor.visit_generics(generics);
-            visitor.visit_ty(ty);
+            ty.iter().for_each(|ty| visitor.visit_ty(ty));
         }
         ItemKind::Enum(generics, variants) => {
             visitor.visit_generics(generics);
@@ -367,7 +396,7 @@ pub fn walk_item<'ast>(visitor: &mut impl Visitor<'ast>, item: &'ast Item) {
         }
         ItemKind::Extern(_abi, items) =>
             items.iter().for_each(|item| visitor.visit_foreign_item(item)),
-        ItemKind::Use(path) => visitor.visit_path(path),
+        ItemKind::Use(tree) => visitor.visit_use_tree(tree),
         ItemKind::Mod(module) => visitor.visit_module(module),
         ItemKind::Trait { generics, items } => {
             visitor.visit_generics(generics);
@@ -38,6 +38,10 @@ impl<'a, 'ir> AstLoweringCtx<'a, 'ir> {
         self.arena.alloc(ir::Ty { id: self.new_id(), span, kind: ir::TyKind::Path(qpath) })
     }
 
+    pub(crate) fn mk_ty_err(&mut self, span: Span) -> &'ir ir::Ty<'ir> {
+        self.arena.alloc(ir::Ty { id: self.new_id(), span, kind: ir::TyKind::Err })
+    }
+
     pub(crate) fn mk_empty_block(&mut self, span: Span) -> &'ir ir::Block<'ir> {
         self.arena.alloc(ir::Block {
             id: self.new_id(),
@@ -53,6 +57,7 @@ impl<'a, 'ir> AstLoweringCtx<'a, 'ir> {
         pat: &'ir ir::Pattern<'ir>,
         expr: &'ir ir::Expr<'ir>,
     ) -> ir::Arm<'ir> {
-        ir::Arm { id: self.new_id(), span: pat.span.merge(expr.span), pat, guard: None, body: expr }
+        let span = pat.span.merge(expr.span);
+        ir::Arm { id: self.new_id(), span, attrs: &[], pat, guard: None, body: expr }
     }
 }
@@ -25,6 +25,8 @@ impl<'ir> AstLoweringCtx<'_, 'ir> {
             ExprKind::Block(block) => ir::ExprKind::Block(self.lower_block(block)),
             ExprKind::Path(path) => ir::ExprKind::Path(self.lower_qpath(path)),
             ExprKind::Tuple(xs) => ir::ExprKind::Tuple(self.lower_exprs(xs)),
+            ExprKind::Array(xs) => ir::ExprKind::Array(self.lower_exprs(xs)),
+            ExprKind::Repeat(x, n) => ir::ExprKind::Repeat(self.lower_expr(x), *n),
             ExprKind::Bin(op, l, r) =>
                 ir::ExprKind::Bin(*op, self.lower_expr(l), self.lower_expr(r)),
             ExprKind::Closure(_name, sig, expr) => {
@@ -34,13 +36,19 @@ impl<'ir> AstLoweringCtx<'_, 'ir> {
             }
             ExprKind::Call(f, args) =>
                 ir::ExprKind::Call(self.lower_expr(f), self.lower_exprs(args)),
+            ExprKind::MethodCall(receiver, ident, args) =>
+                ir::ExprKind::MethodCall(self.lower_expr(receiver), *ident, self.lower_exprs(args)),
             ExprKind::If(c, l, r) => self.lower_expr_if(expr.span, c, l, r.as_deref()),
             ExprKind::Struct(path, fields) => ir::ExprKind::Struct(
                 self.lower_qpath(path),
                 self.arena.alloc_from_iter(fields.iter().map(|f| self.lower_field(f))),
             ),
             ExprKind::Assign(l, r) => ir::ExprKind::Assign(self.lower_expr(l), self.lower_expr(r)),
+            ExprKind::AssignOp(op, l, r) =>
+                ir::ExprKind::AssignOp(*op, self.lower_expr(l), self.lower_expr(r)),
             ExprKind::Field(expr, ident) => ir::ExprKind::Field(self.lower_expr(expr), *ident),
+            ExprKind::Index(base, index) =>
+                ir::ExprKind::Index(self.lower_expr(base), self.lower_expr(index)),
             ExprKind::Match(expr, arms) => ir::ExprKind::Match(
                 self.lower_expr(expr),
                 self.lower_arms(arms),
@@ -49,7 +57,7 @@ impl<'ir> AstLoweringCtx<'_, 'ir> {
             ExprKind::Continue => ir::ExprKind::Continue,
             ExprKind::Break => ir::ExprKind::Break,
             ExprKind::Err => ir::ExprKind::Err,
-            ExprKind::Macro(_, _) => todo!(),
+            ExprKind::Macro(..) => unreachable!("macro invocations are removed during expansion"),
         };
 
         ir::Expr { span: expr.span, id: self.lower_node_id(expr.id), kind }
@@ -63,6 +71,7 @@ impl<'ir> AstLoweringCtx<'_, 'ir> {
         ir::Arm {
             id: self.lower_node_id(arm.id),
             span: arm.span,
+            attrs: self.lower_attrs(&arm.attrs),
             pat: self.lower_pattern(&arm.pat),
             guard: arm.guard.as_ref().map(|guard| self.lower_expr(guard)),
             body: self.lower_expr(&arm.body),
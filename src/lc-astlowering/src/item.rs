@@ -2,7 +2,7 @@ use super::AstLoweringCtx;
 use lc_ast::*;
 use lc_index::Idx;
 use ir::{DefId, DefNode, VariantIdx};
-use lc_span::sym;
+use lc_span::{kw, sym};
 use lc_span::Symbol;
 
 impl<'a, 'ir> AstLoweringCtx<'a, 'ir> {
@@ -12,8 +12,9 @@ impl<'a, 'ir> AstLoweringCtx<'a, 'ir> {
 
     pub fn lower_item(&mut self, item: &Item) {
         self.with_def_id(item.id, |lctx| {
-            let &Item { span, id, vis, ref kind, ident } = item;
+            let &Item { span, id, vis, ref kind, ident, ref attrs } = item;
             let id = lctx.lower_node_id(id);
+            let attrs = lctx.lower_attrs(attrs);
             let kind = match &kind {
                 ItemKind::Fn(sig, generics, expr) => {
                     if ident.symbol == sym::main {
@@ -40,13 +41,21 @@ impl<'a, 'ir> AstLoweringCtx<'a, 'ir> {
                 }
                 ItemKind::Extern(abi, items) => lctx.lower_foreign_mod(*abi, items),
                 ItemKind::TypeAlias(generics, ty) => {
+                    let ty = match ty {
+                        Some(ty) => ty,
+                        None => {
+                            lctx.sess.emit_error(item.span, AstError::TypeAliasWithoutType);
+                            return;
+                        }
+                    };
                     let generics = lctx.lower_generics(generics);
                     let ty = lctx.lower_ty(ty);
                     ir::ItemKind::TypeAlias(generics, ty)
                 }
-                ItemKind::Use(path) => ir::ItemKind::Use(lctx.lower_path(path)),
+                ItemKind::Use(tree) => ir::ItemKind::Use(lctx.lower_use_tree(tree)),
                 ItemKind::Mod(module) => ir::ItemKind::Mod(lctx.lower_module(module)),
-                ItemKind::Macro(_) => todo!(),
+                ItemKind::Macro(_) =>
+                    unreachable!("macro definitions are removed during expansion"),
                 ItemKind::Impl { generics, trait_path, self_ty, items } =>
                     lctx.lower_impl(generics, trait_path.as_ref(), self_ty, items),
                 ItemKind::Trait { generics, items } => ir::ItemKind::Trait {
@@ -56,7 +65,7 @@ impl<'a, 'ir> AstLoweringCtx<'a, 'ir> {
                         .alloc_from_iter(items.iter().map(|item| lctx.lower_trait_item_ref(item))),
                 },
             };
-            let item = lctx.alloc(ir::Item { span, id, vis, ident, kind });
+            let item = lctx.alloc(ir::Item { span, id, attrs, vis, ident, kind });
             lctx.mk_def_node(id.def, item);
             lctx.items.insert(id.def, item);
         });
@@ -94,14 +103,15 @@ impl<'a, 'ir> AstLoweringCtx<'a, 'ir> {
     }
 
     fn lower_foreign_item(&mut self, abi: Abi, item: &ForeignItem) -> ir::ForeignItem<'ir> {
-        let &ForeignItem { span, id, vis, ident, ref kind } = item;
+        let &ForeignItem { span, id, vis, ident, ref kind, ref attrs } = item;
         self.with_def_id(id, |lctx| {
             let id = lctx.lower_node_id(id);
+            let attrs = lctx.lower_attrs(attrs);
             let kind = match kind {
                 ForeignItemKind::Fn(sig, generics) =>
                     ir::ForeignItemKind::Fn(lctx.lower_fn_sig(sig), lctx.lower_generics(generics)),
             };
-            ir::ForeignItem { id, abi, ident, span, vis, kind }
+            ir::ForeignItem { id, abi, ident, span, attrs, vis, kind }
         })
     }
 
@@ -131,8 +141,9 @@ impl<'a, 'ir> AstLoweringCtx<'a, 'ir> {
     }
 
     fn lower_trait_item(&mut self, trait_item: &TraitItem) -> &'ir ir::TraitItem<'ir> {
-        let &TraitItem { id, ident, vis, span, ref kind } = trait_item;
+        let &TraitItem { id, ident, vis, span, ref kind, ref attrs } = trait_item;
         let id = self.lower_node_id(id);
+        let attrs = self.lower_attrs(attrs);
         let (generics, kind) = match kind {
             AssocItemKind::Fn(sig, generics, body) => (
                 generics,
@@ -141,9 +152,21 @@ impl<'a, 'ir> AstLoweringCtx<'a, 'ir> {
                     body.as_ref().map(|body| self.lower_body(sig, body)),
                 ),
             ),
+            AssocItemKind::TyAlias(generics, ty) =>
+                (generics, ir::TraitItemKind::TyAlias(ty.as_ref().map(|ty| self.lower_ty(ty)))),
         };
         let generics = self.lower_generics(generics);
-        let trait_item = self.alloc(ir::TraitItem { id, ident, vis, span, generics, kind });
+        let trait_def_id = self.parent_def_id(id);
+        let trait_item = self.alloc(ir::TraitItem {
+            id,
+            trait_def_id,
+            ident,
+            vis,
+            span,
+            attrs,
+            generics,
+            kind,
+        });
         self.mk_def_node(id.def, trait_item);
         trait_item
     }
@@ -158,8 +181,9 @@ impl<'a, 'ir> AstLoweringCtx<'a, 'ir> {
     }
 
     fn lower_impl_item(&mut self, impl_item: &AssocItem) -> &'ir ir::ImplItem<'ir> {
-        let &AssocItem { span, id, vis, ident, ref kind } = impl_item;
+        let &AssocItem { span, id, vis, ident, ref kind, ref attrs } = impl_item;
         let id = self.lower_node_id(id);
+        let attrs = self.lower_attrs(attrs);
         let (generics, kind) = match kind {
             AssocItemKind::Fn(sig, generics, body) => {
                 let generics = self.lower_generics(generics);
@@ -167,11 +191,30 @@ impl<'a, 'ir> AstLoweringCtx<'a, 'ir> {
                 let sig = self.lower_fn_sig(sig);
                 (generics, ir::ImplItemKind::Fn(sig, body))
             }
+            AssocItemKind::TyAlias(generics, ty) => {
+                let generics = self.lower_generics(generics);
+                let ty = match ty {
+                    Some(ty) => self.lower_ty(ty),
+                    None => {
+                        self.sess.emit_error(span, AstError::TypeAliasWithoutType);
+                        self.mk_ty_err(span)
+                    }
+                };
+                (generics, ir::ImplItemKind::TyAlias(ty))
+            }
         };
 
         let impl_def_id = self.parent_def_id(id);
-        let impl_item =
-            self.alloc(ir::ImplItem { id, impl_def_id, ident, span, vis, generics, kind });
+        let impl_item = self.alloc(ir::ImplItem {
+            id,
+            impl_def_id,
+            ident,
+            span,
+            attrs,
+            vis,
+            generics,
+            kind,
+        });
         self.mk_def_node(id.def, impl_item);
         impl_item
     }
@@ -209,11 +252,18 @@ impl<'a, 'ir> AstLoweringCtx<'a, 'ir> {
 
     fn lower_field_decl(&mut self, (i, field): (usize, &FieldDecl)) -> ir::FieldDecl<'ir> {
         self.with_def_id(field.id, |lctx| {
-            let &FieldDecl { span, ident, vis, id, ref ty } = field;
+            let &FieldDecl { span, ident, vis, id, ref ty, ref attrs } = field;
             // if it is a tuple struct/variant, the field will just be named after its index
             let ident =
                 ident.unwrap_or_else(|| Ident::new(field.span, Symbol::intern(&i.to_string())));
-            ir::FieldDecl { span, ident, vis, id: lctx.lower_node_id(id), ty: lctx.lower_ty(ty) }
+            ir::FieldDecl {
+                span,
+                attrs: lctx.lower_attrs(attrs),
+                ident,
+                vis,
+                id: lctx.lower_node_id(id),
+                ty: lctx.lower_ty(ty),
+            }
         })
     }
 
@@ -229,6 +279,10 @@ impl<'a, 'ir> AstLoweringCtx<'a, 'ir> {
         let inputs =
             self.arena.alloc_from_iter(sig.params.iter().map(|p| self.lower_ty_inner(&p.ty)));
         let output = sig.ret_ty.as_ref().map(|ty| self.lower_ty(ty));
-        self.arena.alloc(ir::FnSig { inputs, output })
+        let has_self = sig.params.first().map_or(false, |param| match param.pattern.kind {
+            PatternKind::Ident(ident, ..) => ident.symbol == kw::LSelf,
+            _ => false,
+        });
+        self.arena.alloc(ir::FnSig { inputs, output, has_self })
     }
 }
@@ -102,24 +102,41 @@ impl<'a, 'ir> AstLoweringCtx<'a, 'ir> {
         ir
     }
 
+    /// reports any attributes that the compiler does not understand
+    /// (these are still carried into the ir as they are harmless)
+    fn lower_attrs(&mut self, attrs: &[Attribute]) -> &'ir [ir::Attribute] {
+        for attr in attrs.iter().filter(|attr| !attr.is_builtin()) {
+            self.sess.emit_error(attr.span, AstError::UnknownAttribute(attr.ident));
+        }
+        self.arena.alloc_from_iter(attrs.iter().cloned())
+    }
+
     fn lower_generics(&mut self, generics: &Generics) -> &'ir ir::Generics<'ir> {
-        let &Generics { span, ref params } = generics;
+        let &Generics { span, ref params, ref predicates } = generics;
         let params = self.arena.alloc_from_iter(params.iter().map(|p| self.lower_ty_param(p)));
         params
             .iter()
             .for_each(|ty_param| self.resolver.mk_def_node(ty_param.id.def, ty_param.into()));
-        self.arena.alloc(ir::Generics { span, params })
+        let predicates =
+            self.arena.alloc_from_iter(predicates.iter().map(|p| self.lower_where_predicate(p)));
+        self.arena.alloc(ir::Generics { span, params, predicates })
+    }
+
+    fn lower_where_predicate(&mut self, predicate: &WherePredicate) -> ir::WherePredicate<'ir> {
+        let WherePredicate { span, ty, bounds } = predicate;
+        ir::WherePredicate { span: *span, ty: self.lower_ty(ty), bounds: self.lower_paths(bounds) }
     }
 
     fn lower_ty_param(&mut self, param: &TyParam) -> ir::TyParam<'ir> {
         // `TyParam`s have their own `DefId`
         self.with_def_id(param.id, |lctx| {
-            let &TyParam { span, id, ident, ref default } = param;
+            let &TyParam { span, id, ident, ref bounds, ref default } = param;
             ir::TyParam {
                 span,
                 id: lctx.lower_node_id(id),
                 index: lctx.resolver.idx_of_ty_param(id),
                 ident,
+                bounds: lctx.lower_paths(bounds),
                 default: default.as_ref().map(|ty| lctx.lower_ty(ty)),
             }
         })
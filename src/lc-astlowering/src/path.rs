@@ -28,8 +28,52 @@ impl<'ir> AstLoweringCtx<'_, 'ir> {
         self.partial_lower_path(path)
     }
 
+    /// lowers a list of fully resolved paths
+    pub(crate) fn lower_paths(&mut self, paths: &[Path]) -> &'ir [ir::Path<'ir>] {
+        self.arena.alloc_from_iter(paths.iter().map(|path| {
+            self.resolver.full_res(path.id);
+            self.partial_lower_path_inner(path)
+        }))
+    }
+
+    /// lowers each single import of the use tree to the full path that it imports
+    pub(crate) fn lower_use_tree(&mut self, tree: &UseTree) -> &'ir [ir::UsePath<'ir>] {
+        let mut paths = vec![];
+        self.lower_use_tree_into(&[], tree, &mut paths);
+        self.arena.alloc_from_iter(paths)
+    }
+
+    fn lower_use_tree_into(
+        &mut self,
+        prefix: &[&PathSegment],
+        tree: &UseTree,
+        paths: &mut Vec<ir::UsePath<'ir>>,
+    ) {
+        let prefix = prefix.iter().copied().chain(&tree.prefix.segments).collect::<Vec<_>>();
+        match &tree.kind {
+            UseTreeKind::Simple(..) => {
+                // the resolution of a single import is recorded against the path of its leaf
+                let res = self.resolver.full_res(tree.prefix.id);
+                let res = self.lower_res(res);
+                let segments = self
+                    .arena
+                    .alloc_from_iter(prefix.iter().map(|seg| self.lower_path_segment(seg)));
+                let path = ir::Path { span: tree.span, segments, res };
+                paths.push(ir::UsePath { binding: tree.ident().unwrap(), path });
+            }
+            UseTreeKind::Glob => {}
+            UseTreeKind::Nested(trees) =>
+                trees.iter().for_each(|tree| self.lower_use_tree_into(&prefix, tree, paths)),
+        }
+    }
+
     /// lowers the resolved portion of the path
     pub(crate) fn partial_lower_path(&mut self, path: &Path) -> &'ir ir::Path<'ir> {
+        let path = self.partial_lower_path_inner(path);
+        self.alloc(path)
+    }
+
+    fn partial_lower_path_inner(&mut self, path: &Path) -> ir::Path<'ir> {
         let partial_res = self.resolver.partial_res(path.id);
         let unresolved_start = path.segments.len() - partial_res.unresolved;
         let res = self.lower_res(partial_res.resolved);
@@ -37,7 +81,7 @@ impl<'ir> AstLoweringCtx<'_, 'ir> {
         let segments = self.arena.alloc_from_iter(
             path.segments[..unresolved_start].iter().map(|seg| self.lower_path_segment(seg)),
         );
-        self.alloc(ir::Path { span: path.span, segments, res })
+        ir::Path { span: path.span, segments, res }
     }
 
     pub fn lower_path_segment(&mut self, segment: &PathSegment) -> ir::PathSegment<'ir> {
@@ -18,9 +18,12 @@ impl<'ir> AstLoweringCtx<'_, 'ir> {
                 ir::TyKind::Fn(self.lower_tys(params), ret.as_ref().map(|ty| self.lower_ty(ty))),
             TyKind::Box(ty) => ir::TyKind::Box(self.lower_ty(ty)),
             TyKind::Paren(ty) => return self.lower_ty_inner(ty),
-            TyKind::Array(ty) => ir::TyKind::Array(self.lower_ty(ty)),
+            TyKind::Array(ty, n) => ir::TyKind::Array(self.lower_ty(ty), *n),
             TyKind::Tuple(tys) => ir::TyKind::Tuple(self.lower_tys(tys)),
             TyKind::Path(path) => ir::TyKind::Path(self.lower_qpath(path)),
+            TyKind::Dyn(path) => ir::TyKind::Dyn(self.lower_path(path)),
+            TyKind::Projection(ty, path, ident) =>
+                ir::TyKind::Projection(self.lower_ty(ty), self.lower_path(path), *ident),
             TyKind::Ptr(ty) => ir::TyKind::Ptr(self.lower_ty(ty)),
             TyKind::ImplicitSelf => {
                 let res = self.resolver.full_res(ty.id);
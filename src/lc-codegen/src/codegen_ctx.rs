@@ -4,7 +4,8 @@ use inkwell::passes::PassManager;
 use inkwell::types::*;
 use inkwell::values::*;
 use inkwell::*;
-use inkwell::{builder::Builder, module::Module};
+use inkwell::{builder::Builder, module::Linkage, module::Module};
+use itertools::Itertools;
 use lc_core::ty::*;
 use lc_error::{ErrorReported, LResult};
 use lc_span::{sym, Span};
@@ -26,6 +27,8 @@ pub struct CodegenCtx<'tcx> {
     pub intrinsics: RefCell<FxHashMap<Instance<'tcx>, FunctionValue<'tcx>>>,
     pub instances: RefCell<FxHashMap<Instance<'tcx>, FunctionValue<'tcx>>>,
     pub lltypes: RefCell<FxHashMap<Ty<'tcx>, BasicTypeEnum<'tcx>>>,
+    /// the vtable of each unsizing cast from a pointer type to a trait object pointer type
+    pub vtables: RefCell<FxHashMap<(Ty<'tcx>, Ty<'tcx>), PointerValue<'tcx>>>,
 }
 
 pub struct CommonValues<'tcx> {
@@ -48,6 +51,10 @@ pub struct CommonTypes<'tcx> {
     pub i8ptr: PointerType<'tcx>,
     pub i32ptr: PointerType<'tcx>,
     pub i64ptr: PointerType<'tcx>,
+    /// `str` is represented as a pointer to its bytes and its length
+    pub str: StructType<'tcx>,
+    /// a pointer to a trait object is represented as a pointer to its data and its vtable
+    pub dyn_ptr: StructType<'tcx>,
     // using a fix sized discriminant for ease for now
     pub discr: IntType<'tcx>,
 }
@@ -66,6 +73,8 @@ impl<'tcx> CodegenCtx<'tcx> {
         fpm.add_reassociate_pass();
         fpm.initialize();
 
+        let i8ptr = llctx.i8_type().ptr_type(AddressSpace::default());
+        let vtable_ptr = i8ptr.ptr_type(AddressSpace::default());
         let types = CommonTypes {
             unit: llctx.struct_type(&[], false),
             i64: llctx.i64_type(),
@@ -73,9 +82,11 @@ impl<'tcx> CodegenCtx<'tcx> {
             float: llctx.f64_type(),
             byte: llctx.i8_type(),
             bool: llctx.bool_type(),
-            i8ptr: llctx.i8_type().ptr_type(AddressSpace::default()),
+            i8ptr,
             i32ptr: llctx.i32_type().ptr_type(AddressSpace::default()),
             i64ptr: llctx.i64_type().ptr_type(AddressSpace::default()),
+            str: llctx.struct_type(&[i8ptr.into(), llctx.i64_type().into()], false),
+            dyn_ptr: llctx.struct_type(&[i8ptr.into(), vtable_ptr.into()], false),
             discr: llctx.i16_type(),
         };
 
@@ -89,7 +100,7 @@ impl<'tcx> CodegenCtx<'tcx> {
             unit: types.unit.get_undef(),
         };
 
-        let native_functions = NativeFunctionsBuilder::new(llctx, &module).build();
+        let native_functions = NativeFunctionsBuilder::new(llctx, &module, &types).build();
         let llvm_intrinsics = LLVMIntrinsics::new(llctx, &module);
         let gc = GCFunctions::new(llctx, &module);
 
@@ -107,9 +118,101 @@ impl<'tcx> CodegenCtx<'tcx> {
             intrinsics: Default::default(),
             instances: Default::default(),
             lltypes: Default::default(),
+            vtables: Default::default(),
         }
     }
 
+    /// returns the vtable (as an `i8**`) for the unsizing cast from the pointer type `source` to
+    /// the trait object pointer type `target`
+    /// the vtable is a constant global array of the (type erased) methods of the impl
+    pub fn codegen_vtable(&self, source: Ty<'tcx>, target: Ty<'tcx>) -> PointerValue<'tcx> {
+        if let Some(&vtable) = self.vtables.borrow().get(&(source, target)) {
+            return vtable;
+        }
+        let methods = self
+            .tcx
+            .vtable_instances(source, target)
+            .into_iter()
+            .map(|instance| {
+                let llfn = self.instances.borrow()[&instance];
+                llfn.as_global_value().as_pointer_value().const_cast(self.types.i8ptr)
+            })
+            .collect_vec();
+        let entries = self.types.i8ptr.const_array(&methods);
+        let name = format!("vtable<{}, {}>", source.deref_ty(), target.deref_ty());
+        let global = self.module.add_global(entries.get_type(), None, &name);
+        global.set_initializer(&entries);
+        global.set_constant(true);
+        global.set_linkage(Linkage::Internal);
+        let vtable_ty = self.types.i8ptr.ptr_type(AddressSpace::default());
+        let vtable = global.as_pointer_value().const_cast(vtable_ty);
+        self.vtables.borrow_mut().insert((source, target), vtable);
+        vtable
+    }
+
+    /// calls the method with index `idx` in the vtable of the trait object `receiver`
+    /// the method is passed the data pointer of the receiver in place of `self`
+    pub fn build_virtual_call(
+        &self,
+        instance: Instance<'tcx>,
+        idx: usize,
+        receiver: StructValue<'tcx>,
+        args: impl IntoIterator<Item = BasicMetadataValueEnum<'tcx>>,
+    ) -> BasicValueEnum<'tcx> {
+        let data = self.build_extract_value(receiver, 0, "dyn_data").unwrap();
+        let vtable = self.build_extract_value(receiver, 1, "dyn_vtable").unwrap();
+        let idx = self.types.i64.const_int(idx as u64, false);
+        // Safety: the vtable has an entry for each method of the trait
+        let entry_ptr = unsafe {
+            self.build_in_bounds_gep(
+                self.types.i8ptr,
+                vtable.into_pointer_value(),
+                &[idx],
+                "vtable_gep",
+            )
+        };
+        let entry = self.build_load(self.types.i8ptr, entry_ptr, "load_vtable_entry");
+
+        // the type erased signature of the method, where `self` is a `i8*`
+        let sig = instance.ty(self.tcx).expect_fn_ptr();
+        let params = std::iter::once(self.types.i8ptr.into())
+            .chain(sig.params[1..].iter().map(|&ty| self.llty(ty).into()))
+            .collect_vec();
+        let llfn_ty = self.llty(sig.ret).fn_type(&params, false);
+        let f = self.build_pointer_cast(
+            entry.into_pointer_value(),
+            llfn_ty.ptr_type(AddressSpace::default()),
+            "vtable_fn_cast",
+        );
+
+        let args = std::iter::once(data.into()).chain(args).collect_vec();
+        self
+            // Safety: the function pointer was cast to the type of the method
+            .build_call(unsafe { std::mem::transmute(f) }, &args[..], "virtual_call")
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+    }
+
+    /// builds the function a method of a trait object refers to when it is used as a value
+    /// (rather than called directly), which forwards its arguments to the method in the vtable
+    fn codegen_virtual_shim(&self, instance: Instance<'tcx>, idx: usize) {
+        let ty = instance.ty(self.tcx);
+        let ident = self.tcx.defs().ident(instance.def_id);
+        let name = format!("{}<{}>", ident, instance.substs);
+        let llfn =
+            self.module.add_function(&name, self.llvm_fn_ty_from_ty(ty), Some(Linkage::Internal));
+        let block = self.llctx.append_basic_block(llfn, "virtual_shim_entry");
+
+        self.position_at_end(block);
+        let params = llfn.get_params();
+        let receiver = params[0].into_struct_value();
+        let args = params[1..].iter().map(|&arg| arg.into());
+        let ret = self.build_virtual_call(instance, idx, receiver, args);
+        self.build_return(Some(&ret));
+        self.instances.borrow_mut().insert(instance, llfn);
+    }
+
     pub fn declare_instances<I>(&self, instances: &I)
     where
         for<'a> &'a I: IntoIterator<Item = &'a Instance<'tcx>>,
@@ -123,9 +226,13 @@ impl<'tcx> CodegenCtx<'tcx> {
                 let Instance { def_id, substs, .. } = instance;
                 let ty = self.tcx.mk_fn_ptr(self.tcx.fn_sig(def_id));
                 let ident = self.tcx.defs().ident(def_id);
+                let is_extern = self.tcx.defs().is_extern(def_id);
                 // we need a special case with main, as the name actually matters
                 // for lli etc
-                let name = if ident.symbol == sym::main {
+                // non-generic functions are exported under a name qualified by their package
+                // (and those of other packages are linked against that name)
+                // everything else is internal so that packages linked together can't clash
+                let (name, linkage) = if ident.symbol == sym::main && !is_extern {
                     let span = self.tcx.defs().span(def_id);
                     if self.module.get_function(sym::main.as_str()).is_some() {
                         self.tcx.sess.emit_error(span, LLVMError::DuplicateMain);
@@ -135,17 +242,22 @@ impl<'tcx> CodegenCtx<'tcx> {
                     }
                     // `clang bitcode.bc` expects `main` symbol
                     // `ld` itself expects `_start`
-                    ident.to_string()
+                    (ident.to_string(), None)
+                } else if self.tcx.defs().is_linked_extern(def_id) {
+                    (self.tcx.symbol_name(def_id), Some(Linkage::External))
+                } else if substs.is_empty() && !is_extern {
+                    (self.tcx.symbol_name(def_id), None)
                 } else {
-                    format!("{}<{}>", ident, substs)
+                    (format!("{}<{}>", ident, substs), Some(Linkage::Internal))
                 };
                 let llty = self.llvm_fn_ty_from_ty(ty.subst(self.tcx, substs));
-                let llfn = self.module.add_function(&name, llty, None);
+                let llfn = self.module.add_function(&name, llty, linkage);
                 self.instances
                     .borrow_mut()
                     .insert(Instance::resolve(self.tcx, def_id, substs), llfn);
             }
             InstanceKind::Intrinsic => self.codegen_intrinsic(instance),
+            InstanceKind::Virtual(idx) => self.codegen_virtual_shim(instance, idx),
         }
     }
 
@@ -155,14 +267,16 @@ impl<'tcx> CodegenCtx<'tcx> {
 
     pub fn codegen_instance(&self, instance: Instance<'tcx>) {
         match instance.kind {
+            // the functions of other packages that are linked against are only declared
+            InstanceKind::Item if self.tcx.defs().is_linked_extern(instance.def_id) => {}
             InstanceKind::Item => FnCtx::new(self, instance).codegen(),
             // codegenned during declaration
-            InstanceKind::Intrinsic => {}
+            InstanceKind::Intrinsic | InstanceKind::Virtual(..) => {}
         }
     }
 
-    /// returns the main function
-    pub fn codegen(&self) -> LResult<()> {
+    /// codegens every instance reachable from the non-generic functions of the package
+    pub fn codegen_lib(&self) -> LResult<()> {
         let instances = self.tcx.monomorphization_instances(());
         if self.tcx.sess.has_errors() {
             return Err(ErrorReported);
@@ -170,6 +284,12 @@ impl<'tcx> CodegenCtx<'tcx> {
         self.declare_instances(instances);
         self.codegen_instances();
         self.module.verify().unwrap();
+        Ok(())
+    }
+
+    /// codegens an executable, which unlike a library must have a `main` function
+    pub fn codegen(&self) -> LResult<()> {
+        self.codegen_lib()?;
         if self.module.get_function(sym::main.as_str()).is_none() {
             self.tcx.sess.build_error(Span::default(), LLVMError::MissingMain).emit();
         }
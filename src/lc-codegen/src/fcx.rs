@@ -9,6 +9,7 @@ use lc_ast::BinOp;
 use lc_core::mir::{self, BlockId, VarId};
 use lc_core::ty::*;
 use lc_index::{Idx, IndexVec};
+use lc_span::Symbol;
 use rustc_hash::FxHashSet;
 use std::ops::Deref;
 
@@ -178,6 +179,18 @@ impl<'a, 'tcx> FnCtx<'a, 'tcx> {
                     }
                 }
             }
+            mir::Rvalue::Array(_, xs) => {
+                for (i, x) in xs.iter().enumerate() {
+                    let operand = self.codegen_operand(x);
+                    self.build_array_element_store(lvalue_ref, i, operand);
+                }
+            }
+            mir::Rvalue::Repeat(x, n) => {
+                let operand = self.codegen_operand(x);
+                for i in 0..*n {
+                    self.build_array_element_store(lvalue_ref, i, operand);
+                }
+            }
             _ => {
                 let value = self.codegen_rvalue(rvalue);
                 self.build_store(lvalue_ref.ptr, value.val);
@@ -185,6 +198,26 @@ impl<'a, 'tcx> FnCtx<'a, 'tcx> {
         }
     }
 
+    /// stores `value` into the `i`th element of the array pointed to by `array`
+    fn build_array_element_store(
+        &mut self,
+        array: LvalueRef<'tcx>,
+        i: usize,
+        value: ValueRef<'tcx>,
+    ) {
+        let idx = self.types.i64.const_int(i as u64, false);
+        // Safety: the index is within the bounds of the array type
+        let elem_ptr = unsafe {
+            self.build_in_bounds_gep(
+                self.llty(array.ty),
+                array.ptr,
+                &[self.vals.zero, idx],
+                "array_elem_gep",
+            )
+        };
+        self.build_store(elem_ptr, value.val);
+    }
+
     /// returns a pointer to where the lvalue points to
     fn codegen_lvalue(&mut self, lvalue: mir::Lvalue<'tcx>) -> LvalueRef<'tcx> {
         self.codegen_lvalue_inner(lvalue.id, lvalue.projs.as_ref())
@@ -220,6 +253,23 @@ impl<'a, 'tcx> FnCtx<'a, 'tcx> {
                         let ptr = self.build_pointer_cast(var.ptr, llty, "lvalue_pointer_cast");
                         LvalueRef { ptr, ty }
                     }
+                    Projection::Index(index) => {
+                        let index = self.vars[*index];
+                        let idx = self
+                            .build_load(self.llty(index.ty), index.ptr, "load_index")
+                            .into_int_value();
+                        let idx = self.extend_index(idx, index.ty);
+                        // Safety: the index was bounds checked by the preceding terminator
+                        let ptr = unsafe {
+                            self.build_in_bounds_gep(
+                                self.llty(var.ty),
+                                var.ptr,
+                                &[self.vals.zero, idx],
+                                "array_gep",
+                            )
+                        };
+                        LvalueRef { ptr, ty: var.ty.array_elem_ty() }
+                    }
                 }
             }
         }
@@ -271,6 +321,9 @@ impl<'a, 'tcx> FnCtx<'a, 'tcx> {
                         self.codegen_float_op(*op, lhs, rhs),
                     (BasicValueEnum::IntValue(_), BasicValueEnum::IntValue(_)) =>
                         self.codegen_int_op(*op, lhs, rhs),
+                    (BasicValueEnum::StructValue(_), BasicValueEnum::StructValue(_))
+                        if lhs.ty == self.tcx.types.str =>
+                        self.codegen_str_op(*op, lhs, rhs),
                     _ => unreachable!(),
                 }
             }
@@ -282,23 +335,57 @@ impl<'a, 'tcx> FnCtx<'a, 'tcx> {
                 let val = self.build_load(self.llty(self.tcx.types.int), discr_ptr, "load_discr");
                 ValueRef { val, ty: self.tcx.types.int }
             }
-            mir::Rvalue::Unary(_, _) => todo!(),
+            mir::Rvalue::Cast(cast, operand, ty) => {
+                let ty = self.monomorphize(*ty);
+                let value = self.codegen_operand(operand);
+                match cast {
+                    PointerCast::ReifyFn | PointerCast::Autoref => ValueRef { val: value.val, ty },
+                    PointerCast::Unsize => {
+                        let data = self.build_pointer_cast(
+                            value.val.into_pointer_value(),
+                            self.types.i8ptr,
+                            "dyn_data_cast",
+                        );
+                        let vtable = self.codegen_vtable(value.ty, ty);
+                        let undef = self.types.dyn_ptr.get_undef();
+                        let fat_ptr = self.build_insert_value(undef, data, 0, "dyn_data").unwrap();
+                        let fat_ptr =
+                            self.build_insert_value(fat_ptr, vtable, 1, "dyn_vtable").unwrap();
+                        ValueRef { val: fat_ptr.into_struct_value().into(), ty }
+                    }
+                }
+            }
+            mir::Rvalue::Unary(op, operand) => {
+                let operand = self.codegen_operand(operand);
+                let val = match (*op, operand.val) {
+                    (mir::UnaryOp::Neg, BasicValueEnum::IntValue(i)) =>
+                        self.build_int_neg(i, "neg").into(),
+                    (mir::UnaryOp::Neg, BasicValueEnum::FloatValue(f)) =>
+                        self.build_float_neg(f, "fneg").into(),
+                    // `!` is the bitwise not on integers which is also the logical not on `bool`
+                    (mir::UnaryOp::Not, BasicValueEnum::IntValue(i)) =>
+                        self.build_not(i, "not").into(),
+                    _ => unreachable!(),
+                };
+                ValueRef { val, ty: operand.ty }
+            }
             // handle these cases in `codegen_assignment`
-            mir::Rvalue::Adt { .. } => unreachable!(),
+            mir::Rvalue::Adt { .. } | mir::Rvalue::Array(..) | mir::Rvalue::Repeat(..) =>
+                unreachable!(),
         }
     }
 
     fn codegen_operand(&mut self, operand: &mir::Operand<'tcx>) -> ValueRef<'tcx> {
         match *operand {
             mir::Operand::Const(c) => match c.kind {
-                ConstKind::Float(f) => ValueRef {
-                    val: self.types.float.const_float(f).into(),
-                    ty: self.tcx.types.float,
-                },
-                ConstKind::Int(i) => ValueRef {
-                    val: self.types.i64.const_int(i as u64, true).into(),
-                    ty: self.tcx.types.int,
-                },
+                ConstKind::Float(f) => {
+                    let llty = self.llty(c.ty).into_float_type();
+                    ValueRef { val: llty.const_float(f).into(), ty: c.ty }
+                }
+                ConstKind::Int(i) => {
+                    let llty = self.llty(c.ty).into_int_type();
+                    ValueRef { val: llty.const_int(i as u64, c.ty.is_signed()).into(), ty: c.ty }
+                }
                 ConstKind::Bool(b) => ValueRef {
                     val: self.types.bool.const_int(b as u64, true).into(),
                     ty: self.tcx.types.bool,
@@ -307,6 +394,11 @@ impl<'a, 'tcx> FnCtx<'a, 'tcx> {
                     val: self.types.discr.const_int(d as u64, true).into(),
                     ty: self.tcx.types.discr,
                 },
+                ConstKind::Char(c) => ValueRef {
+                    val: self.types.i32.const_int(c as u64, false).into(),
+                    ty: self.tcx.types.char,
+                },
+                ConstKind::Str(s) => self.codegen_const_str(s),
                 ConstKind::Unit => ValueRef { val: self.vals.unit.into(), ty: self.tcx.types.unit },
             },
             mir::Operand::Lvalue(lvalue) => {
@@ -318,7 +410,8 @@ impl<'a, 'tcx> FnCtx<'a, 'tcx> {
                 let substs = self.monomorphize(substs);
                 let instance = Instance::resolve(self.tcx, def_id, substs);
                 let llfn = match instance.kind {
-                    InstanceKind::Item => self.instances.borrow()[&instance],
+                    InstanceKind::Item | InstanceKind::Virtual(..) =>
+                        self.instances.borrow()[&instance],
                     InstanceKind::Intrinsic => self.intrinsics.borrow()[&instance],
                 };
                 let val = llfn.into_llvm_ptr().into();
@@ -327,6 +420,35 @@ impl<'a, 'tcx> FnCtx<'a, 'tcx> {
         }
     }
 
+    /// string literals are stored as global constants
+    fn codegen_const_str(&mut self, s: Symbol) -> ValueRef<'tcx> {
+        let s = s.as_str();
+        let global = self.build_global_string_ptr(s, "str");
+        let len = self.types.i64.const_int(s.len() as u64, false);
+        let val = self.llctx.const_struct(&[global.as_pointer_value().into(), len.into()], false);
+        ValueRef { val: val.into(), ty: self.tcx.types.str }
+    }
+
+    fn codegen_str_op(
+        &mut self,
+        op: BinOp,
+        lhs: ValueRef<'tcx>,
+        rhs: ValueRef<'tcx>,
+    ) -> ValueRef<'tcx> {
+        let eq = self
+            .build_call(self.native_functions.str_eq, &[lhs.val.into(), rhs.val.into()], "str_eq")
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_int_value();
+        let val = match op {
+            BinOp::Eq => eq,
+            BinOp::Neq => self.build_not(eq, "str_neq"),
+            _ => unreachable!("unsupported operation on `str`"),
+        };
+        ValueRef { val: val.into(), ty: self.tcx.types.bool }
+    }
+
     fn codegen_int_op(
         &mut self,
         op: BinOp,
@@ -337,16 +459,23 @@ impl<'a, 'tcx> FnCtx<'a, 'tcx> {
         let r = rhs.val.into_int_value();
         let val = match op {
             BinOp::Mul => self.build_int_mul(l, r, "imul").into(),
-            BinOp::Div => self.build_int_signed_div(l, r, "idiv").into(),
+            BinOp::Div if lhs.ty.is_signed() => self.build_int_signed_div(l, r, "idiv").into(),
+            BinOp::Div => self.build_int_unsigned_div(l, r, "udiv").into(),
+            BinOp::Rem if lhs.ty.is_signed() => self.build_int_signed_rem(l, r, "irem").into(),
+            BinOp::Rem => self.build_int_unsigned_rem(l, r, "urem").into(),
             BinOp::Add => self.build_int_add(l, r, "iadd").into(),
             BinOp::Sub => self.build_int_sub(l, r, "isub").into(),
+            BinOp::Shl => self.build_left_shift(l, r, "shl").into(),
+            BinOp::Shr => self.build_right_shift(l, r, lhs.ty.is_signed(), "shr").into(),
             BinOp::And => self.build_and(l, r, "and").into(),
             BinOp::Or => self.build_or(l, r, "or").into(),
-            BinOp::Eq | BinOp::Neq | BinOp::Lt | BinOp::Gt =>
+            BinOp::Xor => self.build_xor(l, r, "xor").into(),
+            BinOp::Eq | BinOp::Neq | BinOp::Lt | BinOp::Gt | BinOp::Le | BinOp::Ge =>
                 return self.compile_icmp(op, lhs, rhs),
+            BinOp::AndAnd | BinOp::OrOr => unreachable!("short-circuiting operator in mir"),
         };
         debug_assert_eq!(lhs.ty, rhs.ty);
-        ValueRef { val, ty: self.tcx.types.int }
+        ValueRef { val, ty: lhs.ty }
     }
 
     fn codegen_float_op(
@@ -361,13 +490,20 @@ impl<'a, 'tcx> FnCtx<'a, 'tcx> {
         let val = match op {
             BinOp::Mul => self.build_float_mul(l, r, "tmpfmul"),
             BinOp::Div => self.build_float_div(l, r, "tmpfdiv"),
+            BinOp::Rem => self.build_float_rem(l, r, "tmpfrem"),
             BinOp::Add => self.build_float_add(l, r, "tmpadd"),
             BinOp::Sub => self.build_float_sub(l, r, "tmpfsub"),
-            BinOp::And | BinOp::Or => unreachable!(),
-            BinOp::Lt | BinOp::Gt | BinOp::Eq | BinOp::Neq =>
+            BinOp::Lt | BinOp::Gt | BinOp::Le | BinOp::Ge | BinOp::Eq | BinOp::Neq =>
                 return self.compile_fcmp(op, lhs, rhs),
+            BinOp::Shl
+            | BinOp::Shr
+            | BinOp::And
+            | BinOp::Or
+            | BinOp::Xor
+            | BinOp::AndAnd
+            | BinOp::OrOr => unreachable!(),
         };
-        ValueRef { val: val.into(), ty: self.tcx.types.float }
+        ValueRef { val: val.into(), ty: lhs.ty }
     }
 
     fn compile_icmp(
@@ -388,13 +524,19 @@ impl<'a, 'tcx> FnCtx<'a, 'tcx> {
                 r = self.build_int_z_extend(r, l.get_type(), "extend_discr");
             }
         }
+        let (lt, gt, le, ge) = if lhs.ty.is_signed() {
+            (IntPredicate::SLT, IntPredicate::SGT, IntPredicate::SLE, IntPredicate::SGE)
+        } else {
+            (IntPredicate::ULT, IntPredicate::UGT, IntPredicate::ULE, IntPredicate::UGE)
+        };
         let val = match op {
-            BinOp::Lt => self.builder.build_int_compare(IntPredicate::SLT, l, r, "icmp_lt"),
-            BinOp::Gt => self.builder.build_int_compare(IntPredicate::SGT, l, r, "icmp_gt"),
+            BinOp::Lt => self.builder.build_int_compare(lt, l, r, "icmp_lt"),
+            BinOp::Gt => self.builder.build_int_compare(gt, l, r, "icmp_gt"),
+            BinOp::Le => self.builder.build_int_compare(le, l, r, "icmp_le"),
+            BinOp::Ge => self.builder.build_int_compare(ge, l, r, "icmp_ge"),
             BinOp::Eq => self.builder.build_int_compare(IntPredicate::EQ, l, r, "icmp_eq"),
             BinOp::Neq => self.build_int_compare(IntPredicate::NE, l, r, "icmp_neq"),
-            BinOp::And | BinOp::Or | BinOp::Mul | BinOp::Div | BinOp::Add | BinOp::Sub =>
-                unreachable!(),
+            _ => unreachable!("`{}` is not a comparison operator", op),
         };
         ValueRef { val: val.into(), ty: self.tcx.types.bool }
     }
@@ -407,14 +549,15 @@ impl<'a, 'tcx> FnCtx<'a, 'tcx> {
     ) -> ValueRef<'tcx> {
         debug_assert_eq!(lhs.ty, rhs.ty);
         let l = lhs.val.into_float_value();
-        let r = lhs.val.into_float_value();
+        let r = rhs.val.into_float_value();
         let val = match op {
             BinOp::Lt => self.builder.build_float_compare(FloatPredicate::OLT, l, r, "fcmp_lt"),
             BinOp::Gt => self.builder.build_float_compare(FloatPredicate::OGT, l, r, "fcmp_gt"),
+            BinOp::Le => self.builder.build_float_compare(FloatPredicate::OLE, l, r, "fcmp_le"),
+            BinOp::Ge => self.builder.build_float_compare(FloatPredicate::OGE, l, r, "fcmp_ge"),
             BinOp::Eq => self.build_float_compare(FloatPredicate::OEQ, l, r, "fcmp_oeq"),
             BinOp::Neq => self.build_float_compare(FloatPredicate::UNE, l, r, "fcmp_une"),
-            BinOp::And | BinOp::Or | BinOp::Mul | BinOp::Div | BinOp::Add | BinOp::Sub =>
-                unreachable!(),
+            _ => unreachable!("`{}` is not a comparison operator", op),
         };
         ValueRef { val: val.into(), ty: self.tcx.types.bool }
     }
@@ -439,16 +582,23 @@ impl<'a, 'tcx> FnCtx<'a, 'tcx> {
                 self.build_unconditional_branch(self.blocks[*block]);
             }
             mir::TerminatorKind::Call { f, args, lvalue, target, unwind: _ } => {
-                let f = self.codegen_operand(f).val.into_pointer_value();
-                let args =
-                    args.iter().map(|arg| self.codegen_operand(arg).val.into()).collect_vec();
-                let value = self
-                    // Safety, we transmuted the function value into a pointer value just to shove
-                    // it into the enum, now transmute it back
-                    .build_call(unsafe { std::mem::transmute(f) }, &args[..], "fcall")
-                    .try_as_basic_value()
-                    .left()
-                    .unwrap();
+                let value = match self.resolve_virtual_call(f) {
+                    Some((instance, idx)) => self.codegen_virtual_call(instance, idx, args),
+                    None => {
+                        let f = self.codegen_operand(f).val.into_pointer_value();
+                        let args = args
+                            .iter()
+                            .map(|arg| self.codegen_operand(arg).val.into())
+                            .collect_vec();
+                        self
+                            // Safety, we transmuted the function value into a pointer value just
+                            // to shove it into the enum, now transmute it back
+                            .build_call(unsafe { std::mem::transmute(f) }, &args[..], "fcall")
+                            .try_as_basic_value()
+                            .left()
+                            .unwrap()
+                    }
+                };
                 let lvalue_ref = self.codegen_lvalue(*lvalue);
                 self.build_store(lvalue_ref.ptr, value);
                 self.build_unconditional_branch(self.blocks[*target]);
@@ -463,6 +613,66 @@ impl<'a, 'tcx> FnCtx<'a, 'tcx> {
                     self.blocks[*els],
                 );
             }
+            mir::TerminatorKind::BoundsCheck { index, len, target } =>
+                self.codegen_bounds_check(index, len, *target),
+        }
+    }
+
+    /// if `f` refers to a method of a trait object, returns its instance and vtable index
+    fn resolve_virtual_call(&self, f: &mir::Operand<'tcx>) -> Option<(Instance<'tcx>, usize)> {
+        match *f {
+            mir::Operand::Item(def_id, substs) => {
+                let instance = Instance::resolve(self.tcx, def_id, self.monomorphize(substs));
+                match instance.kind {
+                    InstanceKind::Virtual(idx) => Some((instance, idx)),
+                    InstanceKind::Item | InstanceKind::Intrinsic => None,
+                }
+            }
+            mir::Operand::Lvalue(..) | mir::Operand::Const(..) => None,
+        }
+    }
+
+    /// calls the method with index `idx` in the vtable of the receiver (the first argument)
+    fn codegen_virtual_call(
+        &mut self,
+        instance: Instance<'tcx>,
+        idx: usize,
+        args: &[mir::Operand<'tcx>],
+    ) -> BasicValueEnum<'tcx> {
+        let (receiver, args) = args.split_first().unwrap();
+        let receiver = self.codegen_operand(receiver).val.into_struct_value();
+        let args = args.iter().map(|arg| self.codegen_operand(arg).val.into()).collect_vec();
+        self.build_virtual_call(instance, idx, receiver, args)
+    }
+
+    fn codegen_bounds_check(
+        &mut self,
+        index: &mir::Operand<'tcx>,
+        len: &mir::Operand<'tcx>,
+        target: BlockId,
+    ) {
+        let index = self.codegen_operand(index);
+        let index = self.extend_index(index.val.into_int_value(), index.ty);
+        let len = self.codegen_operand(len).val.into_int_value();
+        // an unsigned comparison also rejects negative indices
+        let in_bounds = self.build_int_compare(IntPredicate::ULT, index, len, "bounds_check");
+        let fail = self.llctx.append_basic_block(self.llfn, "bounds_check_fail");
+        self.build_conditional_branch(in_bounds, self.blocks[target], fail);
+        self.position_at_end(fail);
+        self.build_call(
+            self.native_functions.index_out_of_bounds,
+            &[index.into(), len.into()],
+            "index_out_of_bounds",
+        );
+        self.builder.build_unreachable();
+    }
+
+    /// extends an index of any integer type `ty` to the `int` that lengths are represented as
+    fn extend_index(&self, index: IntValue<'tcx>, ty: Ty<'tcx>) -> IntValue<'tcx> {
+        if ty.is_signed() {
+            self.build_int_s_extend_or_bit_cast(index, self.types.i64, "index_sext")
+        } else {
+            self.build_int_z_extend_or_bit_cast(index, self.types.i64, "index_zext")
         }
     }
 
@@ -1,8 +1,9 @@
 use crate::CodegenCtx;
+use inkwell::module::Linkage;
 use inkwell::types::BasicType;
-use inkwell::values::FunctionValue;
+use inkwell::values::{BasicMetadataValueEnum, FunctionValue};
 use inkwell::AddressSpace;
-use lc_core::ty::Instance;
+use lc_core::ty::{Instance, Subst, TyKind};
 use lc_span::sym;
 
 impl<'tcx> CodegenCtx<'tcx> {
@@ -14,6 +15,8 @@ impl<'tcx> CodegenCtx<'tcx> {
         let llfn = match ident.symbol {
             sym::addr => self.codegen_addr_intrinsic(instance),
             sym::print => self.native_functions.print,
+            sym::print_str => self.native_functions.print_str,
+            sym::write => self.codegen_write_intrinsic(instance),
             _ => panic!("unknown intrinsic `{}`", ident),
         };
         self.intrinsics.borrow_mut().insert(instance, llfn);
@@ -27,7 +30,7 @@ impl<'tcx> CodegenCtx<'tcx> {
         // `addr<T>: fn(&T) -> int` where the returned int is the address as an i64
         let addr_fn_ty =
             self.types.i64.fn_type(&[llty.ptr_type(AddressSpace::default()).into()], false);
-        let llfn = self.module.add_function(&name, addr_fn_ty, None);
+        let llfn = self.module.add_function(&name, addr_fn_ty, Some(Linkage::Internal));
         let block = self.llctx.append_basic_block(llfn, "addr_entry");
 
         self.position_at_end(block);
@@ -37,4 +40,50 @@ impl<'tcx> CodegenCtx<'tcx> {
         self.build_return(Some(&int));
         llfn
     }
+
+    /// `write<T>: fn(T)` writes the primitive value to stdout without a trailing newline
+    fn codegen_write_intrinsic(&self, instance: Instance<'tcx>) -> FunctionValue<'tcx> {
+        let ident = self.tcx.defs().ident(instance.def_id);
+        let name = format!("{}<{}>", ident, instance.substs);
+        let t = instance.substs[0];
+        let fn_ty = self.tcx.mk_fn_ptr(self.tcx.fn_sig(instance.def_id));
+        let llty = self.llvm_fn_ty_from_ty(fn_ty.subst(self.tcx, instance.substs));
+        let llfn = self.module.add_function(&name, llty, Some(Linkage::Internal));
+        let block = self.llctx.append_basic_block(llfn, "write_entry");
+
+        self.position_at_end(block);
+        let param = llfn.get_first_param().unwrap();
+        // the variadic arguments of `printf` are promoted to 64 bit integers and doubles
+        let (fmt, args): (_, Vec<BasicMetadataValueEnum<'tcx>>) = match t.kind {
+            TyKind::Int(..) => {
+                let int = param.into_int_value();
+                let int = self.build_int_s_extend_or_bit_cast(int, self.types.i64, "sext");
+                ("%lld", vec![int.into()])
+            }
+            TyKind::Uint(..) => {
+                let int = param.into_int_value();
+                let int = self.build_int_z_extend_or_bit_cast(int, self.types.i64, "zext");
+                ("%llu", vec![int.into()])
+            }
+            TyKind::Float(..) => {
+                let float = param.into_float_value();
+                ("%g", vec![self.build_float_cast(float, self.types.float, "fpext").into()])
+            }
+            TyKind::Str => {
+                // the string is not null terminated in general, so we must print it with a
+                // precision
+                let s = param.into_struct_value();
+                let ptr = self.build_extract_value(s, 0, "str_ptr").unwrap();
+                let len = self.build_extract_value(s, 1, "str_len").unwrap().into_int_value();
+                let len = self.build_int_truncate(len, self.types.i32, "str_len_trunc");
+                ("%.*s", vec![len.into(), ptr.into()])
+            }
+            _ => panic!("cannot write a value of type `{}`", t),
+        };
+        let fmt = self.build_global_string_ptr(fmt, "write_fmt").as_pointer_value();
+        let args = std::iter::once(fmt.into()).chain(args).collect::<Vec<_>>();
+        self.build_call(self.native_functions.printf, &args, "printf");
+        self.build_return(Some(&self.vals.unit));
+        llfn
+    }
 }
@@ -80,12 +80,16 @@ impl<'tcx> CodegenCtx<'tcx> {
         }
         let llty = match ty.kind {
             TyKind::Bool => self.types.bool.into(),
-            TyKind::Int => self.types.i64.into(),
+            TyKind::Int(int_ty) => self.llctx.custom_width_int_type(int_ty.bit_width()).into(),
+            TyKind::Uint(uint_ty) => self.llctx.custom_width_int_type(uint_ty.bit_width()).into(),
             TyKind::Discr => self.types.discr.into(),
-            TyKind::Float => self.types.float.into(),
-            TyKind::Char => todo!(),
+            TyKind::Float(FloatTy::F32) => self.llctx.f32_type().into(),
+            TyKind::Float(FloatTy::F64) => self.types.float.into(),
+            // chars are represented as a 32-bit unicode scalar value
+            TyKind::Char => self.types.i32.into(),
+            TyKind::Str => self.types.str.into(),
             TyKind::Tuple(xs) if xs.is_empty() => self.types.unit.into(),
-            TyKind::Array(_ty, _n) => todo!(),
+            TyKind::Array(ty, n) => self.llty(ty).array_type(n as u32).into(),
             TyKind::FnPtr(sig) => self.llvm_fn_ty(sig).ptr_type(AddressSpace::default()).into(),
             TyKind::Tuple(tys) => {
                 // tuples are represented as anonymous structs
@@ -122,13 +126,22 @@ impl<'tcx> CodegenCtx<'tcx> {
                 }
                 return opaque_ty.into();
             }
+            // a pointer to a trait object is a pointer to the data along with the vtable
+            TyKind::Boxed(ty) | TyKind::Ptr(ty) if matches!(ty.kind, TyKind::Dynamic(..)) =>
+                self.types.dyn_ptr.into(),
             // boxes and pointers have the same runtime type
             // however, boxes will have a refcount implicitly stored after the content
             TyKind::Boxed(ty) | TyKind::Ptr(ty) =>
                 self.llty(ty).ptr_type(AddressSpace::default()).into(),
             TyKind::Opaque(..) => todo!(),
-            TyKind::Param(..) | TyKind::Infer(..) | TyKind::Never | TyKind::Error =>
-                unreachable!("{}", ty),
+            // projections are normalized during monomorphization
+            // and trait objects only exist behind a pointer
+            TyKind::Param(..)
+            | TyKind::Infer(..)
+            | TyKind::Projection(..)
+            | TyKind::Dynamic(..)
+            | TyKind::Never
+            | TyKind::Error => unreachable!("{}", ty),
         };
         self.lltypes.borrow_mut().insert(ty, llty);
         llty
@@ -1,7 +1,7 @@
 use ir::{DefId, FnVisitor, ItemVisitor};
-use lc_core::mir::{MirVisitor, Operand, SpanInfo};
+use lc_core::mir::{Mir, MirTy, MirVisitor, Operand, Rvalue, SpanInfo};
 use lc_core::queries::Queries;
-use lc_core::ty::{HasTyFlags, Instance, InstanceKind, Subst, TyCtx, TypeFoldable};
+use lc_core::ty::{HasTyFlags, Instance, InstanceKind, PointerCast, Subst, TyCtx, TypeFoldable};
 use rustc_hash::FxHashSet;
 use std::cell::RefCell;
 use std::ops::Deref;
@@ -50,12 +50,15 @@ impl<'a, 'tcx> MonomorphizationCollector<'a, 'tcx> {
     fn collect_instance(&self, instance: Instance<'tcx>) {
         self.mono_instances.borrow_mut().insert(instance);
         match instance.kind {
+            // the functions of other packages that are linked against have no mir to visit
+            InstanceKind::Item if self.tcx.defs().is_linked_extern(instance.def_id) => {}
             InstanceKind::Item => {
                 let mir = self.tcx.mir_of(instance.def_id);
-                InstanceCollector { collector: self, instance }.visit_mir(mir);
+                InstanceCollector { collector: self, instance, mir }.visit_mir(mir);
             }
             // no need to recurse on intrinsics as they do not have associated mir
-            InstanceKind::Intrinsic => {}
+            // the methods that may be called virtually are collected when the vtable is created
+            InstanceKind::Intrinsic | InstanceKind::Virtual(..) => {}
         }
     }
 }
@@ -95,6 +98,7 @@ impl<'tcx> FnVisitor<'tcx> for RootCollector<'tcx> {
 struct InstanceCollector<'a, 'tcx> {
     collector: &'a MonomorphizationCollector<'a, 'tcx>,
     instance: Instance<'tcx>,
+    mir: &'tcx Mir<'tcx>,
 }
 
 impl<'a, 'tcx> Monomorphize<'tcx> for InstanceCollector<'a, 'tcx> {
@@ -106,7 +110,28 @@ impl<'a, 'tcx> Monomorphize<'tcx> for InstanceCollector<'a, 'tcx> {
     }
 }
 
+impl<'a, 'tcx> InstanceCollector<'a, 'tcx> {
+    fn collect_neighbour(&self, instance: Instance<'tcx>) {
+        if !self.mono_instances.borrow().contains(&instance) {
+            // recursively collect all its neighbours
+            self.collector.collect_instance(instance);
+        }
+    }
+}
+
 impl<'a, 'tcx> MirVisitor<'tcx> for InstanceCollector<'a, 'tcx> {
+    fn visit_rvalue(&mut self, info: SpanInfo, rvalue: &Rvalue<'tcx>) {
+        // an unsizing cast creates a vtable that refers to each method of the impl
+        if let &Rvalue::Cast(PointerCast::Unsize, operand, ty) = rvalue {
+            let source = self.monomorphize(operand.ty(self.tcx, self.mir));
+            let target = self.monomorphize(ty);
+            for instance in self.tcx.vtable_instances(source, target) {
+                self.collect_neighbour(instance);
+            }
+        }
+        self.walk_rvalue(info, rvalue);
+    }
+
     fn visit_operand(&mut self, _info: SpanInfo, operand: &Operand<'tcx>) {
         // `Operand::Item` is currently the only way to reference a generic item
         if let &Operand::Item(def_id, substs) = operand {
@@ -119,11 +144,7 @@ impl<'a, 'tcx> MirVisitor<'tcx> for InstanceCollector<'a, 'tcx> {
             let substs = self.monomorphize(substs);
             debug_assert!(!substs.has_ty_params());
             let instance = Instance::resolve(self.tcx, def_id, substs);
-
-            if !self.mono_instances.borrow().contains(&instance) {
-                // recursively collect all its neighbours
-                self.collector.collect_instance(instance);
-            }
+            self.collect_neighbour(instance);
         }
     }
 }
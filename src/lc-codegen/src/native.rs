@@ -1,35 +1,64 @@
+use crate::codegen_ctx::CommonTypes;
 use inkwell::context::Context;
 use inkwell::module::Linkage;
 use inkwell::module::Module;
 use inkwell::values::*;
-use inkwell::AddressSpace;
+use inkwell::{AddressSpace, IntPredicate};
 use std::ops::Deref;
 
 pub struct NativeFunctions<'tcx> {
     pub abort: FunctionValue<'tcx>,
     pub exit: FunctionValue<'tcx>,
     pub print: FunctionValue<'tcx>,
+    pub print_str: FunctionValue<'tcx>,
     pub printf: FunctionValue<'tcx>,
     pub print_addr: FunctionValue<'tcx>,
+    pub memcmp: FunctionValue<'tcx>,
+    /// `fn(index: int, len: int) -> !`
+    /// reports an out of bounds index to stderr and then aborts
+    pub index_out_of_bounds: FunctionValue<'tcx>,
+    /// `fn(str, str) -> bool`
+    pub str_eq: FunctionValue<'tcx>,
 }
 
 pub struct NativeFunctionsBuilder<'a, 'tcx> {
     llcx: &'tcx Context,
     module: &'a Module<'tcx>,
+    types: &'a CommonTypes<'tcx>,
 }
 
 impl<'a, 'tcx> NativeFunctionsBuilder<'a, 'tcx> {
-    pub fn new(llcx: &'tcx Context, module: &'a Module<'tcx>) -> Self {
-        Self { llcx, module }
+    pub fn new(
+        llcx: &'tcx Context,
+        module: &'a Module<'tcx>,
+        types: &'a CommonTypes<'tcx>,
+    ) -> Self {
+        Self { llcx, module, types }
     }
 
+    /// the functions defined (rather than declared) here are internal as each package has its
+    /// own copy of them
     pub fn build(&self) -> NativeFunctions<'tcx> {
         let printf = self.build_printf();
         let print = self.build_print();
+        let print_str = self.build_print_str();
         let print_addr = self.build_print_addr();
         let abort = self.build_abort();
         let exit = self.build_exit();
-        NativeFunctions { abort, print, exit, print_addr, printf }
+        let memcmp = self.build_memcmp();
+        let str_eq = self.build_str_eq();
+        let index_out_of_bounds = self.build_index_out_of_bounds();
+        NativeFunctions {
+            abort,
+            print,
+            print_str,
+            exit,
+            print_addr,
+            printf,
+            memcmp,
+            index_out_of_bounds,
+            str_eq,
+        }
     }
 
     fn build_print_addr(&self) -> FunctionValue<'tcx> {
@@ -37,7 +66,7 @@ impl<'a, 'tcx> NativeFunctionsBuilder<'a, 'tcx> {
         let printfn = self.module.add_function(
             "print_addr",
             unit.fn_type(&[self.i8_type().ptr_type(AddressSpace::default()).into()], false),
-            None,
+            Some(Linkage::Internal),
         );
         let bb = self.append_basic_block(printfn, "printint");
         let builder = self.create_builder();
@@ -60,8 +89,11 @@ impl<'a, 'tcx> NativeFunctionsBuilder<'a, 'tcx> {
 
     fn build_print(&self) -> FunctionValue<'tcx> {
         let unit = self.struct_type(&[], false);
-        let printfn =
-            self.module.add_function("print", unit.fn_type(&[self.i64_type().into()], false), None);
+        let printfn = self.module.add_function(
+            "print",
+            unit.fn_type(&[self.i64_type().into()], false),
+            Some(Linkage::Internal),
+        );
         let bb = self.append_basic_block(printfn, "printint");
         let builder = self.create_builder();
         builder.position_at_end(bb);
@@ -81,6 +113,123 @@ impl<'a, 'tcx> NativeFunctionsBuilder<'a, 'tcx> {
         printfn
     }
 
+    fn build_print_str(&self) -> FunctionValue<'tcx> {
+        let unit = self.struct_type(&[], false);
+        let printfn = self.module.add_function(
+            "print_str",
+            unit.fn_type(&[self.types.str.into()], false),
+            Some(Linkage::Internal),
+        );
+        let bb = self.append_basic_block(printfn, "printstr");
+        let builder = self.create_builder();
+        builder.position_at_end(bb);
+
+        let param = printfn.get_first_param().unwrap().into_struct_value();
+        let ptr = builder.build_extract_value(param, 0, "str_ptr").unwrap();
+        let len = builder.build_extract_value(param, 1, "str_len").unwrap().into_int_value();
+        // the string is not null terminated in general, so we must print it with a precision
+        let len = builder.build_int_truncate(len, self.i32_type(), "str_len_trunc");
+        let fmt = builder.build_global_string_ptr("%.*s\n", "print_str_fmt");
+        let printf = self.module.get_function("printf").unwrap();
+        builder.build_call(
+            printf,
+            &[fmt.as_pointer_value().into(), len.into(), ptr.into()],
+            "printf",
+        );
+        builder.build_return(Some(&self.const_struct(&[], false)));
+        printfn
+    }
+
+    fn build_str_eq(&self) -> FunctionValue<'tcx> {
+        let str_ty = self.types.str;
+        let str_eq = self.module.add_function(
+            "str_eq",
+            self.bool_type().fn_type(&[str_ty.into(), str_ty.into()], false),
+            Some(Linkage::Internal),
+        );
+        let entry = self.append_basic_block(str_eq, "str_eq_entry");
+        let cmp_bytes = self.append_basic_block(str_eq, "str_eq_cmp_bytes");
+        let ret_false = self.append_basic_block(str_eq, "str_eq_ret_false");
+        let builder = self.create_builder();
+
+        builder.position_at_end(entry);
+        let l = str_eq.get_nth_param(0).unwrap().into_struct_value();
+        let r = str_eq.get_nth_param(1).unwrap().into_struct_value();
+        let llen = builder.build_extract_value(l, 1, "llen").unwrap().into_int_value();
+        let rlen = builder.build_extract_value(r, 1, "rlen").unwrap().into_int_value();
+        let same_len = builder.build_int_compare(IntPredicate::EQ, llen, rlen, "same_len");
+        builder.build_conditional_branch(same_len, cmp_bytes, ret_false);
+
+        // only compare the bytes if the lengths match
+        builder.position_at_end(cmp_bytes);
+        let lptr = builder.build_extract_value(l, 0, "lptr").unwrap();
+        let rptr = builder.build_extract_value(r, 0, "rptr").unwrap();
+        let memcmp = self.module.get_function("memcmp").unwrap();
+        let cmp = builder
+            .build_call(memcmp, &[lptr.into(), rptr.into(), llen.into()], "memcmp")
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_int_value();
+        let eq =
+            builder.build_int_compare(IntPredicate::EQ, cmp, self.i32_type().const_zero(), "eq");
+        builder.build_return(Some(&eq));
+
+        builder.position_at_end(ret_false);
+        builder.build_return(Some(&self.bool_type().const_zero()));
+        str_eq
+    }
+
+    fn build_index_out_of_bounds(&self) -> FunctionValue<'tcx> {
+        let i64_type = self.i64_type();
+        let index_out_of_bounds = self.module.add_function(
+            "index_out_of_bounds",
+            self.void_type().fn_type(&[i64_type.into(), i64_type.into()], false),
+            Some(Linkage::Internal),
+        );
+        let bb = self.append_basic_block(index_out_of_bounds, "index_out_of_bounds_entry");
+        let builder = self.create_builder();
+        builder.position_at_end(bb);
+
+        let index = index_out_of_bounds.get_nth_param(0).unwrap();
+        let len = index_out_of_bounds.get_nth_param(1).unwrap();
+        // write directly to stderr as any buffered output is lost on abort
+        let fmt = builder.build_global_string_ptr(
+            "index out of bounds: the length is %lld but the index is %lld\n",
+            "index_out_of_bounds_fmt",
+        );
+        let stderr = self.i32_type().const_int(2, false);
+        let dprintf = self.build_dprintf();
+        builder.build_call(
+            dprintf,
+            &[stderr.into(), fmt.as_pointer_value().into(), len.into(), index.into()],
+            "dprintf",
+        );
+        let abort = self.module.get_function("abort").unwrap();
+        builder.build_call(abort, &[], "abort");
+        builder.build_unreachable();
+        index_out_of_bounds
+    }
+
+    fn build_dprintf(&self) -> FunctionValue<'tcx> {
+        let i8ptr = self.i8_type().ptr_type(AddressSpace::default());
+        self.module.add_function(
+            "dprintf",
+            self.i32_type().fn_type(&[self.i32_type().into(), i8ptr.into()], true),
+            Some(Linkage::External),
+        )
+    }
+
+    fn build_memcmp(&self) -> FunctionValue<'tcx> {
+        let i8ptr = self.i8_type().ptr_type(AddressSpace::default());
+        self.module.add_function(
+            "memcmp",
+            self.i32_type()
+                .fn_type(&[i8ptr.into(), i8ptr.into(), self.i64_type().into()], false),
+            Some(Linkage::External),
+        )
+    }
+
     fn build_printf(&self) -> FunctionValue<'tcx> {
         self.module.add_function(
             "printf",
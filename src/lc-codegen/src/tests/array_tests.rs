@@ -0,0 +1,68 @@
+use super::*;
+
+#[test]
+fn llvm_array_literal_index() {
+    let src = r#"
+    fn main() -> int {
+        let xs = [4, 5, 6];
+        xs[0] + xs[2]
+    }"#;
+    assert_eq!(llvm_jit!(src), 10);
+}
+
+#[test]
+fn llvm_array_repeat_assign_index() {
+    let src = r#"
+    fn main() -> int {
+        let mut xs = [1; 5];
+        let mut i = 0;
+        loop {
+            if i >= 5 {
+                break
+            };
+            xs[i] *= i;
+            i += 1;
+        };
+        xs[3] + xs[4]
+    }"#;
+    assert_eq!(llvm_jit!(src), 7);
+}
+
+#[test]
+fn llvm_nested_array_index() {
+    let src = r#"
+    fn main() -> int {
+        let mut grid = [[0; 2], [3, 4]];
+        grid[0][1] = 9;
+        grid[0][1] - grid[1][0]
+    }"#;
+    assert_eq!(llvm_jit!(src), 6);
+}
+
+#[test]
+fn llvm_array_param_and_field() {
+    let src = r#"
+    struct S { xs: [int; 2] }
+
+    fn second(xs: [int; 2]) -> int {
+        xs[1]
+    }
+
+    fn main() -> int {
+        let s = S { xs: [7, 8] };
+        second(s.xs)
+    }"#;
+    assert_eq!(llvm_jit!(src), 8);
+}
+
+#[test]
+fn llvm_array_index_out_of_bounds_aborts() {
+    let src = r#"
+    fn main() -> int {
+        let xs = [1, 2, 3];
+        let i = 3;
+        xs[i]
+    }"#;
+    // the process is killed by `SIGABRT` so there is no exit code
+    assert_eq!(llvm_exec_inner!(src), None);
+}
@@ -29,3 +29,22 @@ fn llvm_loop_break() {
 
     assert_eq!(llvm_jit!(src), 6);
 }
+
+#[test]
+fn llvm_loop_compound_assignment() {
+    let src = r#"
+    fn main() -> int {
+        let mut x = 0;
+        let mut sum = 0;
+        loop {
+            if x > 5 {
+                break
+            };
+            sum += x;
+            x += 1;
+        };
+        sum
+    }"#;
+
+    assert_eq!(llvm_jit!(src), 15);
+}
@@ -39,3 +39,78 @@ fn generic_impl() {
 
     assert_eq!(llvm_jit!(src), 5);
 }
+
+#[test]
+fn method_call() {
+    let src = r#"
+    struct Counter {
+        n: int,
+    }
+
+    impl Counter {
+        fn get(&self) -> int {
+            self.n
+        }
+
+        fn add(self, k: int) -> Counter {
+            Counter { n: self.n + k }
+        }
+    }
+
+    fn main() -> int {
+        let c = Counter { n: 5 };
+        c.add(3).add(1).get()
+    }
+    "#;
+
+    assert_eq!(llvm_jit!(src), 9);
+}
+
+#[test]
+fn method_call_autoref_receiver_is_mutated_in_place() {
+    let src = r#"
+    struct Counter {
+        n: int,
+    }
+
+    impl Counter {
+        fn set(&self, k: int) {
+            self.n = k;
+        }
+    }
+
+    fn main() -> int {
+        let c = Counter { n: 5 };
+        c.set(7);
+        c.n
+    }
+    "#;
+
+    assert_eq!(llvm_jit!(src), 7);
+}
+
+#[test]
+fn method_call_autoderef_receiver() {
+    let src = r#"
+    struct S<T> {
+        t: T,
+    }
+
+    impl<T> S<T> {
+        fn by_ref(&self) -> T {
+            self.t
+        }
+
+        fn by_value(self) -> T {
+            self.t
+        }
+    }
+
+    fn main() -> int {
+        let s = box box S { t: 4 };
+        s.by_ref() + s.by_value()
+    }
+    "#;
+
+    assert_eq!(llvm_jit!(src), 8);
+}
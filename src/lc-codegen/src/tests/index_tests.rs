@@ -0,0 +1,33 @@
+use super::*;
+
+#[test]
+fn llvm_index_with_unsigned_index() {
+    let src = r#"
+    fn main() -> int {
+        let xs = [4, 5, 6];
+        let i: u8 = 2;
+        xs[i] + xs[1u64]
+    }"#;
+    assert_eq!(llvm_jit!(src), 11);
+}
+
+#[test]
+fn llvm_index_bounds_check_aborts() {
+    // the process is killed by `SIGABRT` so there is no exit code
+    let src = r#"
+    fn main() -> int {
+        let xs = [4, 5, 6];
+        let i: u8 = 255;
+        xs[i]
+    }"#;
+    assert_eq!(llvm_exec_inner!(src), None);
+
+    // negative indices are rejected rather than wrapping around
+    let src = r#"
+    fn main() -> int {
+        let xs = [4, 5, 6];
+        let i: i8 = -1;
+        xs[i]
+    }"#;
+    assert_eq!(llvm_exec_inner!(src), None);
+}
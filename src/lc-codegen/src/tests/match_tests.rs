@@ -51,6 +51,43 @@ fn simple_literal_match() {
     assert_eq!(llvm_jit!(src), 34);
 }
 
+#[test]
+fn string_literal_match() {
+    let src = r#"
+    fn main() -> int {
+        classify("world") + classify("wor") + classify("hello")
+    }
+
+    fn classify(s: str) -> int {
+        match s {
+            "hello" => 1,
+            "world" => 10,
+            _ => 100,
+        }
+    }"#;
+
+    assert_eq!(llvm_jit!(src), 111);
+}
+
+#[test]
+fn char_literal_match() {
+    let src = r#"
+    fn main() -> int {
+        classify('a') + classify('\n') + classify('\u{3bb}') + classify('z')
+    }
+
+    fn classify(c: char) -> int {
+        match c {
+            'a' => 1,
+            '\n' => 10,
+            'λ' => 100,
+            _ => 1000,
+        }
+    }"#;
+
+    assert_eq!(llvm_jit!(src), 1111);
+}
+
 #[test]
 fn simple_expr_match() {
     let src = r#"
@@ -1,14 +1,19 @@
+mod array_tests;
 mod closure_tests;
 mod control_flow_tests;
 mod enum_tests;
 mod impl_tests;
+mod index_tests;
 mod lltype_tests;
 mod match_tests;
 mod monomorphization_tests;
+mod numeric_tests;
 mod output_tests;
+mod package_tests;
 mod pattern_tests;
 mod ptr_tests;
 mod struct_tests;
+mod trait_tests;
 
 pub macro llvm_jit_inner($src:expr) {
     lc_driver::Driver::from_src($src).llvm_jit()
@@ -0,0 +1,100 @@
+use super::*;
+
+#[test]
+fn llvm_unsigned_comparison() {
+    // 200 would be negative if interpreted as an `i8`
+    let src = r#"
+    fn main() -> int {
+        let x = 200u8;
+        if x > 100 { 1 } else { 0 }
+    }"#;
+
+    assert_eq!(llvm_jit!(src), 1);
+}
+
+#[test]
+fn llvm_unsigned_division() {
+    let src = r#"
+    fn main() -> int {
+        match 250u8 / 2 {
+            125 => 1,
+            _ => 0,
+        }
+    }"#;
+
+    assert_eq!(llvm_jit!(src), 1);
+}
+
+#[test]
+fn llvm_inferred_int_width() {
+    let src = r#"
+    fn main() -> int {
+        let x: i32 = 7;
+        let y = 2;
+        match x / y {
+            3 => 1,
+            _ => 0,
+        }
+    }"#;
+
+    assert_eq!(llvm_jit!(src), 1);
+}
+
+#[test]
+fn llvm_f32_arithmetic() {
+    let src = r#"
+    fn main() -> int {
+        let x = 1.5f32;
+        if x + 1.0 > 2.0 { 1 } else { 0 }
+    }"#;
+
+    assert_eq!(llvm_jit!(src), 1);
+}
+
+#[test]
+fn llvm_remainder() {
+    let src = r#"
+    fn main() -> int {
+        let x = (0 - 7) % 3;
+        let y = 250u8 % 7;
+        let z = 7.5 % 2.0;
+        if x == 0 - 1 && y == 5 && z == 1.5 { 1 } else { 0 }
+    }"#;
+
+    assert_eq!(llvm_jit!(src), 1);
+}
+
+#[test]
+fn llvm_shifts() {
+    let src = r#"
+    fn main() -> int {
+        // arithmetic shift for signed integers, logical shift for unsigned integers
+        let x: i8 = 0 - 16;
+        let y = 128u8 >> 7;
+        let z = 1 << 4;
+        if x >> 2 == 0 - 4 && y == 1 && z == 16 { 1 } else { 0 }
+    }"#;
+
+    assert_eq!(llvm_jit!(src), 1);
+}
+
+#[test]
+fn llvm_bitwise_operators() {
+    let src = r#"
+    fn main() -> int {
+        (12 & 10) | (12 ^ 10) << 8
+    }"#;
+
+    assert_eq!(llvm_jit!(src), 8 | (6 << 8));
+}
+
+#[test]
+fn llvm_inclusive_comparisons() {
+    let src = r#"
+    fn main() -> int {
+        let x = 200u8;
+        if x >= 200 && x <= 200 && 1.0 <= 1.0 && 0 - 1 <= 0 { 1 } else { 0 }
+    }"#;
+
+    assert_eq!(llvm_jit!(src), 1);
+}
@@ -0,0 +1,167 @@
+#[test]
+fn test_link_dependency_with_clashing_names() {
+    // both packages define `f` and instantiate `id<int>`
+    let dep_src = r#"
+    fn id<T>(t: T) -> T { t }
+    pub fn f() -> int { id(1) }"#;
+
+    let src = r#"
+    fn id<T>(t: T) -> T { t }
+    fn f() -> int { id(7) }
+    fn main() -> int { f() }"#;
+
+    let driver = lc_driver::Driver::from_src_with_dependencies(src, &[("dep", dep_src)]);
+    assert_eq!(driver.run().unwrap(), Some(7));
+}
+
+#[test]
+fn test_call_dependency_fn() {
+    let dep_src = r#"
+    pub fn add(x: int, y: int) -> int { x + y }"#;
+
+    let src = r#"
+    fn main() -> int { dep::add(3, 4) }"#;
+
+    let driver = lc_driver::Driver::from_src_with_dependencies(src, &[("dep", dep_src)]);
+    assert_eq!(driver.run().unwrap(), Some(7));
+}
+
+#[test]
+fn test_instantiate_generic_dependency_fn() {
+    // `snd<bool, int>` is instantiated from the mir in the metadata of `dep`
+    let dep_src = r#"
+    pub fn snd<T, U>(t: T, u: U) -> U { u }"#;
+
+    let src = r#"
+    fn main() -> int { dep::snd(false, 7) }"#;
+
+    let driver = lc_driver::Driver::from_src_with_dependencies(src, &[("dep", dep_src)]);
+    assert_eq!(driver.run().unwrap(), Some(7));
+}
+
+#[test]
+fn test_use_dependency_adts() {
+    let dep_src = r#"
+    pub enum Shape {
+        Square(int),
+        Rect(int, int),
+    }
+
+    pub struct Point { pub x: int, pub y: int }"#;
+
+    let src = r#"
+    fn area(shape: dep::Shape) -> int {
+        match shape {
+            dep::Shape::Square(side) => side * side,
+            dep::Shape::Rect(w, h) => w * h,
+        }
+    }
+
+    fn main() -> int {
+        let p = dep::Point { x: 2, y: 1 };
+        area(dep::Shape::Rect(p.x, 3)) + p.y
+    }"#;
+
+    let driver = lc_driver::Driver::from_src_with_dependencies(src, &[("dep", dep_src)]);
+    assert_eq!(driver.run().unwrap(), Some(7));
+}
+
+#[test]
+fn test_use_transitive_dependency() {
+    // the signatures and generic mir of `outer` refer to the definitions of `inner`
+    let inner_src = r#"
+    pub struct Wrapper<T> { pub value: T }
+    pub fn unwrap<T>(w: Wrapper<T>) -> T { w.value }
+    pub fn seven() -> int { 7 }"#;
+
+    let outer_src = r#"
+    pub fn wrap<T>(value: T) -> inner::Wrapper<T> { inner::Wrapper { value: value } }
+    pub fn get<T>(value: T) -> T { inner::unwrap(wrap(value)) }
+    pub fn seven() -> int { inner::seven() }"#;
+
+    let src = r#"
+    fn main() -> int { outer::get(outer::seven()) }"#;
+
+    let dependencies = [("inner", inner_src), ("outer", outer_src)];
+    let driver = lc_driver::Driver::from_src_with_dependencies(src, &dependencies);
+    assert_eq!(driver.run().unwrap(), Some(7));
+}
+
+#[test]
+fn test_call_dependency_methods() {
+    // `perimeter` is the default body of `Shape`, which is instantiated from the mir of `dep`
+    let dep_src = r#"
+    pub struct Counter { pub n: int }
+
+    impl Counter {
+        pub fn new(n: int) -> Counter { Counter { n } }
+        pub fn get(&self) -> int { self.n }
+    }
+
+    pub trait Shape {
+        fn sides(&self) -> int;
+        fn perimeter(&self, len: int) -> int { self.sides() * len }
+    }
+
+    impl Shape for Counter {
+        fn sides(&self) -> int { self.n }
+    }"#;
+
+    let src = r#"
+    fn main() -> int {
+        let counter = dep::Counter::new(3);
+        counter.get() + counter.sides() + counter.perimeter(2)
+    }"#;
+
+    let driver = lc_driver::Driver::from_src_with_dependencies(src, &[("dep", dep_src)]);
+    assert_eq!(driver.run().unwrap(), Some(12));
+}
+
+#[test]
+fn test_impl_dependency_trait() {
+    // the generic `total` of `dep` selects the impl of this package
+    let dep_src = r#"
+    pub trait Shape {
+        fn sides(&self) -> int;
+        fn perimeter(&self, len: int) -> int { self.sides() * len }
+    }
+
+    pub fn total<T: Shape>(shape: T, len: int) -> int { shape.perimeter(len) + shape.sides() }"#;
+
+    let src = r#"
+    struct Triangle;
+
+    impl dep::Shape for Triangle {
+        fn sides(&self) -> int { 3 }
+    }
+
+    fn main() -> int { dep::total(Triangle, 2) + Triangle.perimeter(10) }"#;
+
+    let driver = lc_driver::Driver::from_src_with_dependencies(src, &[("dep", dep_src)]);
+    assert_eq!(driver.run().unwrap(), Some(39));
+}
+
+#[test]
+fn test_prelude_traits_across_packages() {
+    // both packages refer to the same `Eq` and `Clone` of the prelude package
+    let dep_src = r#"
+    #[derive(Eq, Clone)]
+    pub struct Point { pub x: int, pub y: int }
+
+    pub fn dup<T: Clone>(t: T) -> T { t.clone() }"#;
+
+    let src = r#"
+    #[derive(Clone)]
+    struct Local { z: int }
+
+    fn main() -> int {
+        let p = dep::Point { x: 3, y: 4 };
+        let q = dep::dup(p);
+        let r = dep::Point { x: 3, y: 0 };
+        let local = dep::dup(Local { z: 5 });
+        if p == q && p != r { q.x + q.y + local.z } else { 0 }
+    }"#;
+
+    let driver = lc_driver::Driver::from_src_with_dependencies(src, &[("dep", dep_src)]);
+    assert_eq!(driver.run().unwrap(), Some(12));
+}
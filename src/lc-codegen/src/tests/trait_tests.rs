@@ -0,0 +1,297 @@
+use super::*;
+
+#[test]
+fn trait_method_call() {
+    let src = r#"
+    trait Area {
+        fn area(&self) -> int;
+    }
+
+    struct Square { side: int }
+    struct Rect { w: int, h: int }
+
+    impl Area for Square {
+        fn area(&self) -> int { self.side * self.side }
+    }
+
+    impl Area for Rect {
+        fn area(&self) -> int { self.w * self.h }
+    }
+
+    fn main() -> int {
+        let s = Square { side: 3 };
+        let r = Rect { w: 2, h: 5 };
+        s.area() + r.area()
+    }
+    "#;
+
+    assert_eq!(llvm_jit!(src), 19);
+}
+
+#[test]
+fn trait_relative_path() {
+    let src = r#"
+    trait Double {
+        fn double(self) -> int;
+    }
+
+    impl Double for int {
+        fn double(self) -> int { self * 2 }
+    }
+
+    fn main() -> int {
+        Double::double(21)
+    }
+    "#;
+
+    assert_eq!(llvm_jit!(src), 42);
+}
+
+#[test]
+fn trait_method_call_through_bound() {
+    let src = r#"
+    trait Value {
+        fn value(self) -> int;
+    }
+
+    impl Value for int {
+        fn value(self) -> int { self }
+    }
+
+    impl Value for bool {
+        fn value(self) -> int { if self { 1 } else { 0 } }
+    }
+
+    fn sum<T: Value, U>(t: T, u: U) -> int where U: Value {
+        t.value() + u.value()
+    }
+
+    fn main() -> int {
+        sum(41, true)
+    }
+    "#;
+
+    assert_eq!(llvm_jit!(src), 42);
+}
+
+#[test]
+fn generic_trait_impl() {
+    let src = r#"
+    trait Value {
+        fn value(self) -> int;
+    }
+
+    struct W<T> { t: T }
+
+    impl Value for int {
+        fn value(self) -> int { self }
+    }
+
+    impl<T: Value> Value for W<T> {
+        fn value(self) -> int { self.t.value() + 1 }
+    }
+
+    fn main() -> int {
+        let w = W { t: W { t: 40 } };
+        w.value()
+    }
+    "#;
+
+    assert_eq!(llvm_jit!(src), 42);
+}
+
+#[test]
+fn generic_trait_method() {
+    let src = r#"
+    trait Pick<T> {
+        fn pick<U>(self, t: T, u: U) -> T;
+    }
+
+    struct S;
+
+    impl Pick<int> for S {
+        fn pick<U>(self, t: int, u: U) -> int { t }
+    }
+
+    fn main() -> int {
+        S.pick(42, false)
+    }
+    "#;
+
+    assert_eq!(llvm_jit!(src), 42);
+}
+
+#[test]
+fn default_trait_method() {
+    let src = r#"
+    trait Shape {
+        fn sides(&self) -> int;
+        fn perimeter(&self, len: int) -> int { self.sides() * len }
+    }
+
+    struct Triangle;
+    struct Square;
+
+    impl Shape for Triangle {
+        fn sides(&self) -> int { 3 }
+    }
+
+    impl Shape for Square {
+        fn sides(&self) -> int { 4 }
+    }
+
+    fn main() -> int {
+        let triangle = Triangle;
+        let square = Square;
+        triangle.perimeter(6) + square.perimeter(6)
+    }
+    "#;
+
+    assert_eq!(llvm_jit!(src), 42);
+}
+
+#[test]
+fn overridden_default_trait_method() {
+    let src = r#"
+    trait Value {
+        fn value(self) -> int { 0 }
+    }
+
+    struct S;
+    struct T;
+
+    impl Value for S {}
+
+    impl Value for T {
+        fn value(self) -> int { 42 }
+    }
+
+    fn main() -> int {
+        S.value() + T.value()
+    }
+    "#;
+
+    assert_eq!(llvm_jit!(src), 42);
+}
+
+#[test]
+fn assoc_ty_projection_in_generic_fn() {
+    let src = r#"
+    trait Container {
+        type Item;
+        fn get(&self) -> Self::Item;
+    }
+
+    struct Wrapper { x: int }
+    struct Flag { b: bool }
+
+    impl Container for Wrapper {
+        type Item = int;
+        fn get(&self) -> int { self.x }
+    }
+
+    impl Container for Flag {
+        type Item = bool;
+        fn get(&self) -> Self::Item { self.b }
+    }
+
+    fn get<C: Container>(c: C) -> C::Item { c.get() }
+
+    fn main() -> int {
+        let w = Wrapper { x: 41 };
+        let f = Flag { b: true };
+        if get(f) { get(w) + 1 } else { 0 }
+    }
+    "#;
+
+    assert_eq!(llvm_jit!(src), 42);
+}
+
+#[test]
+fn dynamic_dispatch_through_vtable() {
+    let src = r#"
+    trait Shape {
+        fn area(&self) -> int;
+        fn sides(&self) -> int { 0 }
+    }
+
+    struct Square { side: int }
+    struct Rect { w: int, h: int }
+
+    impl Shape for Square {
+        fn area(&self) -> int { self.side * self.side }
+        fn sides(&self) -> int { 4 }
+    }
+
+    impl Shape for Rect {
+        fn area(&self) -> int { self.w * self.h }
+    }
+
+    fn describe(shape: &dyn Shape) -> int { shape.area() + shape.sides() }
+
+    fn main() -> int {
+        let shapes: [&dyn Shape; 2] = [box Square { side: 5 }, box Rect { w: 2, h: 6 }];
+        describe(shapes[0]) + shapes[1].area() + shapes[1].sides()
+    }
+    "#;
+
+    assert_eq!(llvm_jit!(src), 41);
+}
+
+#[test]
+fn trait_object_method_as_value() {
+    let src = r#"
+    trait Shape {
+        fn area(&self) -> int;
+    }
+
+    struct Square { side: int }
+
+    impl Shape for Square {
+        fn area(&self) -> int { self.side * self.side }
+    }
+
+    fn apply(f: fn(&dyn Shape) -> int, shape: &dyn Shape) -> int { f(shape) }
+
+    fn main() -> int {
+        let shape: &dyn Shape = box Square { side: 6 };
+        apply(Shape::area, shape)
+    }
+    "#;
+
+    assert_eq!(llvm_jit!(src), 36);
+}
+
+#[test]
+fn select_impl_with_disjoint_bounds() {
+    // the headers of both impls match `P<bool, int>` but only the bounds of the second hold
+    let src = r#"
+    trait A {}
+    trait B {}
+
+    impl A for int {}
+    impl B for int {}
+
+    trait Tr {
+        fn f(self) -> int;
+    }
+
+    struct P<T, U> { t: T, u: U }
+
+    impl<T: A> Tr for P<T, int> {
+        fn f(self) -> int { 1 }
+    }
+
+    impl<T: B> Tr for P<bool, T> {
+        fn f(self) -> int { 2 }
+    }
+
+    fn main() -> int {
+        let p: P<int, int> = P { t: 0, u: 0 };
+        let q: P<bool, int> = P { t: true, u: 0 };
+        p.f() * 10 + q.f()
+    }
+    "#;
+
+    assert_eq!(llvm_jit!(src), 12);
+}
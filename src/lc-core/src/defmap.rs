@@ -1,8 +1,8 @@
 //! methods for manipulating ir on `TyCtx`
 use crate::ty::TyCtx;
-use ir::{DefId, DefKind, DefNode};
+use ir::{DefId, DefKind, DefNode, ExternFnKind};
 use lc_ast::Ident;
-use lc_span::Span;
+use lc_span::{Span, Symbol};
 
 impl<'tcx> TyCtx<'tcx> {
     pub fn impl_item(self, id: ir::ImplItemId) -> &'tcx ir::ImplItem<'tcx> {
@@ -24,11 +24,43 @@ impl<'tcx> DefMap<'tcx> {
         self.tcx.resolutions.defs.get_def_node(def_id)
     }
 
+    /// whether `def_id` is a definition of another package
+    pub fn is_extern(&self, def_id: DefId) -> bool {
+        self.tcx.resolutions.defs.extern_def(def_id).is_some()
+    }
+
+    /// how the function `def_id` of another package is instantiated
+    /// (`None` if `def_id` is not a function of another package)
+    pub fn extern_fn_kind(&self, def_id: DefId) -> Option<ExternFnKind> {
+        self.tcx.resolutions.defs.extern_def(def_id).and_then(|extern_def| extern_def.fn_kind)
+    }
+
+    /// whether `def_id` is a function of another package that is linked against
+    /// (rather than instantiated within this package)
+    pub fn is_linked_extern(&self, def_id: DefId) -> bool {
+        matches!(self.extern_fn_kind(def_id), Some(ExternFnKind::Linked(..)))
+    }
+
+    /// the definitions of other packages of the kind `def_kind` (e.g. their impls, which are not
+    /// reachable by any path but must still be considered by trait selection)
+    pub fn extern_defs_of_kind(&self, def_kind: DefKind) -> impl Iterator<Item = DefId> {
+        self.tcx
+            .resolutions
+            .defs
+            .extern_defs()
+            .filter(move |(_, extern_def)| extern_def.def_kind == Some(def_kind))
+            .map(|(def_id, _)| def_id)
+    }
+
     pub fn def_kind(&self, _def_id: DefId) -> DefKind {
         todo!()
     }
 
+    /// the definitions of other packages have no span within this package
     pub fn span(&self, def_id: DefId) -> Span {
+        if self.is_extern(def_id) {
+            return Span::default();
+        }
         match self.get(def_id) {
             DefNode::Item(item) => item.span,
             DefNode::ImplItem(item) => item.span,
@@ -48,9 +80,11 @@ impl<'tcx> DefMap<'tcx> {
             },
             DefNode::ImplItem(impl_item) => match impl_item.kind {
                 ir::ImplItemKind::Fn(_, body) => body,
+                ir::ImplItemKind::TyAlias(..) => panic!(),
             },
             DefNode::TraitItem(trait_item) => match trait_item.kind {
                 ir::TraitItemKind::Fn(_, body) => body.unwrap(),
+                ir::TraitItemKind::TyAlias(..) => panic!(),
             },
             DefNode::ForeignItem(..)
             | DefNode::Ctor(..)
@@ -86,6 +120,9 @@ impl<'tcx> DefMap<'tcx> {
     }
 
     pub fn ident(&self, def_id: DefId) -> Ident {
+        if let Some(extern_def) = self.tcx.resolutions.defs.extern_def(def_id) {
+            return extern_def.ident;
+        }
         match self.get(def_id) {
             DefNode::TyParam(param) => param.ident,
             DefNode::Item(item) => item.ident,
@@ -99,4 +136,20 @@ impl<'tcx> DefMap<'tcx> {
             DefNode::Field(field) => field.ident,
         }
     }
+
+    /// the attributes on the definition (variants and type parameters never have any)
+    pub fn attrs(&self, def_id: DefId) -> &'tcx [ir::Attribute] {
+        match self.get(def_id) {
+            DefNode::Item(item) => item.attrs,
+            DefNode::ImplItem(impl_item) => impl_item.attrs,
+            DefNode::TraitItem(trait_item) => trait_item.attrs,
+            DefNode::ForeignItem(foreign_item) => foreign_item.attrs,
+            DefNode::Field(field) => field.attrs,
+            DefNode::Ctor(..) | DefNode::Variant(..) | DefNode::TyParam(..) => &[],
+        }
+    }
+
+    pub fn has_attr(&self, def_id: DefId, name: Symbol) -> bool {
+        self.attrs(def_id).iter().any(|attr| attr.ident.symbol == name)
+    }
 }
@@ -0,0 +1,339 @@
+//! encoding and decoding of mir (for the instantiation of generic functions of other packages)
+
+use super::*;
+use crate::ty::{fieldless_codec, idx_codec, TyDecodable, TyDecoder, TyEncodable, TyEncoder};
+use lc_ast::BinOp;
+
+idx_codec!(BlockId, VarId);
+
+fieldless_codec!(VarKind {
+    VarKind::Tmp,
+    VarKind::Local,
+    VarKind::Arg,
+    VarKind::Ret,
+    VarKind::Upvar,
+});
+fieldless_codec!(UnaryOp { UnaryOp::Neg, UnaryOp::Not });
+fieldless_codec!(Mutability { Mutability::Mut, Mutability::Imm });
+fieldless_codec!(PointerCast { PointerCast::ReifyFn, PointerCast::Unsize, PointerCast::Autoref });
+fieldless_codec!(BinOp {
+    BinOp::Mul,
+    BinOp::Div,
+    BinOp::Rem,
+    BinOp::Add,
+    BinOp::Sub,
+    BinOp::Shl,
+    BinOp::Shr,
+    BinOp::Lt,
+    BinOp::Gt,
+    BinOp::Le,
+    BinOp::Ge,
+    BinOp::Eq,
+    BinOp::Neq,
+    BinOp::And,
+    BinOp::Or,
+    BinOp::Xor,
+    BinOp::AndAnd,
+    BinOp::OrOr,
+});
+
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for Mir<'tcx> {
+    fn encode(&self, e: &mut E) {
+        self.basic_blocks.encode(e);
+        self.vars.encode(e);
+        self.argc.encode(e);
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for Mir<'tcx> {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        Ok(Mir {
+            basic_blocks: IndexVec::decode(d)?,
+            vars: IndexVec::decode(d)?,
+            argc: usize::decode(d)?,
+        })
+    }
+}
+
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for BasicBlock<'tcx> {
+    fn encode(&self, e: &mut E) {
+        self.stmts.encode(e);
+        self.terminator().encode(e);
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for BasicBlock<'tcx> {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        Ok(BasicBlock { stmts: Vec::decode(d)?, terminator: Some(Terminator::decode(d)?) })
+    }
+}
+
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for SpanInfo {
+    fn encode(&self, e: &mut E) {
+        self.span.encode(e)
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for SpanInfo {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        Ok(SpanInfo { span: Span::decode(d)? })
+    }
+}
+
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for Var<'tcx> {
+    fn encode(&self, e: &mut E) {
+        self.info.encode(e);
+        self.kind.encode(e);
+        self.ty.encode(e);
+        self.mtbl.encode(e);
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for Var<'tcx> {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        Ok(Var {
+            info: SpanInfo::decode(d)?,
+            kind: VarKind::decode(d)?,
+            ty: Ty::decode(d)?,
+            mtbl: Mutability::decode(d)?,
+        })
+    }
+}
+
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for Stmt<'tcx> {
+    fn encode(&self, e: &mut E) {
+        self.info.encode(e);
+        match &self.kind {
+            StmtKind::Assign(lvalue, rvalue) => {
+                e.emit_u8(0);
+                lvalue.encode(e);
+                rvalue.encode(e);
+            }
+            StmtKind::Nop => e.emit_u8(1),
+        }
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for Stmt<'tcx> {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        let info = SpanInfo::decode(d)?;
+        let kind = match d.read_u8()? {
+            0 => StmtKind::Assign(Lvalue::decode(d)?, Rvalue::decode(d)?),
+            1 => StmtKind::Nop,
+            tag => return Err(d.invalid_tag(tag, "StmtKind")),
+        };
+        Ok(Stmt { info, kind })
+    }
+}
+
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for Lvalue<'tcx> {
+    fn encode(&self, e: &mut E) {
+        self.id.encode(e);
+        let projs: &[Projection<'tcx>] = self.projs;
+        projs.encode(e);
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for Lvalue<'tcx> {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        let id = VarId::decode(d)?;
+        let projs = Vec::<Projection<'tcx>>::decode(d)?;
+        Ok(Lvalue { id, projs: d.tcx().intern_lvalue_projections(&projs) })
+    }
+}
+
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for Operand<'tcx> {
+    fn encode(&self, e: &mut E) {
+        match self {
+            Operand::Lvalue(lvalue) => {
+                e.emit_u8(0);
+                lvalue.encode(e);
+            }
+            Operand::Const(c) => {
+                e.emit_u8(1);
+                c.encode(e);
+            }
+            Operand::Item(def_id, substs) => {
+                e.emit_u8(2);
+                def_id.encode(e);
+                substs.encode(e);
+            }
+        }
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for Operand<'tcx> {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        let operand = match d.read_u8()? {
+            0 => Operand::Lvalue(Lvalue::decode(d)?),
+            1 => Operand::Const(<&Const<'tcx>>::decode(d)?),
+            2 => Operand::Item(DefId::decode(d)?, SubstsRef::decode(d)?),
+            tag => return Err(d.invalid_tag(tag, "Operand")),
+        };
+        Ok(operand)
+    }
+}
+
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for Rvalue<'tcx> {
+    fn encode(&self, e: &mut E) {
+        match self {
+            Rvalue::Box(operand) => {
+                e.emit_u8(0);
+                operand.encode(e);
+            }
+            Rvalue::Operand(operand) => {
+                e.emit_u8(1);
+                operand.encode(e);
+            }
+            Rvalue::Unary(op, operand) => {
+                e.emit_u8(2);
+                op.encode(e);
+                operand.encode(e);
+            }
+            Rvalue::Bin(op, l, r) => {
+                e.emit_u8(3);
+                op.encode(e);
+                l.encode(e);
+                r.encode(e);
+            }
+            Rvalue::Ref(lvalue) => {
+                e.emit_u8(4);
+                lvalue.encode(e);
+            }
+            Rvalue::Discriminant(lvalue) => {
+                e.emit_u8(5);
+                lvalue.encode(e);
+            }
+            Rvalue::Closure(ty) => {
+                e.emit_u8(6);
+                ty.encode(e);
+            }
+            // the adt itself is encoded separately, so only its `DefId` is required
+            Rvalue::Adt { adt, variant_idx, substs, fields } => {
+                e.emit_u8(7);
+                adt.def_id.encode(e);
+                variant_idx.encode(e);
+                substs.encode(e);
+                fields.encode(e);
+            }
+            Rvalue::Array(ty, operands) => {
+                e.emit_u8(8);
+                ty.encode(e);
+                operands.encode(e);
+            }
+            Rvalue::Repeat(operand, n) => {
+                e.emit_u8(9);
+                operand.encode(e);
+                n.encode(e);
+            }
+            Rvalue::Cast(cast, operand, ty) => {
+                e.emit_u8(10);
+                cast.encode(e);
+                operand.encode(e);
+                ty.encode(e);
+            }
+        }
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for Rvalue<'tcx> {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        let rvalue = match d.read_u8()? {
+            0 => Rvalue::Box(Operand::decode(d)?),
+            1 => Rvalue::Operand(Operand::decode(d)?),
+            2 => Rvalue::Unary(UnaryOp::decode(d)?, Operand::decode(d)?),
+            3 => Rvalue::Bin(BinOp::decode(d)?, Operand::decode(d)?, Operand::decode(d)?),
+            4 => Rvalue::Ref(Lvalue::decode(d)?),
+            5 => Rvalue::Discriminant(Lvalue::decode(d)?),
+            6 => Rvalue::Closure(Ty::decode(d)?),
+            7 => Rvalue::Adt {
+                adt: d.tcx().adt_ty(DefId::decode(d)?),
+                variant_idx: VariantIdx::decode(d)?,
+                substs: SubstsRef::decode(d)?,
+                fields: Vec::decode(d)?,
+            },
+            8 => Rvalue::Array(Ty::decode(d)?, Vec::decode(d)?),
+            9 => Rvalue::Repeat(Operand::decode(d)?, usize::decode(d)?),
+            10 => Rvalue::Cast(PointerCast::decode(d)?, Operand::decode(d)?, Ty::decode(d)?),
+            tag => return Err(d.invalid_tag(tag, "Rvalue")),
+        };
+        Ok(rvalue)
+    }
+}
+
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for Terminator<'tcx> {
+    fn encode(&self, e: &mut E) {
+        self.info.encode(e);
+        match &self.kind {
+            TerminatorKind::Branch(target) => {
+                e.emit_u8(0);
+                target.encode(e);
+            }
+            TerminatorKind::Cond(cond, then, otherwise) => {
+                e.emit_u8(1);
+                cond.encode(e);
+                then.encode(e);
+                otherwise.encode(e);
+            }
+            TerminatorKind::Return => e.emit_u8(2),
+            TerminatorKind::Unreachable => e.emit_u8(3),
+            TerminatorKind::Abort => e.emit_u8(4),
+            TerminatorKind::Call { f, args, lvalue, target, unwind } => {
+                e.emit_u8(5);
+                f.encode(e);
+                args.encode(e);
+                lvalue.encode(e);
+                target.encode(e);
+                unwind.encode(e);
+            }
+            TerminatorKind::Switch { discr, arms, default } => {
+                e.emit_u8(6);
+                discr.encode(e);
+                arms.encode(e);
+                default.encode(e);
+            }
+            TerminatorKind::BoundsCheck { index, len, target } => {
+                e.emit_u8(7);
+                index.encode(e);
+                len.encode(e);
+                target.encode(e);
+            }
+        }
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for Terminator<'tcx> {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        let info = SpanInfo::decode(d)?;
+        let kind = match d.read_u8()? {
+            0 => TerminatorKind::Branch(BlockId::decode(d)?),
+            1 => TerminatorKind::Cond(
+                Operand::decode(d)?,
+                BlockId::decode(d)?,
+                BlockId::decode(d)?,
+            ),
+            2 => TerminatorKind::Return,
+            3 => TerminatorKind::Unreachable,
+            4 => TerminatorKind::Abort,
+            5 => TerminatorKind::Call {
+                f: Operand::decode(d)?,
+                args: Vec::decode(d)?,
+                lvalue: Lvalue::decode(d)?,
+                target: BlockId::decode(d)?,
+                unwind: Option::decode(d)?,
+            },
+            6 => TerminatorKind::Switch {
+                discr: Operand::decode(d)?,
+                arms: Vec::decode(d)?,
+                default: BlockId::decode(d)?,
+            },
+            7 => TerminatorKind::BoundsCheck {
+                index: Operand::decode(d)?,
+                len: Operand::decode(d)?,
+                target: BlockId::decode(d)?,
+            },
+            tag => return Err(d.invalid_tag(tag, "TerminatorKind")),
+        };
+        Ok(Terminator { info, kind })
+    }
+}
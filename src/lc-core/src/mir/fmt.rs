@@ -135,6 +135,11 @@ impl<'tcx> MirFmt<'tcx> for mir::Lvalue<'tcx> {
                 Projection::Field(field, _) => write!(f, ".{:?}", field)?,
                 Projection::Deref => write!(f, ".*")?,
                 Projection::PointerCast(ty) => write!(f, " as {}", ty)?,
+                Projection::Index(index) => {
+                    write!(f, "[")?;
+                    index.mir_fmt(f)?;
+                    write!(f, "]")?;
+                }
             }
         }
         if !self.projs.is_empty() {
@@ -145,6 +150,7 @@ impl<'tcx> MirFmt<'tcx> for mir::Lvalue<'tcx> {
                     Projection::Field(_, ty) => write!(f, "->{}", ty)?,
                     Projection::Deref => {}
                     Projection::PointerCast(_) => {}
+                    Projection::Index(_) => {}
                 };
             }
             write!(f, ")")?;
@@ -188,6 +194,16 @@ impl<'tcx> MirFmt<'tcx> for mir::Rvalue<'tcx> {
                 f.fmt_iter(fields)?;
                 write!(f, " }}")
             }
+            mir::Rvalue::Array(_, xs) => {
+                write!(f, "[")?;
+                f.fmt_iter(xs)?;
+                write!(f, "]")
+            }
+            mir::Rvalue::Repeat(operand, n) => {
+                write!(f, "[")?;
+                operand.mir_fmt(f)?;
+                write!(f, "; {}]", n)
+            }
 
             mir::Rvalue::Ref(lvalue) => {
                 write!(f, "&")?;
@@ -197,6 +213,10 @@ impl<'tcx> MirFmt<'tcx> for mir::Rvalue<'tcx> {
                 write!(f, "box ")?;
                 operand.mir_fmt(f)
             }
+            mir::Rvalue::Cast(cast, operand, ty) => {
+                operand.mir_fmt(f)?;
+                write!(f, " as {} ({:?})", ty, cast)
+            }
             mir::Rvalue::Unary(op, operand) => {
                 write!(f, "{}", op)?;
                 operand.mir_fmt(f)
@@ -267,6 +287,13 @@ impl<'tcx> MirFmt<'tcx> for mir::TerminatorKind<'tcx> {
                 write!(fmt, " then {:?}", a)?;
                 writeln!(fmt, " else {:?}", b)
             }
+            mir::TerminatorKind::BoundsCheck { index, len, target } => {
+                write!(fmt, "assert ")?;
+                index.mir_fmt(fmt)?;
+                write!(fmt, " < ")?;
+                len.mir_fmt(fmt)?;
+                writeln!(fmt, " -> [{:?}]", target)
+            }
             mir::TerminatorKind::Abort => writeln!(fmt, "abort"),
         }?;
         writeln!(fmt)
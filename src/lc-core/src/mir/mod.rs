@@ -1,4 +1,5 @@
 //! mid-level intermediate representation (control flow graph)
+mod codec;
 mod fmt;
 mod mirty;
 mod traverse;
@@ -9,7 +10,7 @@ pub use traverse::{postorder, preorder, rpo};
 pub use visit::MirVisitor;
 
 use crate::mir;
-use crate::ty::{AdtTy, Const, List, Projection, SubstsRef, Ty, TyCtx};
+use crate::ty::{AdtTy, Const, List, PointerCast, Projection, SubstsRef, Ty, TyCtx};
 use ir::{DefId, VariantIdx};
 use lc_ast::Mutability;
 use lc_index::{newtype_index, Idx, IndexVec};
@@ -197,6 +198,13 @@ pub enum Rvalue<'tcx> {
         substs: SubstsRef<'tcx>,
         fields: Vec<Operand<'tcx>>,
     },
+    /// [x, y, z]
+    /// the type is the element type (which is required if there are no elements)
+    Array(Ty<'tcx>, Vec<Operand<'tcx>>),
+    /// [x; n]
+    Repeat(Operand<'tcx>, usize),
+    /// a pointer cast of the operand to the given type
+    Cast(PointerCast, Operand<'tcx>, Ty<'tcx>),
 }
 
 // this design flattens out recursive expressions into a series of temporaries
@@ -217,7 +225,8 @@ impl<'tcx> Terminator<'tcx> {
     pub fn successors(&self) -> Vec<BlockId> {
         match self.kind {
             TerminatorKind::Cond(_, a, b) => vec![a, b],
-            TerminatorKind::Branch(block) => vec![block],
+            TerminatorKind::Branch(block) | TerminatorKind::BoundsCheck { target: block, .. } =>
+                vec![block],
             TerminatorKind::Call { target, unwind, .. } =>
                 Some(target).into_iter().chain(unwind).collect(),
             TerminatorKind::Switch { ref arms, default, .. } =>
@@ -229,8 +238,9 @@ impl<'tcx> Terminator<'tcx> {
     pub fn successors_mut(&mut self) -> Vec<&mut BlockId> {
         match &mut self.kind {
             TerminatorKind::Cond(_, a, b) => vec![a, b],
-            TerminatorKind::Branch(target) | TerminatorKind::Call { target, unwind: None, .. } =>
-                vec![target],
+            TerminatorKind::Branch(target)
+            | TerminatorKind::BoundsCheck { target, .. }
+            | TerminatorKind::Call { target, unwind: None, .. } => vec![target],
             TerminatorKind::Call { target, unwind: Some(unwind), .. } => vec![target, unwind],
             TerminatorKind::Switch { arms, default, .. } =>
                 arms.iter_mut().map(|(_, b)| b).chain(Some(default)).collect(),
@@ -269,6 +279,13 @@ pub enum TerminatorKind<'tcx> {
         arms: Vec<(Operand<'tcx>, BlockId)>,
         default: BlockId,
     },
+    /// branches to `target` if `index < len`, otherwise aborts reporting the index and length
+    /// the comparison is unsigned, so a negative index will also fail the check
+    BoundsCheck {
+        index: Operand<'tcx>,
+        len: Operand<'tcx>,
+        target: BlockId,
+    },
 }
 
 // instead of these could maybe just implement deref from mir -> basic blocks
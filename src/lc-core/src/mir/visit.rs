@@ -58,8 +58,11 @@ pub trait MirVisitor<'tcx> {
 
     fn walk_rvalue(&mut self, info: SpanInfo, rvalue: &Rvalue<'tcx>) {
         match rvalue {
-            Rvalue::Box(operand) | Rvalue::Operand(operand) | Rvalue::Unary(_, operand) =>
-                self.visit_operand(info, operand),
+            Rvalue::Box(operand)
+            | Rvalue::Operand(operand)
+            | Rvalue::Unary(_, operand)
+            | Rvalue::Repeat(operand, _)
+            | Rvalue::Cast(_, operand, _) => self.visit_operand(info, operand),
             Rvalue::Bin(_, l, r) => {
                 self.visit_operand(info, l);
                 self.visit_operand(info, r);
@@ -70,6 +73,7 @@ pub trait MirVisitor<'tcx> {
                 let (..) = (adt, variant_idx, substs);
                 fields.iter().for_each(|field| self.visit_operand(info, field));
             }
+            Rvalue::Array(_, xs) => xs.iter().for_each(|x| self.visit_operand(info, x)),
         }
     }
 
@@ -106,6 +110,10 @@ pub trait MirVisitor<'tcx> {
                 let _ = default;
             }
             TerminatorKind::Cond(operand, _, _) => self.visit_operand(terminator.info, operand),
+            TerminatorKind::BoundsCheck { index, len, target: _ } => {
+                self.visit_operand(terminator.info, index);
+                self.visit_operand(terminator.info, len);
+            }
             TerminatorKind::Abort => {}
         };
     }
@@ -69,6 +69,25 @@ macro_rules! define_query_context {
                 self.cache.$name.borrow_mut().insert(key, value);
                 value
             })*
+
+            /// provides the values of queries up front rather than computing them
+            pub fn feed(self) -> QueryFeed<'tcx> {
+                QueryFeed { tcx: self }
+            }
+        }
+
+        /// this is how the queries on the definitions of other packages are answered
+        /// (as their values are decoded from the metadata of the package)
+        #[derive(Copy, Clone)]
+        pub struct QueryFeed<'tcx> {
+            tcx: TyCtx<'tcx>,
+        }
+
+        impl<'tcx> QueryFeed<'tcx> {
+            $(pub fn $name(self, key: $K, value: $R) {
+                let prev = self.tcx.cache.$name.borrow_mut().insert(key, value);
+                assert!(prev.is_none(), "`tcx.{}({:?})` fed twice", stringify!($name), key);
+            })*
         }
     };
 }
@@ -87,11 +106,17 @@ define_query_context! {
         ([fn_sig] [DefId] [FnSig<'tcx>])
         ([adt_ty] [DefId] [&'tcx AdtTy])
         ([generics_of] [DefId] [&'tcx Generics<'tcx>])
+        ([predicates_of] [DefId] [&'tcx Predicates<'tcx>])
         ([validate_item_type] [DefId] [()])
         ([inherent_impls] [()] [&'tcx InherentImpls])
         ([inherent_impls_of] [DefId] [&'tcx [DefId]])
         ([trait_impls] [()] [&'tcx TraitImpls])
         ([trait_impls_of] [DefId] [&'tcx [DefId]])
+        ([impl_trait_ref] [DefId] [Option<TraitRef<'tcx>>])
+        ([assoc_items] [DefId] [&'tcx [AssocItem]])
+        ([assoc_item] [DefId] [Option<AssocItem>])
+        ([check_coherence] [()] [()])
+        ([check_privacy] [()] [()])
 
         // mir
         ([mir_of] [DefId] [&'tcx Mir<'tcx>])
@@ -14,17 +14,29 @@ impl<'tcx> Adjustment<'tcx> {
     pub fn new_deref(ty: Ty<'tcx>) -> Self {
         Self::new(ty, AdjustmentKind::Deref)
     }
+
+    pub fn new_borrow(ty: Ty<'tcx>) -> Self {
+        Self::new(ty, AdjustmentKind::Borrow)
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PointerCast {
     /// from fndef to fnptr
     ReifyFn,
+    /// from a pointer to a concrete type to a pointer to a trait object
+    /// (e.g. `&S` to `&dyn Trait`) which attaches the vtable of the impl
+    Unsize,
+    /// from the address of a method receiver to the box that a `&self` method takes
+    Autoref,
 }
 
 #[derive(Debug, Clone, Copy)]
 pub enum AdjustmentKind {
     Deref,
+    /// autoref of a method receiver `x` to `&x`
+    /// this takes the address of the receiver (so the method can mutate it in place)
+    Borrow,
     NeverToAny,
     Cast(PointerCast),
 }
@@ -0,0 +1,109 @@
+//! the items of traits and impls
+//! these are queried rather than read off the ir as those of other packages are only known
+//! through their metadata
+
+use crate::queries::Queries;
+use crate::ty::TyCtx;
+use ir::{DefId, DefKind, DefNode};
+use itertools::Itertools;
+use lc_ast::{Ident, VisibilityKind};
+
+pub(crate) fn provide(queries: &mut Queries) {
+    *queries = Queries { assoc_items, assoc_item, ..*queries }
+}
+
+/// an item of a trait or of an impl
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AssocItem {
+    pub def_id: DefId,
+    pub ident: Ident,
+    /// the items of traits and of trait impls are always public
+    pub vis: VisibilityKind,
+    pub container: AssocContainer,
+    pub kind: AssocItemKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssocContainer {
+    Trait(DefId),
+    Impl(DefId),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssocItemKind {
+    /// `has_body` is false only for the methods of a trait without a default body
+    Fn { has_self: bool, has_body: bool },
+    /// `has_default` is false only for the associated types of a trait without a default
+    Ty { has_default: bool },
+}
+
+impl AssocItem {
+    pub fn def_kind(&self) -> DefKind {
+        match self.kind {
+            AssocItemKind::Fn { .. } => DefKind::AssocFn,
+            AssocItemKind::Ty { .. } => DefKind::AssocTy,
+        }
+    }
+
+    /// whether the item need not be provided by the impls of its trait
+    pub fn has_default(&self) -> bool {
+        match self.kind {
+            AssocItemKind::Fn { has_body, .. } => has_body,
+            AssocItemKind::Ty { has_default } => has_default,
+        }
+    }
+}
+
+impl AssocContainer {
+    pub fn def_id(self) -> DefId {
+        match self {
+            AssocContainer::Trait(def_id) | AssocContainer::Impl(def_id) => def_id,
+        }
+    }
+}
+
+/// the items of the trait or impl with `def_id` in the order they are declared
+fn assoc_items(tcx: TyCtx<'_>, def_id: DefId) -> &[AssocItem] {
+    let def_ids = match tcx.ir.items[&def_id].kind {
+        ir::ItemKind::Trait { trait_item_refs, .. } =>
+            trait_item_refs.iter().map(|item_ref| item_ref.id.0).collect::<Vec<_>>(),
+        ir::ItemKind::Impl { impl_item_refs, .. } =>
+            impl_item_refs.iter().map(|item_ref| item_ref.id.0).collect(),
+        _ => panic!("`{}` is not a trait or an impl", def_id),
+    };
+    let items = def_ids.into_iter().map(|def_id| tcx.assoc_item(def_id).unwrap()).collect_vec();
+    tcx.arena.alloc_slice(&items)
+}
+
+/// the item with `def_id` if it is the item of a trait or an impl
+/// (the items of other packages are provided by their metadata)
+fn assoc_item(tcx: TyCtx<'_>, def_id: DefId) -> Option<AssocItem> {
+    if tcx.defs().is_extern(def_id) {
+        return None;
+    }
+    let (ident, vis, container, kind) = match tcx.defs().get(def_id) {
+        DefNode::TraitItem(trait_item) => {
+            let kind = match trait_item.kind {
+                ir::TraitItemKind::Fn(sig, body) =>
+                    AssocItemKind::Fn { has_self: sig.has_self, has_body: body.is_some() },
+                ir::TraitItemKind::TyAlias(ty) => AssocItemKind::Ty { has_default: ty.is_some() },
+            };
+            let container = AssocContainer::Trait(trait_item.trait_def_id);
+            (trait_item.ident, VisibilityKind::Public, container, kind)
+        }
+        DefNode::ImplItem(impl_item) => {
+            let kind = match impl_item.kind {
+                ir::ImplItemKind::Fn(sig, _) =>
+                    AssocItemKind::Fn { has_self: sig.has_self, has_body: true },
+                ir::ImplItemKind::TyAlias(..) => AssocItemKind::Ty { has_default: true },
+            };
+            let vis = match tcx.ir.items[&impl_item.impl_def_id].kind {
+                ir::ItemKind::Impl { trait_path: Some(..), .. } => VisibilityKind::Public,
+                _ => impl_item.vis.node,
+            };
+            (impl_item.ident, vis, AssocContainer::Impl(impl_item.impl_def_id), kind)
+        }
+        _ => return None,
+    };
+    Some(AssocItem { def_id, ident, vis, container, kind })
+}
@@ -1,33 +1,694 @@
+//! encoding and decoding of types (and of everything containing them) for package metadata
+//! types are encoded structurally so that the decoder can re-intern them into its own `TyCtx`
+//! `DefId`s are translated by the decoder into the `DefId`s of the decoding package
+
 use crate::ty::*;
-use serde::{Deserializer, Serializer};
+use ir::{CtorKind, DefId, FieldIdx, LocalId, ParamIdx, VariantIdx};
+use lc_ast::{Ident, Spanned, VisibilityKind};
+use lc_index::{Idx, IndexVec};
+use lc_span::{Span, Symbol};
+
+pub trait TyEncoder<'tcx> {
+    fn tcx(&self) -> TyCtx<'tcx>;
+    fn emit_u8(&mut self, x: u8);
+    fn emit_u64(&mut self, x: u64);
+    fn emit_str(&mut self, s: &str);
+    fn emit_def_id(&mut self, def_id: DefId);
 
-pub trait TyEncoder<'tcx>: Serializer {
-    fn tcx() -> TyCtx<'tcx>;
+    fn emit_usize(&mut self, x: usize) {
+        self.emit_u64(x as u64)
+    }
 }
 
-pub trait TyDecoder<'tcx>: Deserializer<'tcx> {
+pub trait TyDecoder<'tcx> {
+    type Error;
+
     fn tcx(&self) -> TyCtx<'tcx>;
+    fn read_u8(&mut self) -> Result<u8, Self::Error>;
+    fn read_u64(&mut self) -> Result<u64, Self::Error>;
+    fn read_str(&mut self) -> Result<String, Self::Error>;
+    fn read_def_id(&mut self) -> Result<DefId, Self::Error>;
+    /// the error for malformed data
+    fn error(&self, msg: String) -> Self::Error;
+
+    fn read_usize(&mut self) -> Result<usize, Self::Error> {
+        self.read_u64().map(|x| x as usize)
+    }
+
+    /// the error for an enum tag (i.e. the index of a variant) that is out of range
+    fn invalid_tag(&self, tag: u8, name: &str) -> Self::Error {
+        self.error(format!("invalid tag `{}` for `{}`", tag, name))
+    }
+}
+
+pub trait TyEncodable<'tcx, E: TyEncoder<'tcx>> {
+    fn encode(&self, e: &mut E);
 }
 
 pub trait TyDecodable<'tcx, D: TyDecoder<'tcx>>: Sized {
     fn decode(d: &mut D) -> Result<Self, D::Error>;
 }
 
-impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for TyKind<'tcx> {
+/// encodes the indices of the newtype indices `$ty`
+pub(crate) macro idx_codec($($ty:ty),*) {
+    $(impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for $ty {
+        fn encode(&self, e: &mut E) {
+            e.emit_usize(self.index())
+        }
+    }
+
+    impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for $ty {
+        fn decode(d: &mut D) -> Result<Self, D::Error> {
+            d.read_usize().map(<$ty>::new)
+        }
+    })*
+}
+
+/// encodes the enum `$ty` without fields as the index of its variant
+pub(crate) macro fieldless_codec($ty:ty { $($variant:expr),* $(,)? }) {
+    impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for $ty {
+        fn encode(&self, e: &mut E) {
+            let variants = [$($variant),*];
+            e.emit_u8(variants.iter().position(|variant| variant == self).unwrap() as u8)
+        }
+    }
+
+    impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for $ty {
+        fn decode(d: &mut D) -> Result<Self, D::Error> {
+            let variants = [$($variant),*];
+            let tag = d.read_u8()?;
+            variants.get(tag as usize).cloned().ok_or_else(|| d.invalid_tag(tag, stringify!($ty)))
+        }
+    }
+}
+
+idx_codec!(ParamIdx, VariantIdx, FieldIdx, LocalId);
+
+fieldless_codec!(IntTy { IntTy::I8, IntTy::I16, IntTy::I32, IntTy::I64 });
+fieldless_codec!(UintTy { UintTy::U8, UintTy::U16, UintTy::U32, UintTy::U64 });
+fieldless_codec!(FloatTy { FloatTy::F32, FloatTy::F64 });
+fieldless_codec!(AdtKind { AdtKind::Struct, AdtKind::Enum });
+fieldless_codec!(CtorKind { CtorKind::Tuple, CtorKind::Struct, CtorKind::Unit });
+fieldless_codec!(VisibilityKind { VisibilityKind::Public, VisibilityKind::Private });
+
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for bool {
+    fn encode(&self, e: &mut E) {
+        e.emit_u8(*self as u8)
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for bool {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        match d.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            tag => Err(d.invalid_tag(tag, "bool")),
+        }
+    }
+}
+
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for usize {
+    fn encode(&self, e: &mut E) {
+        e.emit_usize(*self)
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for usize {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        d.read_usize()
+    }
+}
+
+/// `DefId`s are not encoded as is as they must be translated into those of the decoding package
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for DefId {
+    fn encode(&self, e: &mut E) {
+        e.emit_def_id(*self)
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for DefId {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        d.read_def_id()
+    }
+}
+
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for Symbol {
+    fn encode(&self, e: &mut E) {
+        e.emit_str(self.as_str())
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for Symbol {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        d.read_str().map(|s| Symbol::intern(&s))
+    }
+}
+
+/// spans refer to the source files of the encoding package, so they are not encoded
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for Span {
+    fn encode(&self, _e: &mut E) {
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for Span {
     fn decode(_d: &mut D) -> Result<Self, D::Error> {
-        todo!()
+        Ok(Span::default())
     }
 }
 
-impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for SubstsRef<'tcx> {
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for Ident {
+    fn encode(&self, e: &mut E) {
+        self.symbol.encode(e)
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for Ident {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        Symbol::decode(d).map(Ident::unspanned)
+    }
+}
+
+impl<'tcx, E: TyEncoder<'tcx>, T: TyEncodable<'tcx, E>> TyEncodable<'tcx, E> for Spanned<T> {
+    fn encode(&self, e: &mut E) {
+        self.node.encode(e)
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>, T: TyDecodable<'tcx, D>> TyDecodable<'tcx, D> for Spanned<T> {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        Ok(Spanned { span: Span::default(), node: T::decode(d)? })
+    }
+}
+
+impl<'tcx, E: TyEncoder<'tcx>, T: TyEncodable<'tcx, E>> TyEncodable<'tcx, E> for Option<T> {
+    fn encode(&self, e: &mut E) {
+        match self {
+            None => e.emit_u8(0),
+            Some(t) => {
+                e.emit_u8(1);
+                t.encode(e);
+            }
+        }
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>, T: TyDecodable<'tcx, D>> TyDecodable<'tcx, D> for Option<T> {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        match d.read_u8()? {
+            0 => Ok(None),
+            1 => T::decode(d).map(Some),
+            tag => Err(d.invalid_tag(tag, "Option")),
+        }
+    }
+}
+
+impl<'tcx, E: TyEncoder<'tcx>, T: TyEncodable<'tcx, E>> TyEncodable<'tcx, E> for [T] {
+    fn encode(&self, e: &mut E) {
+        e.emit_usize(self.len());
+        self.iter().for_each(|t| t.encode(e));
+    }
+}
+
+impl<'tcx, E: TyEncoder<'tcx>, T: TyEncodable<'tcx, E>> TyEncodable<'tcx, E> for Vec<T> {
+    fn encode(&self, e: &mut E) {
+        self.as_slice().encode(e)
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>, T: TyDecodable<'tcx, D>> TyDecodable<'tcx, D> for Vec<T> {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        let len = d.read_usize()?;
+        (0..len).map(|_| T::decode(d)).collect()
+    }
+}
+
+impl<'tcx, E, I, T> TyEncodable<'tcx, E> for IndexVec<I, T>
+where
+    E: TyEncoder<'tcx>,
+    I: Idx,
+    T: TyEncodable<'tcx, E>,
+{
+    fn encode(&self, e: &mut E) {
+        self.raw.encode(e)
+    }
+}
+
+impl<'tcx, D, I, T> TyDecodable<'tcx, D> for IndexVec<I, T>
+where
+    D: TyDecoder<'tcx>,
+    I: Idx,
+    T: TyDecodable<'tcx, D>,
+{
     fn decode(d: &mut D) -> Result<Self, D::Error> {
-        d.tcx().mk_substs(vec![]);
-        todo!()
+        Vec::decode(d).map(IndexVec::from_raw)
+    }
+}
+
+impl<'tcx, E, A, B> TyEncodable<'tcx, E> for (A, B)
+where
+    E: TyEncoder<'tcx>,
+    A: TyEncodable<'tcx, E>,
+    B: TyEncodable<'tcx, E>,
+{
+    fn encode(&self, e: &mut E) {
+        self.0.encode(e);
+        self.1.encode(e);
+    }
+}
+
+impl<'tcx, D, A, B> TyDecodable<'tcx, D> for (A, B)
+where
+    D: TyDecoder<'tcx>,
+    A: TyDecodable<'tcx, D>,
+    B: TyDecodable<'tcx, D>,
+{
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        Ok((A::decode(d)?, B::decode(d)?))
+    }
+}
+
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for Ty<'tcx> {
+    fn encode(&self, e: &mut E) {
+        self.kind.encode(e)
     }
 }
 
 impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for Ty<'tcx> {
-    fn decode(_d: &mut D) -> Result<Self, D::Error> {
-        todo!()
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        let kind = TyKind::decode(d)?;
+        Ok(d.tcx().mk_ty(kind))
+    }
+}
+
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for TyKind<'tcx> {
+    fn encode(&self, e: &mut E) {
+        match *self {
+            TyKind::Bool => e.emit_u8(0),
+            TyKind::Discr => e.emit_u8(1),
+            TyKind::Char => e.emit_u8(2),
+            TyKind::Float(float_ty) => {
+                e.emit_u8(3);
+                float_ty.encode(e);
+            }
+            TyKind::Int(int_ty) => {
+                e.emit_u8(4);
+                int_ty.encode(e);
+            }
+            TyKind::Uint(uint_ty) => {
+                e.emit_u8(5);
+                uint_ty.encode(e);
+            }
+            TyKind::Str => e.emit_u8(6),
+            TyKind::Error => e.emit_u8(7),
+            TyKind::Never => e.emit_u8(8),
+            TyKind::Boxed(ty) => {
+                e.emit_u8(9);
+                ty.encode(e);
+            }
+            TyKind::FnPtr(fn_sig) => {
+                e.emit_u8(10);
+                fn_sig.encode(e);
+            }
+            TyKind::Array(ty, n) => {
+                e.emit_u8(11);
+                ty.encode(e);
+                n.encode(e);
+            }
+            TyKind::Tuple(tys) => {
+                e.emit_u8(12);
+                tys.encode(e);
+            }
+            TyKind::Ptr(ty) => {
+                e.emit_u8(13);
+                ty.encode(e);
+            }
+            TyKind::Param(ParamTy { def_id, idx, ident }) => {
+                e.emit_u8(14);
+                def_id.encode(e);
+                idx.encode(e);
+                ident.encode(e);
+            }
+            TyKind::Opaque(def_id, substs) => {
+                e.emit_u8(15);
+                def_id.encode(e);
+                substs.encode(e);
+            }
+            TyKind::Projection(def_id, substs) => {
+                e.emit_u8(16);
+                def_id.encode(e);
+                substs.encode(e);
+            }
+            TyKind::Dynamic(def_id, substs) => {
+                e.emit_u8(17);
+                def_id.encode(e);
+                substs.encode(e);
+            }
+            // the adt itself is encoded separately (see `AdtTy`)
+            TyKind::Adt(adt, substs) => {
+                e.emit_u8(18);
+                adt.def_id.encode(e);
+                substs.encode(e);
+            }
+            TyKind::Infer(..) => panic!("cannot encode the inference variable `{}`", self),
+        }
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for TyKind<'tcx> {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        let kind = match d.read_u8()? {
+            0 => TyKind::Bool,
+            1 => TyKind::Discr,
+            2 => TyKind::Char,
+            3 => TyKind::Float(FloatTy::decode(d)?),
+            4 => TyKind::Int(IntTy::decode(d)?),
+            5 => TyKind::Uint(UintTy::decode(d)?),
+            6 => TyKind::Str,
+            7 => TyKind::Error,
+            8 => TyKind::Never,
+            9 => TyKind::Boxed(Ty::decode(d)?),
+            10 => TyKind::FnPtr(FnSig::decode(d)?),
+            11 => TyKind::Array(Ty::decode(d)?, usize::decode(d)?),
+            12 => TyKind::Tuple(SubstsRef::decode(d)?),
+            13 => TyKind::Ptr(Ty::decode(d)?),
+            14 => TyKind::Param(ParamTy {
+                def_id: DefId::decode(d)?,
+                idx: ParamIdx::decode(d)?,
+                ident: Ident::decode(d)?,
+            }),
+            15 => TyKind::Opaque(DefId::decode(d)?, SubstsRef::decode(d)?),
+            16 => TyKind::Projection(DefId::decode(d)?, SubstsRef::decode(d)?),
+            17 => TyKind::Dynamic(DefId::decode(d)?, SubstsRef::decode(d)?),
+            // the adt must have been decoded before any type that refers to it
+            18 => TyKind::Adt(d.tcx().adt_ty(DefId::decode(d)?), SubstsRef::decode(d)?),
+            tag => return Err(d.invalid_tag(tag, "TyKind")),
+        };
+        Ok(kind)
+    }
+}
+
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for SubstsRef<'tcx> {
+    fn encode(&self, e: &mut E) {
+        e.emit_usize(self.len());
+        self.iter().for_each(|ty| ty.encode(e));
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for SubstsRef<'tcx> {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        let tys = Vec::<Ty<'tcx>>::decode(d)?;
+        Ok(d.tcx().mk_substs(tys))
+    }
+}
+
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for FnSig<'tcx> {
+    fn encode(&self, e: &mut E) {
+        self.params.encode(e);
+        self.ret.encode(e);
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for FnSig<'tcx> {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        Ok(FnSig { params: SubstsRef::decode(d)?, ret: Ty::decode(d)? })
+    }
+}
+
+/// the definition of an adt (types only refer to the adt by its `DefId`)
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for &'tcx AdtTy {
+    fn encode(&self, e: &mut E) {
+        self.def_id.encode(e);
+        self.kind.encode(e);
+        self.ident.encode(e);
+        self.variants.encode(e);
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for &'tcx AdtTy {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        let def_id = DefId::decode(d)?;
+        let kind = AdtKind::decode(d)?;
+        let ident = Ident::decode(d)?;
+        let variants = IndexVec::decode(d)?;
+        Ok(d.tcx().mk_adt(def_id, kind, ident, variants))
+    }
+}
+
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for VariantTy {
+    fn encode(&self, e: &mut E) {
+        self.def_id.encode(e);
+        self.ident.encode(e);
+        self.ctor_kind.encode(e);
+        self.fields.encode(e);
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for VariantTy {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        Ok(VariantTy {
+            def_id: DefId::decode(d)?,
+            ident: Ident::decode(d)?,
+            ctor_kind: CtorKind::decode(d)?,
+            fields: Vec::decode(d)?,
+        })
+    }
+}
+
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for FieldTy {
+    fn encode(&self, e: &mut E) {
+        self.def_id.encode(e);
+        self.ident.encode(e);
+        self.vis.encode(e);
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for FieldTy {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        Ok(FieldTy {
+            def_id: DefId::decode(d)?,
+            ident: Ident::decode(d)?,
+            vis: Spanned::decode(d)?,
+        })
+    }
+}
+
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for &'tcx Generics<'tcx> {
+    fn encode(&self, e: &mut E) {
+        self.params.encode(e)
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for &'tcx Generics<'tcx> {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        let tcx = d.tcx();
+        let params = Vec::<TyParam<'tcx>>::decode(d)?;
+        Ok(tcx.alloc(Generics { params: tcx.alloc_iter(params) }))
+    }
+}
+
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for TyParam<'tcx> {
+    fn encode(&self, e: &mut E) {
+        self.id.def.encode(e);
+        self.id.local.encode(e);
+        self.span.encode(e);
+        self.ident.encode(e);
+        self.index.encode(e);
+        self.default.encode(e);
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for TyParam<'tcx> {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        Ok(TyParam {
+            id: ir::Id { def: DefId::decode(d)?, local: LocalId::decode(d)? },
+            span: Span::decode(d)?,
+            ident: Ident::decode(d)?,
+            index: ParamIdx::decode(d)?,
+            default: Option::decode(d)?,
+        })
+    }
+}
+
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for &'tcx Predicates<'tcx> {
+    fn encode(&self, e: &mut E) {
+        self.predicates.encode(e)
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for &'tcx Predicates<'tcx> {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        let predicates = Vec::decode(d)?;
+        Ok(d.tcx().alloc(Predicates { predicates }))
+    }
+}
+
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for Predicate<'tcx> {
+    fn encode(&self, e: &mut E) {
+        match self {
+            Predicate::Trait(trait_ref) => {
+                e.emit_u8(0);
+                trait_ref.encode(e);
+            }
+        }
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for Predicate<'tcx> {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        match d.read_u8()? {
+            0 => TraitRef::decode(d).map(Predicate::Trait),
+            tag => Err(d.invalid_tag(tag, "Predicate")),
+        }
+    }
+}
+
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for TraitRef<'tcx> {
+    fn encode(&self, e: &mut E) {
+        self.def_id.encode(e);
+        self.self_ty.encode(e);
+        self.substs.encode(e);
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for TraitRef<'tcx> {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        Ok(TraitRef::new(DefId::decode(d)?, Ty::decode(d)?, SubstsRef::decode(d)?))
+    }
+}
+
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for AssocItem {
+    fn encode(&self, e: &mut E) {
+        self.def_id.encode(e);
+        self.ident.encode(e);
+        self.vis.encode(e);
+        match self.container {
+            AssocContainer::Trait(def_id) => {
+                e.emit_u8(0);
+                def_id.encode(e);
+            }
+            AssocContainer::Impl(def_id) => {
+                e.emit_u8(1);
+                def_id.encode(e);
+            }
+        }
+        match self.kind {
+            AssocItemKind::Fn { has_self, has_body } => {
+                e.emit_u8(0);
+                has_self.encode(e);
+                has_body.encode(e);
+            }
+            AssocItemKind::Ty { has_default } => {
+                e.emit_u8(1);
+                has_default.encode(e);
+            }
+        }
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for AssocItem {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        let def_id = DefId::decode(d)?;
+        let ident = Ident::decode(d)?;
+        let vis = VisibilityKind::decode(d)?;
+        let container = match d.read_u8()? {
+            0 => AssocContainer::Trait(DefId::decode(d)?),
+            1 => AssocContainer::Impl(DefId::decode(d)?),
+            tag => return Err(d.invalid_tag(tag, "AssocContainer")),
+        };
+        let kind = match d.read_u8()? {
+            0 => AssocItemKind::Fn { has_self: bool::decode(d)?, has_body: bool::decode(d)? },
+            1 => AssocItemKind::Ty { has_default: bool::decode(d)? },
+            tag => return Err(d.invalid_tag(tag, "AssocItemKind")),
+        };
+        Ok(AssocItem { def_id, ident, vis, container, kind })
+    }
+}
+
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for &'tcx Const<'tcx> {
+    fn encode(&self, e: &mut E) {
+        match self.kind {
+            ConstKind::Float(f) => {
+                e.emit_u8(0);
+                e.emit_u64(f.to_bits());
+            }
+            ConstKind::Int(i) => {
+                e.emit_u8(1);
+                e.emit_u64(i as u64);
+            }
+            ConstKind::Discr(discr) => {
+                e.emit_u8(2);
+                e.emit_u64(discr as u64);
+            }
+            ConstKind::Bool(b) => {
+                e.emit_u8(3);
+                b.encode(e);
+            }
+            ConstKind::Char(c) => {
+                e.emit_u8(4);
+                e.emit_u64(c as u64);
+            }
+            ConstKind::Str(s) => {
+                e.emit_u8(5);
+                s.encode(e);
+            }
+            ConstKind::Unit => e.emit_u8(6),
+        }
+        self.ty.encode(e);
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for &'tcx Const<'tcx> {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        let kind = match d.read_u8()? {
+            0 => ConstKind::Float(f64::from_bits(d.read_u64()?)),
+            1 => ConstKind::Int(d.read_u64()? as i64),
+            2 => ConstKind::Discr(d.read_u64()? as i16),
+            3 => ConstKind::Bool(bool::decode(d)?),
+            4 => {
+                let c = d.read_u64()?;
+                let c = std::char::from_u32(c as u32)
+                    .ok_or_else(|| d.error(format!("invalid char `{}`", c)))?;
+                ConstKind::Char(c)
+            }
+            5 => ConstKind::Str(Symbol::decode(d)?),
+            6 => ConstKind::Unit,
+            tag => return Err(d.invalid_tag(tag, "ConstKind")),
+        };
+        let ty = Ty::decode(d)?;
+        Ok(d.tcx().intern_const(Const { kind, ty }))
+    }
+}
+
+impl<'tcx, E: TyEncoder<'tcx>> TyEncodable<'tcx, E> for Projection<'tcx> {
+    fn encode(&self, e: &mut E) {
+        match *self {
+            Projection::Deref => e.emit_u8(0),
+            Projection::Field(idx, ty) => {
+                e.emit_u8(1);
+                idx.encode(e);
+                ty.encode(e);
+            }
+            Projection::PointerCast(ty) => {
+                e.emit_u8(2);
+                ty.encode(e);
+            }
+            Projection::Index(var) => {
+                e.emit_u8(3);
+                var.encode(e);
+            }
+        }
+    }
+}
+
+impl<'tcx, D: TyDecoder<'tcx>> TyDecodable<'tcx, D> for Projection<'tcx> {
+    fn decode(d: &mut D) -> Result<Self, D::Error> {
+        let projection = match d.read_u8()? {
+            0 => Projection::Deref,
+            1 => Projection::Field(FieldIdx::decode(d)?, Ty::decode(d)?),
+            2 => Projection::PointerCast(Ty::decode(d)?),
+            3 => Projection::Index(crate::mir::VarId::decode(d)?),
+            tag => return Err(d.invalid_tag(tag, "Projection")),
+        };
+        Ok(projection)
     }
 }
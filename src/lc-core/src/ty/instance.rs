@@ -1,7 +1,9 @@
 use crate::queries::Queries;
-use crate::ty::{Subst, Substs, SubstsRef, Ty, TyCtx};
+use crate::ty::{
+    AssocContainer, AssocItem, AssocItemKind, Subst, Substs, SubstsRef, TraitRef, Ty, TyCtx, TyKind,
+};
 use lc_ast::Abi;
-use ir::DefId;
+use ir::{DefId, ExternFnKind};
 use rustc_hash::FxHashSet;
 use std::fmt::{self, Display, Formatter};
 
@@ -13,16 +15,74 @@ fn resolve_instance<'tcx>(
     tcx: TyCtx<'tcx>,
     (def_id, substs): (DefId, SubstsRef<'tcx>),
 ) -> Instance<'tcx> {
+    // the items of traits (of this package or of another) are resolved to the items of impls
+    if let Some(trait_item) = tcx.assoc_item(def_id) {
+        if let AssocContainer::Trait(trait_def_id) = trait_item.container {
+            return self::resolve_trait_item(tcx, trait_item, trait_def_id, substs);
+        }
+    }
+    // the functions of other packages are either intrinsics or ordinary items
+    // (whether they are linked against or instantiated from their mir)
+    if tcx.defs().is_extern(def_id) {
+        return match tcx.defs().extern_fn_kind(def_id) {
+            Some(ExternFnKind::Intrinsic) => Instance::intrinsic(def_id, substs),
+            _ => Instance::item(def_id, substs),
+        };
+    }
     match tcx.defs().get(def_id) {
         // can just treat constructors as normal items
         ir::DefNode::Item(..) | ir::DefNode::ImplItem(..) | ir::DefNode::Ctor(..) =>
             Instance::item(def_id, substs),
-        ir::DefNode::TraitItem(..) => todo!(),
         ir::DefNode::ForeignItem(item) if item.abi == Abi::Intrinsic =>
             Instance::intrinsic(def_id, substs),
         ir::DefNode::ForeignItem(_) => todo!(),
-        ir::DefNode::Field(..) | ir::DefNode::Variant(..) | ir::DefNode::TyParam(..) =>
-            unreachable!(),
+        ir::DefNode::TraitItem(..)
+        | ir::DefNode::Field(..)
+        | ir::DefNode::Variant(..)
+        | ir::DefNode::TyParam(..) => unreachable!(),
+    }
+}
+
+/// statically dispatches a reference to a trait item by selecting the impl of the trait that
+/// applies to the (fully concrete) `substs` and resolving to the corresponding impl item
+/// (or to the trait item itself if the impl does not override its default body)
+fn resolve_trait_item<'tcx>(
+    tcx: TyCtx<'tcx>,
+    trait_item: AssocItem,
+    trait_def_id: DefId,
+    substs: SubstsRef<'tcx>,
+) -> Instance<'tcx> {
+    // the substitutions of a trait item start with those of its trait
+    let (trait_substs, item_substs) =
+        substs.split_at(tcx.generics_of(trait_def_id).params.len());
+    let trait_ref = TraitRef::from_substs(tcx, trait_def_id, trait_substs);
+
+    // a method called on a trait object is dispatched dynamically through its vtable
+    if let TyKind::Dynamic(..) = trait_ref.self_ty.kind {
+        let def_id = trait_item.def_id;
+        let idx = tcx.vtable_methods(trait_def_id).iter().position(|&method| method == def_id);
+        return Instance::virtual_call(def_id, substs, idx.unwrap());
+    }
+
+    let (impl_def_id, impl_substs) = match tcx.select_impl(trait_ref) {
+        Some(selection) => selection,
+        None => panic!("no impl satisfies `{}`", trait_ref),
+    };
+
+    match tcx.impl_item_for_trait_item(impl_def_id, trait_item.def_id) {
+        // the impl item's substitutions are those of the impl followed by its own
+        Some(impl_item_def_id) => {
+            let substs = impl_substs.iter().chain(item_substs.iter().copied());
+            Instance::item(impl_item_def_id, tcx.mk_substs(substs))
+        }
+        // the impl doesn't override the item so we use the default provided by the trait
+        // the substitutions already contain the concrete `Self` type
+        None => match trait_item.kind {
+            AssocItemKind::Fn { has_body: true, .. } => Instance::item(trait_item.def_id, substs),
+            AssocItemKind::Fn { has_body: false, .. } =>
+                panic!("impl of `{}` is missing `{}`", trait_ref, trait_item.ident),
+            AssocItemKind::Ty { .. } => unreachable!("associated types have no instances"),
+        },
     }
 }
 
@@ -57,6 +117,10 @@ impl<'tcx> Instance<'tcx> {
         Instance { substs, def_id, kind: InstanceKind::Intrinsic }
     }
 
+    fn virtual_call(def_id: DefId, substs: SubstsRef<'tcx>, idx: usize) -> Self {
+        Instance { substs, def_id, kind: InstanceKind::Virtual(idx) }
+    }
+
     pub fn mono_item(def_id: DefId) -> Self {
         Self::item(def_id, Substs::empty())
     }
@@ -66,6 +130,8 @@ impl<'tcx> Instance<'tcx> {
 pub enum InstanceKind {
     Item,
     Intrinsic,
+    /// a method of a trait object, called through the entry of its vtable with the given index
+    Virtual(usize),
 }
 
 impl<'tcx> Display for Instance<'tcx> {
@@ -1,19 +1,27 @@
 mod adjustments;
+mod assoc;
 mod codec;
 mod instance;
 mod list;
+mod normalize;
+mod predicate;
 mod relate;
 mod substs;
 mod tables;
 mod tcx;
 mod traverse;
 mod type_error;
+mod vtable;
 
 pub use adjustments::{Adjuster, Adjustment, AdjustmentKind, PointerCast};
-use ena::unify::UnifyKey;
+pub use assoc::{AssocContainer, AssocItem, AssocItemKind};
+pub(crate) use codec::{fieldless_codec, idx_codec};
+pub use codec::{TyDecodable, TyDecoder, TyEncodable, TyEncoder};
+use ena::unify::{EqUnifyValue, UnifyKey};
 pub use instance::{Instance, InstanceKind, Instances};
 pub use list::List;
-pub use relate::{Relate, TypeRelation};
+pub use predicate::{Predicate, Predicates, TraitRef};
+pub use relate::{Match, Relate, TypeRelation};
 pub use substs::*;
 pub use tables::TypeckTables;
 pub use tcx::{tls, GlobalCtx, TyCtx};
@@ -25,15 +33,17 @@ pub use TyKind::*;
 use crate::queries::Queries;
 use bitflags::bitflags;
 use ir::{self, CtorKind, DefId, FieldIdx, ParamIdx, Res, VariantIdx};
+pub use lc_ast::{FloatTy, IntTy, UintTy};
 use lc_ast::{Ident, Visibility};
 use lc_index::{Idx, IndexVec};
-use lc_span::Span;
+use lc_span::{Span, Symbol};
 use rustc_hash::FxHashMap;
 use std::fmt::{self, Debug, Display, Formatter};
 use std::hash::{Hash, Hasher};
 use std::ptr;
 
 pub(crate) fn provide(queries: &mut Queries) {
+    assoc::provide(queries);
     instance::provide(queries);
 }
 
@@ -52,6 +62,35 @@ impl<'tcx> Type<'tcx> {
             _ => false,
         }
     }
+
+    /// whether the type is an integer type or an integer inference variable
+    pub fn is_integral(&self) -> bool {
+        matches!(self.kind, TyKind::Int(_) | TyKind::Uint(_) | TyKind::Infer(IntVar(_)))
+    }
+
+    /// whether the type is a float type or a float inference variable
+    pub fn is_floating_point(&self) -> bool {
+        matches!(self.kind, TyKind::Float(_) | TyKind::Infer(FloatVar(_)))
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        self.is_integral() || self.is_floating_point()
+    }
+
+    pub fn is_bool(&self) -> bool {
+        matches!(self.kind, TyKind::Bool)
+    }
+
+    /// whether values of the type can be compared directly with `==` and `!=`
+    pub fn is_scalar(&self) -> bool {
+        self.is_numeric() || matches!(self.kind, TyKind::Bool | TyKind::Char)
+    }
+
+    /// whether the type is a signed integer type
+    /// this determines which instructions are used for division and comparisons
+    pub fn is_signed(&self) -> bool {
+        matches!(self.kind, TyKind::Int(_))
+    }
 }
 
 /// visitor that searches for a specific type variables (for the occurs check)
@@ -87,6 +126,82 @@ impl Display for TyVid {
     }
 }
 
+/// integer inference variable id
+/// these are created for unsuffixed integer literals and may only unify with integer types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub struct IntVid {
+    pub index: u32,
+}
+
+impl UnifyKey for IntVid {
+    type Value = Option<IntVarValue>;
+
+    fn index(&self) -> u32 {
+        self.index
+    }
+
+    fn from_index(i: u32) -> IntVid {
+        IntVid { index: i }
+    }
+
+    fn tag() -> &'static str {
+        "IntVid"
+    }
+}
+
+/// float inference variable id
+/// these are created for unsuffixed float literals and may only unify with float types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub struct FloatVid {
+    pub index: u32,
+}
+
+impl UnifyKey for FloatVid {
+    type Value = Option<FloatVarValue>;
+
+    fn index(&self) -> u32 {
+        self.index
+    }
+
+    fn from_index(i: u32) -> FloatVid {
+        FloatVid { index: i }
+    }
+
+    fn tag() -> &'static str {
+        "FloatVid"
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntVarValue {
+    Int(IntTy),
+    Uint(UintTy),
+}
+
+impl EqUnifyValue for IntVarValue {
+}
+
+impl IntVarValue {
+    pub fn to_ty<'tcx>(self, tcx: TyCtx<'tcx>) -> Ty<'tcx> {
+        match self {
+            IntVarValue::Int(int_ty) => tcx.mk_ty(TyKind::Int(int_ty)),
+            IntVarValue::Uint(uint_ty) => tcx.mk_ty(TyKind::Uint(uint_ty)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FloatVarValue(pub FloatTy);
+
+impl EqUnifyValue for FloatVarValue {
+}
+
+impl FloatVarValue {
+    pub fn to_ty<'tcx>(self, tcx: TyCtx<'tcx>) -> Ty<'tcx> {
+        tcx.mk_ty(TyKind::Float(self.0))
+    }
+}
+
 impl<'tcx> TypeVisitor<'tcx> for TyVidVisitor {
     fn visit_ty(&mut self, ty: Ty<'tcx>) -> bool {
         match ty.kind {
@@ -126,6 +241,13 @@ impl<'tcx> Type<'tcx> {
         }
     }
 
+    pub fn array_elem_ty(&self) -> Ty<'tcx> {
+        match self.kind {
+            TyKind::Array(ty, _) => ty,
+            _ => panic!("expected array type"),
+        }
+    }
+
     pub fn expect_adt(&self) -> (&'tcx AdtTy, SubstsRef<'tcx>) {
         match self.kind {
             TyKind::Adt(adt, substs) => (adt, substs),
@@ -156,10 +278,15 @@ pub enum TyKind<'tcx> {
     Discr,
     /// char
     Char,
-    /// float
-    Float,
-    /// Int
-    Int,
+    /// f32, f64 (float)
+    Float(FloatTy),
+    /// i8, i16, i32, i64 (int)
+    Int(IntTy),
+    /// u8, u16, u32, u64
+    Uint(UintTy),
+    /// str
+    /// an immutable utf-8 string, represented as a pointer to its bytes and its length
+    Str,
     Error,
     Never,
     /// box pointer to a type
@@ -178,6 +305,14 @@ pub enum TyKind<'tcx> {
     Ptr(Ty<'tcx>),
     Param(ParamTy),
     Opaque(DefId, SubstsRef<'tcx>),
+    /// <<self_ty> as Trait<..>>::Item
+    /// the `DefId` is that of the associated type in the trait
+    /// and the substs are those of the trait (including `Self`)
+    Projection(DefId, SubstsRef<'tcx>),
+    /// dyn Trait<..>
+    /// the type of a value that implements `Trait` whose concrete type is only known at runtime
+    /// the substs are those of the trait (excluding `Self`)
+    Dynamic(DefId, SubstsRef<'tcx>),
     Adt(&'tcx AdtTy, SubstsRef<'tcx>),
 }
 
@@ -218,6 +353,9 @@ pub enum Projection<'tcx> {
     /// so the projection from `s` would be `Projection::Field(0, int)`
     Field(FieldIdx, Ty<'tcx>),
     PointerCast(Ty<'tcx>),
+    /// indexes into an array using the value of the variable as the index
+    /// the index is assumed to be in bounds (the check is done separately)
+    Index(crate::mir::VarId),
 }
 
 #[derive(Debug, Eq, Hash, PartialEq, Clone, Serialize, Deserialize)]
@@ -311,6 +449,7 @@ bitflags! {
         const HAS_ERROR = 1 << 0;
         const HAS_INFER = 1 << 1;
         const HAS_PARAM = 1 << 2;
+        const HAS_PROJECTION = 1 << 3;
     }
 }
 
@@ -350,6 +489,10 @@ pub trait HasTyFlags {
     fn contains_err(&self) -> bool {
         self.has_flags(TyFlags::HAS_ERROR)
     }
+
+    fn has_projections(&self) -> bool {
+        self.has_flags(TyFlags::HAS_PROJECTION)
+    }
 }
 
 impl<'tcx> HasTyFlags for Type<'tcx> {
@@ -368,17 +511,21 @@ impl<'tcx> TyFlag for TyKind<'tcx> {
     fn ty_flags(&self) -> TyFlags {
         match self {
             TyKind::FnPtr(sig) => sig.ty_flags(),
-            TyKind::Opaque(_, tys) | TyKind::Tuple(tys) => tys.ty_flags(),
+            TyKind::Opaque(_, tys) | TyKind::Dynamic(_, tys) | TyKind::Tuple(tys) =>
+                tys.ty_flags(),
             TyKind::Infer(..) => TyFlags::HAS_INFER,
             TyKind::Param(..) => TyFlags::HAS_PARAM,
+            TyKind::Projection(_, substs) => substs.ty_flags() | TyFlags::HAS_PROJECTION,
             TyKind::Adt(_, substs) => substs.ty_flags(),
             TyKind::Ptr(ty) | TyKind::Array(ty, _) | TyKind::Boxed(ty) => ty.ty_flags(),
             TyKind::Discr
-            | TyKind::Float
+            | TyKind::Float(_)
             | TyKind::Never
             | TyKind::Bool
             | TyKind::Char
-            | TyKind::Int => TyFlags::empty(),
+            | TyKind::Str
+            | TyKind::Int(_)
+            | TyKind::Uint(_) => TyFlags::empty(),
             TyKind::Error => TyFlags::HAS_ERROR,
         }
     }
@@ -402,10 +549,31 @@ impl<'tcx> Display for TyKind<'tcx> {
             TyKind::Param(param_ty) => write!(f, "{}", param_ty),
             TyKind::Adt(adt, substs) => write!(f, "{}<{}>", adt.ident, substs),
             TyKind::Opaque(_, _) => write!(f, "opaque"),
+            TyKind::Projection(def_id, substs) => tls::with_tcx(|tcx| {
+                let trait_ref = tcx.projection_trait_ref(*def_id, substs);
+                write!(f, "<{} as {}", trait_ref.self_ty, tcx.defs().ident(trait_ref.def_id))?;
+                if !trait_ref.substs.is_empty() {
+                    write!(f, "<{}>", trait_ref.substs)?;
+                }
+                write!(f, ">::{}", tcx.defs().ident(*def_id))
+            }),
+            TyKind::Dynamic(def_id, substs) => {
+                let ident = tls::with_tcx(|tcx| tcx.defs().ident(*def_id));
+                write!(f, "dyn {}", ident)?;
+                if !substs.is_empty() {
+                    write!(f, "<{}>", substs)?;
+                }
+                Ok(())
+            }
             TyKind::Bool => write!(f, "bool"),
             TyKind::Char => write!(f, "char"),
-            TyKind::Int => write!(f, "int"),
-            TyKind::Float => write!(f, "float"),
+            // `i64` and `f64` are displayed using their aliases
+            TyKind::Int(IntTy::I64) => write!(f, "int"),
+            TyKind::Float(FloatTy::F64) => write!(f, "float"),
+            TyKind::Int(int_ty) => write!(f, "{}", int_ty),
+            TyKind::Uint(uint_ty) => write!(f, "{}", uint_ty),
+            TyKind::Float(float_ty) => write!(f, "{}", float_ty),
+            TyKind::Str => write!(f, "str"),
             TyKind::Never => write!(f, "!"),
             TyKind::Discr => write!(f, "discr"),
             TyKind::Error => write!(f, "err"),
@@ -453,12 +621,16 @@ impl Display for ParamTy {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum InferTy {
     TyVar(TyVid),
+    IntVar(IntVid),
+    FloatVar(FloatVid),
 }
 
 impl Display for InferTy {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             Self::TyVar(vid) => write!(f, "{}", vid),
+            Self::IntVar(_) => write!(f, "{{integer}}"),
+            Self::FloatVar(_) => write!(f, "{{float}}"),
         }
     }
 }
@@ -490,9 +662,13 @@ pub struct Const<'tcx> {
 #[derive(Debug, Clone, PartialEq)]
 pub enum ConstKind {
     Float(f64),
+    /// the bits of an integer constant of any width or signedness
+    /// the actual type is given by the type of the constant
     Int(i64),
     Discr(i16),
     Bool(bool),
+    Char(char),
+    Str(Symbol),
     Unit,
 }
 
@@ -507,6 +683,8 @@ impl std::hash::Hash for ConstKind {
             ConstKind::Discr(d) => d.hash(state),
             ConstKind::Int(i) => i.hash(state),
             ConstKind::Bool(b) => b.hash(state),
+            ConstKind::Char(c) => c.hash(state),
+            ConstKind::Str(s) => s.hash(state),
             ConstKind::Unit => {}
         };
     }
@@ -516,9 +694,12 @@ impl<'tcx> Display for Const<'tcx> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self.kind {
             ConstKind::Float(d) => write!(f, "{:?}", d),
+            ConstKind::Int(i) if !self.ty.is_signed() => write!(f, "{}", i as u64),
             ConstKind::Int(i) => write!(f, "{}", i),
             ConstKind::Discr(d) => write!(f, "{}", d),
             ConstKind::Bool(b) => write!(f, "{}", b),
+            ConstKind::Char(c) => write!(f, "{:?}", c),
+            ConstKind::Str(s) => write!(f, "{:?}", s.as_str()),
             ConstKind::Unit => write!(f, "()"),
         }
     }
@@ -529,6 +710,7 @@ pub struct InherentImpls {
     pub inherent_impls: FxHashMap<DefId, Vec<DefId>>,
 }
 
+/// maps the `DefId` of each trait to the `DefId`s of its impls
 #[derive(Clone, Debug, Default)]
 pub struct TraitImpls {
     pub trait_impls: FxHashMap<DefId, Vec<DefId>>,
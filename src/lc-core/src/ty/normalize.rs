@@ -0,0 +1,155 @@
+//! selection of the impl that applies to a (fully concrete) trait ref
+//! and normalization of projection types using the selected impl
+
+use crate::ty::{
+    AssocContainer, HasTyFlags, Match, Predicate, Subst, SubstsRef, TraitRef, Ty, TyCtx, TyKind,
+    TypeFoldable, TypeFolder, TypeRelation,
+};
+use ir::DefId;
+
+/// the maximum depth of nested impl bounds that are checked during selection
+/// (this matches the recursion limit of the trait solver)
+const SELECTION_RECURSION_LIMIT: usize = 64;
+
+impl<'tcx> TyCtx<'tcx> {
+    /// selects the impl whose header matches `trait_ref` and whose bounds hold, returning it
+    /// along with the substitutions for its generic parameters
+    /// `trait_ref` should not contain any type parameters or inference variables
+    pub fn select_impl(self, trait_ref: TraitRef<'tcx>) -> Option<(DefId, SubstsRef<'tcx>)> {
+        self.select_impl_at_depth(trait_ref, 0)
+    }
+
+    fn select_impl_at_depth(
+        self,
+        trait_ref: TraitRef<'tcx>,
+        depth: usize,
+    ) -> Option<(DefId, SubstsRef<'tcx>)> {
+        // the trait solver has already reported an overflow for such a trait ref
+        if depth > SELECTION_RECURSION_LIMIT {
+            return None;
+        }
+        self.trait_impls_of(trait_ref.def_id).iter().find_map(|&impl_def_id| {
+            let impl_trait_ref = self.impl_trait_ref(impl_def_id).unwrap();
+            let mut matcher = Match::new(self);
+            matcher.relate(impl_trait_ref.self_ty, trait_ref.self_ty).ok()?;
+            matcher.relate(impl_trait_ref.substs, trait_ref.substs).ok()?;
+            // the type parameters of the impl are all determined by the trait ref it implements
+            let substs = self
+                .generics_of(impl_def_id)
+                .params
+                .iter()
+                .map(|param| matcher.bindings[&param.index]);
+            let substs = self.mk_substs(substs);
+            // the headers of impls whose bounds are disjoint may overlap
+            let holds = self.predicates_of(impl_def_id).predicates.iter().all(|&(predicate, _)| {
+                self.predicate_holds(predicate.subst(self, substs), depth + 1)
+            });
+            holds.then(|| (impl_def_id, substs))
+        })
+    }
+
+    /// whether the (fully concrete) `predicate` holds
+    fn predicate_holds(self, predicate: Predicate<'tcx>, depth: usize) -> bool {
+        match predicate {
+            Predicate::Trait(trait_ref) => match trait_ref.self_ty.kind {
+                // a trait object `dyn Trait` implements `Trait` through its vtable
+                TyKind::Dynamic(def_id, substs)
+                    if def_id == trait_ref.def_id && substs == trait_ref.substs =>
+                    true,
+                _ => self.select_impl_at_depth(trait_ref, depth).is_some(),
+            },
+        }
+    }
+
+    /// finds the item of the impl with `impl_def_id` that defines the trait item with
+    /// `trait_item_def_id` (if it is not left to the trait's default)
+    pub fn impl_item_for_trait_item(
+        self,
+        impl_def_id: DefId,
+        trait_item_def_id: DefId,
+    ) -> Option<DefId> {
+        let ident = self.defs().ident(trait_item_def_id);
+        self.assoc_items(impl_def_id)
+            .iter()
+            .find(|impl_item| impl_item.ident == ident)
+            .map(|impl_item| impl_item.def_id)
+    }
+
+    /// the trait ref of the projection `<T as Trait<U>>::Item` is `T: Trait<U>`
+    pub fn projection_trait_ref(self, def_id: DefId, substs: SubstsRef<'tcx>) -> TraitRef<'tcx> {
+        match self.assoc_item(def_id).map(|item| item.container) {
+            Some(AssocContainer::Trait(trait_def_id)) =>
+                TraitRef::from_substs(self, trait_def_id, substs),
+            _ => panic!("`{}` is not an associated type of a trait", def_id),
+        }
+    }
+
+    /// replaces each projection in `value` that is fully concrete with the type the
+    /// applicable impl defines for it
+    /// projections involving type parameters are left as is
+    pub fn normalize<T>(self, value: T) -> T
+    where
+        T: TypeFoldable<'tcx>,
+    {
+        value.fold_with(&mut NormalizeFolder { tcx: self })
+    }
+
+    /// normalizes `ty` if it is a fully concrete projection, otherwise returns it unchanged
+    /// (this does not look at the types nested within `ty`)
+    pub(super) fn normalize_shallow(self, ty: Ty<'tcx>) -> Ty<'tcx> {
+        match ty.kind {
+            TyKind::Projection(def_id, substs)
+                if !substs.has_ty_params() && !substs.has_infer_vars() =>
+                self.normalize_projection(def_id, substs),
+            _ => ty,
+        }
+    }
+
+    fn normalize_projection(self, def_id: DefId, substs: SubstsRef<'tcx>) -> Ty<'tcx> {
+        let trait_ref = self.projection_trait_ref(def_id, substs);
+        // an unsatisfied bound is reported during typechecking
+        let (impl_def_id, impl_substs) = match self.select_impl(trait_ref) {
+            Some(selection) => selection,
+            None => return self.mk_ty_err(),
+        };
+        self.impl_assoc_ty(impl_def_id, impl_substs, def_id, substs)
+    }
+
+    /// the type that the impl with `impl_def_id` (instantiated with `impl_substs`) defines for
+    /// the projection of the associated type `def_id` with the trait substitutions `substs`
+    /// (this is the trait's default if the impl doesn't define the associated type itself)
+    pub fn impl_assoc_ty(
+        self,
+        impl_def_id: DefId,
+        impl_substs: SubstsRef<'tcx>,
+        def_id: DefId,
+        substs: SubstsRef<'tcx>,
+    ) -> Ty<'tcx> {
+        match self.impl_item_for_trait_item(impl_def_id, def_id) {
+            Some(impl_item_def_id) => self.type_of(impl_item_def_id).subst(self, impl_substs),
+            None => match self.assoc_item(def_id) {
+                Some(trait_item) if trait_item.has_default() =>
+                    self.type_of(def_id).subst(self, substs),
+                // a missing associated type is reported when checking the impl
+                _ => self.mk_ty_err(),
+            },
+        }
+    }
+}
+
+struct NormalizeFolder<'tcx> {
+    tcx: TyCtx<'tcx>,
+}
+
+impl<'tcx> TypeFolder<'tcx> for NormalizeFolder<'tcx> {
+    fn tcx(&self) -> TyCtx<'tcx> {
+        self.tcx
+    }
+
+    fn fold_ty(&mut self, ty: Ty<'tcx>) -> Ty<'tcx> {
+        if !ty.has_projections() {
+            return ty;
+        }
+        self.tcx.normalize_shallow(ty.inner_fold_with(self))
+    }
+}
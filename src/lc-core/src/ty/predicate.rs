@@ -0,0 +1,116 @@
+use crate::ty::{
+    tls, HasTyFlags, SubstsRef, Ty, TyCtx, TyFlags, TypeFoldable, TypeFolder, TypeVisitor,
+};
+use ir::DefId;
+use lc_span::Span;
+use std::fmt::{self, Display, Formatter};
+
+/// a trait with all its generic arguments provided
+/// `self_ty: Trait<substs>`
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct TraitRef<'tcx> {
+    pub def_id: DefId,
+    pub self_ty: Ty<'tcx>,
+    /// the arguments to the generic parameters of the trait (excluding `Self`)
+    pub substs: SubstsRef<'tcx>,
+}
+
+impl<'tcx> TraitRef<'tcx> {
+    pub fn new(def_id: DefId, self_ty: Ty<'tcx>, substs: SubstsRef<'tcx>) -> Self {
+        Self { def_id, self_ty, substs }
+    }
+
+    /// constructs a trait ref from the types for all the generic parameters of the trait
+    /// (where the first is always for `Self`)
+    pub fn from_substs(tcx: TyCtx<'tcx>, def_id: DefId, substs: &[Ty<'tcx>]) -> Self {
+        let (&self_ty, substs) = substs.split_first().unwrap();
+        Self::new(def_id, self_ty, tcx.mk_substs(substs.iter().copied()))
+    }
+}
+
+impl<'tcx> TypeFoldable<'tcx> for TraitRef<'tcx> {
+    fn inner_fold_with<F>(&self, folder: &mut F) -> Self
+    where
+        F: TypeFolder<'tcx>,
+    {
+        Self {
+            def_id: self.def_id,
+            self_ty: self.self_ty.fold_with(folder),
+            substs: self.substs.fold_with(folder),
+        }
+    }
+
+    fn inner_visit_with<V>(&self, visitor: &mut V) -> bool
+    where
+        V: TypeVisitor<'tcx>,
+    {
+        self.self_ty.visit_with(visitor) || self.substs.visit_with(visitor)
+    }
+}
+
+impl<'tcx> HasTyFlags for TraitRef<'tcx> {
+    fn has_flags(&self, flags: TyFlags) -> bool {
+        self.self_ty.has_flags(flags) || self.substs.has_flags(flags)
+    }
+}
+
+impl<'tcx> Display for TraitRef<'tcx> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let ident = tls::with_tcx(|tcx| tcx.defs().ident(self.def_id));
+        write!(f, "{}: {}", self.self_ty, ident)?;
+        if !self.substs.is_empty() {
+            write!(f, "<{}>", self.substs)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Predicate<'tcx> {
+    /// `T: Trait`
+    Trait(TraitRef<'tcx>),
+}
+
+impl<'tcx> TypeFoldable<'tcx> for Predicate<'tcx> {
+    fn inner_fold_with<F>(&self, folder: &mut F) -> Self
+    where
+        F: TypeFolder<'tcx>,
+    {
+        match self {
+            Predicate::Trait(trait_ref) => Predicate::Trait(trait_ref.fold_with(folder)),
+        }
+    }
+
+    fn inner_visit_with<V>(&self, visitor: &mut V) -> bool
+    where
+        V: TypeVisitor<'tcx>,
+    {
+        match self {
+            Predicate::Trait(trait_ref) => trait_ref.visit_with(visitor),
+        }
+    }
+}
+
+impl<'tcx> HasTyFlags for Predicate<'tcx> {
+    fn has_flags(&self, flags: TyFlags) -> bool {
+        match self {
+            Predicate::Trait(trait_ref) => trait_ref.has_flags(flags),
+        }
+    }
+}
+
+impl<'tcx> Display for Predicate<'tcx> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Predicate::Trait(trait_ref) => write!(f, "{}", trait_ref),
+        }
+    }
+}
+
+/// the predicates that must hold for an item to be well formed
+/// (this includes the predicates of the parent impl for an impl item)
+#[derive(Debug, Default)]
+pub struct Predicates<'tcx> {
+    /// each predicate with the span of the bound it originates from
+    pub predicates: Vec<(Predicate<'tcx>, Span)>,
+}
@@ -1,5 +1,8 @@
 use crate::ty::{self, *};
+use ir::ParamIdx;
 use itertools::Itertools;
+use rustc_hash::FxHashMap;
+use std::collections::hash_map::Entry;
 
 pub trait TypeRelation<'tcx>: Sized {
     fn tcx(&self) -> TyCtx<'tcx>;
@@ -21,11 +24,16 @@ pub trait TypeRelation<'tcx>: Sized {
             (ty::Boxed(t), ty::Boxed(u)) => self.relate(t, u),
             (ty::Param(t), ty::Param(u)) if t.idx == u.idx => Ok(a),
             (ty::Tuple(xs), ty::Tuple(ys)) => self.relate_tuples(xs, ys),
-            (ty::Array(t, m), ty::Array(u, n)) if m == n => self.relate(t, u),
+            (ty::Array(t, m), ty::Array(u, n)) if m == n =>
+                Ok(tcx.mk_array_ty(self.relate(t, u)?, m)),
             (ty::Adt(adtx, substsx), ty::Adt(adty, substsy)) if adtx == adty => {
                 let substs = self.relate(substsx, substsy)?;
                 Ok(tcx.mk_adt_ty(adtx, substs))
             }
+            (ty::Projection(x, substsx), ty::Projection(y, substsy)) if x == y =>
+                Ok(tcx.mk_projection_ty(x, self.relate(substsx, substsy)?)),
+            (ty::Dynamic(x, substsx), ty::Dynamic(y, substsy)) if x == y =>
+                Ok(tcx.mk_dynamic_ty(x, self.relate(substsx, substsy)?)),
             (_, ty::Never) => Ok(a),
             (ty::Never, _) => Ok(b),
             (ty::FnPtr(f), ty::FnPtr(g)) => Ok(tcx.mk_fn_ptr(self.relate(f, g)?)),
@@ -69,3 +77,37 @@ impl<'tcx> Relate<'tcx> for FnSig<'tcx> {
         Ok(Self { params, ret })
     }
 }
+
+/// matches a (possibly generic) type against a type without type parameters
+/// recording the type that each type parameter is matched with
+/// e.g. matching `Option<T>` against `Option<int>` binds `T` to `int`
+pub struct Match<'tcx> {
+    tcx: TyCtx<'tcx>,
+    pub bindings: FxHashMap<ParamIdx, Ty<'tcx>>,
+}
+
+impl<'tcx> Match<'tcx> {
+    pub fn new(tcx: TyCtx<'tcx>) -> Self {
+        Self { tcx, bindings: Default::default() }
+    }
+}
+
+impl<'tcx> TypeRelation<'tcx> for Match<'tcx> {
+    fn tcx(&self) -> TyCtx<'tcx> {
+        self.tcx
+    }
+
+    fn relate_tys(&mut self, a: Ty<'tcx>, b: Ty<'tcx>) -> TypeResult<'tcx, Ty<'tcx>> {
+        if a == b {
+            return Ok(a);
+        }
+        match a.kind {
+            ty::Param(param) => match self.bindings.entry(param.idx) {
+                Entry::Occupied(entry) if *entry.get() == b => Ok(b),
+                Entry::Occupied(entry) => Err(TypeError::Mismatch(*entry.get(), b)),
+                Entry::Vacant(entry) => Ok(*entry.insert(b)),
+            },
+            _ => self.relate_inner(a, b),
+        }
+    }
+}
@@ -27,13 +27,15 @@ impl<'tcx> TypeFolder<'tcx> for SubstsFolder<'tcx> {
     }
 
     fn fold_ty(&mut self, ty: Ty<'tcx>) -> Ty<'tcx> {
-        if !ty.has_ty_params() {
+        if !ty.has_ty_params() && !ty.has_projections() {
             return ty;
         }
 
         match ty.kind {
             TyKind::Param(param_ty) => self.substs[param_ty.idx.index()],
-            _ => ty.inner_fold_with(self),
+            // projections that are fully concrete after substitution are normalized
+            // (this is what resolves associated types during monomorphization)
+            _ => self.tcx.normalize_shallow(ty.inner_fold_with(self)),
         }
     }
 }
@@ -62,14 +64,23 @@ impl<'tcx> Substs<'tcx> {
     }
 }
 
+/// the substitutions for each kind of inference variable
+/// i.e. the type for `InferTy::IntVar(i)` is `int_vars[i]`
+#[derive(Debug, Clone, Copy)]
+pub struct InferSubsts<'tcx> {
+    pub ty_vars: SubstsRef<'tcx>,
+    pub int_vars: SubstsRef<'tcx>,
+    pub float_vars: SubstsRef<'tcx>,
+}
+
 /// substitute inference variables according to some substitution
 pub struct InferVarSubstsFolder<'tcx> {
     tcx: TyCtx<'tcx>,
-    substs: SubstsRef<'tcx>,
+    substs: InferSubsts<'tcx>,
 }
 
 impl<'tcx> InferVarSubstsFolder<'tcx> {
-    pub fn new(tcx: TyCtx<'tcx>, substs: SubstsRef<'tcx>) -> Self {
+    pub fn new(tcx: TyCtx<'tcx>, substs: InferSubsts<'tcx>) -> Self {
         Self { tcx, substs }
     }
 }
@@ -80,7 +91,9 @@ impl<'tcx> TypeFolder<'tcx> for InferVarSubstsFolder<'tcx> {
             return ty;
         }
         match ty.kind {
-            TyKind::Infer(InferTy::TyVar(tyvid)) => self.substs[tyvid.index as usize],
+            TyKind::Infer(InferTy::TyVar(tyvid)) => self.substs.ty_vars[tyvid.index as usize],
+            TyKind::Infer(InferTy::IntVar(vid)) => self.substs.int_vars[vid.index as usize],
+            TyKind::Infer(InferTy::FloatVar(vid)) => self.substs.float_vars[vid.index as usize],
             _ => ty.inner_fold_with(self),
         }
     }
@@ -33,6 +33,10 @@ impl<'tcx> TypeckTables<'tcx> {
         }
     }
 
+    pub fn def_id(&self) -> DefId {
+        self.def_id
+    }
+
     pub fn upvar_captures_for_closure(&self, closure_id: ir::Id) -> &FxHashSet<UpvarId> {
         &self.upvar_captures[&closure_id]
     }
@@ -1,11 +1,12 @@
 use crate::queries::QueryCtx;
 use crate::ty::*;
 use crate::*;
-use ir::{DefId, FieldIdx, ParamIdx, Resolutions, VariantIdx};
+use ir::{DefId, ExternFnKind, FieldIdx, LangItem, ParamIdx, Resolutions, VariantIdx};
 use itertools::Itertools;
 use lc_ast::Ident;
 use lc_index::IndexVec;
 use lc_session::Session;
+use lc_span::Symbol;
 use std::cell::Cell;
 use std::ops::Deref;
 
@@ -58,6 +59,24 @@ impl<'tcx> TyCtx<'tcx> {
         self.interners.arena.alloc_from_iter(iter)
     }
 
+    /// the definition of the lang item `item` (these are always declared in the prelude)
+    pub fn lang_item(self, item: LangItem) -> DefId {
+        self.resolutions
+            .lang_items
+            .get(item)
+            .unwrap_or_else(|| panic!("missing definition of lang item `{}`", item))
+    }
+
+    /// the symbol that the non-generic function `def_id` is exported under
+    /// this is qualified by the name of the package so that it is unique amongst the packages
+    /// that are linked together
+    pub fn symbol_name(self, def_id: DefId) -> String {
+        match self.defs().extern_fn_kind(def_id) {
+            Some(ExternFnKind::Linked(symbol)) => symbol.to_string(),
+            _ => format!("{}::{}#{}", self.sess.pkg_name, self.defs().ident(def_id), def_id),
+        }
+    }
+
     pub fn mk_struct_ty(self, def_id: DefId, ident: Ident, variant: VariantTy) -> &'tcx AdtTy {
         self.mk_adt(def_id, AdtKind::Struct, ident, std::iter::once(variant).collect())
     }
@@ -90,6 +109,14 @@ impl<'tcx> TyCtx<'tcx> {
         self.mk_ty(TyKind::Opaque(def, substs))
     }
 
+    pub fn mk_projection_ty(self, def: DefId, substs: SubstsRef<'tcx>) -> Ty<'tcx> {
+        self.mk_ty(TyKind::Projection(def, substs))
+    }
+
+    pub fn mk_dynamic_ty(self, trait_def_id: DefId, substs: SubstsRef<'tcx>) -> Ty<'tcx> {
+        self.mk_ty(TyKind::Dynamic(trait_def_id, substs))
+    }
+
     pub fn mk_array_ty(self, ty: Ty<'tcx>, n: usize) -> Ty<'tcx> {
         self.mk_ty(TyKind::Array(ty, n))
     }
@@ -124,6 +151,7 @@ impl<'tcx> TyCtx<'tcx> {
             Projection::Deref => ty.deref_ty(),
             Projection::Field(_, ty) => ty,
             Projection::PointerCast(ty) => ty,
+            Projection::Index(_) => ty.array_elem_ty(),
         }
     }
 
@@ -131,8 +159,10 @@ impl<'tcx> TyCtx<'tcx> {
         match prim_ty {
             ir::PrimTy::Char => self.types.char,
             ir::PrimTy::Bool => self.types.bool,
-            ir::PrimTy::Float => self.types.float,
-            ir::PrimTy::Int => self.types.int,
+            ir::PrimTy::Float(float_ty) => self.mk_ty(TyKind::Float(float_ty)),
+            ir::PrimTy::Int(int_ty) => self.mk_ty(TyKind::Int(int_ty)),
+            ir::PrimTy::Uint(uint_ty) => self.mk_ty(TyKind::Uint(uint_ty)),
+            ir::PrimTy::Str => self.types.str,
         }
     }
 
@@ -210,6 +240,14 @@ impl<'tcx> TyCtx<'tcx> {
         self.mk_const(ConstKind::Float(f))
     }
 
+    pub fn mk_const_char(self, c: char) -> &'tcx Const<'tcx> {
+        self.mk_const(ConstKind::Char(c))
+    }
+
+    pub fn mk_const_str(self, s: Symbol) -> &'tcx Const<'tcx> {
+        self.mk_const(ConstKind::Str(s))
+    }
+
     pub fn mk_const_bool(self, b: bool) -> &'tcx Const<'tcx> {
         self.mk_const(ConstKind::Bool(b))
     }
@@ -222,12 +260,20 @@ impl<'tcx> TyCtx<'tcx> {
         self.mk_const(ConstKind::Unit)
     }
 
+    /// creates a constant of the given type
+    /// this is required for numeric constants whose type is not necessarily the default
+    pub fn mk_const_of_ty(self, kind: ConstKind, ty: Ty<'tcx>) -> &'tcx Const<'tcx> {
+        self.intern_const(Const { kind, ty })
+    }
+
     pub fn mk_const(self, kind: ConstKind) -> &'tcx Const<'tcx> {
         let ty = match kind {
             ConstKind::Float(_) => self.types.float,
             ConstKind::Int(_) => self.types.int,
             ConstKind::Discr(_) => self.types.discr,
             ConstKind::Bool(_) => self.types.bool,
+            ConstKind::Char(_) => self.types.char,
+            ConstKind::Str(_) => self.types.str,
             ConstKind::Unit => self.types.unit,
         };
         self.intern_const(Const { kind, ty })
@@ -277,6 +323,7 @@ pub struct CommonTypes<'tcx> {
     pub discr: Ty<'tcx>,
     pub float: Ty<'tcx>,
     pub int: Ty<'tcx>,
+    pub str: Ty<'tcx>,
     pub never: Ty<'tcx>,
     /// type of `main` must be `fn() -> int`
     pub main: Ty<'tcx>,
@@ -285,13 +332,14 @@ pub struct CommonTypes<'tcx> {
 impl<'tcx> CommonTypes<'tcx> {
     fn new(interners: &CtxInterners<'tcx>) -> CommonTypes<'tcx> {
         let mk = |ty| interners.intern_ty(ty);
-        let int = mk(TyKind::Int);
+        let int = mk(TyKind::Int(IntTy::I64));
         CommonTypes {
             bool: mk(TyKind::Bool),
             char: mk(TyKind::Char),
             discr: mk(TyKind::Discr),
             never: mk(TyKind::Never),
-            float: mk(TyKind::Float),
+            float: mk(TyKind::Float(FloatTy::F64)),
+            str: mk(TyKind::Str),
             main: mk(TyKind::FnPtr(FnSig { params: Substs::empty(), ret: int })),
             unit: mk(TyKind::Tuple(Substs::empty())),
             int,
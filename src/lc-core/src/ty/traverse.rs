@@ -56,14 +56,18 @@ impl<'tcx> TypeFoldable<'tcx> for Ty<'tcx> {
             TyKind::Tuple(tys) => TyKind::Tuple(tys.fold_with(folder)),
             TyKind::Adt(adt, substs) => TyKind::Adt(adt, substs.fold_with(folder)),
             TyKind::Opaque(def, substs) => TyKind::Opaque(def, substs.fold_with(folder)),
+            TyKind::Projection(def, substs) => TyKind::Projection(def, substs.fold_with(folder)),
+            TyKind::Dynamic(def, substs) => TyKind::Dynamic(def, substs.fold_with(folder)),
             TyKind::Param(_)
             | TyKind::Infer(_)
             | TyKind::Char
             | TyKind::Discr
             | TyKind::Never
-            | TyKind::Int
+            | TyKind::Int(_)
+            | TyKind::Uint(_)
             | TyKind::Bool
-            | TyKind::Float
+            | TyKind::Float(_)
+            | TyKind::Str
             | TyKind::Error => {
                 return self;
             }
@@ -87,7 +91,9 @@ impl<'tcx> TypeFoldable<'tcx> for Ty<'tcx> {
             TyKind::FnPtr(sig) => sig.visit_with(visitor),
             TyKind::Ptr(ty) | TyKind::Boxed(ty) | TyKind::Array(ty, _) => ty.visit_with(visitor),
             TyKind::Tuple(tys) => tys.visit_with(visitor),
-            TyKind::Opaque(_, substs) => substs.visit_with(visitor),
+            TyKind::Opaque(_, substs)
+            | TyKind::Projection(_, substs)
+            | TyKind::Dynamic(_, substs) => substs.visit_with(visitor),
             TyKind::Adt(_, substs) => substs.visit_with(visitor),
             TyKind::Param(..)
             | TyKind::Infer(..)
@@ -95,8 +101,10 @@ impl<'tcx> TypeFoldable<'tcx> for Ty<'tcx> {
             | TyKind::Never
             | TyKind::Error
             | TyKind::Char
-            | TyKind::Int
-            | TyKind::Float
+            | TyKind::Int(_)
+            | TyKind::Uint(_)
+            | TyKind::Float(_)
+            | TyKind::Str
             | TyKind::Bool => false,
         }
     }
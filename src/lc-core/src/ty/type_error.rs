@@ -1,6 +1,6 @@
 use crate::ty::{Ty, TyVid};
-use ir::{self, Res};
-use lc_ast::Ident;
+use ir::{self, DefKind, Res};
+use lc_ast::{BinOp, Ident, UnaryOp};
 use lc_error::LError;
 use thiserror::Error;
 
@@ -20,6 +20,8 @@ pub enum TypeError<'tcx> {
     BadFieldAccess(Ty<'tcx>),
     #[error("attempted to index `.{0}` into a {1}-tuple")]
     TupleOutOfBounds(usize, usize),
+    #[error("cannot index into a value of type `{0}`")]
+    InvalidIndexBase(Ty<'tcx>),
     #[error("expected {0} generic parameter{} but received {1}", lc_util::pluralize!({*.0}))]
     GenericArgCount(usize, usize),
     #[error("{0}")]
@@ -36,6 +38,38 @@ pub enum TypeError<'tcx> {
     RequireUnsafeCtx,
     #[error("type annotations required")]
     InferenceFailure,
+    #[error("binary operator `{0}` cannot be applied to type `{1}`")]
+    InvalidBinaryOperand(BinOp, Ty<'tcx>),
+    #[error("unary operator `{0}` cannot be applied to type `{1}`")]
+    InvalidUnaryOperand(UnaryOp, Ty<'tcx>),
+    #[error("cannot assign to an overloaded index expression")]
+    AssignToOverloadedIndex,
+    #[error("expected trait, found {0}")]
+    ExpectedTrait(Res),
+    #[error("missing item `{0}` in impl of trait `{1}`")]
+    MissingTraitItem(Ident, Ident),
+    #[error("associated type `{1}` not found for `{0}`")]
+    UnknownAssocTy(Ty<'tcx>, Ident),
+    #[error("ambiguous associated type `{0}` (use the form `<Type as Trait>::{0}`)")]
+    AmbiguousAssocTy(Ident),
+    #[error("the trait `{0}` cannot be made into an object")]
+    ObjectUnsafe(Ident),
+    #[error("conflicting implementations of trait `{0}`")]
+    ConflictingImpls(Ident),
+    #[error("duplicate definitions with name `{0}`")]
+    DuplicateDefinitions(Ident),
+    #[error("only traits defined in this package can be implemented for types of other packages")]
+    OrphanImpl,
+    #[error("cannot define an inherent impl for `{0}` outside of the package that defines it")]
+    ForeignInherentImpl(Ty<'tcx>),
+    #[error("inherent impls for trait objects such as `{0}` are not supported")]
+    DynamicInherentImpl(Ty<'tcx>),
+    #[error("{0} `{1}` is private")]
+    PrivateItem(DefKind, Ident),
+    #[error("field `{0}` of struct `{1}` is private")]
+    PrivateField(Ident, Ident),
+    #[error("literal out of range for `{0}`")]
+    LiteralOutOfRange(Ty<'tcx>),
 }
 
 impl<'tcx> LError for TypeError<'tcx> {
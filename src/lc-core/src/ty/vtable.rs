@@ -0,0 +1,32 @@
+//! the layout of the vtable of a trait object
+//! a vtable is an array of function pointers, one for each method of the trait in the order
+//! they are declared
+
+use crate::ty::{AssocItemKind, Instance, Ty, TyCtx, TyKind};
+use ir::DefId;
+
+impl<'tcx> TyCtx<'tcx> {
+    /// the methods of the trait with `trait_def_id` in vtable order
+    pub fn vtable_methods(self, trait_def_id: DefId) -> Vec<DefId> {
+        self.assoc_items(trait_def_id)
+            .iter()
+            .filter(|trait_item| matches!(trait_item.kind, AssocItemKind::Fn { .. }))
+            .map(|trait_item| trait_item.def_id)
+            .collect()
+    }
+
+    /// the instances that form the vtable for the unsizing cast of the pointer `source` to the
+    /// trait object pointer `target` (e.g. from `&S` to `&dyn Trait`)
+    pub fn vtable_instances(self, source: Ty<'tcx>, target: Ty<'tcx>) -> Vec<Instance<'tcx>> {
+        let (trait_def_id, substs) = match target.deref_ty().kind {
+            TyKind::Dynamic(trait_def_id, substs) => (trait_def_id, substs),
+            _ => panic!("expected a pointer to a trait object, found `{}`", target),
+        };
+        // the methods of an object safe trait have no generic parameters of their own
+        let substs = self.mk_substs(std::iter::once(source.deref_ty()).chain(substs));
+        self.vtable_methods(trait_def_id)
+            .into_iter()
+            .map(|def_id| Instance::resolve(self, def_id, substs))
+            .collect()
+    }
+}
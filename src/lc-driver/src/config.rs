@@ -1,4 +1,4 @@
-use lc_session::CompilerOptions;
+use lc_session::{CfgOption, CompilerOptions};
 use semver::Version;
 use serde::de::{self, Deserialize};
 use std::collections::HashMap;
@@ -19,16 +19,19 @@ pub struct LConfig {
 }
 
 pub(crate) fn load_config(opts: CompilerOptions) -> io::Result<LConfig> {
-    let path = opts
-        .input_path
-        .canonicalize()
-        .unwrap_or_else(|_| panic!("path `{}` does not exist", opts.input_path.display()));
+    let path = opts.input_path.canonicalize().map_err(|_| {
+        let msg = format!("path `{}` does not exist", opts.input_path.display());
+        io::Error::new(io::ErrorKind::NotFound, msg)
+    })?;
 
     // if `path` is a directory we search it for a `L.toml` file and load the config using that
     let mut config = if path.is_dir() {
         let toml_path = match load_toml(&path)? {
             Some(toml) => toml,
-            None => panic!("`L.toml` not found in `{}`", path.display()),
+            None => {
+                let msg = format!("`L.toml` not found in `{}`", path.display());
+                return Err(io::Error::new(io::ErrorKind::NotFound, msg));
+            }
         };
         // the given path could be either `path/to/pkg/L.toml` or `path/to/pkg`
         let content = fs::read_to_string(&toml_path)?;
@@ -48,31 +51,63 @@ pub(crate) fn load_config(opts: CompilerOptions) -> io::Result<LConfig> {
     };
 
     config.opts = opts;
+    // each feature enabled in `L.toml` becomes the cfg option `feature="<name>"`
+    let features = config.toml.features.iter().filter(|&(_, &enabled)| enabled);
+    config.opts.cfg.extend(features.map(|(feature, _)| CfgOption::feature(feature)));
     config.validate()?;
     Ok(config)
 }
 
 impl LConfig {
     pub fn validate(&self) -> io::Result<()> {
-        for dep in self.dependencies.values() {
-            match dep {
-                Dependency::Simple(version) =>
-                    if let Err(err) = Version::parse(version) {
-                        panic!("{}", err)
-                    },
-                // check the dependencies exist
-                Dependency::Detailed(info) =>
-                    if let Some(path) = &info.path {
-                        let dep_path = Path::new(&path);
-                        let joined_path = self.root_path.join(dep_path);
-                        self::load_config(CompilerOptions {
-                            input_path: joined_path,
-                            ..self.opts.clone()
-                        })?;
-                    },
+        self.load_dependencies().map(|_| ())
+    }
+
+    /// loads the config of each dependency (in order of name so builds are deterministic)
+    pub(crate) fn load_dependencies(&self) -> io::Result<Vec<LConfig>> {
+        let mut names = self.dependencies.keys().collect::<Vec<_>>();
+        names.sort();
+        names.into_iter().map(|name| self.load_dependency(name)).collect()
+    }
+
+    fn load_dependency(&self, name: &str) -> io::Result<LConfig> {
+        let path = match &self.dependencies[name] {
+            Dependency::Simple(version) => {
+                Version::parse(version).map_err(|err| invalid_config(err.to_string()))?;
+                return Err(invalid_config(format!(
+                    "dependency `{}` must be a path dependency (there is no registry)",
+                    name
+                )));
             }
+            Dependency::Detailed(DependencyInfo { path: Some(path) }) => path,
+            Dependency::Detailed(..) =>
+                return Err(invalid_config(format!("dependency `{}` has no `path`", name))),
+        };
+        // a dependency is configured by its own features rather than those of its dependent
+        let config = self::load_config(CompilerOptions {
+            input_path: self.root_path.join(path),
+            cfg: vec![],
+            ..self.opts.clone()
+        })?;
+        if config.package.name != name {
+            let pkg_name = &config.package.name;
+            let msg = format!("dependency `{}` refers to the package `{}`", name, pkg_name);
+            return Err(invalid_config(msg));
         }
-        Ok(())
+        let lib_path = config.lib_path();
+        if !lib_path.is_file() {
+            let msg = format!("dependency `{}` has no library `{}`", name, lib_path.display());
+            return Err(io::Error::new(io::ErrorKind::NotFound, msg));
+        }
+        Ok(config)
+    }
+
+    pub fn main_path(&self) -> PathBuf {
+        self.root_path.join(&self.bin.main_path)
+    }
+
+    pub fn lib_path(&self) -> PathBuf {
+        self.root_path.join(&self.lib.path)
     }
 }
 
@@ -111,6 +146,12 @@ pub struct TomlConfig {
     pub dependencies: Dependencies,
     #[serde(default = "BinConfig::default")]
     pub bin: BinConfig,
+    #[serde(default = "LibConfig::default")]
+    pub lib: LibConfig,
+    /// the features of the package and whether each is enabled
+    /// e.g. `[features] instrumented = true`
+    #[serde(default)]
+    pub features: Features,
 }
 
 #[derive(Debug, Deserialize)]
@@ -126,8 +167,23 @@ impl Default for BinConfig {
     }
 }
 
+#[derive(Debug, Deserialize)]
+pub struct LibConfig {
+    /// path of the root file of the library (which is how dependents use the package)
+    #[serde(default = "default_lib_file")]
+    pub path: PathBuf,
+}
+
+impl Default for LibConfig {
+    fn default() -> Self {
+        Self { path: default_lib_file() }
+    }
+}
+
 pub type Dependencies = HashMap<String, Dependency>;
 
+pub type Features = HashMap<String, bool>;
+
 #[derive(Debug)]
 pub enum Dependency {
     Simple(String),
@@ -174,7 +230,7 @@ impl<'de> de::Deserialize<'de> for Dependency {
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct DependencyInfo {
-    path: Option<String>,
+    pub(crate) path: Option<String>,
 }
 
 // this impl only used to running tests
@@ -188,12 +244,21 @@ fn default_main_file() -> PathBuf {
     "src/main.l".into()
 }
 
+fn default_lib_file() -> PathBuf {
+    "src/lib.l".into()
+}
+
 #[derive(Debug, Deserialize)]
 pub struct PkgConfig {
-    name: String,
+    pub(crate) name: String,
     version: Version,
 }
 
+/// an error for a mistake in the `L.toml` of a package
+fn invalid_config(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, msg)
+}
+
 fn load_toml(path: &Path) -> io::Result<Option<PathBuf>> {
     if path.is_dir() {
         for file in path.read_dir()? {
@@ -0,0 +1,184 @@
+//! builds the dependencies of a package
+//! each dependency is compiled as a library into an object file along with its metadata
+//! these are reused by later builds as long as the metadata was built from the same inputs
+//! every package also implicitly depends on the prelude, which is only compiled into metadata
+
+use crate::config::LConfig;
+use crate::Driver;
+use lc_index::IndexVec;
+use lc_ir::PkgId;
+use lc_meta::{MetadataError, PkgMetadata};
+use lc_parse::PRELUDE_SRC;
+use lc_span::sym;
+use rustc_hash::FxHasher;
+use std::cell::OnceCell;
+use std::fs;
+use std::hash::Hasher;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitStatus;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum BuildError {
+    #[error("could not compile the dependency `{0}`")]
+    DependencyFailed(String),
+    #[error("could not load the metadata of the dependency `{0}`: {1}")]
+    InvalidMetadata(String, MetadataError),
+    #[error("cyclic package dependency: {}", .0.join(" -> "))]
+    Cycle(Vec<String>),
+    #[error("`clang` failed to {0} ({1})")]
+    Clang(&'static str, ExitStatus),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Dependencies {
+    /// the metadata of every (direct or indirect) dependency, starting with the prelude
+    /// each package comes after its own dependencies as the metadata of a package may refer to
+    /// the definitions of its dependencies
+    pub metadata: IndexVec<PkgId, PkgMetadata>,
+    /// the direct dependencies, which are the only packages that can be referred to by name
+    pub direct: Vec<PkgId>,
+    /// the object files of every (direct or indirect) dependency (the prelude has none)
+    pub objects: Vec<PathBuf>,
+}
+
+impl Dependencies {
+    /// adds the package with the metadata `metadata` unless it is already a dependency
+    fn insert(&mut self, metadata: PkgMetadata) -> PkgId {
+        match self.metadata.iter_enumerated().find(|(_, dep)| dep.name == metadata.name) {
+            Some((pkg, _)) => pkg,
+            None => self.metadata.push(metadata),
+        }
+    }
+}
+
+/// the object file that the library `name` is compiled into
+pub(crate) fn object_path(root_path: &Path, name: &str) -> PathBuf {
+    root_path.join(format!("lib{}.o", name))
+}
+
+pub(crate) fn metadata_path(root_path: &Path, name: &str) -> PathBuf {
+    root_path.join(format!("lib{}.lmeta", name))
+}
+
+thread_local! {
+    /// the encoded metadata of the prelude, which is only compiled once
+    static PRELUDE_METADATA: OnceCell<Vec<u8>> = OnceCell::new();
+}
+
+/// loads the metadata of the prelude, compiling it if it hasn't been already
+fn load_prelude() -> Result<PkgMetadata, BuildError> {
+    let bytes = match PRELUDE_METADATA.with(|cell| cell.get().cloned()) {
+        Some(bytes) => bytes,
+        None => {
+            let mut hasher = FxHasher::default();
+            hasher.write(PRELUDE_SRC.as_bytes());
+            let name = sym::prelude.to_string();
+            let bytes = Driver::new_prelude()
+                .build_prelude(hasher.finish())
+                .map_err(|_| BuildError::DependencyFailed(name))?
+                .encode();
+            PRELUDE_METADATA.with(|cell| cell.get_or_init(|| bytes).clone())
+        }
+    };
+    PkgMetadata::decode(&bytes)
+        .map_err(|err| BuildError::InvalidMetadata(sym::prelude.to_string(), err))
+}
+
+pub(crate) fn build_dependencies(config: &LConfig) -> Result<Dependencies, BuildError> {
+    build_dependencies_of(config, &mut vec![config.package.name.clone()])
+}
+
+/// `building` is the chain of packages whose dependencies are currently being built, which
+/// must not be depended on again
+fn build_dependencies_of(
+    config: &LConfig,
+    building: &mut Vec<String>,
+) -> Result<Dependencies, BuildError> {
+    let mut deps = Dependencies::default();
+    let prelude = deps.insert(load_prelude()?);
+    deps.direct.push(prelude);
+    for dep_config in config.load_dependencies()? {
+        let name = dep_config.package.name.clone();
+        let root_path = dep_config.root_path.clone();
+        if building.contains(&name) {
+            building.push(name);
+            return Err(BuildError::Cycle(std::mem::take(building)));
+        }
+        // the dependencies of the dependency are built first as it is stale if any of them are
+        building.push(name.clone());
+        let transitive_deps = build_dependencies_of(&dep_config, building)?;
+        building.pop();
+        let hash = input_hash(&dep_config, &transitive_deps);
+        let metadata = match load_fresh_metadata(&dep_config, hash) {
+            Some(metadata) => metadata,
+            None => {
+                Driver::new_lib(dep_config)
+                    .build_lib(hash)
+                    .map_err(|_| BuildError::DependencyFailed(name.clone()))?;
+                let bytes = fs::read(metadata_path(&root_path, &name))?;
+                PkgMetadata::decode(&bytes)
+                    .map_err(|err| BuildError::InvalidMetadata(name.clone(), err))?
+            }
+        };
+        // the same package may be depended on more than once (indirectly)
+        for metadata in transitive_deps.metadata {
+            deps.insert(metadata);
+        }
+        let pkg = deps.insert(metadata);
+        deps.direct.push(pkg);
+        let objects = transitive_deps.objects.into_iter();
+        for object in objects.chain(std::iter::once(object_path(&root_path, &name))) {
+            if !deps.objects.contains(&object) {
+                deps.objects.push(object);
+            }
+        }
+    }
+    Ok(deps)
+}
+
+/// loads the metadata of the package unless it must be rebuilt (i.e. it was built from other
+/// inputs than those hashed to `hash` or by another version of the compiler)
+fn load_fresh_metadata(config: &LConfig, hash: u64) -> Option<PkgMetadata> {
+    let name = &config.package.name;
+    if !object_path(&config.root_path, name).exists() {
+        return None;
+    }
+    let bytes = fs::read(metadata_path(&config.root_path, name)).ok()?;
+    PkgMetadata::decode(&bytes).ok().filter(|metadata| metadata.hash == hash)
+}
+
+/// hashes the inputs the package is built from, which are its sources, its `L.toml` and the
+/// metadata of its dependencies (whose hashes cover their own inputs in turn)
+fn input_hash(config: &LConfig, deps: &Dependencies) -> u64 {
+    let mut hasher = FxHasher::default();
+    let lib_path = config.lib_path();
+    let mut sources = source_files(lib_path.parent().unwrap());
+    sources.sort();
+    sources.push(config.root_path.join("L.toml"));
+    for path in sources {
+        hasher.write(path.to_string_lossy().as_bytes());
+        hasher.write(&fs::read(&path).unwrap_or_default());
+    }
+    for metadata in &deps.metadata {
+        hasher.write_u64(metadata.hash);
+    }
+    hasher.finish()
+}
+
+/// the `.l` files within `dir` (recursively)
+fn source_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = vec![];
+    for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            files.extend(source_files(&path));
+        } else if path.extension().map_or(false, |ext| ext == "l") {
+            files.push(path);
+        }
+    }
+    files
+}
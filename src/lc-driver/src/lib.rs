@@ -4,6 +4,7 @@
 
 mod cli_error;
 mod config;
+mod deps;
 mod passes;
 mod queries;
 
@@ -18,7 +19,8 @@ extern crate lc_ir as ir;
 use codespan_reporting::diagnostic::Diagnostic;
 use codespan_reporting::files::SimpleFiles;
 use codespan_reporting::term;
-use config::LConfig;
+use config::{Dependency, DependencyInfo, LConfig};
+use deps::{BuildError, Dependencies};
 use inkwell::context::Context as LLVMCtx;
 use inkwell::OptimizationLevel;
 use lazy_static::lazy_static;
@@ -27,19 +29,18 @@ use lc_astlowering::AstLoweringCtx;
 use lc_codegen::CodegenCtx;
 use lc_core::{GlobalCtx, TyCtx};
 use lc_error::{ErrorFormat, ErrorReported, LResult};
-use lc_index::IndexVec;
-use lc_ir::{PkgId, Resolutions};
+use lc_ir::Resolutions;
 use lc_lex::{Lexer, TokenIterator, TokenStream};
 use lc_meta::PkgMetadata;
-use lc_parse::Parser;
+use lc_parse::{Parser, PRELUDE_SRC};
 use lc_resolve::{Resolver, ResolverArenas};
 pub use lc_session::{CompilerOptions, Session};
-use lc_span::{sym, SourceMap, ROOT_FILE_IDX, SPAN_GLOBALS};
+use lc_span::{sym, SourceMap, Span, ROOT_FILE_IDX, SPAN_GLOBALS};
 use log::LevelFilter;
 use std::cell::OnceCell;
 use std::fs::File;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use termcolor::{BufferedStandardStream, ColorChoice};
 
 lazy_static! {
@@ -89,8 +90,8 @@ pub fn compile(lconfig: LConfig) -> i32 {
 pub struct Driver<'tcx> {
     sess: Session,
     root_path: PathBuf,
-    /// metadata of the dependencies specified in `L.toml`
-    dependencies: IndexVec<PkgId, PkgMetadata>,
+    /// the dependencies specified in `L.toml`
+    dependencies: Dependencies,
     core_arenas: lc_core::Arena<'tcx>,
     ir_arena: lc_astlowering::Arena<'tcx>,
     resolver_arenas: ResolverArenas<'tcx>,
@@ -124,27 +125,102 @@ impl<'tcx> Driver<'tcx> {
     // this can't be made #[cfg(test)] for some reason
     // as some test code complains this doesn't exist
     pub fn from_src(src: &str) -> Self {
+        Self::from_src_with_opts(src, CompilerOptions::default())
+    }
+
+    /// `from_src` but compiled with the options `opts` (its `input_path` is ignored)
+    pub fn from_src_with_opts(src: &str, opts: CompilerOptions) -> Self {
         let tempdir = tempfile::tempdir().unwrap();
         // into_place ensure the tempdir is *not* dropped
         // we need it later in the `run` stage
         let main_path = tempdir.into_path().join("main.l");
         let mut file = File::create(&main_path).unwrap();
         file.write_all(src.as_bytes()).unwrap();
-        Self::new(LConfig::from_main_path(main_path))
+        let mut lconfig = LConfig::from_main_path(main_path.clone());
+        lconfig.opts = CompilerOptions { input_path: main_path, ..opts };
+        Self::new(lconfig)
+    }
+
+    /// `from_src` along with the file modules `modules` (pairs of name and source)
+    /// which are written alongside the main file
+    pub fn from_src_with_modules(src: &str, modules: &[(&str, &str)]) -> Self {
+        let driver = Self::from_src(src);
+        for (name, module_src) in modules {
+            let path = driver.root_path.join(format!("{}.l", name));
+            std::fs::write(path, module_src).unwrap();
+        }
+        driver
+    }
+
+    /// `from_src` along with the path dependencies `dependencies` (pairs of name and the source
+    /// of the library) which are each written as a package alongside the main file
+    /// each of the packages in turn depends on those before it
+    pub fn from_src_with_dependencies(src: &str, dependencies: &[(&str, &str)]) -> Self {
+        let root_path = tempfile::tempdir().unwrap().into_path();
+        let main_path = root_path.join("main.l");
+        std::fs::write(&main_path, src).unwrap();
+        let mut lconfig = LConfig::from_main_path(main_path);
+        for (i, (name, lib_src)) in dependencies.iter().enumerate() {
+            let pkg_path = root_path.join(name);
+            std::fs::create_dir_all(pkg_path.join("src")).unwrap();
+            let mut toml = format!("[package]\nname = \"{}\"\nversion = \"0.1.0\"\n", name);
+            toml.push_str("[dependencies]\n");
+            for (dep_name, _) in &dependencies[..i] {
+                toml.push_str(&format!("{} = {{ path = \"../{}\" }}\n", dep_name, dep_name));
+            }
+            std::fs::write(pkg_path.join("L.toml"), toml).unwrap();
+            std::fs::write(pkg_path.join("src/lib.l"), lib_src).unwrap();
+            let info = DependencyInfo { path: Some(name.to_string()) };
+            lconfig.dependencies.insert(name.to_string(), Dependency::Detailed(info));
+        }
+        Self::new(lconfig)
     }
 
     pub fn new(config: LConfig) -> Self {
-        let path = config.root_path.join(&config.bin.main_path);
-        SPAN_GLOBALS.with(|globals| *globals.source_map.borrow_mut() = SourceMap::new(&path));
+        let path = config.main_path();
+        Self::with_root_file(config, &path)
+    }
 
-        // let dependencies = config.dependencies.values().collect();
-        let dependencies = IndexVec::new();
+    /// creates a driver that compiles the package as a library (see `build_lib`)
+    pub fn new_lib(config: LConfig) -> Self {
+        let path = config.lib_path();
+        Self::with_root_file(config, &path)
+    }
 
+    /// creates a driver that compiles the prelude (see `build_prelude`)
+    /// the prelude is the only package that does not depend on the prelude
+    fn new_prelude() -> Self {
+        let mut source_map = SourceMap::default();
+        source_map.add_virtual_file("prelude.l", PRELUDE_SRC);
+        SPAN_GLOBALS.with(|globals| *globals.source_map.borrow_mut() = source_map);
         Self {
+            sess: Session::create(CompilerOptions::default(), sym::prelude.to_string()),
+            dependencies: Dependencies::default(),
+            llvm_ctx: LLVMCtx::create(),
+            root_path: PathBuf::new(),
+            resolver_arenas: Default::default(),
+            core_arenas: Default::default(),
+            ir_arena: Default::default(),
+            global_ctx: Default::default(),
+        }
+    }
+
+    fn with_root_file(config: LConfig, path: &Path) -> Self {
+        // the dependencies are built before the source map is reset as they each have their own
+        let dependencies = deps::build_dependencies(&config);
+        SPAN_GLOBALS.with(|globals| *globals.source_map.borrow_mut() = SourceMap::new(path));
+
+        let sess = Session::create(config.opts, config.toml.package.name);
+        let dependencies = dependencies.unwrap_or_else(|err| {
+            sess.emit_error(Span::default(), err);
+            Dependencies::default()
+        });
+
+        Self {
+            sess,
             dependencies,
             llvm_ctx: LLVMCtx::create(),
             root_path: config.root_path,
-            sess: Session::create(config.opts),
             resolver_arenas: Default::default(),
             core_arenas: Default::default(),
             ir_arena: Default::default(),
@@ -153,21 +229,28 @@ impl<'tcx> Driver<'tcx> {
     }
 
     pub fn parse(&self) -> LResult<lc_ast::Ast> {
+        self.parse_with(&mut Parser::new(&self.sess))
+    }
+
+    fn parse_with(&self, parser: &mut Parser<'_>) -> LResult<lc_ast::Ast> {
         // assume one file for now
-        let mut parser = Parser::new(&self.sess);
         let ast = parser.parse();
         // error!("{:#?}", ast);
         check_errors!(self, ast.unwrap())
     }
 
     pub fn expand(&self) -> LResult<lc_ast::Ast> {
-        let ast = self.parse()?;
-        Ok(lc_expand::MacroExpander::new().expand(ast))
+        // the same parser is used for expansion so that node ids remain unique
+        let mut parser = Parser::new(&self.sess);
+        let ast = self.parse_with(&mut parser)?;
+        let ast = lc_expand::MacroExpander::new(&mut parser).expand(ast);
+        check_errors!(self, ast)
     }
 
     pub fn gen_ir(&'tcx self) -> LResult<(&'tcx lc_ir::Ir<'tcx>, Resolutions)> {
         let ast = self.expand()?;
-        let mut resolver = Resolver::new(&self.sess, &self.resolver_arenas, &self.dependencies);
+        let Dependencies { metadata, direct, .. } = &self.dependencies;
+        let mut resolver = Resolver::new(&self.sess, &self.resolver_arenas, metadata, direct);
         resolver.resolve(&ast);
         let lctx = AstLoweringCtx::new(&self.ir_arena, &self.sess, &mut resolver);
         let ir = lctx.lower_ast(&ast);
@@ -177,14 +260,29 @@ impl<'tcx> Driver<'tcx> {
 
     pub fn with_tcx<R>(&'tcx self, f: impl FnOnce(TyCtx<'tcx>) -> R) -> LResult<R> {
         let (ir, resolutions) = self.gen_ir()?;
+        let mut initialized = false;
         let gcx = self.global_ctx.get_or_init(|| {
+            initialized = true;
             GlobalCtx::new(ir, &self.core_arenas, resolutions, &self.sess, queries::query_ctx())
         });
+        // the tables of the dependencies must be fed before anything is queried
+        if initialized {
+            gcx.enter_tcx(|tcx| self.load_dependency_tables(tcx));
+        }
         gcx.enter_tcx(|tcx| tcx.analyze(()));
         let ret = gcx.enter_tcx(f);
         check_errors!(self, ret)
     }
 
+    fn load_dependency_tables(&self, tcx: TyCtx<'tcx>) {
+        let pkgs = &self.dependencies.metadata;
+        for (pkg, metadata) in pkgs.iter_enumerated() {
+            if let Err(err) = metadata.load_tables(tcx, pkg, pkgs) {
+                self.sess.emit_error(Span::default(), err);
+            }
+        }
+    }
+
     pub fn check(&'tcx self) -> LResult<()> {
         self.with_tcx(|tcx| tcx.analyze(()))
     }
@@ -207,8 +305,9 @@ impl<'tcx> Driver<'tcx> {
         // let bitcode_path = self.root_path.join("build.bc");
         // assert!(cctx.module.write_bitcode_to_path(&bitcode_path));
         let output_path = self.root_path.join("l.out");
-        std::process::Command::new("clang")
+        let status = std::process::Command::new("clang")
             .arg(&ir_path)
+            .args(&self.dependencies.objects)
             .arg("-o")
             .arg(output_path)
             .arg("-lgc")
@@ -216,7 +315,49 @@ impl<'tcx> Driver<'tcx> {
             .stderr(std::process::Stdio::null())
             .status()
             .expect("failed to link using clang (is `clang` on your path?)");
-        Ok(cctx)
+        if !status.success() {
+            self.sess.emit_error(Span::default(), BuildError::Clang("link", status));
+        }
+        check_errors!(self, cctx)
+    }
+
+    /// compiles the package as a library into an object file along with the metadata its
+    /// dependents are resolved against (`hash` identifies the inputs it was built from)
+    pub fn build_lib(&'tcx self, hash: u64) -> LResult<()> {
+        let cctx = self.create_codegen_ctx()?;
+        cctx.codegen_lib()?;
+        check_errors!(self);
+        let ir_path = self.root_path.join("llvm-ir.ll");
+        cctx.module.print_to_file(&ir_path).unwrap_or_else(|err| panic!("{}", err));
+        let status = std::process::Command::new("clang")
+            .arg("-c")
+            .arg(&ir_path)
+            .arg("-o")
+            .arg(deps::object_path(&self.root_path, &self.sess.pkg_name))
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .expect("failed to compile using clang (is `clang` on your path?)");
+        if !status.success() {
+            // the metadata is not written so the package is rebuilt next time
+            self.sess.emit_error(Span::default(), BuildError::Clang("compile", status));
+        }
+        check_errors!(self);
+        let pkgs = &self.dependencies.metadata;
+        let metadata = PkgMetadata::collect(cctx.tcx, &self.sess.pkg_name, hash, pkgs);
+        let metadata_path = deps::metadata_path(&self.root_path, &self.sess.pkg_name);
+        if let Err(err) = std::fs::write(metadata_path, metadata.encode()) {
+            self.sess.emit_error(Span::default(), err);
+        }
+        check_errors!(self, ())
+    }
+
+    /// compiles the prelude into its metadata
+    /// the prelude has no object file as its dependents instantiate all of its functions from
+    /// their mir (see `PkgMetadata::collect`)
+    fn build_prelude(&'tcx self, hash: u64) -> LResult<PkgMetadata> {
+        let pkgs = &self.dependencies.metadata;
+        self.with_tcx(|tcx| PkgMetadata::collect(tcx, &self.sess.pkg_name, hash, pkgs))
     }
 
     pub fn run(&'tcx self) -> LResult<Option<i32>> {
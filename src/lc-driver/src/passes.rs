@@ -33,6 +33,7 @@ fn analyze(tcx: TyCtx<'_>) {
             &mut ItemTypeCollectionPass { tcx },
             &mut ItemTypeValidationPass { tcx },
             &mut TypecheckPass { tcx },
+            &mut PrivacyCheckPass { tcx },
             &mut MirLoweringPass { tcx },
         ])
     })
@@ -99,6 +100,22 @@ impl<'tcx> AnalysisPass<'tcx> for ItemTypeValidationPass<'tcx> {
         // TODO currently required to run some validation on impls, maybe can move elsewhere
         self.tcx.inherent_impls(());
         self.tcx.trait_impls(());
+        self.tcx.check_coherence(());
+        false
+    }
+}
+
+struct PrivacyCheckPass<'tcx> {
+    tcx: TyCtx<'tcx>,
+}
+
+impl<'tcx> AnalysisPass<'tcx> for PrivacyCheckPass<'tcx> {
+    fn name(&self) -> &'static str {
+        "privacy check pass"
+    }
+
+    fn run_pass(&mut self) -> bool {
+        self.tcx.check_privacy(());
         false
     }
 }
@@ -1,6 +1,6 @@
 use crate::{Emitter, ErrorFormat, ErrorReported, JsonEmitter, LResult, TextEmitter};
 use codespan_reporting::diagnostic::Severity;
-use lc_span::Span;
+use lc_span::{Span, SyntaxContext};
 use std::cell::{Cell, RefCell};
 use std::error::Error;
 use std::fmt::{self, Debug, Formatter};
@@ -107,11 +107,13 @@ impl From<Span> for MultiSpan {
 impl Diagnostic {
     pub fn from_err(severity: Severity, spans: impl Into<MultiSpan>, error: impl Error) -> Self {
         let multispan = spans.into();
+        let labelled_spans =
+            multispan.spans.first().map(|&span| expansion_backtrace(span)).unwrap_or_default();
         Self {
             severity,
             msg: error.to_string(),
             spans: multispan.spans,
-            labelled_spans: Default::default(),
+            labelled_spans,
             notes: Default::default(),
         }
     }
@@ -121,6 +123,26 @@ impl Diagnostic {
     }
 }
 
+/// if `span` was produced by a macro expansion, labels each (possibly nested) invocation
+/// and definition of the macros involved
+fn expansion_backtrace(span: Span) -> Vec<(Span, String)> {
+    let mut labels = vec![];
+    let mut push_label = |span: Span, msg: String| {
+        // recursive macros would otherwise label the same location many times over
+        let label = (span.with_ctxt(SyntaxContext::ROOT), msg);
+        if !labels.contains(&label) {
+            labels.push(label);
+        }
+    };
+    let mut ctxt = span.ctxt();
+    while let Some(expn) = ctxt.expn_data() {
+        push_label(expn.call_site, format!("in this expansion of `{}!`", expn.macro_name));
+        push_label(expn.def_site, format!("`{}!` defined here", expn.macro_name));
+        ctxt = expn.call_site.ctxt();
+    }
+    labels
+}
+
 pub struct DiagnosticBuilder<'a> {
     diagnostics: &'a Diagnostics,
     diagnostic: Diagnostic,
@@ -0,0 +1,167 @@
+//! `#[cfg(predicate)]` removes the node it is applied to unless `predicate` holds for the
+//! configuration options the compiler was invoked with (i.e. `--cfg` and the package's features)
+//! unconfigured nodes are removed before macros are collected and before anything is resolved
+
+use crate::expand_error::ExpandError;
+use crate::MacroExpander;
+use lc_ast::*;
+use lc_lex::{DelimiterKind, LiteralKind, Token, TokenKind, TokenStream, TokenTree};
+use lc_session::CfgOption;
+use lc_span::{sym, Span, Symbol};
+use rustc_hash::FxHashSet;
+
+/// the enabled configuration options, an option without a value is `(name, None)`
+pub(crate) type CfgSet = FxHashSet<(Symbol, Option<Symbol>)>;
+
+pub(crate) fn mk_cfg_set(options: &[CfgOption]) -> CfgSet {
+    options
+        .iter()
+        .map(|opt| (Symbol::intern(&opt.name), opt.value.as_deref().map(Symbol::intern)))
+        .collect()
+}
+
+/// a node that may be configured out by a `#[cfg]` attribute
+pub(crate) trait HasAttrs {
+    fn attrs(&self) -> &[Attribute];
+}
+
+impl<K> HasAttrs for Item<K> {
+    fn attrs(&self) -> &[Attribute] {
+        &self.attrs
+    }
+}
+
+impl HasAttrs for FieldDecl {
+    fn attrs(&self) -> &[Attribute] {
+        &self.attrs
+    }
+}
+
+impl HasAttrs for Arm {
+    fn attrs(&self) -> &[Attribute] {
+        &self.attrs
+    }
+}
+
+impl<T: HasAttrs> HasAttrs for P<T> {
+    fn attrs(&self) -> &[Attribute] {
+        (**self).attrs()
+    }
+}
+
+impl MacroExpander<'_, '_> {
+    /// removes the unconfigured items of `module` along with any unconfigured
+    /// fields and associated items within them
+    /// (nested modules are configured when they are visited themselves)
+    pub(crate) fn configure_module(&mut self, module: &mut Module) {
+        self.configure(&mut module.items);
+        for item in &mut module.items {
+            match &mut item.kind {
+                ItemKind::Struct(_, kind) => self.configure_variant_kind(kind),
+                ItemKind::Enum(_, variants) => variants
+                    .iter_mut()
+                    .for_each(|variant| self.configure_variant_kind(&mut variant.kind)),
+                ItemKind::Extern(_, items) => self.configure(items),
+                ItemKind::Trait { items, .. } | ItemKind::Impl { items, .. } =>
+                    self.configure(items),
+                ItemKind::Fn(..)
+                | ItemKind::TypeAlias(..)
+                | ItemKind::Use(..)
+                | ItemKind::Mod(..)
+                | ItemKind::Macro(..) => {}
+            }
+        }
+    }
+
+    fn configure_variant_kind(&mut self, kind: &mut VariantKind) {
+        match kind {
+            VariantKind::Struct(fields) | VariantKind::Tuple(fields) => self.configure(fields),
+            VariantKind::Unit => {}
+        }
+    }
+
+    /// removes the nodes whose `#[cfg]` predicates do not all hold
+    pub(crate) fn configure<T: HasAttrs>(&mut self, nodes: &mut Vec<T>) {
+        nodes.retain(|node| self.is_configured(node.attrs()));
+    }
+
+    fn is_configured(&self, attrs: &[Attribute]) -> bool {
+        attrs.iter().filter(|attr| attr.ident.symbol == sym::cfg).all(|attr| {
+            let result = match &attr.args {
+                AttrArgs::Delimited(args) if args.delimiter().kind == DelimiterKind::Paren =>
+                    self.eval_cfg_predicates(attr.span, args.stream()).and_then(|results| {
+                        match results.as_slice() {
+                            &[result] => Ok(result),
+                            _ => Err((attr.span, ExpandError::MalformedCfg)),
+                        }
+                    }),
+                _ => Err((attr.span, ExpandError::MalformedCfg)),
+            };
+            // a malformed `cfg` is reported and the node is kept to avoid further errors
+            result.unwrap_or_else(|(span, err)| {
+                self.diagnostics().emit_error(span, err);
+                true
+            })
+        })
+    }
+
+    /// evaluates each of the comma separated predicates in `stream`
+    fn eval_cfg_predicates(
+        &self,
+        span: Span,
+        stream: &TokenStream,
+    ) -> Result<Vec<bool>, (Span, ExpandError)> {
+        let tts = stream.iter().collect::<Vec<_>>();
+        let mut predicates = tts.split(|tt| is_comma(tt)).collect::<Vec<_>>();
+        // allow a trailing comma
+        if predicates.last().map_or(false, |predicate| predicate.is_empty()) {
+            predicates.pop();
+        }
+        predicates.into_iter().map(|predicate| self.eval_cfg_predicate(span, predicate)).collect()
+    }
+
+    /// `name`, `name = "value"`, `all(..)`, `any(..)` or `not(..)`
+    fn eval_cfg_predicate(
+        &self,
+        span: Span,
+        predicate: &[&TokenTree],
+    ) -> Result<bool, (Span, ExpandError)> {
+        match predicate {
+            &[&TokenTree::Token(Token { kind: TokenKind::Ident(name), .. })] =>
+                Ok(self.cfg.contains(&(name, None))),
+            &[
+                &TokenTree::Token(Token { kind: TokenKind::Ident(name), .. }),
+                &TokenTree::Token(Token { kind: TokenKind::Eq, .. }),
+                &TokenTree::Token(Token { kind: TokenKind::Literal { kind, .. }, span }),
+            ] if matches!(kind, LiteralKind::Str { terminated: true }) => {
+                // the span includes the surrounding quotes, so we just chop them off
+                let value = span.with_slice(|slice| Symbol::intern(&slice[1..slice.len() - 1]));
+                Ok(self.cfg.contains(&(name, Some(value))))
+            }
+            &[
+                &TokenTree::Token(Token { kind: TokenKind::Ident(op), span: op_span }),
+                TokenTree::Group(group),
+            ] if group.delimiter().kind == DelimiterKind::Paren => {
+                let results = self.eval_cfg_predicates(op_span, group.stream())?;
+                match op {
+                    sym::all => Ok(results.into_iter().all(|result| result)),
+                    sym::any => Ok(results.into_iter().any(|result| result)),
+                    sym::not => match results.as_slice() {
+                        &[result] => Ok(!result),
+                        _ => Err((op_span.merge(group.span()), ExpandError::CfgNotArity)),
+                    },
+                    _ => Err((op_span, ExpandError::UnknownCfgOperator(Ident::new(op_span, op)))),
+                }
+            }
+            [] => Err((span, ExpandError::InvalidCfgPredicate)),
+            [fst, ..] => {
+                let span = fst.span().merge(predicate[predicate.len() - 1].span());
+                Err((span, ExpandError::InvalidCfgPredicate))
+            }
+        }
+    }
+}
+
+fn is_comma(tt: &TokenTree) -> bool {
+    matches!(tt, TokenTree::Token(Token { kind: TokenKind::Comma, .. }))
+}
@@ -0,0 +1,296 @@
+//! `#[derive(...)]` generates impls of the structural traits in the prelude for structs and enums
+//! the impl is generated as source and parsed with each of its tokens attributed to the derive
+//! attribute, so it goes through resolution and typechecking as if it were written there
+
+use crate::expand_error::ExpandError;
+use crate::MacroExpander;
+use itertools::Itertools;
+use lc_ast::*;
+use lc_lex::{Lexer, Token, TokenKind, TokenTree};
+use lc_span::{sym, with_source_map, Span, Symbol};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Derive {
+    Eq,
+    Clone,
+    Default,
+    Debug,
+}
+
+impl Derive {
+    fn from_symbol(symbol: Symbol) -> Option<Self> {
+        match symbol {
+            sym::Eq => Some(Self::Eq),
+            sym::Clone => Some(Self::Clone),
+            sym::Default => Some(Self::Default),
+            sym::Debug => Some(Self::Debug),
+            _ => None,
+        }
+    }
+
+    fn trait_name(self) -> &'static str {
+        match self {
+            Derive::Eq => "Eq",
+            Derive::Clone => "Clone",
+            Derive::Default => "Default",
+            Derive::Debug => "Debug",
+        }
+    }
+
+    /// the trait is referred to by its path in the prelude so that the derived impl is not
+    /// affected by any item of the same name in scope where it is derived
+    fn trait_path(self) -> String {
+        format!("prelude::{}", self.trait_name())
+    }
+}
+
+/// the struct or enum that an impl is derived for
+struct DeriveTarget<'ast> {
+    ident: Ident,
+    generics: &'ast Generics,
+    /// the path to each variant (for a struct this is just the struct) along with its fields
+    variants: Vec<(String, &'ast VariantKind)>,
+}
+
+impl<'ast> DeriveTarget<'ast> {
+    fn new(item: &'ast Item) -> Option<Self> {
+        let ident = item.ident;
+        let (generics, variants) = match &item.kind {
+            ItemKind::Struct(generics, kind) => (generics, vec![(ident.to_string(), kind)]),
+            ItemKind::Enum(generics, variants) => (
+                generics,
+                variants
+                    .iter()
+                    .map(|variant| (format!("{}::{}", ident, variant.ident), &variant.kind))
+                    .collect(),
+            ),
+            _ => return None,
+        };
+        Some(Self { ident, generics, variants })
+    }
+
+    /// `impl<T: Trait + Bound> Trait for S<T> where <predicates>`
+    /// each type parameter is required to implement the derived trait
+    fn impl_header(&self, derive: Derive) -> String {
+        let path = derive.trait_path();
+        let params = &self.generics.params;
+        if params.is_empty() && self.generics.predicates.is_empty() {
+            return format!("impl {} for {}", path, self.ident);
+        }
+        let impl_params = params
+            .iter()
+            .map(|param| {
+                let bounds = param.bounds.iter().map(|bound| format!(" + {}", bound.span));
+                format!("{}: {}{}", param.ident, path, bounds.format(""))
+            })
+            .format(", ");
+        let mut header = format!(
+            "impl<{}> {} for {}<{}>",
+            impl_params,
+            path,
+            self.ident,
+            params.iter().map(|param| param.ident).format(", ")
+        );
+        if !self.generics.predicates.is_empty() {
+            let predicates = self.generics.predicates.iter().map(|predicate| predicate.span);
+            header.push_str(&format!(" where {}", predicates.format(", ")));
+        }
+        header
+    }
+
+    fn derive(&self, derive: Derive) -> String {
+        let body = match derive {
+            Derive::Eq => self.derive_eq(),
+            Derive::Clone => self.derive_clone(),
+            Derive::Default => self.derive_default(),
+            Derive::Debug => self.derive_debug(),
+        };
+        format!("{} {{ {} }}", self.impl_header(derive), body)
+    }
+
+    /// `(a0, a1) == (b0, b1)` for each pair of variants
+    fn derive_eq(&self) -> String {
+        let mut arms = self
+            .variants
+            .iter()
+            .map(|(path, kind)| {
+                let eq = match field_count(kind) {
+                    0 => "true".to_owned(),
+                    n => (0..n).map(|i| format!("a{} == b{}", i, i)).join(" && "),
+                };
+                format!("({}, {}) => {},", pattern(path, kind, "a"), pattern(path, kind, "b"), eq)
+            })
+            .join(" ");
+        if self.variants.len() != 1 {
+            arms.push_str(" _ => false,");
+        }
+        format!("fn eq(self, other: Self) -> bool {{ match (self, other) {{ {} }} }}", arms)
+    }
+
+    fn derive_clone(&self) -> String {
+        let arms = self
+            .variants
+            .iter()
+            .map(|(path, kind)| {
+                let fields =
+                    (0..field_count(kind)).map(|i| format!("prelude::Clone::clone(a{})", i));
+                format!("{} => {},", pattern(path, kind, "a"), construct(path, kind, fields))
+            })
+            .join(" ");
+        format!("fn clone(self) -> Self {{ match self {{ {} }} }}", arms)
+    }
+
+    /// the default of an enum is its first variant
+    fn derive_default(&self) -> String {
+        let (path, kind) = &self.variants[0];
+        let fields = (0..field_count(kind)).map(|_| "prelude::Default::default()".to_owned());
+        format!("fn default() -> Self {{ {} }}", construct(path, kind, fields))
+    }
+
+    /// writes the name of the variant followed by the debug output of each of its fields,
+    /// e.g. `S { x: 1, y: 2 }` or `E::V(1, 2)`
+    fn derive_debug(&self) -> String {
+        let arms = self
+            .variants
+            .iter()
+            .map(|(path, kind)| {
+                let writes = debug_fragments(path, kind)
+                    .into_iter()
+                    .map(|fragment| format!("prelude::Debug::fmt({});", fragment))
+                    .join(" ");
+                format!("{} => {{ {} }},", pattern(path, kind, "a"), writes)
+            })
+            .join(" ");
+        format!("fn fmt(self) {{ match self {{ {} }} }}", arms)
+    }
+}
+
+/// the string literals and the fields (bound by `pattern(path, kind, "a")`) that make up the
+/// debug output of a variant
+fn debug_fragments(path: &str, kind: &VariantKind) -> Vec<String> {
+    let (open, close, names) = match kind {
+        VariantKind::Struct(fields) => {
+            let names = fields.iter().map(|field| format!("{}: ", field.ident.unwrap()));
+            (" { ", " }", names.collect())
+        }
+        VariantKind::Tuple(fields) => ("(", ")", vec![String::new(); fields.len()]),
+        VariantKind::Unit => ("", "", vec![]),
+    };
+    if names.is_empty() {
+        return vec![format!("\"{}\"", path)];
+    }
+    let mut fragments = vec![];
+    for (i, name) in names.iter().enumerate() {
+        let separator = if i == 0 { format!("{}{}", path, open) } else { ", ".to_owned() };
+        fragments.push(format!("\"{}{}\"", separator, name));
+        fragments.push(format!("a{}", i));
+    }
+    fragments.push(format!("\"{}\"", close));
+    fragments
+}
+
+fn field_count(kind: &VariantKind) -> usize {
+    match kind {
+        VariantKind::Struct(fields) | VariantKind::Tuple(fields) => fields.len(),
+        VariantKind::Unit => 0,
+    }
+}
+
+/// a pattern that binds each field of the variant to `<prefix><index>`
+fn pattern(path: &str, kind: &VariantKind, prefix: &str) -> String {
+    construct(path, kind, (0..field_count(kind)).map(|i| format!("{}{}", prefix, i)))
+}
+
+/// `S { x: <field>, y: <field> }`, `S(<field>, <field>)`, or `S`
+/// (the syntax of patterns and expressions coincide here)
+fn construct(path: &str, kind: &VariantKind, fields: impl Iterator<Item = String>) -> String {
+    match kind {
+        VariantKind::Struct(decls) => {
+            let fields = decls
+                .iter()
+                .zip(fields)
+                .map(|(decl, field)| format!("{}: {}", decl.ident.unwrap(), field));
+            format!("{} {{ {} }}", path, fields.format(", "))
+        }
+        VariantKind::Tuple(_) => format!("{}({})", path, fields.format(", ")),
+        VariantKind::Unit => path.to_owned(),
+    }
+}
+
+impl MacroExpander<'_, '_> {
+    /// appends the impls generated by each `#[derive(...)]` on the items of `module`
+    pub(crate) fn expand_derives(&mut self, module: &mut Module) {
+        let mut impls = vec![];
+        for item in &module.items {
+            for attr in item.attrs.iter().filter(|attr| attr.ident.symbol == sym::derive) {
+                impls.extend(self.expand_derive(item, attr));
+            }
+        }
+        module.items.extend(impls);
+    }
+
+    fn expand_derive(&mut self, item: &Item, attr: &Attribute) -> Vec<P<Item>> {
+        let target = match DeriveTarget::new(item) {
+            Some(target) => target,
+            None => {
+                self.diagnostics().emit_error(attr.span, ExpandError::InvalidDeriveTarget);
+                return vec![];
+            }
+        };
+        let args = match &attr.args {
+            AttrArgs::Delimited(args) => args.stream(),
+            AttrArgs::Empty | AttrArgs::Eq(..) => {
+                self.diagnostics().emit_error(attr.span, ExpandError::MalformedDerive);
+                return vec![];
+            }
+        };
+
+        let mut impls = vec![];
+        for tt in args.iter() {
+            let (span, symbol) = match tt {
+                TokenTree::Token(Token { kind: TokenKind::Comma, .. }) => continue,
+                &TokenTree::Token(Token { span, kind: TokenKind::Ident(symbol) }) =>
+                    (span, symbol),
+                tt => {
+                    self.diagnostics().emit_error(tt.span(), ExpandError::MalformedDerive);
+                    continue;
+                }
+            };
+            let derive = match Derive::from_symbol(symbol) {
+                Some(derive) => derive,
+                None => {
+                    let err = ExpandError::UnknownDerive(Ident::new(span, symbol));
+                    self.diagnostics().emit_error(span, err);
+                    continue;
+                }
+            };
+            if derive == Derive::Default && target.variants.is_empty() {
+                let err = ExpandError::DeriveDefaultWithoutVariants(target.ident);
+                self.diagnostics().emit_error(span, err);
+                continue;
+            }
+            impls.extend(self.parse_derived_impl(attr.span, derive, &target.derive(derive)));
+        }
+        impls
+    }
+
+    /// parses the source of a derived impl where every token except for literals is given the
+    /// span of the derive attribute (a literal is read from its span so it must keep it)
+    fn parse_derived_impl(&mut self, span: Span, derive: Derive, src: &str) -> Option<P<Item>> {
+        let name = format!("<derive({})>", derive.trait_name());
+        let file = with_source_map(|map| map.add_virtual_file(&name, src));
+        let tokens = Lexer::new()
+            .lex(file)
+            .filter(|token| token.kind != TokenKind::Eof)
+            .map(|token| match token.kind {
+                TokenKind::Literal { .. } => token,
+                _ => Token { span, ..token },
+            })
+            .collect();
+        self.parser.with_tokens(span.file, tokens, |parser| {
+            let item = parser.parse_item().map_err(|err| err.emit()).ok()?;
+            parser.expect_eof().map_err(|err| err.emit()).ok()?;
+            Some(item)
+        })
+    }
+}
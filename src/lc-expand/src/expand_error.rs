@@ -0,0 +1,43 @@
+use lc_ast::{Ident, Path};
+use lc_error::DiagnosticBuilder;
+use thiserror::Error;
+
+pub type ExpandResult<'a, T> = Result<T, DiagnosticBuilder<'a>>;
+
+#[derive(Debug, Error)]
+pub enum ExpandError {
+    #[error("cannot find macro `{0}` in this scope")]
+    UnresolvedMacro(Path),
+    #[error("macro with name `{0}` already defined")]
+    DuplicateMacroDefinition(Ident),
+    #[error("no rules of macro `{0}` matched this invocation")]
+    NoRulesMatched(Ident),
+    #[error("recursion limit reached while expanding `{0}!`")]
+    RecursionLimitReached(Ident),
+    #[error("unbound metavariable `${0}` in macro transcriber")]
+    UnboundMetavariable(Ident),
+    #[error("metavariable `${0}` is still repeating at this depth")]
+    MetavariableStillRepeating(Ident),
+    #[error("repetition in macro transcriber does not contain any repeating metavariables")]
+    NoRepeatingMetavariable,
+    #[error("metavariables within a single repetition repeat a differing number of times")]
+    RepetitionLengthMismatch,
+    #[error("expected a repetition operator (`*`, `+`, or `?`) after `$( ... )`")]
+    MissingRepetitionOperator,
+    #[error("`derive` may only be applied to structs and enums")]
+    InvalidDeriveTarget,
+    #[error("malformed `derive` attribute, expected `#[derive(Trait, ...)]`")]
+    MalformedDerive,
+    #[error("cannot derive `{0}`, only `Eq`, `Clone`, `Default` and `Debug` can be derived")]
+    UnknownDerive(Ident),
+    #[error("cannot derive `Default` for the enum `{0}` as it has no variants")]
+    DeriveDefaultWithoutVariants(Ident),
+    #[error("malformed `cfg` attribute, expected `#[cfg(predicate)]`")]
+    MalformedCfg,
+    #[error("invalid `cfg` predicate, expected `name`, `name = \"value\"` or `all`/`any`/`not`")]
+    InvalidCfgPredicate,
+    #[error("unknown `cfg` operator `{0}`, expected `all`, `any` or `not`")]
+    UnknownCfgOperator(Ident),
+    #[error("`not` expects exactly one `cfg` predicate")]
+    CfgNotArity,
+}
@@ -1,23 +1,187 @@
-use ast::Ast;
+#![feature(decl_macro)]
+
+#[cfg(test)]
+mod tests;
+
+mod cfg;
+mod derive;
+mod expand_error;
+mod matcher;
+mod transcribe;
+
+use ast::*;
+use cfg::CfgSet;
+use expand_error::ExpandError;
 use lc_ast as ast;
+use lc_error::Diagnostics;
+use lc_lex::TokenGroup;
+use lc_parse::Parser;
+use lc_span::{ExpnData, Span, SyntaxContext};
+use matcher::MacroMatcherCtx;
+use rustc_hash::FxHashMap;
+use std::rc::Rc;
+use transcribe::Transcriber;
 
-pub struct MacroExpander {}
+/// the maximum depth of nested macro expansions
+/// this guards against macros that expand to themselves (directly or indirectly)
+const RECURSION_LIMIT: usize = 128;
 
-impl MacroExpander {
-    pub fn new() -> Self {
-        Self {}
+struct MacroDef {
+    span: Span,
+    ident: Ident,
+    mac: Macro,
+}
+
+/// expands macro invocations using the macro-by-example definitions in the program
+/// macro definitions are removed from the ast once they have been collected
+pub struct MacroExpander<'a, 'p> {
+    /// the parser that produced the ast is reused so that new nodes receive unique ids
+    parser: &'p mut Parser<'a>,
+    /// the macros in scope; each module introduces a new scope which shadows its parent
+    scopes: Vec<FxHashMap<Ident, Rc<MacroDef>>>,
+    /// the configuration options that `#[cfg]` predicates are evaluated against
+    cfg: CfgSet,
+    depth: usize,
+}
+
+impl<'a, 'p> MacroExpander<'a, 'p> {
+    pub fn new(parser: &'p mut Parser<'a>) -> Self {
+        let cfg = cfg::mk_cfg_set(&parser.sess.opts.cfg);
+        Self { parser, scopes: vec![], cfg, depth: 0 }
     }
 
-    pub fn expand(self, ast: Ast) -> Ast {
+    pub fn expand(mut self, mut ast: Ast) -> Ast {
+        self.visit_ast(&mut ast);
         ast
     }
-}
 
-impl Default for MacroExpander {
-    fn default() -> Self {
-        Self::new()
+    fn diagnostics(&self) -> &'a Diagnostics {
+        self.parser.sess
+    }
+
+    fn resolve_macro(&self, path: &Path) -> Option<Rc<MacroDef>> {
+        match path.segments.as_slice() {
+            [segment] if segment.args.is_none() =>
+                self.scopes.iter().rev().find_map(|scope| scope.get(&segment.ident)).cloned(),
+            _ => None,
+        }
+    }
+
+    /// removes all the macro definitions from `module` and brings them into scope
+    fn collect_macros(&mut self, module: &mut Module) {
+        let mut scope = FxHashMap::<Ident, Rc<MacroDef>>::default();
+        let (macros, items) = std::mem::take(&mut module.items)
+            .into_iter()
+            .partition::<Vec<_>, _>(|item| matches!(item.kind, ItemKind::Macro(..)));
+        module.items = items;
+
+        for item in macros {
+            let Item { span, ident, kind, .. } = *item;
+            let mac = match kind {
+                ItemKind::Macro(mac) => mac,
+                _ => unreachable!(),
+            };
+            if let Some(prev) = scope.insert(ident, Rc::new(MacroDef { span, ident, mac })) {
+                self.diagnostics()
+                    .build_error(span, ExpandError::DuplicateMacroDefinition(ident))
+                    .labelled_span(prev.span, "previous definition here".to_owned())
+                    .emit();
+            }
+        }
+        self.scopes.push(scope);
+    }
+
+    /// expands a single macro invocation (but not any macro invocations it expands to)
+    /// returns `None` if any errors occur during expansion
+    /// invocations are only parsed in expression position (which includes expression statements),
+    /// so the transcription is always parsed as a single expression; the parser reports an
+    /// invocation in item or pattern position
+    fn expand_invocation(
+        &mut self,
+        span: Span,
+        path: &Path,
+        group: &TokenGroup,
+    ) -> Option<P<Expr>> {
+        let def = match self.resolve_macro(path) {
+            Some(def) => def,
+            None => {
+                let err = ExpandError::UnresolvedMacro(path.clone());
+                self.diagnostics().emit_error(path.span, err);
+                return None;
+            }
+        };
+
+        if self.depth >= RECURSION_LIMIT {
+            self.diagnostics()
+                .build_error(span, ExpandError::RecursionLimitReached(def.ident))
+                .labelled_span(def.span, "macro defined here".to_owned())
+                .note(&format!("the expansion depth limit is {}", RECURSION_LIMIT))
+                .emit();
+            return None;
+        }
+
+        let tokens = group.stream().to_tokens();
+        for rule in &def.mac.rules {
+            let bindings = match MacroMatcherCtx::new(self.parser, span.file, &tokens)
+                .match_rule(&rule.matcher)
+            {
+                Some(bindings) => bindings,
+                None => continue,
+            };
+            // identifiers introduced by the transcriber are given a fresh syntax context so that
+            // they are kept distinct from identifiers of the same name at the invocation site
+            let ctxt = SyntaxContext::fresh_expansion(ExpnData {
+                macro_name: def.ident.symbol,
+                call_site: span,
+                def_site: def.span,
+            });
+            let transcribed = Transcriber::new(self.diagnostics(), &bindings, ctxt)
+                .transcribe(rule.transcriber.stream())
+                .map_err(|err| err.emit())
+                .ok()?;
+            return self.parser.with_tokens(span.file, transcribed, |parser| {
+                let expr = parser.parse_expr();
+                parser.expect_eof().map_err(|err| err.emit()).ok()?;
+                Some(expr)
+            });
+        }
+
+        self.diagnostics()
+            .build_error(span, ExpandError::NoRulesMatched(def.ident))
+            .labelled_span(def.span, "macro defined here".to_owned())
+            .emit();
+        None
     }
 }
 
-impl ast::Visitor<'_> for MacroExpander {
+impl MutVisitor for MacroExpander<'_, '_> {
+    fn visit_module(&mut self, module: &mut Module) {
+        self.configure_module(module);
+        self.collect_macros(module);
+        self.expand_derives(module);
+        ast::walk_module_mut(self, module);
+        self.scopes.pop();
+    }
+
+    fn visit_expr(&mut self, expr: &mut Expr) {
+        let expanded = match &mut expr.kind {
+            ExprKind::Macro(path, group) => self.expand_invocation(expr.span, path, group),
+            ExprKind::Match(_, arms) => {
+                self.configure(arms);
+                return ast::walk_expr_mut(self, expr);
+            }
+            _ => return ast::walk_expr_mut(self, expr),
+        };
+
+        match expanded {
+            Some(expanded) => {
+                *expr = *expanded;
+                // the expansion itself may contain further macro invocations
+                self.depth += 1;
+                self.visit_expr(expr);
+                self.depth -= 1;
+            }
+            None => expr.kind = ExprKind::Err,
+        }
+    }
 }
@@ -0,0 +1,135 @@
+use lc_ast::{FragmentSpecifier, MacroMatch, MacroMatcher, MacroRepetitor};
+use lc_lex::{Token, TokenKind};
+use lc_parse::Parser;
+use lc_span::{FileIdx, Symbol};
+use rustc_hash::FxHashMap;
+
+/// what a metavariable was bound to during matching
+#[derive(Debug, Clone)]
+pub enum NamedMatch {
+    /// the tokens of a single matched fragment
+    Fragment(FragmentSpecifier, Vec<Token>),
+    /// the matches of a metavariable declared within a repetition (one per iteration)
+    Seq(Vec<NamedMatch>),
+}
+
+pub type Bindings = FxHashMap<Symbol, NamedMatch>;
+
+/// matches the tokens of a macro invocation against the matcher of a single macro rule
+pub struct MacroMatcherCtx<'m, 'a> {
+    parser: &'m mut Parser<'a>,
+    /// the file the invocation occurs in
+    file: FileIdx,
+    tokens: &'m [Token],
+    idx: usize,
+}
+
+impl<'m, 'a> MacroMatcherCtx<'m, 'a> {
+    pub fn new(parser: &'m mut Parser<'a>, file: FileIdx, tokens: &'m [Token]) -> Self {
+        Self { parser, file, tokens, idx: 0 }
+    }
+
+    /// returns the metavariable bindings if the entire token stream matches `matcher`
+    pub fn match_rule(mut self, matcher: &MacroMatcher) -> Option<Bindings> {
+        let mut bindings = Bindings::default();
+        let matched = self.match_seq(&matcher.matches, &mut bindings);
+        if matched && self.idx == self.tokens.len() { Some(bindings) } else { None }
+    }
+
+    fn match_seq(&mut self, matches: &[MacroMatch], bindings: &mut Bindings) -> bool {
+        matches.iter().all(|m| self.match_one(m, bindings))
+    }
+
+    fn match_one(&mut self, m: &MacroMatch, bindings: &mut Bindings) -> bool {
+        match m {
+            MacroMatch::Token(token) => self.eat(token.kind),
+            MacroMatch::Matcher(inner) => self.match_one(inner, bindings),
+            MacroMatch::Fragment(ident, spec) => match self.parse_fragment(*spec) {
+                Some(tokens) => {
+                    bindings.insert(ident.symbol, NamedMatch::Fragment(*spec, tokens));
+                    true
+                }
+                None => false,
+            },
+            MacroMatch::Repetition(matches, sep, repetitor) =>
+                self.match_repetition(matches, *sep, *repetitor, bindings),
+        }
+    }
+
+    /// repetitions are matched greedily; no backtracking is done into a repetition once it has
+    /// matched some number of iterations
+    fn match_repetition(
+        &mut self,
+        matches: &[MacroMatch],
+        sep: Option<Token>,
+        repetitor: MacroRepetitor,
+        bindings: &mut Bindings,
+    ) -> bool {
+        let mut iterations = vec![];
+        loop {
+            if repetitor == MacroRepetitor::Opt && iterations.len() == 1 {
+                break;
+            }
+            let checkpoint = self.idx;
+            if let Some(sep) = sep {
+                if !iterations.is_empty() && !self.eat(sep.kind) {
+                    break;
+                }
+            }
+            let mut iteration = Bindings::default();
+            // an iteration that consumes nothing would repeat forever
+            if !self.match_seq(matches, &mut iteration) || self.idx == checkpoint {
+                self.idx = checkpoint;
+                break;
+            }
+            iterations.push(iteration);
+        }
+
+        if repetitor == MacroRepetitor::Plus && iterations.is_empty() {
+            return false;
+        }
+
+        // every metavariable declared within the repetition is bound to a sequence,
+        // even if there were zero iterations
+        let mut metavars = vec![];
+        collect_metavars(matches, &mut metavars);
+        for metavar in metavars {
+            let seq = iterations.iter_mut().filter_map(|iteration| iteration.remove(&metavar));
+            bindings.insert(metavar, NamedMatch::Seq(seq.collect()));
+        }
+        true
+    }
+
+    fn eat(&mut self, kind: TokenKind) -> bool {
+        match self.tokens.get(self.idx) {
+            Some(token) if token.kind == kind => {
+                self.idx += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// attempts to parse a fragment from the remaining tokens, returning the tokens that the
+    /// fragment consists of on success
+    fn parse_fragment(&mut self, spec: FragmentSpecifier) -> Option<Vec<Token>> {
+        let remaining = self.tokens[self.idx..].to_vec();
+        let n = self.parser.with_tokens(self.file, remaining, |parser| parser.parse_fragment(spec));
+        let n = n.ok()?;
+        let tokens = self.tokens[self.idx..self.idx + n].to_vec();
+        self.idx += n;
+        Some(tokens)
+    }
+}
+
+fn collect_metavars(matches: &[MacroMatch], metavars: &mut Vec<Symbol>) {
+    for m in matches {
+        match m {
+            MacroMatch::Token(..) => {}
+            MacroMatch::Matcher(inner) =>
+                collect_metavars(std::slice::from_ref(&**inner), metavars),
+            MacroMatch::Repetition(matches, ..) => collect_metavars(matches, metavars),
+            MacroMatch::Fragment(ident, _) => metavars.push(ident.symbol),
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use lc_util::stringify_tt;
+
+macro check($src:tt) {{
+    let src = stringify_tt!($src);
+    let driver = lc_driver::Driver::from_src(src);
+    driver.check().unwrap();
+}}
+
+macro expect_expansion_error($src:tt) {{
+    let src = stringify_tt!($src);
+    let driver = lc_driver::Driver::from_src(src);
+    let _ = driver.expand();
+    if !driver.has_errors() {
+        panic!("expected error during macro expansion")
+    }
+}}
+
+#[test]
+fn expand_expr_fragment() {
+    check!({
+        macro id {
+            ($expr:expr) => { $expr }
+        }
+
+        fn main() -> int {
+            id!(5)
+        }
+    });
+}
+
+#[test]
+fn expand_recursive_macro_with_repetition() {
+    check!({
+        macro sum {
+            () => { 0 }
+            ($x:expr $(, $xs:expr)*) => { $x + sum!($($xs),*) }
+        }
+
+        fn main() -> int {
+            sum!(1, 2, 3)
+        }
+    });
+}
+
+#[test]
+fn expand_macro_in_submodule() {
+    check!({
+        macro double {
+            ($x:expr) => { $x + $x }
+        }
+
+        mod m {
+            fn f() -> int {
+                double!(2)
+            }
+        }
+
+        fn main() -> int {
+            0
+        }
+    });
+}
+
+#[test]
+fn expand_ident_and_tt_fragments() {
+    check!({
+        macro call {
+            ($f:ident $($args:tt)*) => { $f($($args)*) }
+        }
+
+        fn k(x: int, y: int) -> int {
+            x
+        }
+
+        fn main() -> int {
+            call!(k 1, 2)
+        }
+    });
+}
+
+#[test]
+fn no_rules_matched() {
+    expect_expansion_error!({
+        macro m {
+            ($x:ident) => { $x }
+        }
+
+        fn main() -> int {
+            m!(1)
+        }
+    });
+}
+
+#[test]
+fn unresolved_macro() {
+    expect_expansion_error!({
+        fn main() -> int {
+            m!(1)
+        }
+    });
+}
+
+#[test]
+fn expansion_recursion_limit() {
+    expect_expansion_error!({
+        macro forever {
+            () => { forever!() }
+        }
+
+        fn main() -> int {
+            forever!()
+        }
+    });
+}
+
+#[test]
+fn unbound_metavariable_in_transcriber() {
+    expect_expansion_error!({
+        macro m {
+            ($x:expr) => { $y }
+        }
+
+        fn main() -> int {
+            m!(1)
+        }
+    });
+}
+
+#[test]
+fn derive_structural_traits() {
+    check!({
+        #[derive(Eq, Clone, Default, Debug)]
+        struct S<T> {
+            x: int,
+            t: T,
+        }
+
+        #[derive(Eq, Clone, Debug)]
+        enum E {
+            A(int, bool),
+            B,
+        }
+
+        fn main() -> int {
+            let s: S<bool> = Default::default();
+            let e = E::A(1, true);
+            if s.clone() == s && e != E::B { 0 } else { 1 }
+        }
+    });
+}
+
+#[test]
+fn derive_with_shadowed_prelude_items() {
+    // the derived impls refer to the traits of the prelude rather than the local items
+    check!({
+        trait Clone {}
+
+        fn Default() {}
+
+        mod Debug {}
+
+        #[derive(Eq, Clone, Default, Debug)]
+        struct S {
+            x: int,
+        }
+
+        fn main() -> int {
+            let s: S = prelude::Default::default();
+            s.debug();
+            if prelude::Clone::clone(s) == s { 0 } else { 1 }
+        }
+    });
+}
+
+#[test]
+fn derive_unknown_trait() {
+    expect_expansion_error!({
+        #[derive(Hash)]
+        struct S;
+
+        fn main() -> int {
+            0
+        }
+    });
+}
+
+#[test]
+fn derive_on_function() {
+    expect_expansion_error!({
+        #[derive(Eq)]
+        fn f() {}
+
+        fn main() -> int {
+            0
+        }
+    });
+}
+
+/// checks `$src` with each of the `--cfg` options in `$cfg` enabled
+macro check_with_cfg($cfg:expr, $src:expr) {{
+    let cfg = $cfg.iter().map(|opt: &&str| opt.parse().unwrap()).collect();
+    let opts = lc_driver::CompilerOptions { cfg, ..Default::default() };
+    let driver = lc_driver::Driver::from_src_with_opts($src, opts);
+    driver.check().is_ok()
+}}
+
+#[test]
+fn cfg_strips_unconfigured_nodes() {
+    check!({
+        #[cfg(not(all()))]
+        fn main() -> bool {
+            false
+        }
+
+        #[cfg(any(unset, not(unset)))]
+        fn main() -> int {
+            let s = S { x: 0 };
+            match s.x {
+                #[cfg(unset)]
+                _ => false,
+                _ => s.x,
+            }
+        }
+
+        struct S {
+            x: int,
+            #[cfg(unset)]
+            y: int,
+        }
+    });
+}
+
+#[test]
+fn cfg_evaluates_options() {
+    let src = stringify_tt!({
+        #[cfg(all(debug, feature = "instrumented"))]
+        fn f() -> int {
+            0
+        }
+
+        #[cfg(not(all(debug, feature = "instrumented")))]
+        fn f() -> bool {
+            false
+        }
+
+        fn main() -> int {
+            f()
+        }
+    });
+    assert!(check_with_cfg!(["debug", "feature=\"instrumented\""], src));
+    assert!(check_with_cfg!(["debug", "feature=instrumented"], src));
+    assert!(!check_with_cfg!(["debug"], src));
+    assert!(!check_with_cfg!(["feature=\"instrumented\""], src));
+}
+
+#[test]
+fn cfg_invalid_predicate() {
+    expect_expansion_error!({
+        #[cfg(feature = unquoted)]
+        fn f() {}
+
+        fn main() -> int {
+            0
+        }
+    });
+}
@@ -0,0 +1,183 @@
+use crate::expand_error::{ExpandError, ExpandResult};
+use crate::matcher::{Bindings, NamedMatch};
+use lc_ast::{FragmentSpecifier, Ident};
+use lc_error::Diagnostics;
+use lc_lex::{DelimiterKind, Token, TokenKind, TokenStream, TokenTree};
+use lc_span::{Span, Symbol, SyntaxContext};
+
+/// substitutes the metavariables in a transcriber with the fragments they were bound to
+pub struct Transcriber<'a, 'b> {
+    diagnostics: &'a Diagnostics,
+    bindings: &'b Bindings,
+    /// the syntax context of this expansion, which is applied to all tokens originating from
+    /// the transcriber (but not to the fragments substituted into it)
+    ctxt: SyntaxContext,
+    /// the current iteration of each repetition we are transcribing within (outermost first)
+    repetition_idxs: Vec<usize>,
+    tokens: Vec<Token>,
+}
+
+impl<'a, 'b> Transcriber<'a, 'b> {
+    pub fn new(diagnostics: &'a Diagnostics, bindings: &'b Bindings, ctxt: SyntaxContext) -> Self {
+        Self { diagnostics, bindings, ctxt, repetition_idxs: vec![], tokens: vec![] }
+    }
+
+    pub fn transcribe(mut self, stream: &TokenStream) -> ExpandResult<'a, Vec<Token>> {
+        self.transcribe_stream(stream)?;
+        Ok(self.tokens)
+    }
+
+    fn transcribe_stream(&mut self, stream: &TokenStream) -> ExpandResult<'a, ()> {
+        let mut tts = stream.iter().peekable();
+        while let Some(tt) = tts.next() {
+            let token = match tt {
+                TokenTree::Token(token) => *token,
+                TokenTree::Group(group) => {
+                    self.push_marked(group.open_token());
+                    self.transcribe_stream(group.stream())?;
+                    self.push_marked(group.close_token());
+                    continue;
+                }
+            };
+
+            if token.kind != TokenKind::Dollar {
+                self.push_marked(token);
+                continue;
+            }
+
+            match tts.peek().copied() {
+                Some(TokenTree::Token(Token { span, kind: TokenKind::Ident(symbol) })) => {
+                    let ident = Ident::new(*span, *symbol);
+                    tts.next();
+                    self.transcribe_metavar(ident)?;
+                }
+                Some(TokenTree::Group(group))
+                    if group.delimiter().kind == DelimiterKind::Paren =>
+                {
+                    let span = group.span();
+                    let stream = group.stream();
+                    tts.next();
+                    // the token following the group is either the repetition operator,
+                    // or a separator which is then followed by the repetition operator
+                    let mut next_token = || tts.next().and_then(|tt| tt.as_token().copied());
+                    let sep = match next_token() {
+                        Some(token) if is_repetition_operator(token.kind) => None,
+                        Some(token)
+                            if next_token().map_or(false, |op| is_repetition_operator(op.kind)) =>
+                            Some(token),
+                        _ => {
+                            let err = ExpandError::MissingRepetitionOperator;
+                            return Err(self.build_err(span, err));
+                        }
+                    };
+                    self.transcribe_repetition(span, stream, sep)?;
+                }
+                // a lone `$` is just transcribed as is
+                _ => self.push_marked(token),
+            }
+        }
+        Ok(())
+    }
+
+    fn transcribe_metavar(&mut self, ident: Ident) -> ExpandResult<'a, ()> {
+        match self.lookup(ident.symbol) {
+            Some(NamedMatch::Fragment(spec, tokens)) => {
+                // expressions are wrapped in parentheses so the fragment is treated as a unit
+                // otherwise `$x * 2` where `$x := 1 + 1` would be parsed as `1 + 1 * 2`
+                if *spec == FragmentSpecifier::Expr && tokens.len() > 1 {
+                    let (first, last) = (tokens[0].span, tokens[tokens.len() - 1].span);
+                    self.tokens.push(Token { span: first, kind: TokenKind::OpenParen });
+                    self.tokens.extend_from_slice(tokens);
+                    self.tokens.push(Token { span: last, kind: TokenKind::CloseParen });
+                } else {
+                    self.tokens.extend_from_slice(tokens);
+                }
+                Ok(())
+            }
+            Some(NamedMatch::Seq(..)) =>
+                Err(self.build_err(ident.span, ExpandError::MetavariableStillRepeating(ident))),
+            None => Err(self.build_err(ident.span, ExpandError::UnboundMetavariable(ident))),
+        }
+    }
+
+    fn transcribe_repetition(
+        &mut self,
+        span: Span,
+        stream: &TokenStream,
+        sep: Option<Token>,
+    ) -> ExpandResult<'a, ()> {
+        let mut metavars = vec![];
+        collect_transcriber_metavars(stream, &mut metavars);
+
+        // the number of iterations is determined by the metavariables that are still repeating
+        let mut len = None;
+        for metavar in metavars {
+            if let Some(NamedMatch::Seq(seq)) = self.lookup(metavar) {
+                match len {
+                    Some(n) if n != seq.len() =>
+                        return Err(self.build_err(span, ExpandError::RepetitionLengthMismatch)),
+                    _ => len = Some(seq.len()),
+                }
+            }
+        }
+
+        let len = match len {
+            Some(len) => len,
+            None => return Err(self.build_err(span, ExpandError::NoRepeatingMetavariable)),
+        };
+
+        for i in 0..len {
+            if let Some(sep) = sep {
+                if i > 0 {
+                    self.push_marked(sep);
+                }
+            }
+            self.repetition_idxs.push(i);
+            self.transcribe_stream(stream)?;
+            self.repetition_idxs.pop();
+        }
+        Ok(())
+    }
+
+    fn push_marked(&mut self, token: Token) {
+        self.tokens.push(Token { span: token.span.with_ctxt(self.ctxt), ..token });
+    }
+
+    /// finds what a metavariable is bound to in the current iteration of each repetition
+    fn lookup(&self, symbol: Symbol) -> Option<&'b NamedMatch> {
+        let mut named_match = self.bindings.get(&symbol)?;
+        for &idx in &self.repetition_idxs {
+            match named_match {
+                NamedMatch::Seq(seq) => named_match = &seq[idx],
+                // metavariables that are not repeating can be used at any depth
+                NamedMatch::Fragment(..) => break,
+            }
+        }
+        Some(named_match)
+    }
+
+    fn build_err(&self, span: Span, err: ExpandError) -> lc_error::DiagnosticBuilder<'a> {
+        self.diagnostics.build_error(span, err)
+    }
+}
+
+fn is_repetition_operator(kind: TokenKind) -> bool {
+    matches!(kind, TokenKind::Star | TokenKind::Plus | TokenKind::Question)
+}
+
+fn collect_transcriber_metavars(stream: &TokenStream, metavars: &mut Vec<Symbol>) {
+    let mut tts = stream.iter().peekable();
+    while let Some(tt) = tts.next() {
+        match tt {
+            TokenTree::Token(Token { kind: TokenKind::Dollar, .. }) =>
+                if let Some(TokenTree::Token(Token { kind: TokenKind::Ident(symbol), .. })) =
+                    tts.peek()
+                {
+                    metavars.push(*symbol);
+                    tts.next();
+                },
+            TokenTree::Token(..) => {}
+            TokenTree::Group(group) => collect_transcriber_metavars(group.stream(), metavars),
+        }
+    }
+}
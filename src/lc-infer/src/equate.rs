@@ -1,5 +1,7 @@
 use crate::At;
-use lc_core::ty::{self, Ty, TyCtx, TypeRelation, TypeResult};
+use lc_core::ty::{
+    self, FloatVarValue, IntVarValue, Ty, TyCtx, TypeError, TypeRelation, TypeResult,
+};
 use std::ops::Deref;
 
 pub struct Equate<'a, 'tcx> {
@@ -12,19 +14,41 @@ impl<'a, 'tcx> TypeRelation<'tcx> for Equate<'a, 'tcx> {
     }
 
     fn relate_tys(&mut self, a: Ty<'tcx>, b: Ty<'tcx>) -> TypeResult<'tcx, Ty<'tcx>> {
-        let mut inner = self.inner.borrow_mut();
-        let mut type_vars = inner.type_variables();
+        let a = self.shallow_resolve(a);
+        let b = self.shallow_resolve(b);
 
-        let a = type_vars.instantiate_if_known(a);
-        let b = type_vars.instantiate_if_known(b);
+        let mut inner = self.inner.borrow_mut();
+        let mismatch = || TypeError::Mismatch(a, b);
 
         match (&a.kind, &b.kind) {
             _ if a == b => {}
             (&ty::Infer(ty::TyVar(a_id)), &ty::Infer(ty::TyVar(b_id))) =>
-                type_vars.equate(a_id, b_id),
-            (&ty::Infer(ty::TyVar(vid)), _) => type_vars.instantiate(vid, b)?,
-            (_, &ty::Infer(ty::TyVar(vid))) => type_vars.instantiate(vid, a)?,
+                inner.type_variables().equate(a_id, b_id),
+            (&ty::Infer(ty::TyVar(vid)), _) => inner.type_variables().instantiate(vid, b)?,
+            (_, &ty::Infer(ty::TyVar(vid))) => inner.type_variables().instantiate(vid, a)?,
+            (&ty::Infer(ty::IntVar(a_id)), &ty::Infer(ty::IntVar(b_id))) =>
+                inner.int_unification_table().unify_var_var(a_id, b_id).map_err(|_| mismatch())?,
+            (&ty::Infer(ty::IntVar(vid)), &ty::Int(int_ty))
+            | (&ty::Int(int_ty), &ty::Infer(ty::IntVar(vid))) => inner
+                .int_unification_table()
+                .unify_var_value(vid, Some(IntVarValue::Int(int_ty)))
+                .map_err(|_| mismatch())?,
+            (&ty::Infer(ty::IntVar(vid)), &ty::Uint(uint_ty))
+            | (&ty::Uint(uint_ty), &ty::Infer(ty::IntVar(vid))) => inner
+                .int_unification_table()
+                .unify_var_value(vid, Some(IntVarValue::Uint(uint_ty)))
+                .map_err(|_| mismatch())?,
+            (&ty::Infer(ty::FloatVar(a_id)), &ty::Infer(ty::FloatVar(b_id))) =>
+                inner.float_unification_table().unify_var_var(a_id, b_id).map_err(|_| mismatch())?,
+            (&ty::Infer(ty::FloatVar(vid)), &ty::Float(float_ty))
+            | (&ty::Float(float_ty), &ty::Infer(ty::FloatVar(vid))) => inner
+                .float_unification_table()
+                .unify_var_value(vid, Some(FloatVarValue(float_ty)))
+                .map_err(|_| mismatch())?,
             (ty::Error, _) | (_, ty::Error) => return Ok(self.infcx.set_ty_err()),
+            // integer and float variables only unify with types of their own kind
+            (ty::Infer(_), _) | (_, ty::Infer(_)) if a.kind != ty::Never && b.kind != ty::Never =>
+                return Err(TypeError::Mismatch(a, b)),
             _ => {
                 // drop the refcell borrow so the recursive call doesn't panic
                 drop(inner);
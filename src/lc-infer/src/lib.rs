@@ -5,18 +5,22 @@ extern crate lc_ir as ir;
 
 mod at;
 mod equate;
+mod normalize;
+mod obligation;
 mod snapshot;
 mod type_variables;
 mod undo;
 
 use self::type_variables::*;
 use at::At;
+use ena::unify as ut;
 use equate::Equate;
 use ir::{DefId, FieldIdx, Res};
 use lc_core::ty::*;
 use lc_error::{DiagnosticBuilder, LError, MultiSpan};
 use lc_index::Idx;
 use lc_span::Span;
+pub use obligation::Obligation;
 use snapshot::*;
 use std::cell::{Cell, RefCell};
 use std::ops::Deref;
@@ -40,6 +44,8 @@ impl<'tcx> InferCtxBuilder<'tcx> {
 #[derive(Default)]
 pub struct InferCtxInner<'tcx> {
     type_variable_storage: TypeVariableStorage<'tcx>,
+    int_unification_storage: ut::UnificationTableStorage<IntVid>,
+    float_unification_storage: ut::UnificationTableStorage<FloatVid>,
     undo_logs: InferCtxUndoLogs<'tcx>,
 }
 
@@ -47,12 +53,22 @@ impl<'tcx> InferCtxInner<'tcx> {
     pub fn type_variables(&mut self) -> TypeVariableTable<'_, 'tcx> {
         self.type_variable_storage.with_log(&mut self.undo_logs)
     }
+
+    pub(crate) fn int_unification_table(&mut self) -> UnificationTable<'_, 'tcx, IntVid> {
+        self.int_unification_storage.with_log(&mut self.undo_logs)
+    }
+
+    pub(crate) fn float_unification_table(&mut self) -> UnificationTable<'_, 'tcx, FloatVid> {
+        self.float_unification_storage.with_log(&mut self.undo_logs)
+    }
 }
 
 pub struct InferCtx<'a, 'tcx> {
     pub tcx: TyCtx<'tcx>,
     pub inner: RefCell<InferCtxInner<'tcx>>,
     pub tables: &'a RefCell<TypeckTables<'tcx>>,
+    /// the obligations that arise from instantiating items with bounds
+    obligations: RefCell<Vec<Obligation<'tcx>>>,
     has_error: Cell<bool>,
 }
 
@@ -66,7 +82,13 @@ impl<'tcx> Deref for InferCtx<'_, 'tcx> {
 
 impl<'a, 'tcx> InferCtx<'a, 'tcx> {
     pub fn new(tcx: TyCtx<'tcx>, tables: &'a RefCell<TypeckTables<'tcx>>) -> Self {
-        Self { tcx, tables, has_error: Cell::new(false), inner: Default::default() }
+        Self {
+            tcx,
+            tables,
+            has_error: Cell::new(false),
+            inner: Default::default(),
+            obligations: Default::default(),
+        }
     }
 
     /// execute `f` then undo any variables it creates
@@ -116,12 +138,11 @@ impl<'a, 'tcx> InferCtx<'a, 'tcx> {
     }
 
     /// creates the conrete substitutions for all inference variables
-    pub fn inference_substs(&self) -> SubstsRef<'tcx> {
-        // let vec: Vec<_> = self.inner.borrow_mut().type_variables();
+    pub fn inference_substs(&self) -> InferSubsts<'tcx> {
         let mut inner = self.inner.borrow_mut();
         let mut type_variables = inner.type_variables();
         // generates an indexed substitution based on the contents of the UnificationTable
-        let mut substs =
+        let ty_vars =
             self.tcx.mk_substs((0..type_variables.storage.tyvar_data.len()).map(|index| {
                 let vid = TyVid { index: index as u32 };
                 let val = type_variables.probe(vid);
@@ -134,40 +155,73 @@ impl<'a, 'tcx> InferCtx<'a, 'tcx> {
                 }
             }));
 
+        // unconstrained integer and float variables fallback to `int` and `float` respectively
+        let mut int_table = inner.int_unification_table();
+        let int_vars = self.tcx.mk_substs((0..int_table.len()).map(|index| {
+            let value = int_table.probe_value(IntVid { index: index as u32 });
+            value.map_or(self.tcx.types.int, |value| value.to_ty(self.tcx))
+        }));
+
+        let mut float_table = inner.float_unification_table();
+        let float_vars = self.tcx.mk_substs((0..float_table.len()).map(|index| {
+            let value = float_table.probe_value(FloatVid { index: index as u32 });
+            value.map_or(self.tcx.types.float, |value| value.to_ty(self.tcx))
+        }));
+
         // repeatedly substitute its inference variables for its value
         // until it contains no inference variables or failure
         // I think this will always terminate?
+        let mut substs = InferSubsts { ty_vars, int_vars, float_vars };
         let mut folder = InferVarSubstsFolder::new(self.tcx, substs);
         loop {
-            let new_substs = substs.fold_with(&mut folder);
-            if substs == new_substs {
+            let ty_vars = substs.ty_vars.fold_with(&mut folder);
+            if substs.ty_vars == ty_vars {
                 break;
             }
-            substs = new_substs;
+            substs.ty_vars = ty_vars;
         }
-        assert!(substs.iter().all(|ty| !ty.has_infer_vars()));
+        assert!(substs.ty_vars.iter().all(|ty| !ty.has_infer_vars()));
         substs
     }
 
     /// if `ty` is an inference variable, attempts to resolve it at least one level deep
+    /// reports an error if it is a type variable whose value is unknown
+    /// (unresolved integer and float variables are returned as is)
     pub fn partially_resolve_ty(&self, span: Span, ty: Ty<'tcx>) -> Ty<'tcx> {
+        let ty = self.shallow_resolve(ty);
         match ty.kind {
-            TyKind::Infer(infer) =>
-                self.partially_resolve_ty(span, self.resolve_infer_var(span, infer)),
+            TyKind::Infer(TyVar(_)) => self.emit_ty_err(span, TypeError::InferenceFailure),
             _ => ty,
         }
     }
 
-    /// returns the concrete type for a type variable and reports an error if it is unknown
-    pub fn resolve_infer_var(&self, span: Span, infer: InferTy) -> Ty<'tcx> {
+    /// if `ty` is an inference variable with a known value, returns that value
+    /// (similarly, if `ty` is a projection that can be normalized, returns the normalized type)
+    /// otherwise, just returns `ty`
+    pub fn shallow_resolve(&self, ty: Ty<'tcx>) -> Ty<'tcx> {
+        if let TyKind::Projection(def_id, substs) = ty.kind {
+            return self
+                .normalize_projection(def_id, substs)
+                .map_or(ty, |ty| self.shallow_resolve(ty));
+        }
         let mut inner = self.inner.borrow_mut();
-        let mut tyvars = inner.type_variables();
-        let value = match infer {
-            TyVar(tyvid) => tyvars.probe(tyvid),
-        };
-        match value {
-            TyVarValue::Known(ty) => ty,
-            TyVarValue::Unknown => self.emit_ty_err(span, TypeError::InferenceFailure),
+        match ty.kind {
+            TyKind::Infer(TyVar(vid)) => match inner.type_variables().probe(vid) {
+                TyVarValue::Known(ty) => {
+                    drop(inner);
+                    self.shallow_resolve(ty)
+                }
+                TyVarValue::Unknown => ty,
+            },
+            TyKind::Infer(IntVar(vid)) => {
+                let value = inner.int_unification_table().probe_value(vid);
+                value.map_or(ty, |value| value.to_ty(self.tcx))
+            }
+            TyKind::Infer(FloatVar(vid)) => {
+                let value = inner.float_unification_table().probe_value(vid);
+                value.map_or(ty, |value| value.to_ty(self.tcx))
+            }
+            _ => ty,
         }
     }
 
@@ -182,7 +236,8 @@ impl<'a, 'tcx> InferCtx<'a, 'tcx> {
         let substs =
             self.mk_substs(generics.params.iter().map(|_| self.new_infer_var(xpat.span())));
         self.record_substs(xpat.id(), substs);
-        ty.subst(self.tcx, substs)
+        self.register_predicates(xpat.span(), def_id, substs);
+        self.normalize(ty.subst(self.tcx, substs))
     }
 
     /// create a fresh type inference variable
@@ -191,6 +246,18 @@ impl<'a, 'tcx> InferCtx<'a, 'tcx> {
         self.tcx.mk_ty(TyKind::Infer(InferTy::TyVar(vid)))
     }
 
+    /// create a fresh integer inference variable (for unsuffixed integer literals)
+    pub fn new_int_var(&self) -> Ty<'tcx> {
+        let vid = self.inner.borrow_mut().int_unification_table().new_key(None);
+        self.tcx.mk_ty(TyKind::Infer(InferTy::IntVar(vid)))
+    }
+
+    /// create a fresh float inference variable (for unsuffixed float literals)
+    pub fn new_float_var(&self) -> Ty<'tcx> {
+        let vid = self.inner.borrow_mut().float_unification_table().new_key(None);
+        self.tcx.mk_ty(TyKind::Infer(InferTy::FloatVar(vid)))
+    }
+
     pub fn node_ty(&self, id: ir::Id) -> Ty<'tcx> {
         debug!("fcx query node type for {:?}", id);
         self.tables.borrow().node_type(id)
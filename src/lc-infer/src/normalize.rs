@@ -0,0 +1,79 @@
+use crate::InferCtx;
+use ir::DefId;
+use lc_core::ty::*;
+use lc_span::Span;
+
+impl<'a, 'tcx> InferCtx<'a, 'tcx> {
+    /// replaces the projections in `value` with the type defined by the impl that applies
+    /// projections that can't be resolved to a single impl yet are left as is
+    /// (e.g. when the self type is a type parameter or an unresolved inference variable)
+    pub fn normalize<T>(&self, value: T) -> T
+    where
+        T: TypeFoldable<'tcx>,
+    {
+        value.fold_with(&mut Normalizer { infcx: self })
+    }
+
+    /// attempts to normalize the projection of the associated type `def_id`
+    /// selecting the impl constrains any inference variables in `substs` accordingly
+    pub(crate) fn normalize_projection(
+        &self,
+        def_id: DefId,
+        substs: SubstsRef<'tcx>,
+    ) -> Option<Ty<'tcx>> {
+        let trait_ref = self.projection_trait_ref(def_id, substs);
+        match self.shallow_resolve(trait_ref.self_ty).kind {
+            TyKind::Param(..) | TyKind::Infer(TyVar(..)) => return None,
+            _ => {}
+        }
+
+        let mut candidates =
+            self.trait_impls_of(trait_ref.def_id).iter().copied().filter(|&impl_def_id| {
+                self.probe(|_| self.match_impl(impl_def_id, trait_ref).is_some())
+            });
+        match (candidates.next(), candidates.next()) {
+            (Some(impl_def_id), None) => {
+                let impl_substs = self.match_impl(impl_def_id, trait_ref)?;
+                Some(self.impl_assoc_ty(impl_def_id, impl_substs, def_id, substs))
+            }
+            // there is either no applicable impl or it is ambiguous which impl applies
+            _ => None,
+        }
+    }
+
+    /// unifies the header of the impl with `impl_def_id` with `trait_ref`
+    /// returning the substitutions for the impl if successful
+    fn match_impl(&self, impl_def_id: DefId, trait_ref: TraitRef<'tcx>) -> Option<SubstsRef<'tcx>> {
+        let substs = self.fresh_substs_for_item(impl_def_id);
+        let impl_trait_ref = self.impl_trait_ref(impl_def_id).unwrap().subst(self.tcx, substs);
+        let at = self.at(Span::default());
+        at.equate(impl_trait_ref.self_ty, trait_ref.self_ty).ok()?;
+        at.equate(impl_trait_ref.substs, trait_ref.substs).ok()?;
+        Some(substs)
+    }
+}
+
+struct Normalizer<'a, 'tcx> {
+    infcx: &'a InferCtx<'a, 'tcx>,
+}
+
+impl<'a, 'tcx> TypeFolder<'tcx> for Normalizer<'a, 'tcx> {
+    fn tcx(&self) -> TyCtx<'tcx> {
+        self.infcx.tcx
+    }
+
+    fn fold_ty(&mut self, ty: Ty<'tcx>) -> Ty<'tcx> {
+        if !ty.has_projections() {
+            return ty;
+        }
+        let ty = ty.inner_fold_with(self);
+        match ty.kind {
+            // `shallow_resolve` normalizes the projection if possible
+            TyKind::Projection(..) => {
+                let resolved = self.infcx.shallow_resolve(ty);
+                if resolved == ty { ty } else { self.fold_ty(resolved) }
+            }
+            _ => ty,
+        }
+    }
+}
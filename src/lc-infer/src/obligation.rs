@@ -0,0 +1,34 @@
+use crate::InferCtx;
+use ir::DefId;
+use lc_core::ty::{Predicate, Subst, SubstsRef};
+use lc_span::Span;
+
+/// a predicate that must be proven to hold for the program to be well typed
+#[derive(Debug, Clone, Copy)]
+pub struct Obligation<'tcx> {
+    /// the span of the expression that gave rise to the obligation
+    pub span: Span,
+    /// the span of the bound that the obligation originates from
+    pub bound_span: Span,
+    pub predicate: Predicate<'tcx>,
+}
+
+impl<'a, 'tcx> InferCtx<'a, 'tcx> {
+    pub fn register_obligation(&self, obligation: Obligation<'tcx>) {
+        debug!("register obligation {:?}", obligation);
+        self.obligations.borrow_mut().push(obligation);
+    }
+
+    /// registers an obligation for each predicate of `def_id` instantiated with `substs`
+    pub fn register_predicates(&self, span: Span, def_id: DefId, substs: SubstsRef<'tcx>) {
+        for &(predicate, bound_span) in &self.predicates_of(def_id).predicates {
+            let predicate = predicate.subst(self.tcx, substs);
+            self.register_obligation(Obligation { span, bound_span, predicate });
+        }
+    }
+
+    /// removes and returns all the obligations that have been registered so far
+    pub fn take_obligations(&self) -> Vec<Obligation<'tcx>> {
+        self.obligations.take()
+    }
+}
@@ -41,6 +41,8 @@ impl<'tcx> Rollback<UndoLog<'tcx>> for InferCtxInner<'tcx> {
     fn reverse(&mut self, undo: UndoLog<'tcx>) {
         match undo {
             UndoLog::TyVar(undo) => self.type_variable_storage.reverse(undo),
+            UndoLog::IntUnificationTable(undo) => self.int_unification_storage.reverse(undo),
+            UndoLog::FloatUnificationTable(undo) => self.float_unification_storage.reverse(undo),
         }
     }
 }
@@ -6,6 +6,8 @@ use lc_core::ty;
 
 pub enum UndoLog<'tcx> {
     TyVar(type_variables::TyVarUndoLog<'tcx>),
+    IntUnificationTable(sv::UndoLog<ut::Delegate<ty::IntVid>>),
+    FloatUnificationTable(sv::UndoLog<ut::Delegate<ty::FloatVid>>),
 }
 
 #[derive(Default)]
@@ -77,4 +79,6 @@ impl_from! {
     TyVar(type_variables::TyVarUndoLog<'tcx>),
     TyVar(sv::UndoLog<ut::Delegate<type_variables::TyVidEqKey<'tcx>>>),
     TyVar(sv::UndoLog<ut::Delegate<ty::TyVid>>),
+    IntUnificationTable(sv::UndoLog<ut::Delegate<ty::IntVid>>),
+    FloatUnificationTable(sv::UndoLog<ut::Delegate<ty::FloatVid>>),
 }
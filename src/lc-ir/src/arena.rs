@@ -6,6 +6,7 @@ macro_rules! arena_types {
             [few] ir: ir::Ir<$tcx>,
             [] bodies: ir::Body<$tcx>,
             [] arms: ir::Arm<$tcx>,
+            [] attributes: ir::Attribute,
             [] blocks: ir::Block<$tcx>,
             [] generic_args: ir::GenericArgs<$tcx>,
             [] generics: ir::Generics<$tcx>,
@@ -30,6 +31,7 @@ macro_rules! arena_types {
             [] ty_param: ir::TyParam<$tcx>,
             [] tys: ir::Ty<$tcx>,
             [] variants: ir::Variant<$tcx>,
+            [] where_predicates: ir::WherePredicate<$tcx>,
         ]);
     )
 }
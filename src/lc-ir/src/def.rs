@@ -1,7 +1,8 @@
-use super::{DefId, ParamIdx};
+use super::{DefId, ParamIdx, PkgId};
 use crate as ir;
-use lc_ast::NodeId;
+use lc_ast::{Ident, NodeId};
 use lc_index::Idx;
+use lc_span::Symbol;
 use rustc_hash::FxHashMap;
 use std::cell::Cell;
 use std::fmt::{self, Display, Formatter};
@@ -24,6 +25,7 @@ pub enum Res<Id = ir::Id> {
 #[derive(Debug, Default)]
 pub struct Resolutions<'a> {
     pub defs: Definitions<'a>,
+    pub lang_items: ir::LangItems,
 }
 
 impl<Id> Res<Id> {
@@ -75,6 +77,7 @@ impl HasDefKind for lc_ast::AssocItemKind {
     fn def_kind(&self) -> DefKind {
         match self {
             Self::Fn(..) => DefKind::AssocFn,
+            Self::TyAlias(..) => DefKind::AssocTy,
         }
     }
 }
@@ -129,10 +132,11 @@ impl CtorKind {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
 pub enum DefKind {
     Fn,
     AssocFn,
+    AssocTy,
     TypeAlias,
     Enum,
     Struct,
@@ -159,6 +163,7 @@ impl Display for DefKind {
         match self {
             DefKind::Fn => write!(f, "function"),
             DefKind::AssocFn => write!(f, "associated function"),
+            DefKind::AssocTy => write!(f, "associated type"),
             DefKind::Impl => write!(f, "impl"),
             DefKind::Enum => write!(f, "enum"),
             DefKind::Struct => write!(f, "struct"),
@@ -250,6 +255,36 @@ pub struct Definitions<'a> {
     // id_to_def_id: FxHashMap<ir::Id, DefId>,
     // def_id_to_ir_id: IndexVec<DefId, Option<ir::Id>>,
     def_map: FxHashMap<DefId, DefNode<'a>>,
+    /// the definitions of other packages that are referred to by this package
+    extern_defs: FxHashMap<DefId, ExternDef>,
+    /// the local `DefId` of each definition of another package
+    extern_def_ids: FxHashMap<(PkgId, DefId), DefId>,
+}
+
+/// a definition of another package
+/// (this includes its private items, as they may be referred to by its generic functions)
+#[derive(Debug, Clone, Copy)]
+pub struct ExternDef {
+    pub pkg: PkgId,
+    /// the `DefId` of the definition within its own package
+    pub def_id: DefId,
+    pub ident: Ident,
+    /// `None` for the definitions that are not resolved to by paths (fields and the variants that
+    /// are not constructors)
+    pub def_kind: Option<DefKind>,
+    /// how the definition is instantiated if it is a function (or a constructor)
+    pub fn_kind: Option<ExternFnKind>,
+}
+
+/// how a function (or a constructor) of another package is instantiated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExternFnKind {
+    /// a non-generic function, which is linked against the symbol its package exports
+    Linked(Symbol),
+    /// a generic function or a constructor, which is instantiated from its mir
+    Mir,
+    /// an intrinsic, which is generated by the compiler itself
+    Intrinsic,
 }
 
 impl<'a> Definitions<'a> {
@@ -262,6 +297,36 @@ impl<'a> Definitions<'a> {
         self.def_map[&def_id]
     }
 
+    /// the definitions of this package
+    pub fn def_nodes(&self) -> impl Iterator<Item = (DefId, DefNode<'a>)> + '_ {
+        self.def_map.iter().map(|(&def_id, &node)| (def_id, node))
+    }
+
+    /// allocates a local `DefId` for the definition `extern_def` of another package
+    pub fn mk_extern_def(&mut self, extern_def: ExternDef) -> DefId {
+        let def_id = self.alloc_def_id();
+        self.extern_defs.insert(def_id, extern_def);
+        self.extern_def_ids.insert((extern_def.pkg, extern_def.def_id), def_id);
+        def_id
+    }
+
+    pub fn extern_def(&self, def_id: DefId) -> Option<ExternDef> {
+        self.extern_defs.get(&def_id).copied()
+    }
+
+    /// the definitions of other packages (ordered by their local `DefId`)
+    pub fn extern_defs(&self) -> impl Iterator<Item = (DefId, ExternDef)> {
+        let mut extern_defs =
+            self.extern_defs.iter().map(|(&def_id, &def)| (def_id, def)).collect::<Vec<_>>();
+        extern_defs.sort_by_key(|&(def_id, _)| def_id);
+        extern_defs.into_iter()
+    }
+
+    /// the local `DefId` of the definition `def_id` of the package `pkg`
+    pub fn extern_def_id(&self, pkg: PkgId, def_id: DefId) -> Option<DefId> {
+        self.extern_def_ids.get(&(pkg, def_id)).copied()
+    }
+
     pub fn alloc_def_id(&self) -> DefId {
         let def_id = self.def_id_counter.get();
         self.def_id_counter.set(1 + def_id);
@@ -29,7 +29,7 @@ impl<'ir> ir::Expr<'ir> {
                 },
                 ir::QPath::TypeRelative(..) => todo!(),
             },
-            ExprKind::Field(..) | ExprKind::Unary(UnaryOp::Deref, _) => true,
+            ExprKind::Field(..) | ExprKind::Index(..) | ExprKind::Unary(UnaryOp::Deref, _) => true,
             _ => false,
         }
     }
@@ -46,13 +46,23 @@ pub enum ExprKind<'ir> {
     Block(&'ir ir::Block<'ir>),
     Path(&'ir QPath<'ir>),
     Tuple(&'ir [ir::Expr<'ir>]),
+    /// [x, y, z]
+    Array(&'ir [ir::Expr<'ir>]),
+    /// [x; n]
+    Repeat(&'ir ir::Expr<'ir>, usize),
     Closure(&'ir ir::FnSig<'ir>, &'ir ir::Body<'ir>),
     Assign(&'ir ir::Expr<'ir>, &'ir ir::Expr<'ir>),
+    /// compound assignment `l op= r`
+    AssignOp(lc_ast::BinOp, &'ir ir::Expr<'ir>, &'ir ir::Expr<'ir>),
     Call(&'ir ir::Expr<'ir>, &'ir [ir::Expr<'ir>]),
+    /// `receiver.method(args)`
+    MethodCall(&'ir ir::Expr<'ir>, Ident, &'ir [ir::Expr<'ir>]),
     Match(&'ir ir::Expr<'ir>, &'ir [ir::Arm<'ir>], ir::MatchSource),
     Struct(&'ir QPath<'ir>, &'ir [ir::Field<'ir>]),
     /// named field access `foo.x` or `tuple.1`
     Field(&'ir ir::Expr<'ir>, Ident),
+    /// `base[index]`
+    Index(&'ir ir::Expr<'ir>, &'ir ir::Expr<'ir>),
     Break,
     Continue,
     Err,
@@ -6,6 +6,7 @@ use lc_span::{Span};
 pub struct Item<'ir> {
     pub id: ir::Id,
     pub span: Span,
+    pub attrs: &'ir [ir::Attribute],
     pub vis: Visibility,
     pub ident: Ident,
     pub kind: ir::ItemKind<'ir>,
@@ -34,10 +35,18 @@ impl<'ir> Item<'ir> {
     }
 }
 
+/// a single import `use <path> as <binding>` (where the binding defaults to the last segment)
+#[derive(Debug, Clone)]
+pub struct UsePath<'ir> {
+    pub binding: Ident,
+    pub path: ir::Path<'ir>,
+}
+
 #[derive(Debug, Clone)]
 pub enum ItemKind<'ir> {
     Fn(&'ir ir::FnSig<'ir>, &'ir ir::Generics<'ir>, &'ir ir::Body<'ir>),
-    Use(&'ir ir::Path<'ir>),
+    /// the single imports of the (flattened) use tree, excluding glob imports
+    Use(&'ir [ir::UsePath<'ir>]),
     TypeAlias(&'ir ir::Generics<'ir>, &'ir ir::Ty<'ir>),
     Struct(&'ir ir::Generics<'ir>, ir::VariantKind<'ir>),
     Enum(&'ir ir::Generics<'ir>, &'ir [ir::Variant<'ir>]),
@@ -55,6 +64,22 @@ pub enum ItemKind<'ir> {
     },
 }
 
+impl<'ir> ItemKind<'ir> {
+    pub fn def_kind(&self) -> DefKind {
+        match self {
+            ItemKind::Fn(..) => DefKind::Fn,
+            ItemKind::Use(..) => DefKind::Use,
+            ItemKind::TypeAlias(..) => DefKind::TypeAlias,
+            ItemKind::Struct(..) => DefKind::Struct,
+            ItemKind::Enum(..) => DefKind::Enum,
+            ItemKind::Extern(..) => DefKind::Extern,
+            ItemKind::Mod(..) => DefKind::Mod,
+            ItemKind::Trait { .. } => DefKind::Trait,
+            ItemKind::Impl { .. } => DefKind::Impl,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct Mod<'ir> {
     pub span: Span,
@@ -67,6 +92,7 @@ pub struct ForeignItem<'ir> {
     pub abi: Abi,
     pub ident: Ident,
     pub span: Span,
+    pub attrs: &'ir [ir::Attribute],
     pub vis: Visibility,
     pub kind: ForeignItemKind<'ir>,
 }
@@ -79,8 +105,10 @@ pub enum ForeignItemKind<'ir> {
 #[derive(Debug)]
 pub struct TraitItem<'ir> {
     pub id: ir::Id,
+    pub trait_def_id: DefId,
     pub ident: Ident,
     pub span: Span,
+    pub attrs: &'ir [ir::Attribute],
     pub vis: Visibility,
     pub generics: &'ir ir::Generics<'ir>,
     pub kind: TraitItemKind<'ir>,
@@ -92,6 +120,7 @@ pub struct ImplItem<'ir> {
     pub impl_def_id: DefId,
     pub ident: Ident,
     pub span: Span,
+    pub attrs: &'ir [ir::Attribute],
     pub vis: Visibility,
     pub generics: &'ir ir::Generics<'ir>,
     pub kind: ImplItemKind<'ir>,
@@ -100,12 +129,14 @@ pub struct ImplItem<'ir> {
 #[derive(Debug, Clone)]
 pub enum ImplItemKind<'ir> {
     Fn(&'ir ir::FnSig<'ir>, &'ir ir::Body<'ir>),
+    TyAlias(&'ir ir::Ty<'ir>),
 }
 
 impl<'ir> ImplItemKind<'ir> {
     pub fn def_kind(&self) -> DefKind {
         match self {
             ImplItemKind::Fn(..) => DefKind::AssocFn,
+            ImplItemKind::TyAlias(..) => DefKind::AssocTy,
         }
     }
 }
@@ -113,6 +144,17 @@ impl<'ir> ImplItemKind<'ir> {
 #[derive(Debug, Clone)]
 pub enum TraitItemKind<'ir> {
     Fn(&'ir ir::FnSig<'ir>, Option<&'ir ir::Body<'ir>>),
+    /// an associated type with an optional default
+    TyAlias(Option<&'ir ir::Ty<'ir>>),
+}
+
+impl<'ir> TraitItemKind<'ir> {
+    pub fn def_kind(&self) -> DefKind {
+        match self {
+            TraitItemKind::Fn(..) => DefKind::AssocFn,
+            TraitItemKind::TyAlias(..) => DefKind::AssocTy,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
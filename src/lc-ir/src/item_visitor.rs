@@ -59,6 +59,7 @@ where
     fn visit_impl_item(&mut self, impl_item: &'ir ir::ImplItem<'ir>) {
         match impl_item.kind {
             ir::ImplItemKind::Fn(..) => self.visit_fn(impl_item.id.def),
+            ir::ImplItemKind::TyAlias(..) => {}
         }
     }
 
@@ -66,7 +67,7 @@ where
         match trait_item.kind {
             // only visit as a function if it has a body
             ir::TraitItemKind::Fn(_, Some(_)) => self.visit_fn(trait_item.id.def),
-            ir::TraitItemKind::Fn(_, None) => {}
+            ir::TraitItemKind::Fn(_, None) | ir::TraitItemKind::TyAlias(..) => {}
         }
     }
 }
@@ -0,0 +1,74 @@
+//! lang items are the items declared in the prelude that the compiler has builtin knowledge of
+//! (i.e. the traits that overloaded operators are desugared to)
+
+use crate::DefId;
+use lc_span::{sym, Symbol};
+use rustc_hash::FxHashMap;
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LangItem {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Neg,
+    Not,
+    Eq,
+    Ord,
+    Index,
+}
+
+impl LangItem {
+    pub const ALL: [LangItem; 10] = [
+        LangItem::Add,
+        LangItem::Sub,
+        LangItem::Mul,
+        LangItem::Div,
+        LangItem::Rem,
+        LangItem::Neg,
+        LangItem::Not,
+        LangItem::Eq,
+        LangItem::Ord,
+        LangItem::Index,
+    ];
+
+    /// the name of the item in the prelude
+    pub fn name(self) -> Symbol {
+        match self {
+            LangItem::Add => sym::Add,
+            LangItem::Sub => sym::Sub,
+            LangItem::Mul => sym::Mul,
+            LangItem::Div => sym::Div,
+            LangItem::Rem => sym::Rem,
+            LangItem::Neg => sym::Neg,
+            LangItem::Not => sym::Not,
+            LangItem::Eq => sym::Eq,
+            LangItem::Ord => sym::Ord,
+            LangItem::Index => sym::Index,
+        }
+    }
+}
+
+impl Display for LangItem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// maps each lang item to its definition
+#[derive(Debug, Default)]
+pub struct LangItems {
+    items: FxHashMap<LangItem, DefId>,
+}
+
+impl LangItems {
+    pub fn insert(&mut self, item: LangItem, def_id: DefId) {
+        self.items.insert(item, def_id);
+    }
+
+    pub fn get(&self, item: LangItem) -> Option<DefId> {
+        self.items.get(&item).copied()
+    }
+}
@@ -6,6 +6,7 @@ mod def;
 mod expr;
 mod item;
 mod item_visitor;
+mod lang_items;
 mod pat;
 mod stmt;
 mod ty;
@@ -18,6 +19,8 @@ pub use def::*;
 pub use expr::{Expr, ExprKind};
 pub use item::*;
 pub use item_visitor::*;
+pub use lang_items::{LangItem, LangItems};
+pub use lc_ast::Attribute;
 use lc_ast::{Ident, Visibility};
 use lc_index::newtype_index;
 use lc_span::Span;
@@ -66,10 +69,10 @@ pub struct Ir<'ir> {
 }
 
 newtype_index!(
+    /// identifies a dependency of the package being compiled
     #[derive(Serialize, Deserialize)]
     pub struct PkgId {
-        DEBUG_FORMAT = "{}",
-        const LOCAL_PKG_ID = 0,
+        DEBUG_FORMAT = "{}"
     }
 );
 
@@ -218,6 +221,7 @@ pub struct Field<'ir> {
 #[derive(Debug)]
 pub struct FieldDecl<'ir> {
     pub span: Span,
+    pub attrs: &'ir [ir::Attribute],
     pub ident: Ident,
     pub vis: Visibility,
     pub id: ir::Id,
@@ -234,6 +238,15 @@ impl Display for ParamIdx {
 pub struct Generics<'ir> {
     pub span: Span,
     pub params: &'ir [ir::TyParam<'ir>],
+    pub predicates: &'ir [ir::WherePredicate<'ir>],
+}
+
+/// `<ty>: <bound> + <bound>`
+#[derive(Debug)]
+pub struct WherePredicate<'ir> {
+    pub span: Span,
+    pub ty: &'ir ir::Ty<'ir>,
+    pub bounds: &'ir [ir::Path<'ir>],
 }
 
 #[derive(Debug)]
@@ -248,6 +261,7 @@ pub struct TyParam<'ir> {
     pub id: ir::Id,
     pub ident: Ident,
     pub index: ParamIdx,
+    pub bounds: &'ir [ir::Path<'ir>],
     pub default: Option<&'ir ir::Ty<'ir>>,
 }
 
@@ -273,6 +287,7 @@ pub enum MatchSource {
 pub struct Arm<'ir> {
     pub id: ir::Id,
     pub span: Span,
+    pub attrs: &'ir [ir::Attribute],
     pub pat: &'ir ir::Pattern<'ir>,
     pub guard: Option<&'ir ir::Expr<'ir>>,
     pub body: &'ir ir::Expr<'ir>,
@@ -283,6 +298,9 @@ pub struct FnSig<'ir> {
     // rest of parameter information is in `Body`
     pub inputs: &'ir [ir::Ty<'ir>],
     pub output: Option<&'ir ir::Ty<'ir>>,
+    /// whether the first parameter is `self`
+    /// (this is recorded here as trait items need not have a `Body`)
+    pub has_self: bool,
 }
 
 /// qualified path
@@ -1,4 +1,5 @@
 use crate::{self as ir, QPath};
+use lc_ast::{FloatTy, Ident, IntTy, UintTy};
 use lc_span::Span;
 use std::fmt::{self, Display, Formatter};
 
@@ -6,8 +7,10 @@ use std::fmt::{self, Display, Formatter};
 pub enum PrimTy {
     Char,
     Bool,
-    Float,
-    Int,
+    Float(FloatTy),
+    Int(IntTy),
+    Uint(UintTy),
+    Str,
 }
 
 #[derive(Debug)]
@@ -22,7 +25,11 @@ pub enum TyKind<'ir> {
     Box(&'ir ir::Ty<'ir>),
     Fn(&'ir [ir::Ty<'ir>], Option<&'ir ir::Ty<'ir>>),
     Path(&'ir QPath<'ir>),
-    Array(&'ir ir::Ty<'ir>),
+    /// dyn <trait>
+    Dyn(&'ir ir::Path<'ir>),
+    /// <<ty> as <trait>>::<ident>
+    Projection(&'ir ir::Ty<'ir>, &'ir ir::Path<'ir>, Ident),
+    Array(&'ir ir::Ty<'ir>, usize),
     Tuple(&'ir [ir::Ty<'ir>]),
     Ptr(&'ir ir::Ty<'ir>),
     Infer,
@@ -34,6 +41,8 @@ impl<'ir> Display for Ty<'ir> {
         match self.kind {
             ir::TyKind::Box(ty) => write!(f, "box {}", ty),
             ir::TyKind::Path(qpath) => write!(f, "{}", qpath),
+            ir::TyKind::Dyn(path) => write!(f, "dyn {}", path),
+            ir::TyKind::Projection(ty, path, ident) => write!(f, "<{} as {}>::{}", ty, path, ident),
             _ => todo!(),
         }
     }
@@ -91,6 +91,10 @@ pub trait Visitor<'ir>: Sized {
         walk_generics(self, generics)
     }
 
+    fn visit_where_predicate(&mut self, predicate: &'ir ir::WherePredicate<'ir>) {
+        walk_where_predicate(self, predicate)
+    }
+
     fn visit_variant_kind(&mut self, kind: &'ir ir::VariantKind<'ir>) {
         walk_variant_kind(self, kind)
     }
@@ -127,6 +131,7 @@ pub fn walk_trait_item<'ir>(v: &mut impl Visitor<'ir>, trait_item: &'ir ir::Trai
             v.visit_fn_sig(sig);
             body.iter().for_each(|body| v.visit_body(body));
         }
+        ir::TraitItemKind::TyAlias(ty) => ty.iter().for_each(|ty| v.visit_ty(ty)),
     }
 }
 
@@ -139,6 +144,7 @@ pub fn walk_impl_item<'ir>(v: &mut impl Visitor<'ir>, impl_item: &'ir ir::ImplIt
             v.visit_fn_sig(sig);
             v.visit_body(body);
         }
+        ir::ImplItemKind::TyAlias(ty) => v.visit_ty(ty),
     }
 }
 
@@ -177,11 +183,21 @@ pub fn walk_variant_kind<'ir>(v: &mut impl Visitor<'ir>, kind: &'ir ir::VariantK
 
 pub fn walk_ty_param<'ir>(v: &mut impl Visitor<'ir>, param: &'ir ir::TyParam<'ir>) {
     v.visit_id(param.id);
+    param.bounds.iter().for_each(|bound| v.visit_path(bound));
     param.default.iter().for_each(|ty| v.visit_ty(ty));
 }
 
 pub fn walk_generics<'ir>(v: &mut impl Visitor<'ir>, generics: &'ir ir::Generics<'ir>) {
     generics.params.iter().for_each(|param| v.visit_ty_param(param));
+    generics.predicates.iter().for_each(|predicate| v.visit_where_predicate(predicate));
+}
+
+pub fn walk_where_predicate<'ir>(
+    v: &mut impl Visitor<'ir>,
+    predicate: &'ir ir::WherePredicate<'ir>,
+) {
+    v.visit_ty(predicate.ty);
+    predicate.bounds.iter().for_each(|bound| v.visit_path(bound));
 }
 
 pub fn walk_fn_sig<'ir, V: Visitor<'ir>>(v: &mut V, sig: &'ir ir::FnSig<'ir>) {
@@ -209,7 +225,8 @@ pub fn walk_item<'ir, V: Visitor<'ir>>(v: &mut V, item: &'ir ir::Item<'ir>) {
             v.visit_generics(generics);
             v.visit_body(body);
         }
-        ir::ItemKind::Use(path) => v.visit_path(path),
+        ir::ItemKind::Use(use_paths) =>
+            use_paths.iter().for_each(|use_path| v.visit_path(&use_path.path)),
         ir::ItemKind::TypeAlias(generics, ty) => {
             v.visit_generics(generics);
             v.visit_ty(ty);
@@ -265,13 +282,20 @@ pub fn walk_expr<'ir, V: Visitor<'ir>>(v: &mut V, expr: &'ir ir::Expr<'ir>) {
         ir::ExprKind::Unary(_, expr) => v.visit_expr(expr),
         ir::ExprKind::Block(block) => v.visit_block(block),
         ir::ExprKind::Path(qpath) => v.visit_qpath(qpath),
-        ir::ExprKind::Tuple(xs) => xs.iter().for_each(|x| v.visit_expr(x)),
+        ir::ExprKind::Tuple(xs) | ir::ExprKind::Array(xs) =>
+            xs.iter().for_each(|x| v.visit_expr(x)),
+        ir::ExprKind::Repeat(x, _) => v.visit_expr(x),
         ir::ExprKind::Closure(sig, body) => v.visit_lambda(sig, body),
         ir::ExprKind::Call(f, args) => {
             v.visit_expr(f);
             args.iter().for_each(|arg| v.visit_expr(arg));
         }
-        ir::ExprKind::Bin(_, l, r) => {
+        ir::ExprKind::MethodCall(receiver, ident, args) => {
+            v.visit_expr(receiver);
+            v.visit_ident(*ident);
+            args.iter().for_each(|arg| v.visit_expr(arg));
+        }
+        ir::ExprKind::Bin(_, l, r) | ir::ExprKind::Index(l, r) => {
             v.visit_expr(l);
             v.visit_expr(r);
         }
@@ -285,7 +309,7 @@ pub fn walk_expr<'ir, V: Visitor<'ir>>(v: &mut V, expr: &'ir ir::Expr<'ir>) {
             v.visit_qpath(qpath);
             fields.iter().for_each(|f| v.visit_field(f));
         }
-        ir::ExprKind::Assign(l, r) => {
+        ir::ExprKind::Assign(l, r) | ir::ExprKind::AssignOp(_, l, r) => {
             v.visit_expr(l);
             v.visit_expr(r);
         }
@@ -331,10 +355,14 @@ pub fn walk_ty<'ir, V: Visitor<'ir>>(v: &mut V, ty: &'ir ir::Ty<'ir>) {
             if let Some(ty) = ret {
                 v.visit_ty(ty);
             }
-            v.visit_ty(ty);
         }
-        ir::TyKind::Box(ty) | ir::TyKind::Ptr(ty) | ir::TyKind::Array(ty) => v.visit_ty(ty),
+        ir::TyKind::Box(ty) | ir::TyKind::Ptr(ty) | ir::TyKind::Array(ty, _) => v.visit_ty(ty),
         ir::TyKind::Path(qpath) => v.visit_qpath(qpath),
+        ir::TyKind::Dyn(path) => v.visit_path(path),
+        ir::TyKind::Projection(ty, path, _) => {
+            v.visit_ty(ty);
+            v.visit_path(path);
+        }
         ir::TyKind::Tuple(tys) => tys.iter().for_each(|ty| v.visit_ty(ty)),
         ir::TyKind::Err | ir::TyKind::Infer => {}
     }
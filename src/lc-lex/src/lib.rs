@@ -2,9 +2,11 @@
 
 mod lexing;
 mod token_tree;
+mod unescape;
 
 pub use lexing::{Base, LiteralKind};
 pub use token_tree::*;
+pub use unescape::{unescape_char, unescape_str, EscapeError};
 
 use itertools::Itertools;
 use lazy_static::lazy_static;
@@ -43,9 +45,14 @@ lazy_static! {
         "impl" => TokenKind::Impl,
         "extern" => TokenKind::Extern,
         "for" => TokenKind::For,
+        "as" => TokenKind::As,
+        "dyn" => TokenKind::Dyn,
         "loop" => TokenKind::Loop,
         "while" => TokenKind::While,
+        "where" => TokenKind::Where,
         "self" => TokenKind::LSelf,
+        "super" => TokenKind::Super,
+        "crate" => TokenKind::Crate,
     };
 }
 
@@ -200,13 +207,18 @@ pub enum TokenKind {
     Trait,
     Continue,
     While,
+    Where,
     Internal,
     Mod,
     Use,
     LSelf,
+    Super,
+    Crate,
     Extern,
     Const,
     For,
+    As,
+    Dyn,
     Loop,
     Impl,
     Unsafe,
@@ -35,6 +35,30 @@ impl TokenStream {
     pub fn size(&self) -> usize {
         self.size
     }
+
+    pub fn iter(&self) -> impl Iterator<Item = &TokenTree> {
+        self.token_trees.iter()
+    }
+
+    /// flattens the stream back into a sequence of tokens (including the delimiters of groups)
+    pub fn to_tokens(&self) -> Vec<Token> {
+        let mut tokens = Vec::with_capacity(self.size);
+        self.flatten_into(&mut tokens);
+        tokens
+    }
+
+    fn flatten_into(&self, tokens: &mut Vec<Token>) {
+        for tt in self.iter() {
+            match tt {
+                TokenTree::Token(token) => tokens.push(*token),
+                TokenTree::Group(group) => {
+                    tokens.push(group.open_token());
+                    group.stream.flatten_into(tokens);
+                    tokens.push(group.close_token());
+                }
+            }
+        }
+    }
 }
 
 impl Index<usize> for TokenStream {
@@ -119,6 +143,31 @@ impl TokenGroup {
     pub fn size(&self) -> usize {
         self.size
     }
+
+    pub fn delimiter(&self) -> &Delimiter {
+        &self.delimiter
+    }
+
+    /// The token stream within the delimiters
+    pub fn stream(&self) -> &TokenStream {
+        &self.stream
+    }
+
+    /// reconstructs the opening delimiter token from the span of the group
+    pub fn open_token(&self) -> Token {
+        let span = self.span();
+        let start = span.start().to_usize();
+        let kind = self.delimiter.kind.open_token_kind();
+        Token { span: Span::new(span.file, start, start + 1), kind }
+    }
+
+    /// reconstructs the closing delimiter token from the span of the group
+    pub fn close_token(&self) -> Token {
+        let span = self.span();
+        let end = span.end().to_usize();
+        let kind = self.delimiter.kind.close_token_kind();
+        Token { span: Span::new(span.file, end - 1, end), kind }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -135,6 +184,14 @@ pub enum DelimiterKind {
 }
 
 impl DelimiterKind {
+    pub fn open_token_kind(self) -> TokenKind {
+        match self {
+            DelimiterKind::Bracket => TokenKind::OpenBracket,
+            DelimiterKind::Brace => TokenKind::OpenBrace,
+            DelimiterKind::Paren => TokenKind::OpenParen,
+        }
+    }
+
     pub fn close_token_kind(self) -> TokenKind {
         match self {
             DelimiterKind::Bracket => TokenKind::CloseBracket,
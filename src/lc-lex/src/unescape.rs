@@ -0,0 +1,117 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::Chars;
+
+/// errors that can occur while unescaping the contents of a literal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EscapeError {
+    /// a `\` at the very end of the literal
+    LoneSlash,
+    /// `\q` where `q` is not a known escape character
+    UnknownEscape(char),
+    /// `\x` that is not followed by two hex digits that fall within the ascii range
+    InvalidHexEscape,
+    /// a malformed `\u{...}` escape or one that is not a valid unicode scalar value
+    InvalidUnicodeEscape,
+    /// `''`
+    ZeroChars,
+    /// a character literal containing more than one character, such as `'ab'`
+    MoreThanOneChar,
+}
+
+impl Display for EscapeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            EscapeError::LoneSlash => write!(f, "unterminated escape sequence"),
+            EscapeError::UnknownEscape(c) => write!(f, "unknown character escape `{}`", c),
+            EscapeError::InvalidHexEscape =>
+                write!(f, "expected an escape of the form `\\xNN` with a value of at most `\\x7f`"),
+            EscapeError::InvalidUnicodeEscape => write!(f, "invalid unicode escape"),
+            EscapeError::ZeroChars => write!(f, "empty character literal"),
+            EscapeError::MoreThanOneChar =>
+                write!(f, "character literal may only contain one codepoint"),
+        }
+    }
+}
+
+/// unescapes the contents of a string literal (excluding the surrounding quotes)
+/// on failure, returns the byte offset of the offending escape within `src` along with the error
+pub fn unescape_str(src: &str) -> Result<String, (usize, EscapeError)> {
+    let mut chars = src.chars();
+    let mut unescaped = String::with_capacity(src.len());
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        let offset = src.len() - chars.as_str().len() - 1;
+        unescaped.push(scan_escape(&mut chars).map_err(|err| (offset, err))?);
+    }
+    Ok(unescaped)
+}
+
+/// unescapes the contents of a character literal (excluding the surrounding quotes)
+/// on failure, returns the byte offset of the error within `src` along with the error
+pub fn unescape_char(src: &str) -> Result<char, (usize, EscapeError)> {
+    let mut chars = src.chars();
+    let c = match chars.next() {
+        Some('\\') => scan_escape(&mut chars).map_err(|err| (0, err))?,
+        Some(c) => c,
+        None => return Err((0, EscapeError::ZeroChars)),
+    };
+    if !chars.as_str().is_empty() {
+        return Err((src.len() - chars.as_str().len(), EscapeError::MoreThanOneChar));
+    }
+    Ok(c)
+}
+
+/// scans the escape sequence following a `\`
+fn scan_escape(chars: &mut Chars<'_>) -> Result<char, EscapeError> {
+    let c = match chars.next() {
+        Some('n') => '\n',
+        Some('t') => '\t',
+        Some('r') => '\r',
+        Some('0') => '\0',
+        Some('\\') => '\\',
+        Some('\'') => '\'',
+        Some('"') => '"',
+        Some('x') => {
+            let mut hex_digit = || chars.next().and_then(|c| c.to_digit(16));
+            let (hi, lo) = match (hex_digit(), hex_digit()) {
+                (Some(hi), Some(lo)) => (hi, lo),
+                _ => return Err(EscapeError::InvalidHexEscape),
+            };
+            let value = hi * 16 + lo;
+            if value > 0x7f {
+                return Err(EscapeError::InvalidHexEscape);
+            }
+            value as u8 as char
+        }
+        Some('u') => scan_unicode_escape(chars)?,
+        Some(c) => return Err(EscapeError::UnknownEscape(c)),
+        None => return Err(EscapeError::LoneSlash),
+    };
+    Ok(c)
+}
+
+/// scans the `{...}` of a `\u{...}` escape, which may contain at most 6 hex digits
+fn scan_unicode_escape(chars: &mut Chars<'_>) -> Result<char, EscapeError> {
+    if chars.next() != Some('{') {
+        return Err(EscapeError::InvalidUnicodeEscape);
+    }
+    let mut value = 0;
+    let mut n_digits = 0;
+    loop {
+        match chars.next() {
+            Some('}') if n_digits > 0 => break,
+            Some(c) => match c.to_digit(16) {
+                Some(digit) if n_digits < 6 => {
+                    value = value * 16 + digit;
+                    n_digits += 1;
+                }
+                _ => return Err(EscapeError::InvalidUnicodeEscape),
+            },
+            None => return Err(EscapeError::InvalidUnicodeEscape),
+        }
+    }
+    std::char::from_u32(value).ok_or(EscapeError::InvalidUnicodeEscape)
+}
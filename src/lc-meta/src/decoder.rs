@@ -0,0 +1,105 @@
+//! decodes the tables of a package into the `TyCtx` of a package that depends on it
+
+use crate::opaque::Decoder;
+use crate::{MetadataError, Table};
+use ir::{DefId, PkgId};
+use lc_core::mir::Mir;
+use lc_core::ty::{AssocItem, FnSig, Generics, Predicates, Ty, TyDecodable, TyDecoder};
+use lc_core::TyCtx;
+use lc_index::Idx;
+
+pub(crate) struct MetadataDecoder<'a, 'tcx> {
+    tcx: TyCtx<'tcx>,
+    /// the package that the metadata belongs to
+    pkg: PkgId,
+    /// the local `PkgId` of each package that the package depends on (by its own `PkgId`)
+    dependencies: &'a [PkgId],
+    opaque: Decoder<'a>,
+}
+
+impl<'a, 'tcx> TyDecoder<'tcx> for MetadataDecoder<'a, 'tcx> {
+    type Error = MetadataError;
+
+    fn tcx(&self) -> TyCtx<'tcx> {
+        self.tcx
+    }
+
+    fn read_u8(&mut self) -> Result<u8, Self::Error> {
+        self.opaque.read_u8()
+    }
+
+    fn read_u64(&mut self) -> Result<u64, Self::Error> {
+        self.opaque.read_u64()
+    }
+
+    fn read_str(&mut self) -> Result<String, Self::Error> {
+        self.opaque.read_str().map(str::to_owned)
+    }
+
+    /// translates the `DefId` of the package (or of one of its dependencies) into the local
+    /// `DefId` of the definition
+    fn read_def_id(&mut self) -> Result<DefId, Self::Error> {
+        let pkg = match self.opaque.read_u64()? as usize {
+            0 => self.pkg,
+            dependency => *self
+                .dependencies
+                .get(dependency - 1)
+                .ok_or_else(|| self.error(format!("unknown dependency `{}`", dependency - 1)))?,
+        };
+        let def_id = DefId::new(self.opaque.read_u64()? as usize);
+        local_def_id(self.tcx, pkg, def_id)
+    }
+
+    fn error(&self, msg: String) -> Self::Error {
+        MetadataError::Malformed(msg)
+    }
+}
+
+fn local_def_id(tcx: TyCtx<'_>, pkg: PkgId, def_id: DefId) -> Result<DefId, MetadataError> {
+    tcx.resolutions.defs.extern_def_id(pkg, def_id).ok_or(MetadataError::ForeignDef(def_id))
+}
+
+pub(crate) fn decode_tables(
+    tcx: TyCtx<'_>,
+    pkg: PkgId,
+    dependencies: &[PkgId],
+    tables: &[u8],
+) -> Result<(), MetadataError> {
+    let mut opaque = Decoder::new(tables);
+    while !opaque.is_empty() {
+        let table = Table::from_tag(opaque.read_u8()?)?;
+        let def_id = DefId::new(opaque.read_u64()? as usize);
+        let def_id = local_def_id(tcx, pkg, def_id)?;
+        let value = Decoder::new(opaque.read_bytes()?);
+        let mut d = MetadataDecoder { tcx, pkg, dependencies, opaque: value };
+        feed(&mut d, table, def_id)?;
+    }
+    Ok(())
+}
+
+fn feed<'tcx>(
+    d: &mut MetadataDecoder<'_, 'tcx>,
+    table: Table,
+    def_id: DefId,
+) -> Result<(), MetadataError> {
+    let tcx = d.tcx;
+    let feed = tcx.feed();
+    match table {
+        Table::Adt => feed.adt_ty(def_id, TyDecodable::decode(d)?),
+        Table::TypeOf => feed.type_of(def_id, Ty::decode(d)?),
+        Table::FnSig => feed.fn_sig(def_id, FnSig::decode(d)?),
+        Table::Generics => feed.generics_of(def_id, <&Generics<'tcx>>::decode(d)?),
+        Table::Predicates => feed.predicates_of(def_id, <&Predicates<'tcx>>::decode(d)?),
+        Table::ImplTraitRef => feed.impl_trait_ref(def_id, Option::decode(d)?),
+        // the items are also fed individually so that they can be found from their `DefId`
+        Table::AssocItems => {
+            let items = <Vec<AssocItem>>::decode(d)?;
+            for &item in &items {
+                feed.assoc_item(item.def_id, Some(item));
+            }
+            feed.assoc_items(def_id, tcx.arena.alloc_slice(&items));
+        }
+        Table::Mir => feed.mir_of(def_id, tcx.alloc(Mir::decode(d)?)),
+    }
+    Ok(())
+}
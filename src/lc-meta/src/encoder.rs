@@ -0,0 +1,137 @@
+//! encodes the values of the queries on the definitions of a package into its tables
+
+use crate::opaque::Encoder;
+use crate::{DefEntry, Table};
+use ir::{DefId, DefNode, ExternFnKind};
+use lc_core::ty::{TyEncodable, TyEncoder};
+use lc_core::TyCtx;
+use lc_index::Idx;
+
+pub(crate) struct MetadataEncoder<'tcx> {
+    tcx: TyCtx<'tcx>,
+    opaque: Encoder,
+}
+
+impl<'tcx> TyEncoder<'tcx> for MetadataEncoder<'tcx> {
+    fn tcx(&self) -> TyCtx<'tcx> {
+        self.tcx
+    }
+
+    fn emit_u8(&mut self, x: u8) {
+        self.opaque.emit_u8(x)
+    }
+
+    fn emit_u64(&mut self, x: u64) {
+        self.opaque.emit_u64(x)
+    }
+
+    fn emit_str(&mut self, s: &str) {
+        self.opaque.emit_str(s)
+    }
+
+    /// a `DefId` is encoded as its package (`0` for this package and otherwise one more than the
+    /// `PkgId` of the dependency) and its `DefId` within that package, which the decoder
+    /// translates into a local `DefId`
+    fn emit_def_id(&mut self, def_id: DefId) {
+        match self.tcx.resolutions.defs.extern_def(def_id) {
+            Some(extern_def) => {
+                self.opaque.emit_u64(1 + extern_def.pkg.index() as u64);
+                self.opaque.emit_u64(extern_def.def_id.index() as u64);
+            }
+            None => {
+                self.opaque.emit_u64(0);
+                self.opaque.emit_u64(def_id.index() as u64);
+            }
+        }
+    }
+}
+
+/// each record of the tables is the table, the `DefId` and the (length prefixed) value
+pub(crate) fn encode_tables(tcx: TyCtx<'_>, defs: &[DefEntry]) -> Vec<u8> {
+    // the adts are encoded first as types only refer to them by their `DefId`
+    let adts = defs.iter().filter(|entry| is_adt(tcx, entry.def_id));
+    let adts = adts.map(|entry| (Table::Adt, entry));
+    let others = defs.iter().flat_map(|entry| {
+        let tables = tables_of(tcx, entry);
+        tables.into_iter().map(move |table| (table, entry))
+    });
+
+    let mut opaque = Encoder::default();
+    for (table, entry) in adts.chain(others) {
+        let def_id = entry.def_id;
+        let mut e = MetadataEncoder { tcx, opaque: Encoder::default() };
+        match table {
+            Table::Adt => tcx.adt_ty(def_id).encode(&mut e),
+            Table::TypeOf => tcx.type_of(def_id).encode(&mut e),
+            Table::FnSig => tcx.fn_sig(def_id).encode(&mut e),
+            Table::Generics => tcx.generics_of(def_id).encode(&mut e),
+            Table::Predicates => tcx.predicates_of(def_id).encode(&mut e),
+            Table::ImplTraitRef => tcx.impl_trait_ref(def_id).encode(&mut e),
+            Table::AssocItems => tcx.assoc_items(def_id).encode(&mut e),
+            Table::Mir => tcx.mir_of(def_id).encode(&mut e),
+        }
+        opaque.emit_u8(table as u8);
+        opaque.emit_u64(def_id.index() as u64);
+        opaque.emit_bytes(&e.opaque.into_inner());
+    }
+    opaque.into_inner()
+}
+
+fn is_adt(tcx: TyCtx<'_>, def_id: DefId) -> bool {
+    match tcx.defs().get(def_id) {
+        DefNode::Item(item) =>
+            matches!(item.kind, ir::ItemKind::Struct(..) | ir::ItemKind::Enum(..)),
+        _ => false,
+    }
+}
+
+/// the tables that the definition has a value in (excluding `Table::Adt`)
+fn tables_of(tcx: TyCtx<'_>, entry: &DefEntry) -> Vec<Table> {
+    let def_id = entry.def_id;
+    let fn_tables = || {
+        let mut tables = vec![Table::TypeOf, Table::FnSig, Table::Generics, Table::Predicates];
+        // only the mir of the functions that are instantiated by other packages is required
+        if entry.fn_kind == Some(ExternFnKind::Mir) {
+            tables.push(Table::Mir);
+        }
+        tables
+    };
+    let ty_tables = || vec![Table::TypeOf, Table::Generics, Table::Predicates];
+    match tcx.defs().get(def_id) {
+        DefNode::Item(item) => match item.kind {
+            ir::ItemKind::Fn(..) => fn_tables(),
+            ir::ItemKind::Impl { .. } => {
+                let mut tables = ty_tables();
+                tables.extend([Table::ImplTraitRef, Table::AssocItems]);
+                tables
+            }
+            ir::ItemKind::Trait { .. } => {
+                let mut tables = ty_tables();
+                tables.push(Table::AssocItems);
+                tables
+            }
+            ir::ItemKind::Struct(..) | ir::ItemKind::Enum(..) | ir::ItemKind::TypeAlias(..) =>
+                ty_tables(),
+            ir::ItemKind::Mod(..) | ir::ItemKind::Use(..) | ir::ItemKind::Extern(..) => vec![],
+        },
+        DefNode::ImplItem(impl_item) => match impl_item.kind {
+            ir::ImplItemKind::Fn(..) => fn_tables(),
+            ir::ImplItemKind::TyAlias(..) => ty_tables(),
+        },
+        // default bodies are always generic (over `Self`) so are instantiated from their mir
+        DefNode::TraitItem(trait_item) => match trait_item.kind {
+            ir::TraitItemKind::Fn(_, Some(_)) => fn_tables(),
+            ir::TraitItemKind::Fn(_, None) =>
+                vec![Table::TypeOf, Table::FnSig, Table::Generics, Table::Predicates],
+            ir::TraitItemKind::TyAlias(..) => ty_tables(),
+        },
+        DefNode::ForeignItem(..) =>
+            vec![Table::TypeOf, Table::FnSig, Table::Generics, Table::Predicates],
+        // constructors are always instantiated from their mir
+        DefNode::Ctor(..) =>
+            vec![Table::TypeOf, Table::FnSig, Table::Generics, Table::Predicates, Table::Mir],
+        DefNode::Variant(..) => ty_tables(),
+        DefNode::Field(..) => vec![Table::TypeOf],
+        DefNode::TyParam(..) => vec![],
+    }
+}
@@ -1,18 +1,520 @@
-#[macro_use]
-extern crate serde;
 extern crate lc_ir as ir;
 
-use ir::DefId;
-use lc_core::ty::Ty;
-use rustc_hash::FxHashMap;
+mod decoder;
+mod encoder;
+mod opaque;
+
+use ir::{CtorKind, DefId, DefKind, DefNode, ExternFnKind, PkgId, Res};
+use lc_ast::{Abi, VisibilityKind};
+use lc_core::TyCtx;
+use lc_index::{Idx, IndexVec};
+use lc_span::{sym, Symbol};
+use opaque::{Decoder, Encoder};
+use rustc_hash::FxHashSet;
+use thiserror::Error;
+
+/// the first bytes of every metadata file
+const MAGIC: &[u8] = b"lmeta";
+
+/// the version of the metadata format, which must be bumped whenever the format changes
+/// (metadata of any other version is rejected rather than misinterpreted)
+pub const METADATA_VERSION: u32 = 4;
+
+#[derive(Debug, Error)]
+pub enum MetadataError {
+    #[error("not a metadata file")]
+    InvalidMagic,
+    #[error("incompatible metadata version `{0}` (expected `{}`)", METADATA_VERSION)]
+    VersionMismatch(u32),
+    #[error("unexpected end of metadata")]
+    UnexpectedEof,
+    #[error("malformed metadata: {0}")]
+    Malformed(String),
+    #[error("`{0}` is not a definition of the package")]
+    ForeignDef(DefId),
+    #[error("the metadata refers to the package `{0}` which has not been loaded")]
+    UnknownDependency(String),
+}
 
 /// a representation of everything you would need to know about a given package
-#[derive(Debug, Serialize)]
+#[derive(Debug)]
 pub struct PkgMetadata {
-    pkg: Pkg<'static>,
+    /// the name of the package (which is how its dependents refer to it)
+    pub name: String,
+    /// a hash of the inputs the package was built from, which identifies stale metadata
+    pub hash: u64,
+    /// the names of the packages the package was compiled against (indexed by their `PkgId`
+    /// within the package), whose definitions its tables may refer to
+    pub dependencies: Vec<String>,
+    /// the public items of the root module of the package
+    pub exports: Vec<Export>,
+    /// every definition of the package
+    pub defs: Vec<DefEntry>,
+    /// the encoded signatures, adts, generics, predicates, associated items and generic mir of
+    /// `defs`
+    /// (these can only be decoded into a `TyCtx`, see `PkgMetadata::load_tables`)
+    tables: Vec<u8>,
+}
+
+/// a public item of a package (or an item of another package that it re-exports)
+#[derive(Debug, Clone)]
+pub struct Export {
+    /// the name the item is exported under (which differs from its own if it is re-exported
+    /// under another name)
+    pub name: String,
+    /// the name of the package that defines the item (`None` is the exporting package itself)
+    pub pkg: Option<String>,
+    /// the `DefId` of the item within the package that defines it
+    pub def_id: DefId,
+    pub def_kind: DefKind,
+    /// the public items of a module or the variants of an enum
+    pub children: Vec<Export>,
+}
+
+/// a definition of a package
+#[derive(Debug)]
+pub struct DefEntry {
+    /// the `DefId` of the definition within the package
+    pub def_id: DefId,
+    pub name: Symbol,
+    pub def_kind: Option<DefKind>,
+    pub fn_kind: Option<ExternFnKind>,
+}
+
+/// the queries whose values are encoded into the tables
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Table {
+    Adt,
+    TypeOf,
+    FnSig,
+    Generics,
+    Predicates,
+    ImplTraitRef,
+    AssocItems,
+    Mir,
+}
+
+impl Table {
+    const ALL: [Table; 8] = [
+        Table::Adt,
+        Table::TypeOf,
+        Table::FnSig,
+        Table::Generics,
+        Table::Predicates,
+        Table::ImplTraitRef,
+        Table::AssocItems,
+        Table::Mir,
+    ];
+
+    fn from_tag(tag: u8) -> Result<Self, MetadataError> {
+        Self::ALL.get(tag as usize).copied().ok_or_else(|| invalid_tag(tag, "Table"))
+    }
+}
+
+impl PkgMetadata {
+    /// collects the metadata of the package that `tcx` is compiling against the packages `pkgs`
+    pub fn collect(
+        tcx: TyCtx<'_>,
+        name: &str,
+        hash: u64,
+        pkgs: &IndexVec<PkgId, PkgMetadata>,
+    ) -> Self {
+        // the items of the root module are those that are not the item of any other module
+        let mut nested = FxHashSet::default();
+        for item in tcx.ir.items.values() {
+            if let ir::ItemKind::Mod(module) = &item.kind {
+                nested.extend(module.items);
+            }
+        }
+        let root_items = tcx
+            .ir
+            .items
+            .values()
+            .filter(|item| !nested.contains(&item.id.def))
+            .map(|item| item.id.def);
+        let exports = collect_exports(tcx, pkgs, root_items);
+        // the prelude has no object file to link against, so its dependents instantiate all of
+        // its functions from their mir
+        let is_prelude = name == sym::prelude.as_str();
+
+        let mut defs = tcx
+            .resolutions
+            .defs
+            .def_nodes()
+            .map(|(def_id, node)| DefEntry {
+                def_id,
+                name: tcx.defs().ident(def_id).symbol,
+                def_kind: def_kind(node),
+                fn_kind: fn_kind(tcx, is_prelude, def_id, node),
+            })
+            .collect::<Vec<_>>();
+        defs.sort_by_key(|entry| entry.def_id);
+        let tables = encoder::encode_tables(tcx, &defs);
+        let dependencies = pkgs.iter().map(|metadata| metadata.name.clone()).collect();
+        Self { name: name.to_owned(), hash, dependencies, exports, defs, tables }
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut e = Encoder::default();
+        e.emit_raw(MAGIC);
+        e.emit_u32(METADATA_VERSION);
+        e.emit_u64(self.hash);
+        e.emit_str(&self.name);
+        e.emit_u64(self.dependencies.len() as u64);
+        for dependency in &self.dependencies {
+            e.emit_str(dependency);
+        }
+        encode_exports(&mut e, &self.exports);
+        e.emit_u64(self.defs.len() as u64);
+        for entry in &self.defs {
+            e.emit_u64(entry.def_id.index() as u64);
+            e.emit_str(entry.name.as_str());
+            match entry.def_kind {
+                None => e.emit_u8(0),
+                Some(def_kind) => {
+                    e.emit_u8(1);
+                    encode_def_kind(&mut e, def_kind);
+                }
+            }
+            match entry.fn_kind {
+                None => e.emit_u8(0),
+                Some(ExternFnKind::Linked(symbol)) => {
+                    e.emit_u8(1);
+                    e.emit_str(symbol.as_str());
+                }
+                Some(ExternFnKind::Mir) => e.emit_u8(2),
+                Some(ExternFnKind::Intrinsic) => e.emit_u8(3),
+            }
+        }
+        e.emit_bytes(&self.tables);
+        e.into_inner()
+    }
+
+    /// decodes the metadata, rejecting that of any other version of the format
+    pub fn decode(bytes: &[u8]) -> Result<Self, MetadataError> {
+        let mut d = Decoder::new(bytes);
+        if d.read_raw(MAGIC.len()).map_err(|_| MetadataError::InvalidMagic)? != MAGIC {
+            return Err(MetadataError::InvalidMagic);
+        }
+        let version = d.read_u32()?;
+        if version != METADATA_VERSION {
+            return Err(MetadataError::VersionMismatch(version));
+        }
+        let hash = d.read_u64()?;
+        let name = d.read_str()?.to_owned();
+        let dependencies =
+            (0..d.read_u64()?).map(|_| Ok(d.read_str()?.to_owned())).collect::<Result<_, _>>()?;
+        let exports = decode_exports(&mut d)?;
+        let defs = (0..d.read_u64()?)
+            .map(|_| {
+                let def_id = DefId::new(d.read_u64()? as usize);
+                let name = Symbol::intern(d.read_str()?);
+                let def_kind = match d.read_u8()? {
+                    0 => None,
+                    1 => Some(decode_def_kind(&mut d)?),
+                    tag => return Err(invalid_tag(tag, "Option<DefKind>")),
+                };
+                let fn_kind = match d.read_u8()? {
+                    0 => None,
+                    1 => Some(ExternFnKind::Linked(Symbol::intern(d.read_str()?))),
+                    2 => Some(ExternFnKind::Mir),
+                    3 => Some(ExternFnKind::Intrinsic),
+                    tag => return Err(invalid_tag(tag, "ExternFnKind")),
+                };
+                Ok(DefEntry { def_id, name, def_kind, fn_kind })
+            })
+            .collect::<Result<_, _>>()?;
+        let tables = d.read_bytes()?.to_vec();
+        if !d.is_empty() {
+            return Err(MetadataError::Malformed("trailing bytes".to_owned()));
+        }
+        Ok(Self { name, hash, dependencies, exports, defs, tables })
+    }
+
+    /// decodes the tables into `tcx` (where the package is `pkg` amongst the loaded packages
+    /// `pkgs`), which answers the queries on the definitions of the package
+    /// each definition (including those of the packages it depends on) must have been given a
+    /// local `DefId` beforehand by the resolver
+    pub fn load_tables(
+        &self,
+        tcx: TyCtx<'_>,
+        pkg: PkgId,
+        pkgs: &IndexVec<PkgId, PkgMetadata>,
+    ) -> Result<(), MetadataError> {
+        // the packages of the metadata are found amongst the loaded packages by name
+        let dependencies = self
+            .dependencies
+            .iter()
+            .map(|name| {
+                pkgs.iter_enumerated()
+                    .find(|(_, metadata)| &metadata.name == name)
+                    .map(|(pkg, _)| pkg)
+                    .ok_or_else(|| MetadataError::UnknownDependency(name.clone()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        decoder::decode_tables(tcx, pkg, &dependencies, &self.tables)
+    }
+}
+
+/// the kind of definition that a path of another package resolves `node` to
+fn def_kind(node: DefNode<'_>) -> Option<DefKind> {
+    match node {
+        DefNode::Item(item) => Some(item.kind.def_kind()),
+        DefNode::ImplItem(impl_item) => Some(impl_item.kind.def_kind()),
+        DefNode::TraitItem(trait_item) => Some(trait_item.kind.def_kind()),
+        DefNode::ForeignItem(..) => Some(DefKind::Fn),
+        DefNode::Ctor(variant) => Some(DefKind::Ctor((&variant.kind).into())),
+        DefNode::TyParam(param) => Some(DefKind::TyParam(param.index)),
+        DefNode::Variant(..) | DefNode::Field(..) => None,
+    }
+}
+
+/// how the function `def_id` is instantiated by other packages
+fn fn_kind(
+    tcx: TyCtx<'_>,
+    is_prelude: bool,
+    def_id: DefId,
+    node: DefNode<'_>,
+) -> Option<ExternFnKind> {
+    // generic functions can only be instantiated by the packages that use them
+    let linked_or_mir = || {
+        if !is_prelude && tcx.generics_of(def_id).params.is_empty() {
+            ExternFnKind::Linked(Symbol::intern(&tcx.symbol_name(def_id)))
+        } else {
+            ExternFnKind::Mir
+        }
+    };
+    match node {
+        DefNode::Item(item) => match item.kind {
+            ir::ItemKind::Fn(..) => Some(linked_or_mir()),
+            _ => None,
+        },
+        DefNode::ImplItem(impl_item) => match impl_item.kind {
+            ir::ImplItemKind::Fn(..) => Some(linked_or_mir()),
+            ir::ImplItemKind::TyAlias(..) => None,
+        },
+        DefNode::ForeignItem(foreign_item) => match foreign_item.abi {
+            Abi::Intrinsic => Some(ExternFnKind::Intrinsic),
+            Abi::L => Some(ExternFnKind::Linked(foreign_item.ident.symbol)),
+        },
+        DefNode::Ctor(..) => Some(ExternFnKind::Mir),
+        // trait items are resolved to the items of impls, except for the default bodies that
+        // are not overridden which are instantiated (with the concrete `Self` type) from their mir
+        DefNode::TraitItem(trait_item) => match trait_item.kind {
+            ir::TraitItemKind::Fn(_, Some(_)) => Some(ExternFnKind::Mir),
+            ir::TraitItemKind::Fn(_, None) | ir::TraitItemKind::TyAlias(..) => None,
+        },
+        DefNode::Variant(..) | DefNode::TyParam(..) | DefNode::Field(..) => None,
+    }
+}
+
+/// collects the public items amongst `items` along with the targets of their `pub use` imports
+/// (the names brought in by glob imports are not re-exported)
+fn collect_exports(
+    tcx: TyCtx<'_>,
+    pkgs: &IndexVec<PkgId, PkgMetadata>,
+    items: impl IntoIterator<Item = DefId>,
+) -> Vec<Export> {
+    let mut exports = vec![];
+    for def_id in items {
+        let item = tcx.ir.items[&def_id];
+        match item.kind {
+            // the foreign items of an extern block belong to the module of the block
+            ir::ItemKind::Extern(_, foreign_items) => {
+                let foreign_exports = foreign_items
+                    .iter()
+                    .filter(|foreign_item| foreign_item.vis.node == VisibilityKind::Public)
+                    .map(|foreign_item| Export {
+                        name: foreign_item.ident.to_string(),
+                        pkg: None,
+                        def_id: foreign_item.id.def,
+                        def_kind: DefKind::Fn,
+                        children: vec![],
+                    });
+                exports.extend(foreign_exports);
+            }
+            ir::ItemKind::Impl { .. } => {}
+            _ if item.vis.node != VisibilityKind::Public => {}
+            ir::ItemKind::Use(use_paths) => exports.extend(
+                use_paths.iter().filter_map(|use_path| collect_reexport(tcx, pkgs, use_path)),
+            ),
+            _ => exports.push(Export {
+                name: item.ident.to_string(),
+                pkg: None,
+                def_id,
+                def_kind: item.kind.def_kind(),
+                children: collect_children(tcx, pkgs, item),
+            }),
+        }
+    }
+    exports
+}
+
+/// the public items of a module or the variants of an enum
+fn collect_children(
+    tcx: TyCtx<'_>,
+    pkgs: &IndexVec<PkgId, PkgMetadata>,
+    item: &ir::Item<'_>,
+) -> Vec<Export> {
+    match item.kind {
+        ir::ItemKind::Mod(module) => collect_exports(tcx, pkgs, module.items.iter().copied()),
+        ir::ItemKind::Enum(_, variants) => variants
+            .iter()
+            .map(|variant| Export {
+                name: variant.ident.to_string(),
+                pkg: None,
+                def_id: variant.id.def,
+                def_kind: DefKind::Ctor((&variant.kind).into()),
+                children: vec![],
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// exports the target of the `pub use` import under the name it is bound to
+/// (the target may be defined by this package or any package it depends on)
+fn collect_reexport(
+    tcx: TyCtx<'_>,
+    pkgs: &IndexVec<PkgId, PkgMetadata>,
+    use_path: &ir::UsePath<'_>,
+) -> Option<Export> {
+    let (def_id, def_kind) = match use_path.path.res {
+        Res::Def(def_id, def_kind) => (def_id, def_kind),
+        // an import of a dependency itself (or one that failed to resolve) has no definition
+        _ => return None,
+    };
+    let name = use_path.binding.to_string();
+    let extern_def = match tcx.resolutions.defs.extern_def(def_id) {
+        Some(extern_def) => extern_def,
+        None => {
+            let children =
+                tcx.ir.items.get(&def_id).map_or(vec![], |item| collect_children(tcx, pkgs, item));
+            return Some(Export { name, pkg: None, def_id, def_kind, children });
+        }
+    };
+    // the children of a module or an enum of another package are those it exports for it
+    let metadata = &pkgs[extern_def.pkg];
+    let children = find_export(&metadata.exports, extern_def.def_id)
+        .map_or(vec![], |export| with_pkg(&export.children, &metadata.name));
+    Some(Export {
+        name,
+        pkg: Some(metadata.name.clone()),
+        def_id: extern_def.def_id,
+        def_kind,
+        children,
+    })
+}
+
+/// finds the export of an item defined by the package that `exports` belongs to
+fn find_export(exports: &[Export], def_id: DefId) -> Option<&Export> {
+    exports.iter().find_map(|export| match export.pkg {
+        None if export.def_id == def_id => Some(export),
+        _ => find_export(&export.children, def_id),
+    })
+}
+
+/// the exports of the package `pkg` as seen by another package
+fn with_pkg(exports: &[Export], pkg: &str) -> Vec<Export> {
+    exports
+        .iter()
+        .map(|export| Export {
+            pkg: Some(export.pkg.clone().unwrap_or_else(|| pkg.to_owned())),
+            children: with_pkg(&export.children, pkg),
+            ..export.clone()
+        })
+        .collect()
+}
+
+fn encode_exports(e: &mut Encoder, exports: &[Export]) {
+    e.emit_u64(exports.len() as u64);
+    for export in exports {
+        e.emit_str(&export.name);
+        match &export.pkg {
+            None => e.emit_u8(0),
+            Some(pkg) => {
+                e.emit_u8(1);
+                e.emit_str(pkg);
+            }
+        }
+        e.emit_u64(export.def_id.index() as u64);
+        encode_def_kind(e, export.def_kind);
+        encode_exports(e, &export.children);
+    }
+}
+
+fn decode_exports(d: &mut Decoder<'_>) -> Result<Vec<Export>, MetadataError> {
+    (0..d.read_u64()?)
+        .map(|_| {
+            Ok(Export {
+                name: d.read_str()?.to_owned(),
+                pkg: match d.read_u8()? {
+                    0 => None,
+                    1 => Some(d.read_str()?.to_owned()),
+                    tag => return Err(invalid_tag(tag, "Option<String>")),
+                },
+                def_id: DefId::new(d.read_u64()? as usize),
+                def_kind: decode_def_kind(d)?,
+                children: decode_exports(d)?,
+            })
+        })
+        .collect()
+}
+
+const CTOR_KINDS: [CtorKind; 3] = [CtorKind::Tuple, CtorKind::Struct, CtorKind::Unit];
+
+fn encode_def_kind(e: &mut Encoder, def_kind: DefKind) {
+    let tag = match def_kind {
+        DefKind::Fn => 0,
+        DefKind::AssocFn => 1,
+        DefKind::AssocTy => 2,
+        DefKind::TypeAlias => 3,
+        DefKind::Enum => 4,
+        DefKind::Struct => 5,
+        DefKind::Impl => 6,
+        DefKind::Use => 7,
+        DefKind::Mod => 8,
+        DefKind::Macro => 9,
+        DefKind::Trait => 10,
+        DefKind::Extern => 11,
+        DefKind::Ctor(ctor_kind) => {
+            e.emit_u8(12);
+            let idx = CTOR_KINDS.iter().position(|&kind| kind == ctor_kind).unwrap();
+            return e.emit_u8(idx as u8);
+        }
+        DefKind::TyParam(idx) => {
+            e.emit_u8(13);
+            return e.emit_u64(idx.index() as u64);
+        }
+    };
+    e.emit_u8(tag)
+}
+
+fn decode_def_kind(d: &mut Decoder<'_>) -> Result<DefKind, MetadataError> {
+    let def_kind = match d.read_u8()? {
+        0 => DefKind::Fn,
+        1 => DefKind::AssocFn,
+        2 => DefKind::AssocTy,
+        3 => DefKind::TypeAlias,
+        4 => DefKind::Enum,
+        5 => DefKind::Struct,
+        6 => DefKind::Impl,
+        7 => DefKind::Use,
+        8 => DefKind::Mod,
+        9 => DefKind::Macro,
+        10 => DefKind::Trait,
+        11 => DefKind::Extern,
+        12 => {
+            let tag = d.read_u8()?;
+            let ctor_kind = CTOR_KINDS.get(tag as usize);
+            DefKind::Ctor(*ctor_kind.ok_or_else(|| invalid_tag(tag, "CtorKind"))?)
+        }
+        13 => DefKind::TyParam(ir::ParamIdx::new(d.read_u64()? as usize)),
+        tag => return Err(invalid_tag(tag, "DefKind")),
+    };
+    Ok(def_kind)
 }
 
-#[derive(Debug, Serialize)]
-pub struct Pkg<'tcx> {
-    types: FxHashMap<DefId, Ty<'tcx>>,
+fn invalid_tag(tag: u8, name: &str) -> MetadataError {
+    MetadataError::Malformed(format!("invalid tag `{}` for `{}`", tag, name))
 }
@@ -0,0 +1,89 @@
+//! the raw byte encoding that the metadata is built upon
+//! integers are fixed width little endian and strings and byte slices are length prefixed
+
+use crate::MetadataError;
+
+#[derive(Default)]
+pub(crate) struct Encoder {
+    data: Vec<u8>,
+}
+
+impl Encoder {
+    pub fn into_inner(self) -> Vec<u8> {
+        self.data
+    }
+
+    pub fn emit_raw(&mut self, bytes: &[u8]) {
+        self.data.extend_from_slice(bytes)
+    }
+
+    pub fn emit_u8(&mut self, x: u8) {
+        self.data.push(x)
+    }
+
+    pub fn emit_u32(&mut self, x: u32) {
+        self.emit_raw(&x.to_le_bytes())
+    }
+
+    pub fn emit_u64(&mut self, x: u64) {
+        self.emit_raw(&x.to_le_bytes())
+    }
+
+    pub fn emit_bytes(&mut self, bytes: &[u8]) {
+        self.emit_u64(bytes.len() as u64);
+        self.emit_raw(bytes);
+    }
+
+    pub fn emit_str(&mut self, s: &str) {
+        self.emit_bytes(s.as_bytes())
+    }
+}
+
+pub(crate) struct Decoder<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.position == self.data.len()
+    }
+
+    pub fn read_raw(&mut self, n: usize) -> Result<&'a [u8], MetadataError> {
+        let end = self.position.checked_add(n).filter(|&end| end <= self.data.len());
+        let end = end.ok_or(MetadataError::UnexpectedEof)?;
+        let bytes = &self.data[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, MetadataError> {
+        self.read_raw(1).map(|bytes| bytes[0])
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, MetadataError> {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(self.read_raw(4)?);
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    pub fn read_u64(&mut self) -> Result<u64, MetadataError> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.read_raw(8)?);
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    pub fn read_bytes(&mut self) -> Result<&'a [u8], MetadataError> {
+        let len = self.read_u64()?;
+        self.read_raw(len as usize)
+    }
+
+    pub fn read_str(&mut self) -> Result<&'a str, MetadataError> {
+        let bytes = self.read_bytes()?;
+        std::str::from_utf8(bytes).map_err(|err| MetadataError::Malformed(err.to_string()))
+    }
+}
@@ -1,6 +1,7 @@
 //! this module performs sanity type checks on mir
 //! there are not intended to be any errors so it panics on error
 
+use lc_ast::BinOp;
 use lc_core::mir::*;
 use lc_core::ty::{Ty, TyCtx};
 
@@ -45,13 +46,25 @@ impl<'a, 'tcx> Typechecker<'a, 'tcx> {
                 let rty = self.op_ty(r);
                 assert_eq!(lty, rty);
                 match op {
-                    lc_ast::BinOp::Mul | lc_ast::BinOp::Div | lc_ast::BinOp::Add | lc_ast::BinOp::Sub => lty,
-                    lc_ast::BinOp::Lt | lc_ast::BinOp::Gt | lc_ast::BinOp::Eq | lc_ast::BinOp::Neq =>
+                    BinOp::Mul
+                    | BinOp::Div
+                    | BinOp::Rem
+                    | BinOp::Add
+                    | BinOp::Sub
+                    | BinOp::Shl
+                    | BinOp::Shr => {
+                        assert!(lty.is_numeric());
+                        lty
+                    }
+                    BinOp::Lt | BinOp::Gt | BinOp::Le | BinOp::Ge | BinOp::Eq | BinOp::Neq =>
                         tcx.types.bool,
-                    lc_ast::BinOp::And | lc_ast::BinOp::Or => {
-                        assert_eq!(lty, tcx.types.bool);
-                        tcx.types.bool
+                    BinOp::And | BinOp::Or | BinOp::Xor => {
+                        assert!(lty.is_integral() || lty.is_bool());
+                        lty
                     }
+                    // short-circuiting operators are lowered into control flow
+                    BinOp::AndAnd | BinOp::OrOr =>
+                        unreachable!("short-circuiting binary operator `{}` in mir", op),
                 }
             }
             Rvalue::Adt { adt, variant_idx, substs, fields } => {
@@ -63,6 +76,12 @@ impl<'a, 'tcx> Typechecker<'a, 'tcx> {
                     .for_each(|(ty, op)| assert_eq!(ty, self.op_ty(op)));
                 tcx.mk_adt_ty(adt, substs)
             }
+            Rvalue::Array(ty, xs) => {
+                xs.iter().for_each(|x| assert_eq!(*ty, self.op_ty(x)));
+                tcx.mk_array_ty(ty, xs.len())
+            }
+            Rvalue::Repeat(operand, n) => tcx.mk_array_ty(self.op_ty(operand), *n),
+            Rvalue::Cast(_, _, ty) => ty,
         }
     }
 }
@@ -11,19 +11,24 @@ impl<'a, 'tcx> MirBuilder<'a, 'tcx> {
         match expr.kind {
             tir::ExprKind::Const(c) => block.and(c),
             tir::ExprKind::Box(..)
+            | tir::ExprKind::Cast(..)
             | tir::ExprKind::Unary(..)
             | tir::ExprKind::Deref(..)
             | tir::ExprKind::Ref(..)
             | tir::ExprKind::Bin(..)
             | tir::ExprKind::Field(..)
+            | tir::ExprKind::Index(..)
             | tir::ExprKind::Block(..)
             | tir::ExprKind::VarRef(..)
             | tir::ExprKind::ItemRef(..)
             | tir::ExprKind::Tuple(..)
+            | tir::ExprKind::Array(..)
+            | tir::ExprKind::Repeat(..)
             | tir::ExprKind::Call(..)
             | tir::ExprKind::Match(..)
             | tir::ExprKind::Ret(..)
             | tir::ExprKind::Assign(..)
+            | tir::ExprKind::AssignOp(..)
             | tir::ExprKind::Loop(..)
             | tir::ExprKind::Break
             | tir::ExprKind::Continue
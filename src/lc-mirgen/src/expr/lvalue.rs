@@ -1,5 +1,5 @@
 use super::*;
-use lc_core::ty::{Ty, TyCtx};
+use lc_core::ty::{Ty, TyCtx, TyKind};
 
 /// helper struct for building projections of an lvalue
 pub struct LvalueBuilder<'tcx> {
@@ -28,6 +28,10 @@ impl<'tcx> LvalueBuilder<'tcx> {
     pub fn project_field(self, field: FieldIdx, ty: Ty<'tcx>) -> Self {
         self.project(Projection::Field(field, ty))
     }
+
+    pub fn project_index(self, index: VarId) -> Self {
+        self.project(Projection::Index(index))
+    }
 }
 
 impl<'tcx> From<Lvalue<'tcx>> for LvalueBuilder<'tcx> {
@@ -75,7 +79,25 @@ impl<'a, 'tcx> MirBuilder<'a, 'tcx> {
                 let builder = set!(block = self.as_lvalue_builder(block, expr));
                 block.and(builder.project_deref())
             }
+            tir::ExprKind::Index(ref base, ref index) => {
+                let info = self.span_info(expr.span);
+                let builder = set!(block = self.as_lvalue_builder(block, base));
+                let index = set!(block = self.as_tmp(block, index));
+                let len = match base.ty.kind {
+                    TyKind::Array(_, n) => n,
+                    _ => unreachable!("indexed into non-array type `{}`", base.ty),
+                };
+                let target = self.append_basic_block();
+                let kind = TerminatorKind::BoundsCheck {
+                    index: Operand::Lvalue(index.into()),
+                    len: Operand::Const(self.tcx.mk_const_int(len as i64)),
+                    target,
+                };
+                self.terminate(info, block, kind);
+                target.and(builder.project_index(index))
+            }
             tir::ExprKind::Box(..)
+            | tir::ExprKind::Cast(..)
             | tir::ExprKind::Loop(..)
             | tir::ExprKind::Const(..)
             | tir::ExprKind::Bin(..)
@@ -84,9 +106,12 @@ impl<'a, 'tcx> MirBuilder<'a, 'tcx> {
             | tir::ExprKind::Block(..)
             | tir::ExprKind::ItemRef(..)
             | tir::ExprKind::Tuple(..)
+            | tir::ExprKind::Array(..)
+            | tir::ExprKind::Repeat(..)
             | tir::ExprKind::Call(..)
             | tir::ExprKind::Match(..)
             | tir::ExprKind::Assign(..)
+            | tir::ExprKind::AssignOp(..)
             | tir::ExprKind::Adt { .. }
             | tir::ExprKind::Closure { .. }
             | tir::ExprKind::Break
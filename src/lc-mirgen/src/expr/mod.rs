@@ -42,16 +42,23 @@ impl<'a, 'tcx> MirBuilder<'a, 'tcx> {
             tir::ExprKind::Ret(..) | tir::ExprKind::Break | tir::ExprKind::Continue =>
                 self.build_expr_stmt(block, expr),
             tir::ExprKind::Tuple(xs) => self.build_tuple(block, dest, expr, xs),
+            tir::ExprKind::Bin(op, l, r) if op.is_short_circuiting() =>
+                self.build_short_circuiting_op(block, dest, expr, *op, l, r),
             tir::ExprKind::Box(..)
+            | tir::ExprKind::Cast(..)
             | tir::ExprKind::VarRef(..)
             | tir::ExprKind::Ref(..)
             | tir::ExprKind::ItemRef(..)
             | tir::ExprKind::Field(..)
+            | tir::ExprKind::Index(..)
             | tir::ExprKind::Assign(..)
+            | tir::ExprKind::AssignOp(..)
             | tir::ExprKind::Unary(..)
             | tir::ExprKind::Bin(..)
             | tir::ExprKind::Deref(..)
             | tir::ExprKind::Adt { .. }
+            | tir::ExprKind::Array(..)
+            | tir::ExprKind::Repeat(..)
             | tir::ExprKind::Closure { .. }
             | tir::ExprKind::Const(..) => {
                 let rvalue = set!(block = self.as_rvalue(block, expr));
@@ -77,6 +84,43 @@ impl<'a, 'tcx> MirBuilder<'a, 'tcx> {
         block.unit()
     }
 
+    /// `l && r` is lowered as follows (and `l || r` similarly, but with the branches swapped)
+    ///
+    /// entry:
+    ///   dest = l
+    ///   cond dest rhs next
+    ///
+    /// rhs:
+    ///   dest = r
+    ///   br next
+    ///
+    /// next:
+    ///
+    fn build_short_circuiting_op(
+        &mut self,
+        mut block: BlockId,
+        dest: Lvalue<'tcx>,
+        expr: &tir::Expr<'tcx>,
+        op: lc_ast::BinOp,
+        l: &tir::Expr<'tcx>,
+        r: &tir::Expr<'tcx>,
+    ) -> BlockAnd<()> {
+        let info = self.span_info(expr.span);
+        set!(block = self.write_expr(block, dest, l));
+        let rhs = self.append_basic_block();
+        let next = self.append_basic_block();
+        let cond = Operand::Lvalue(dest);
+        let kind = match op {
+            lc_ast::BinOp::AndAnd => TerminatorKind::Cond(cond, rhs, next),
+            lc_ast::BinOp::OrOr => TerminatorKind::Cond(cond, next, rhs),
+            _ => unreachable!("`{}` is not a short-circuiting operator", op),
+        };
+        self.terminate(info, block, kind);
+        let rhs_end = set!(self.write_expr(rhs, dest, r));
+        self.branch(info, rhs_end, next);
+        next.unit()
+    }
+
     fn build_call(
         &mut self,
         mut block: BlockId,
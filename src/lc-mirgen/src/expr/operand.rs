@@ -11,7 +11,10 @@ impl<'a, 'tcx> MirBuilder<'a, 'tcx> {
             // which is later resolved to a particular instance
             // during monomorphization
             tir::ExprKind::ItemRef(def_id, substs) => block.and(Operand::Item(def_id, substs)),
-            tir::ExprKind::Field(..) | tir::ExprKind::Deref(..) | tir::ExprKind::VarRef(..) => {
+            tir::ExprKind::Field(..)
+            | tir::ExprKind::Index(..)
+            | tir::ExprKind::Deref(..)
+            | tir::ExprKind::VarRef(..) => {
                 let lvalue = set!(block = self.as_lvalue(block, expr));
                 block.and(Operand::Lvalue(lvalue))
             }
@@ -20,16 +23,20 @@ impl<'a, 'tcx> MirBuilder<'a, 'tcx> {
                 block.and(Operand::Const(constant))
             }
             tir::ExprKind::Box(..)
+            | tir::ExprKind::Cast(..)
             | tir::ExprKind::Loop(..)
             | tir::ExprKind::Unary(..)
             | tir::ExprKind::Block(..)
             | tir::ExprKind::Match(..)
             | tir::ExprKind::Ref(..)
             | tir::ExprKind::Assign(..)
+            | tir::ExprKind::AssignOp(..)
             | tir::ExprKind::Ret(..)
             | tir::ExprKind::Bin(..)
             | tir::ExprKind::Call(..)
             | tir::ExprKind::Tuple(..)
+            | tir::ExprKind::Array(..)
+            | tir::ExprKind::Repeat(..)
             | tir::ExprKind::Adt { .. }
             | tir::ExprKind::Closure { .. }
             | tir::ExprKind::Break
@@ -29,7 +29,8 @@ impl<'a, 'tcx> MirBuilder<'a, 'tcx> {
 
                 block.and(Rvalue::Adt { adt, variant_idx, substs, fields })
             }
-            tir::ExprKind::Bin(op, ref l, ref r) => {
+            // short-circuiting operators require control flow so are written via a temporary
+            tir::ExprKind::Bin(op, ref l, ref r) if !op.is_short_circuiting() => {
                 let lhs = set!(block = self.as_operand(block, l));
                 let rhs = set!(block = self.as_operand(block, r));
                 self.build_binary_op(block, expr.span, expr.ty, op, lhs, rhs)
@@ -47,22 +48,44 @@ impl<'a, 'tcx> MirBuilder<'a, 'tcx> {
                 let rhs = set!(block = self.as_rvalue(block, r));
                 let lhs = set!(block = self.as_lvalue(block, l));
                 self.push_assignment(info, block, lhs, rhs.clone());
-                block.and(rhs)
+                // an overloaded `l op= r` is lowered to `l = op(l, r)` but is still of unit type
+                match expr.ty.is_unit() {
+                    true => block.and(Rvalue::Operand(Operand::Const(self.tcx.mk_const_unit()))),
+                    false => block.and(rhs),
+                }
+            }
+            tir::ExprKind::AssignOp(op, ref l, ref r) => {
+                set!(block = self.build_assign_op(block, expr, op, l, r));
+                block.and(Rvalue::Operand(Operand::Const(self.tcx.mk_const_unit())))
+            }
+            tir::ExprKind::Array(ref xs) => {
+                let xs = xs.iter().map(|x| set!(block = self.as_operand(block, x))).collect_vec();
+                block.and(Rvalue::Array(expr.ty.array_elem_ty(), xs))
+            }
+            tir::ExprKind::Repeat(ref x, n) => {
+                let operand = set!(block = self.as_operand(block, x));
+                block.and(Rvalue::Repeat(operand, n))
             }
             tir::ExprKind::Box(ref inner) => {
                 let operand = set!(block = self.as_operand(block, inner));
                 block.and(Rvalue::Box(operand))
             }
+            tir::ExprKind::Cast(ref inner, cast) => {
+                let operand = set!(block = self.as_operand(block, inner));
+                block.and(Rvalue::Cast(cast, operand, expr.ty))
+            }
             tir::ExprKind::Ref(ref expr) => {
                 let lvalue = set!(block = self.as_lvalue(block, expr));
                 block.and(Rvalue::Ref(lvalue))
             }
             tir::ExprKind::Block(..)
+            | tir::ExprKind::Bin(..)
             | tir::ExprKind::ItemRef(..)
             | tir::ExprKind::Loop(..)
             | tir::ExprKind::Call(..)
             | tir::ExprKind::Match(..)
             | tir::ExprKind::Field(..)
+            | tir::ExprKind::Index(..)
             | tir::ExprKind::Tuple(..)
             | tir::ExprKind::Deref(_)
             | tir::ExprKind::Const(..)
@@ -76,6 +99,26 @@ impl<'a, 'tcx> MirBuilder<'a, 'tcx> {
         }
     }
 
+    /// lowers `l op= r` into `l = l op r`, where the place `l` is only evaluated once
+    pub(crate) fn build_assign_op(
+        &mut self,
+        mut block: BlockId,
+        expr: &tir::Expr<'tcx>,
+        op: lc_ast::BinOp,
+        l: &tir::Expr<'tcx>,
+        r: &tir::Expr<'tcx>,
+    ) -> BlockAnd<()> {
+        let info = self.span_info(expr.span);
+        // as with assignment, the right hand side is evaluated first
+        let rhs = set!(block = self.as_operand(block, r));
+        let lhs = set!(block = self.as_lvalue(block, l));
+        let lhs_operand = Operand::Lvalue(lhs);
+        let rvalue =
+            set!(block = self.build_binary_op(block, expr.span, l.ty, op, lhs_operand, rhs));
+        self.push_assignment(info, block, lhs, rvalue);
+        block.unit()
+    }
+
     pub(crate) fn build_binary_op(
         &mut self,
         block: BlockId,
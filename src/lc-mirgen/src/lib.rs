@@ -57,6 +57,7 @@ fn instance_mir<'tcx>(tcx: TyCtx<'tcx>, instance: Instance<'tcx>) -> &'tcx Mir<'
     match instance.kind {
         InstanceKind::Item => tcx.mir_of(instance.def_id),
         InstanceKind::Intrinsic => unreachable!("intrinsics don't have mir"),
+        InstanceKind::Virtual(..) => unreachable!("virtual calls don't have mir"),
     }
 }
 
@@ -70,9 +71,11 @@ fn mir_of(tcx: TyCtx<'_>, def_id: DefId) -> &Mir<'_> {
         },
         DefNode::ImplItem(item) => match item.kind {
             ir::ImplItemKind::Fn(_, body) => self::build_mir(tcx, def_id, body),
+            ir::ImplItemKind::TyAlias(..) => panic!(),
         },
         DefNode::TraitItem(trait_item) => match trait_item.kind {
             ir::TraitItemKind::Fn(_, body) => self::build_mir(tcx, def_id, body.unwrap()),
+            ir::TraitItemKind::TyAlias(..) => panic!(),
         },
         DefNode::Field(..)
         | DefNode::ForeignItem(..)
@@ -115,10 +118,11 @@ pub fn build_tir(tcx: TyCtx<'_>) -> LResult<tir::Prog<'_>> {
             // note that no tir is generated for enum constructors
             // the constructor code is generated at mir level only
             ir::ItemKind::TypeAlias(..) | ir::ItemKind::Enum(..) | ir::ItemKind::Struct(..) => {}
+            // the items of traits and impls are not lowered to tir
             ir::ItemKind::Mod(..)
             | ir::ItemKind::Use(..)
             | ir::ItemKind::Trait { .. }
-            | ir::ItemKind::Impl { .. } => unreachable!(),
+            | ir::ItemKind::Impl { .. } => {}
         }
     }
     halt_on_error!(tcx);
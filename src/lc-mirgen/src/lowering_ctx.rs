@@ -110,7 +110,7 @@ impl<'tcx> Tir<'tcx> for ir::Item<'tcx> {
     type Output = tir::Item<'tcx>;
 
     fn to_tir(&self, ctx: &mut LoweringCtx<'tcx>) -> Self::Output {
-        let &Self { span, id, ident, vis, ref kind } = self;
+        let &Self { span, id, ident, vis, ref kind, .. } = self;
         match kind {
             ir::ItemKind::Fn(_sig, generics, body) => {
                 let ty = ctx.type_of(self.id.def);
@@ -169,16 +169,23 @@ impl<'tcx> Tir<'tcx> for ir::Pattern<'tcx> {
 impl<'tcx> LoweringCtx<'tcx> {
     fn lower_pat_lit(&mut self, expr: &ir::Expr<'tcx>) -> tir::PatternKind<'tcx> {
         let c = match expr.kind {
-            ir::ExprKind::Lit(lit) => match lit {
-                Lit::Float(f) => self.mk_const_float(f),
-                Lit::Int(i) => self.mk_const_int(i),
-                Lit::Bool(b) => self.mk_const_bool(b),
-            },
+            ir::ExprKind::Lit(lit) => self.lower_lit(lit, self.node_ty(expr.id)),
             _ => unreachable!(),
         };
         tir::PatternKind::Lit(c)
     }
 
+    /// numeric literals may be of any width so their type must be provided
+    fn lower_lit(&mut self, lit: Lit, ty: Ty<'tcx>) -> &'tcx Const<'tcx> {
+        match lit {
+            Lit::Float(f, _) => self.mk_const_of_ty(ConstKind::Float(f), ty),
+            Lit::Int(i, _) => self.mk_const_of_ty(ConstKind::Int(i as i64), ty),
+            Lit::Bool(b) => self.mk_const_bool(b),
+            Lit::Char(c) => self.mk_const_char(c),
+            Lit::Str(s) => self.mk_const_str(s),
+        }
+    }
+
     fn lower_struct_pat(
         &mut self,
         pat: &ir::Pattern<'tcx>,
@@ -341,6 +348,47 @@ impl<'tcx> LoweringCtx<'tcx> {
         tir::ExprKind::ItemRef(def_id, substs)
     }
 
+    /// lowers `receiver.method(args)` into a call to the method with the receiver as the first
+    /// argument
+    fn lower_method_call(
+        &mut self,
+        expr: &ir::Expr<'tcx>,
+        receiver: &ir::Expr<'tcx>,
+        args: &[ir::Expr<'tcx>],
+    ) -> tir::ExprKind<'tcx> {
+        let f = self.lower_method_ref(expr);
+        let args = std::iter::once(receiver.to_tir(self)).chain(args.to_tir(self)).collect();
+        tir::ExprKind::Call(Box::new(f), args)
+    }
+
+    /// lowers an operator that typeck resolved to a method of a lang item trait into a call to
+    /// that method with the operands as the arguments
+    fn lower_overloaded_op(
+        &mut self,
+        expr: &ir::Expr<'tcx>,
+        operands: &[&ir::Expr<'tcx>],
+    ) -> tir::ExprKind<'tcx> {
+        let f = self.lower_method_ref(expr);
+        let args = operands.iter().map(|operand| operand.to_tir(self)).collect();
+        tir::ExprKind::Call(Box::new(f), args)
+    }
+
+    /// whether `expr` is an operator expression that is overloaded
+    fn is_overloaded_op(&self, expr: &ir::Expr<'tcx>) -> bool {
+        self.tables.type_relative_resolutions().get(expr.id).is_some()
+    }
+
+    /// a reference to the method that `expr` was resolved to during typeck
+    fn lower_method_ref(&self, expr: &ir::Expr<'tcx>) -> tir::Expr<'tcx> {
+        let (def_id, _) = self.tables.type_relative_res(expr).expect_def();
+        let substs = self.expr_substs(expr);
+        tir::Expr {
+            span: expr.span,
+            ty: self.type_of(def_id).subst(self.tcx, substs),
+            kind: tir::ExprKind::ItemRef(def_id, substs),
+        }
+    }
+
     fn lower_res(&self, expr: &ir::Expr<'tcx>, res: Res) -> tir::ExprKind<'tcx> {
         match res {
             Res::Local(id) => tir::ExprKind::VarRef(id),
@@ -373,7 +421,11 @@ impl<'tcx> LoweringCtx<'tcx> {
                 DefKind::Enum => todo!(),
                 DefKind::Trait => todo!(),
                 DefKind::Macro => todo!(),
-                DefKind::TyParam(..) | DefKind::TypeAlias | DefKind::Use | DefKind::Mod => panic!(),
+                DefKind::TyParam(..)
+                | DefKind::TypeAlias
+                | DefKind::AssocTy
+                | DefKind::Use
+                | DefKind::Mod => panic!(),
             },
             Res::SelfTy { .. } => todo!(),
             Res::SelfVal { impl_def } => {
@@ -423,6 +475,8 @@ impl<'tcx> LoweringCtx<'tcx> {
         let ty = self.node_ty(expr.id);
         let kind = match kind {
             ir::ExprKind::Box(expr) => tir::ExprKind::Box(Box::new(expr.to_tir(self))),
+            ir::ExprKind::Bin(_, l, r) if self.is_overloaded_op(expr) =>
+                self.lower_overloaded_op(expr, &[l, r]),
             ir::ExprKind::Bin(op, l, r) =>
                 tir::ExprKind::Bin(*op, Box::new(l.to_tir(self)), Box::new(r.to_tir(self))),
             ir::ExprKind::Unary(UnaryOp::Deref, expr) =>
@@ -430,14 +484,20 @@ impl<'tcx> LoweringCtx<'tcx> {
             ir::ExprKind::Unary(UnaryOp::Ref, expr) =>
                 tir::ExprKind::Ref(Box::new(expr.to_tir(self))),
             ir::ExprKind::Loop(block) => tir::ExprKind::Loop(Box::new(block.to_tir(self))),
+            ir::ExprKind::Unary(_, operand) if self.is_overloaded_op(expr) =>
+                self.lower_overloaded_op(expr, &[operand]),
             ir::ExprKind::Unary(op, expr) => tir::ExprKind::Unary(*op, Box::new(expr.to_tir(self))),
             ir::ExprKind::Block(block) => tir::ExprKind::Block(Box::new(block.to_tir(self))),
             ir::ExprKind::Path(qpath) => self.lower_qpath(expr, qpath),
             ir::ExprKind::Tuple(xs) => tir::ExprKind::Tuple(xs.to_tir(self)),
+            ir::ExprKind::Array(xs) => tir::ExprKind::Array(xs.to_tir(self)),
+            ir::ExprKind::Repeat(x, n) => tir::ExprKind::Repeat(Box::new(x.to_tir(self)), *n),
             ir::ExprKind::Closure(_sig, body) => self.lower_closure(expr, body),
             ir::ExprKind::Call(f, args) =>
                 tir::ExprKind::Call(Box::new(f.to_tir(self)), args.to_tir(self)),
-            ir::ExprKind::Lit(lit) => tir::ExprKind::Const(lit.to_tir(self)),
+            ir::ExprKind::MethodCall(receiver, _, args) =>
+                self.lower_method_call(expr, receiver, args),
+            ir::ExprKind::Lit(lit) => tir::ExprKind::Const(self.lower_lit(*lit, ty)),
             ir::ExprKind::Match(scrut, arms, _) => self.lower_match(expr, scrut, arms),
             ir::ExprKind::Struct(_path, fields) => match ty.kind {
                 TyKind::Adt(adt, substs) => match adt.kind {
@@ -459,8 +519,23 @@ impl<'tcx> LoweringCtx<'tcx> {
                 tir::ExprKind::Ret(expr.map(|expr| Box::new(expr.to_tir(self)))),
             ir::ExprKind::Assign(l, r) =>
                 tir::ExprKind::Assign(Box::new(l.to_tir(self)), Box::new(r.to_tir(self))),
+            // the overloaded `l op= r` is lowered to `l = op(l, r)`
+            ir::ExprKind::AssignOp(_, l, r) if self.is_overloaded_op(expr) => {
+                let kind = self.lower_overloaded_op(expr, &[l, r]);
+                let call = tir::Expr { span, kind, ty: self.node_ty(l.id) };
+                tir::ExprKind::Assign(Box::new(l.to_tir(self)), Box::new(call))
+            }
+            ir::ExprKind::AssignOp(op, l, r) => tir::ExprKind::AssignOp(
+                *op,
+                Box::new(l.to_tir(self)),
+                Box::new(r.to_tir(self)),
+            ),
             ir::ExprKind::Field(base, _) =>
                 tir::ExprKind::Field(Box::new(base.to_tir(self)), self.tables.field_index(expr.id)),
+            ir::ExprKind::Index(base, index) if self.is_overloaded_op(expr) =>
+                self.lower_overloaded_op(expr, &[base, index]),
+            ir::ExprKind::Index(base, index) =>
+                tir::ExprKind::Index(Box::new(base.to_tir(self)), Box::new(index.to_tir(self))),
             ir::ExprKind::Break => tir::ExprKind::Break,
             ir::ExprKind::Continue => tir::ExprKind::Continue,
             ir::ExprKind::Err => unreachable!(),
@@ -490,30 +565,24 @@ impl<'tcx> LoweringCtx<'tcx> {
         let span = expr.span;
         let kind = match adjustment.kind {
             AdjustmentKind::Deref => tir::ExprKind::Deref(Box::new(expr)),
-            AdjustmentKind::NeverToAny => todo!(),
-            AdjustmentKind::Cast(_cast) => todo!(),
+            AdjustmentKind::Borrow => {
+                let ty = self.mk_ptr_ty(expr.ty);
+                let addr = tir::Expr { span, ty, kind: tir::ExprKind::Ref(Box::new(expr)) };
+                tir::ExprKind::Cast(Box::new(addr), PointerCast::Autoref)
+            }
+            // the expression diverges so there is no value to convert
+            AdjustmentKind::NeverToAny => return expr,
+            AdjustmentKind::Cast(cast) => tir::ExprKind::Cast(Box::new(expr), cast),
         };
         tir::Expr { ty: adjustment.ty, span, kind }
     }
 }
 
-impl<'tcx> Tir<'tcx> for Lit {
-    type Output = &'tcx Const<'tcx>;
-
-    fn to_tir(&self, ctx: &mut LoweringCtx<'tcx>) -> Self::Output {
-        match *self {
-            Lit::Float(f) => ctx.mk_const_float(f),
-            Lit::Bool(b) => ctx.mk_const_bool(b),
-            Lit::Int(i) => ctx.mk_const_int(i),
-        }
-    }
-}
-
 impl<'tcx> Tir<'tcx> for ir::Arm<'tcx> {
     type Output = tir::Arm<'tcx>;
 
     fn to_tir(&self, ctx: &mut LoweringCtx<'tcx>) -> Self::Output {
-        let &ir::Arm { id, span, pat, body, ref guard } = self;
+        let &ir::Arm { id, span, pat, body, ref guard, .. } = self;
         tir::Arm {
             id,
             span,
@@ -233,7 +233,9 @@ impl<'a, 'p, 'tcx> UsefulnessCtxt<'a, 'p, 'tcx> {
                 Ctor::nullary(CtorKind::Literal(self.mk_const_bool(true))),
                 Ctor::nullary(CtorKind::Literal(self.mk_const_bool(false))),
             },
-            TyKind::Int => indexset! { Ctor::nullary(CtorKind::NonExhaustive) },
+            // it is not feasible to enumerate every possible value of these types
+            TyKind::Int(_) | TyKind::Uint(_) | TyKind::Float(_) | TyKind::Char | TyKind::Str =>
+                indexset! { Ctor::nullary(CtorKind::NonExhaustive) },
             _ => unimplemented!("`{}`", ty),
         }
     }
@@ -43,9 +43,11 @@ impl<'a, 'tcx> MirBuilder<'a, 'tcx> {
                 self.push_assignment(info, block, lvalue, rvalue);
                 block.unit()
             }
+            tir::ExprKind::AssignOp(op, l, r) => self.build_assign_op(block, expr, *op, l, r),
             tir::ExprKind::Break => self.break_scope(info, block, BreakType::Break),
             tir::ExprKind::Continue => self.break_scope(info, block, BreakType::Continue),
             tir::ExprKind::Box(..)
+            | tir::ExprKind::Cast(..)
             | tir::ExprKind::Loop(..)
             | tir::ExprKind::Const(..)
             | tir::ExprKind::Bin(..)
@@ -54,9 +56,12 @@ impl<'a, 'tcx> MirBuilder<'a, 'tcx> {
             | tir::ExprKind::VarRef(..)
             | tir::ExprKind::ItemRef(..)
             | tir::ExprKind::Tuple(..)
+            | tir::ExprKind::Array(..)
+            | tir::ExprKind::Repeat(..)
             | tir::ExprKind::Ref(..)
             | tir::ExprKind::Deref(..)
             | tir::ExprKind::Field(..)
+            | tir::ExprKind::Index(..)
             | tir::ExprKind::Call(..)
             | tir::ExprKind::Closure { .. }
             | tir::ExprKind::Adt { .. }
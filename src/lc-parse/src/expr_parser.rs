@@ -5,9 +5,10 @@ use lc_lex::*;
 const UNARY_OPS: [TokenKind; 4] =
     [TokenKind::Not, TokenKind::Minus, TokenKind::Star, TokenKind::And];
 const POSTFIX_OPS: [TokenKind; 3] = [TokenKind::Dot, TokenKind::OpenBracket, TokenKind::OpenParen];
-const CMP_OPS: [TokenKind; 2] = [TokenKind::Lt, TokenKind::Gt];
-const TERM_OPS: [TokenKind; 2] = [TokenKind::Plus, TokenKind::Minus];
-const FACTOR_OPS: [TokenKind; 2] = [TokenKind::Star, TokenKind::Slash];
+const CMP_OPS: [BinOp; 6] = [BinOp::Eq, BinOp::Neq, BinOp::Lt, BinOp::Gt, BinOp::Le, BinOp::Ge];
+const SHIFT_OPS: [BinOp; 2] = [BinOp::Shl, BinOp::Shr];
+const TERM_OPS: [BinOp; 2] = [BinOp::Add, BinOp::Sub];
+const FACTOR_OPS: [BinOp; 3] = [BinOp::Mul, BinOp::Div, BinOp::Rem];
 
 // expr parsers are written in increasing order of precedence
 
@@ -43,22 +44,90 @@ impl<'a> Parse<'a> for AssnExprParser {
     type Output = P<Expr>;
 
     fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
-        let mut expr = CmpExprParser.parse(parser)?;
-        while let Some(_eq) = parser.accept(TokenKind::Eq) {
-            let right = self.parse(parser)?;
-            expr = parser.mk_expr(expr.span.merge(right.span), ExprKind::Assign(expr, right));
+        let mut expr = OrOrExprParser.parse(parser)?;
+        loop {
+            if let Some(_eq) = parser.accept(TokenKind::Eq) {
+                let right = self.parse(parser)?;
+                expr = parser.mk_expr(expr.span.merge(right.span), ExprKind::Assign(expr, right));
+            } else if let Some((op, _)) = parser.accept_assign_op() {
+                let right = self.parse(parser)?;
+                let span = expr.span.merge(right.span);
+                expr = parser.mk_expr(span, ExprKind::AssignOp(op, expr, right));
+            } else {
+                break;
+            }
         }
         Ok(expr)
     }
 }
 
+struct OrOrExprParser;
+
+impl<'a> Parse<'a> for OrOrExprParser {
+    type Output = P<Expr>;
+
+    fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
+        LBinaryExprParser { ops: [BinOp::OrOr], inner: AndAndExprParser }.parse(parser)
+    }
+}
+
+struct AndAndExprParser;
+
+impl<'a> Parse<'a> for AndAndExprParser {
+    type Output = P<Expr>;
+
+    fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
+        LBinaryExprParser { ops: [BinOp::AndAnd], inner: CmpExprParser }.parse(parser)
+    }
+}
+
 struct CmpExprParser;
 
 impl<'a> Parse<'a> for CmpExprParser {
     type Output = P<Expr>;
 
     fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
-        LBinaryExprParser { ops: CMP_OPS, inner: TermExprParser }.parse(parser)
+        LBinaryExprParser { ops: CMP_OPS, inner: BitOrExprParser }.parse(parser)
+    }
+}
+
+struct BitOrExprParser;
+
+impl<'a> Parse<'a> for BitOrExprParser {
+    type Output = P<Expr>;
+
+    fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
+        LBinaryExprParser { ops: [BinOp::Or], inner: BitXorExprParser }.parse(parser)
+    }
+}
+
+struct BitXorExprParser;
+
+impl<'a> Parse<'a> for BitXorExprParser {
+    type Output = P<Expr>;
+
+    fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
+        LBinaryExprParser { ops: [BinOp::Xor], inner: BitAndExprParser }.parse(parser)
+    }
+}
+
+struct BitAndExprParser;
+
+impl<'a> Parse<'a> for BitAndExprParser {
+    type Output = P<Expr>;
+
+    fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
+        LBinaryExprParser { ops: [BinOp::And], inner: ShiftExprParser }.parse(parser)
+    }
+}
+
+struct ShiftExprParser;
+
+impl<'a> Parse<'a> for ShiftExprParser {
+    type Output = P<Expr>;
+
+    fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
+        LBinaryExprParser { ops: SHIFT_OPS, inner: TermExprParser }.parse(parser)
     }
 }
 
@@ -116,7 +185,12 @@ impl<'a> Parse<'a> for PostfixExprParser {
                     expr = parser.mk_expr(expr.span.merge(arg_span), ExprKind::Call(expr, args));
                 }
                 TokenKind::Dot => expr = FieldAccessParser { expr }.parse(parser)?,
-                TokenKind::OpenBracket => todo!(),
+                TokenKind::OpenBracket => {
+                    let index = ExprParser.parse(parser)?;
+                    let close_bracket = parser.expect(TokenKind::CloseBracket)?;
+                    let span = expr.span.merge(close_bracket.span);
+                    expr = parser.mk_expr(span, ExprKind::Index(expr, index));
+                }
                 _ => unreachable!(),
             }
         }
@@ -141,10 +215,12 @@ impl<'a> Parse<'a> for PrimaryExprParser {
                 let (span, elements) = tuple_parser.parse(parser)?;
                 Ok(parser.mk_expr(span, ExprKind::Tuple(elements)))
             }
-        } else if let Some((kind, span)) = parser.accept_literal() {
-            LiteralParser { kind, span }.parse(parser)
+        } else if let Some((kind, suffix_start, span)) = parser.accept_literal() {
+            LiteralParser { kind, suffix_start, span }.parse(parser)
         } else if let Some(ret_kw) = parser.accept(TokenKind::Return) {
             RetParser { ret_kw }.parse(parser)
+        } else if parser.is_path_start()? {
+            PathExprParser.parse(parser)
         } else if let Some(self_kw) = parser.accept(TokenKind::LSelf) {
             let segment = PathSegment {
                 id: parser.mk_id(),
@@ -153,8 +229,8 @@ impl<'a> Parse<'a> for PrimaryExprParser {
             };
             let path = parser.mk_path(self_kw.span, vec![segment]);
             Ok(parser.mk_expr(self_kw.span, ExprKind::Path(path)))
-        } else if parser.is_ident()?.is_some() {
-            PathExprParser.parse(parser)
+        } else if let Some(open_bracket) = parser.accept(TokenKind::OpenBracket) {
+            ArrayExprParser { open_bracket }.parse(parser)
         } else if let Some(tok) = parser.accept(TokenKind::False) {
             Ok(parser.mk_expr(tok.span, ExprKind::Lit(Lit::Bool(false))))
         } else if let Some(tok) = parser.accept(TokenKind::True) {
@@ -199,15 +275,14 @@ pub(super) struct LBinaryExprParser<Q, I> {
 
 impl<'a, Q, I> Parse<'a> for LBinaryExprParser<Q, I>
 where
-    I: IntoIterator<Item = TokenKind> + Copy,
+    I: IntoIterator<Item = BinOp> + Copy,
     Q: Parse<'a, Output = P<Expr>>,
 {
     type Output = P<Expr>;
 
     fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
         let mut expr = self.inner.parse(parser)?;
-        while let Some(t) = parser.accept_one_of(self.ops) {
-            let binop = BinOp::from(t);
+        while let Some((binop, _)) = parser.accept_binop(self.ops) {
             let right = self.inner.parse(parser)?;
             let span = expr.span.merge(right.span);
             expr = parser.mk_expr(span, ExprKind::Bin(binop, expr, right));
@@ -279,7 +354,7 @@ mod test {
             Box::new(Expr::new(
                 Span::new(ROOT_FILE_IDX, 4, 5),
                 NodeId::new(0),
-                ExprKind::Lit(Lit::Int(3)),
+                ExprKind::Lit(Lit::Int(3, LitIntTy::Unsuffixed)),
             ))
         );
     }
@@ -314,12 +389,12 @@ mod test {
                     Box::new(Expr::new(
                         Span::new(ROOT_FILE_IDX, 1, 2),
                         NodeId::new(0),
-                        ExprKind::Lit(Lit::Int(2)),
+                        ExprKind::Lit(Lit::Int(2, LitIntTy::Unsuffixed)),
                     )),
                     Box::new(Expr::new(
                         Span::new(ROOT_FILE_IDX, 4, 5),
                         NodeId::new(1),
-                        ExprKind::Lit(Lit::Int(3)),
+                        ExprKind::Lit(Lit::Int(3, LitIntTy::Unsuffixed)),
                     ))
                 ],),
             ))
@@ -334,7 +409,7 @@ mod test {
             Box::new(Expr::new(
                 Span::new(ROOT_FILE_IDX, 0, 1),
                 NodeId::new(0),
-                ExprKind::Lit(Lit::Int(2)),
+                ExprKind::Lit(Lit::Int(2, LitIntTy::Unsuffixed)),
             ))
         );
     }
@@ -352,12 +427,12 @@ mod test {
                     Box::new(Expr::new(
                         Span::new(ROOT_FILE_IDX, 0, 1),
                         NodeId::new(0),
-                        ExprKind::Lit(Lit::Int(2)),
+                        ExprKind::Lit(Lit::Int(2, LitIntTy::Unsuffixed)),
                     )),
                     Box::new(Expr::new(
                         Span::new(ROOT_FILE_IDX, 4, 5),
                         NodeId::new(1),
-                        ExprKind::Lit(Lit::Int(3)),
+                        ExprKind::Lit(Lit::Int(3, LitIntTy::Unsuffixed)),
                     )),
                 ),
             ))
@@ -397,7 +472,7 @@ mod test {
                     Box::new(Expr::new(
                         Span::new(ROOT_FILE_IDX, 0, 1),
                         NodeId::new(0),
-                        ExprKind::Lit(Lit::Int(2)),
+                        ExprKind::Lit(Lit::Int(2, LitIntTy::Unsuffixed)),
                     )),
                     Box::new(Expr::new(
                         Span::new(ROOT_FILE_IDX, 4, 9),
@@ -407,12 +482,12 @@ mod test {
                             Box::new(Expr::new(
                                 Span::new(ROOT_FILE_IDX, 4, 5),
                                 NodeId::new(1),
-                                ExprKind::Lit(Lit::Int(3)),
+                                ExprKind::Lit(Lit::Int(3, LitIntTy::Unsuffixed)),
                             )),
                             Box::new(Expr::new(
                                 Span::new(ROOT_FILE_IDX, 8, 9),
                                 NodeId::new(2),
-                                ExprKind::Lit(Lit::Int(4)),
+                                ExprKind::Lit(Lit::Int(4, LitIntTy::Unsuffixed)),
                             )),
                         ),
                     )),
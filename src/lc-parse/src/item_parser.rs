@@ -1,7 +1,7 @@
 use crate::*;
 use lc_ast::*;
 use lc_lex::TokenKind;
-use lc_span::{with_source_map, ModuleKind};
+use lc_span::{sym, with_source_map, ModuleKind};
 use std::convert::TryFrom;
 
 const ITEM_KEYWORDS: [TokenKind; 11] = [
@@ -18,11 +18,50 @@ const ITEM_KEYWORDS: [TokenKind; 11] = [
     TokenKind::Trait,
 ];
 
+/// #[<ident>(<token-trees>)?] or #[<ident> = "<value>"]
+pub struct AttributeParser;
+
+impl<'a> Parse<'a> for AttributeParser {
+    type Output = Attribute;
+
+    fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
+        let pound = parser.expect(TokenKind::Pound)?;
+        parser.expect(TokenKind::OpenBracket)?;
+        let ident = parser.expect_ident()?;
+        let args = match parser.peek().kind {
+            TokenKind::OpenParen => AttrArgs::Delimited(parser.parse_tt_group()),
+            TokenKind::Eq => {
+                parser.bump();
+                AttrArgs::Eq(parser.expect_str()?)
+            }
+            _ => AttrArgs::Empty,
+        };
+        let close_bracket = parser.expect(TokenKind::CloseBracket)?;
+        Ok(Attribute { span: pound.span.merge(close_bracket.span), ident, args })
+    }
+}
+
 pub struct ItemParser;
 
 impl<'a> Parse<'a> for ItemParser {
     type Output = P<Item>;
 
+    fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
+        let attrs = parser.parse_outer_attributes()?;
+        let mut item = ItemKindParser { attrs: &attrs }.parse(parser)?;
+        item.attrs = attrs;
+        Ok(item)
+    }
+}
+
+/// parses an item following its attributes
+struct ItemKindParser<'p> {
+    attrs: &'p [Attribute],
+}
+
+impl<'a> Parse<'a> for ItemKindParser<'_> {
+    type Output = P<Item>;
+
     fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
         let vis = VisibilityParser.parse(parser)?;
 
@@ -37,12 +76,22 @@ impl<'a> Parse<'a> for ItemParser {
 
         if let Some(mod_kw) = parser.accept(TokenKind::Mod) {
             let name = parser.expect_lident()?;
-            parser.expect(TokenKind::Semi)?;
-            let span = mod_kw.span.merge(name.span);
-            let module = SubModuleParser { span, name }.parse(parser)?;
+            let module = if let Some(open_brace) = parser.accept(TokenKind::OpenBrace) {
+                InlineModuleParser { open_brace, name }.parse(parser)?
+            } else {
+                parser.expect(TokenKind::Semi)?;
+                let span = mod_kw.span.merge(name.span);
+                let path = self.parse_path_attr(parser)?;
+                SubModuleParser { span, name, path }.parse(parser)?
+            };
             return Ok(parser.mk_item(vis.span.merge(name.span), vis, name, ItemKind::Mod(module)));
         }
 
+        if parser.is_macro_invocation() {
+            let err = ParseError::MacroInvocationOutsideExpr("an item");
+            return Err(parser.build_err(parser.peek().span, err));
+        }
+
         let kw = parser.expect_one_of(ITEM_KEYWORDS)?;
         let ident = parser.expect_ident()?;
         let (kind_span, kind) = parser.with_span(
@@ -64,6 +113,21 @@ impl<'a> Parse<'a> for ItemParser {
     }
 }
 
+impl ItemKindParser<'_> {
+    /// the file given by a `#[path = "<file>"]` attribute, if any
+    fn parse_path_attr<'a>(&self, parser: &Parser<'a>) -> ParseResult<'a, Option<Ident>> {
+        let attr = match self.attrs.iter().find(|attr| attr.ident.symbol == sym::path) {
+            Some(attr) => attr,
+            None => return Ok(None),
+        };
+        match attr.args {
+            AttrArgs::Eq(path) => Ok(Some(path)),
+            AttrArgs::Empty | AttrArgs::Delimited(..) =>
+                Err(parser.build_err(attr.span, ParseError::MalformedPathAttribute)),
+        }
+    }
+}
+
 pub struct ModuleParser;
 
 impl<'a> Parse<'a> for ModuleParser {
@@ -86,36 +150,68 @@ impl<'a> Parse<'a> for ModuleParser {
     }
 }
 
+/// mod <name> { <item>* }
+pub struct InlineModuleParser {
+    open_brace: Token,
+    name: Ident,
+}
+
+impl<'a> Parse<'a> for InlineModuleParser {
+    type Output = Module;
+
+    fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
+        parser.inline_modules.push(self.name.symbol);
+        let items = parse_fn(|parser| {
+            let mut items = vec![];
+            while parser.accept(TokenKind::CloseBrace).is_none() {
+                items.push(ItemParser.parse(parser)?);
+            }
+            Ok(items)
+        })
+        .parse(parser);
+        parser.inline_modules.pop();
+        let span = self.open_brace.span.merge(parser.prev().span);
+        Ok(Module { span, items: items? })
+    }
+}
+
+/// a module declared as `mod <name>;` whose items are in a separate file
 pub struct SubModuleParser {
     /// the span of the declaration
     pub(crate) span: Span,
     /// the name of the module
     pub(crate) name: Ident,
+    /// the path of the module file given by a `#[path]` attribute (relative to the directory
+    /// of the declaring module)
+    pub(crate) path: Option<Ident>,
 }
 
 impl<'a> Parse<'a> for SubModuleParser {
     type Output = Module;
 
     fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
-        // disallow a `file module` having submodules
-        if with_source_map(|map| {
-            map.get_opt(parser.file).map(|src| src.file.kind) == Some(ModuleKind::File)
-        }) {
+        // disallow a `file module` having submodules (unless the file is given explicitly)
+        if self.path.is_none()
+            && with_source_map(|map| {
+                map.get_opt(parser.file).map(|src| src.file.kind) == Some(ModuleKind::File)
+            })
+        {
             return Err(
                 parser.build_err(self.span, ParseError::FileModuleWithSubmodules(self.name))
             );
         }
 
-        let module_file = match with_source_map(|map| map.add_module(parser.file, *self.name)) {
-            Some(file) => file,
-            None =>
-                return Err(parser.build_err(
-                    self.span,
-                    ParseError::UnresolvedModule(
-                        with_source_map(|map| map.dir_of(parser.file).to_path_buf()),
-                        self.name,
-                    ),
-                )),
+        let dir = with_source_map(|map| map.module_dir(parser.file, &parser.inline_modules));
+        let module_file = match self.path {
+            Some(path) => {
+                let path = dir.join(path.as_str());
+                with_source_map(|map| map.add_module_at_path(&path)).ok_or_else(|| {
+                    parser.build_err(self.span, ParseError::ModuleFileNotFound(path))
+                })?
+            }
+            None => with_source_map(|map| map.add_module(&dir, *self.name)).ok_or_else(|| {
+                parser.build_err(self.span, ParseError::UnresolvedModule(dir, self.name))
+            })?,
         };
 
         with_source_map(|map| {
@@ -134,14 +230,53 @@ impl<'a> Parse<'a> for UseParser {
     type Output = P<Item>;
 
     fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
-        let path = parser.parse_module_path()?;
-        let span = self.vis.span.merge(path.span);
-        let kind = ItemKind::Use(path);
+        let tree = UseTreeParser.parse(parser)?;
+        let span = self.vis.span.merge(tree.span);
+        let kind = ItemKind::Use(tree);
         parser.expect(TokenKind::Semi)?;
         Ok(parser.mk_item(span, self.vis, Ident::empty(), kind))
     }
 }
 
+/// `a::b`, `a::b as c`, `a::*` or `a::{<use-tree>, ...}`
+pub struct UseTreeParser;
+
+impl<'a> Parse<'a> for UseTreeParser {
+    type Output = UseTree;
+
+    fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
+        let lo = parser.empty_span();
+        let mut segments = vec![];
+        let kind = loop {
+            if parser.accept(TokenKind::Star).is_some() {
+                break UseTreeKind::Glob;
+            } else if parser.accept(TokenKind::OpenBrace).is_some() {
+                let trees =
+                    PunctuatedParser { inner: UseTreeParser, separator: TokenKind::Comma }
+                        .parse(parser)?;
+                parser.expect(TokenKind::CloseBrace)?;
+                break UseTreeKind::Nested(trees);
+            }
+            let ident = parser.expect_path_segment_ident()?;
+            segments.push(PathSegment { ident, id: parser.mk_id(), args: None });
+            if parser.accept(TokenKind::Dcolon).is_none() {
+                let rename = match parser.accept(TokenKind::As) {
+                    Some(_as_kw) => Some(parser.expect_ident()?),
+                    None => None,
+                };
+                break UseTreeKind::Simple(rename);
+            }
+        };
+        let span = lo.merge(parser.empty_span());
+        let prefix_span = match (segments.first(), segments.last()) {
+            (Some(fst), Some(last)) => fst.ident.span.merge(last.ident.span),
+            _ => lo,
+        };
+        let prefix = parser.mk_path(prefix_span, segments);
+        Ok(UseTree { span, prefix, kind })
+    }
+}
+
 pub struct ExternParser {
     vis: Visibility,
 }
@@ -157,9 +292,10 @@ impl<'a> Parse<'a> for ExternParser {
             if let Some(close_brace) = parser.accept(TokenKind::CloseBrace) {
                 break close_brace;
             }
-            let box Item { span, id, kind, vis, ident } = parser.parse_item()?;
+            let box Item { span, id, attrs, kind, vis, ident } = parser.parse_item()?;
             match ForeignItemKind::try_from(kind) {
-                Ok(kind) => foreign_items.push(Box::new(Item { span, id, vis, ident, kind })),
+                Ok(kind) =>
+                    foreign_items.push(Box::new(Item { span, id, attrs, vis, ident, kind })),
                 Err(kind) => parser.build_err(span, ParseError::InvalidForeignItem(kind)).emit(),
             };
         };
@@ -171,7 +307,7 @@ impl<'a> Parse<'a> for ExternParser {
     }
 }
 
-/// <vis> trait <ident><generics>? {
+/// <vis> trait <ident><generics>? <where-clause>? {
 ///     <trait-item>
 /// }
 pub struct TraitParser;
@@ -180,16 +316,17 @@ impl<'a> Parse<'a> for TraitParser {
     type Output = ItemKind;
 
     fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
+        let mut generics = parser.parse_generics()?;
+        parser.parse_where_clause(&mut generics)?;
         parser.expect(TokenKind::OpenBrace)?;
-        let generics = parser.parse_generics()?;
         let items = ItemParser
             .many()
             .parse(parser)?
             .into_iter()
             .filter_map(|item| {
-                let Item { span, id, vis, ident, .. } = *item;
-                match AssocItemKind::try_from(item.kind) {
-                    Ok(kind) => Some(Box::new(Item { span, id, vis, ident, kind })),
+                let Item { span, id, attrs, vis, ident, kind } = *item;
+                match AssocItemKind::try_from(kind) {
+                    Ok(kind) => Some(Box::new(Item { span, id, attrs, vis, ident, kind })),
                     Err(kind) => {
                         parser.build_err(span, ParseError::InvalidTraitItem(kind)).emit();
                         None
@@ -209,7 +346,7 @@ impl<'a> Parse<'a> for ImplParser {
     type Output = P<Item>;
 
     fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
-        let generics = parser.parse_generics()?;
+        let mut generics = parser.parse_generics()?;
         let mut trait_path = Some(parser.parse_type_path()?);
         let self_ty = if parser.accept(TokenKind::For).is_some() {
             parser.parse_ty(false)
@@ -218,15 +355,16 @@ impl<'a> Parse<'a> for ImplParser {
             let ty_path = trait_path.take().unwrap();
             parser.mk_ty(ty_path.span, TyKind::Path(ty_path))
         };
+        parser.parse_where_clause(&mut generics)?;
         parser.expect(TokenKind::OpenBrace)?;
         let mut items = vec![];
         let close_brace = loop {
             if let Some(close_brace) = parser.accept(TokenKind::CloseBrace) {
                 break close_brace;
             }
-            let box Item { span, id, kind, vis, ident } = parser.parse_item()?;
+            let box Item { span, id, attrs, kind, vis, ident } = parser.parse_item()?;
             match AssocItemKind::try_from(kind) {
-                Ok(kind) => items.push(Box::new(Item { span, id, vis, ident, kind })),
+                Ok(kind) => items.push(Box::new(Item { span, id, attrs, vis, ident, kind })),
                 Err(kind) => parser.build_err(span, ParseError::InvalidImplItem(kind)).emit(),
             };
         };
@@ -241,11 +379,10 @@ pub struct TypeAliasParser;
 impl<'a> Parse<'a> for TypeAliasParser {
     type Output = ItemKind;
 
-    /// type <ident> "<" <generics> ">" = <type>
+    /// type <ident> "<" <generics> ">" (= <type>)?
     fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
         let generics = parser.parse_generics()?;
-        parser.expect(TokenKind::Eq)?;
-        let ty = parser.parse_ty(false);
+        let ty = parser.accept(TokenKind::Eq).map(|_| parser.parse_ty(false));
         parser.expect(TokenKind::Semi)?;
         Ok(ItemKind::TypeAlias(generics, ty))
     }
@@ -264,6 +401,7 @@ impl<'a> Parse<'a> for FieldDeclParser {
     type Output = FieldDecl;
 
     fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
+        let attrs = parser.parse_outer_attributes()?;
         let vis = VisibilityParser.parse(parser)?;
         let ident = match self.form {
             FieldForm::Struct => {
@@ -275,7 +413,7 @@ impl<'a> Parse<'a> for FieldDeclParser {
         };
         let ty = parser.parse_ty(false);
         let span = vis.span.merge(ty.span);
-        Ok(FieldDecl { id: parser.mk_id(), span, vis, ident, ty })
+        Ok(FieldDecl { id: parser.mk_id(), span, attrs, vis, ident, ty })
     }
 }
 
@@ -322,7 +460,8 @@ impl<'a> Parse<'a> for StructDeclParser {
     type Output = ItemKind;
 
     fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
-        let generics = GenericsParser.parse(parser)?;
+        let mut generics = GenericsParser.parse(parser)?;
+        parser.parse_where_clause(&mut generics)?;
         let kind = VariantKindParser.parse(parser)?;
         if let VariantKind::Tuple(_) | VariantKind::Unit = kind {
             parser.expect(TokenKind::Semi)?;
@@ -337,7 +476,8 @@ impl<'a> Parse<'a> for EnumParser {
     type Output = ItemKind;
 
     fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
-        let generics = GenericsParser.parse(parser)?;
+        let mut generics = GenericsParser.parse(parser)?;
+        parser.parse_where_clause(&mut generics)?;
         parser.expect(TokenKind::OpenBrace)?;
         let variants =
             PunctuatedParser { inner: VariantParser, separator: TokenKind::Comma }.parse(parser)?;
@@ -353,8 +493,9 @@ impl<'a> Parse<'a> for FnParser {
 
     /// assumes that { <vis> fn <ident> } has already been parsed
     fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
-        let generics = GenericsParser.parse(parser)?;
+        let mut generics = GenericsParser.parse(parser)?;
         let sig = FnSigParser { require_type_annotations: true }.parse(parser)?;
+        parser.parse_where_clause(&mut generics)?;
         let block = if let Some(open_brace) = parser.accept(TokenKind::OpenBrace) {
             Some(parser.parse_block(open_brace)?)
         } else {
@@ -378,6 +519,20 @@ mod tests {
         let _prog = parse!("fn test<T, U>() -> bool { false }");
     }
 
+    #[test]
+    fn parse_bounds() {
+        let _prog = parse!("fn test<T: Foo, U: Foo + Bar<T>>() -> bool { false }");
+        let _prog = parse!("struct S<T: Foo = int> { t: T }");
+    }
+
+    #[test]
+    fn parse_where_clause() {
+        let _prog = parse!("fn test<T>() -> bool where T: Foo + Bar, int: Foo<T> { false }");
+        let _prog = parse!("struct S<T> where T: Foo, { t: T }");
+        let _prog = parse!("impl<T> Foo for S<T> where T: Foo {}");
+        let _prog = parse!("trait Foo<T> where T: Bar {}");
+    }
+
     #[test]
     fn parse_enum() {
         let _prog = parse!("enum B { T, F, }");
@@ -20,12 +20,15 @@ mod ty_parser;
 mod validate;
 
 pub use parser::Parser;
+pub use prog_parser::PRELUDE_SRC;
 
 use expr_parser::*;
 use item_parser::*;
 use lc_ast::*;
-use lc_lex::{Base, LiteralKind, Token, TokenGroup, TokenKind, TokenTree};
-use lc_span::{kw, Span};
+use lc_error::DiagnosticBuilder;
+use lc_lex::{unescape_char, unescape_str, Base, EscapeError, LiteralKind};
+use lc_lex::{Token, TokenGroup, TokenKind, TokenTree};
+use lc_span::{kw, Span, Symbol};
 use macro_parser::*;
 use parse_error::{ParseError, ParseResult};
 use pattern_parser::*;
@@ -162,7 +165,7 @@ where
         }
     }
 }
-/// parses an ident for a field access
+/// parses an ident for a field access or a method call
 pub struct FieldAccessParser {
     pub expr: P<Expr>,
 }
@@ -172,8 +175,16 @@ impl<'a> Parse<'a> for FieldAccessParser {
 
     fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
         let ident = if let Some(ident) = parser.accept_lident() {
+            // `receiver.method(args)`
+            if parser.accept(TokenKind::OpenParen).is_some() {
+                let (arg_span, args) =
+                    TupleParser { inner: ExprParser }.spanned(true).parse(parser)?;
+                let span = self.expr.span.merge(arg_span);
+                let receiver = std::mem::take(&mut self.expr);
+                return Ok(parser.mk_expr(span, ExprKind::MethodCall(receiver, ident, args)));
+            }
             ident
-        } else if let Some((kind, span)) = parser.accept_literal() {
+        } else if let Some((kind, _, span)) = parser.accept_literal() {
             // tuple field access can have integer after the dot
             // `tuple.0`
             match kind {
@@ -577,7 +588,7 @@ impl<'a> Parse<'a> for PathSegmentParser {
     type Output = PathSegment;
 
     fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
-        let ident = parser.expect_ident()?;
+        let ident = parser.expect_path_segment_ident()?;
         // TODO this does not deal with the where there is a preceding ::
         // and also does not handle the errors for expr position paths where :: is required
         let args = parser.parse_generic_args(self.kind)?;
@@ -705,6 +716,8 @@ impl<'a> Parse<'a> for ClosureParser {
 
 pub struct LiteralParser {
     pub kind: LiteralKind,
+    /// the offset of the literal's suffix from the start of the literal
+    pub suffix_start: usize,
     pub span: Span,
 }
 
@@ -713,33 +726,194 @@ impl<'a> Parse<'a> for LiteralParser {
 
     fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
         let string = self.span.to_string();
+        let (string, suffix) = string.split_at(self.suffix_start);
         let literal = match self.kind {
             LiteralKind::Float { base, .. } => {
                 if base != Base::Decimal {
-                    panic!("only decimal float literals are supported")
+                    return Err(parser.build_err(self.span, ParseError::NonDecimalFloatLiteral));
+                }
+                let value = string
+                    .replace('_', "")
+                    .parse()
+                    .map_err(|_| parser.build_err(self.span, ParseError::InvalidFloatLiteral))?;
+                match suffix {
+                    "" => Lit::Float(value, LitFloatTy::Unsuffixed),
+                    _ => {
+                        let float_ty = self.float_suffix(parser, suffix)?;
+                        Lit::Float(value, LitFloatTy::Suffixed(float_ty))
+                    }
                 }
-                Lit::Float(string.parse().unwrap())
             }
-            LiteralKind::Str { terminated: _ } => todo!(),
-            LiteralKind::Int { base, .. } =>
-                Lit::Int(i64::from_str_radix(&string, base as u32).unwrap()),
+            LiteralKind::Str { .. } | LiteralKind::Char { .. } if !suffix.is_empty() =>
+                return Err(self.suffix_err(parser, suffix, "textual")),
+            LiteralKind::Str { terminated } => {
+                if !terminated {
+                    return Err(parser.build_err(self.span, ParseError::UnterminatedStringLiteral));
+                }
+                match unescape_str(self.contents(string)) {
+                    Ok(unescaped) => Lit::Str(Symbol::intern(&unescaped)),
+                    Err((offset, err)) => return Err(self.escape_err(parser, offset, err)),
+                }
+            }
+            LiteralKind::Char { terminated } => {
+                if !terminated {
+                    return Err(parser.build_err(self.span, ParseError::UnterminatedCharLiteral));
+                }
+                match unescape_char(self.contents(string)) {
+                    Ok(c) => Lit::Char(c),
+                    Err((offset, err)) => return Err(self.escape_err(parser, offset, err)),
+                }
+            }
+            LiteralKind::Int { base, .. } => self.parse_int(parser, string, base, suffix)?,
             _ => todo!(),
         };
         Ok(parser.mk_expr(self.span, ExprKind::Lit(literal)))
     }
 }
+
+impl LiteralParser {
+    fn parse_int<'a>(
+        &self,
+        parser: &Parser<'a>,
+        string: &str,
+        base: Base,
+        suffix: &str,
+    ) -> ParseResult<'a, Lit> {
+        // strip the `0x`, `0o`, or `0b` prefix
+        let digits = if base == Base::Decimal { string } else { &string[2..] };
+        let value = u64::from_str_radix(&digits.replace('_', ""), base as u32)
+            .map_err(|_| parser.build_err(self.span, ParseError::IntLiteralTooLarge))?;
+        // integer literals with a float suffix such as `2f32` are float literals
+        if base == Base::Decimal && suffix.starts_with('f') {
+            let float_ty = self.float_suffix(parser, suffix)?;
+            return Ok(Lit::Float(value as f64, LitFloatTy::Suffixed(float_ty)));
+        }
+        let (lit_int_ty, max) = match suffix {
+            "" => return Ok(Lit::Int(value, LitIntTy::Unsuffixed)),
+            "i8" => (LitIntTy::Signed(IntTy::I8), i8::MAX as u64),
+            "i16" => (LitIntTy::Signed(IntTy::I16), i16::MAX as u64),
+            "i32" => (LitIntTy::Signed(IntTy::I32), i32::MAX as u64),
+            "i64" => (LitIntTy::Signed(IntTy::I64), i64::MAX as u64),
+            "u8" => (LitIntTy::Unsigned(UintTy::U8), u8::MAX as u64),
+            "u16" => (LitIntTy::Unsigned(UintTy::U16), u16::MAX as u64),
+            "u32" => (LitIntTy::Unsigned(UintTy::U32), u32::MAX as u64),
+            "u64" => (LitIntTy::Unsigned(UintTy::U64), u64::MAX),
+            _ => return Err(self.suffix_err(parser, suffix, "integer")),
+        };
+        // signed literals may be one larger than the maximum to allow for things like `-128i8`
+        // (whether the literal is actually negated is checked during typechecking)
+        let max = if let LitIntTy::Signed(..) = lit_int_ty { max + 1 } else { max };
+        if value > max {
+            return Err(parser.build_err(self.span, ParseError::LiteralOutOfRange(lit_int_ty)));
+        }
+        Ok(Lit::Int(value, lit_int_ty))
+    }
+
+    fn float_suffix<'a>(&self, parser: &Parser<'a>, suffix: &str) -> ParseResult<'a, FloatTy> {
+        match suffix {
+            "f32" => Ok(FloatTy::F32),
+            "f64" => Ok(FloatTy::F64),
+            _ => Err(self.suffix_err(parser, suffix, "float")),
+        }
+    }
+
+    fn suffix_err<'a>(
+        &self,
+        parser: &Parser<'a>,
+        suffix: &str,
+        descr: &'static str,
+    ) -> DiagnosticBuilder<'a> {
+        let start = self.span.start().to_usize() + self.suffix_start;
+        let span = parser.mk_span(start, self.span.end().to_usize());
+        parser.build_err(span, ParseError::InvalidLiteralSuffix(suffix.to_owned(), descr))
+    }
+
+    /// the span of a quoted literal includes the surrounding quotes, so we just chop them off
+    fn contents<'s>(&self, string: &'s str) -> &'s str {
+        &string[1..string.len() - 1]
+    }
+
+    /// reports an invalid escape at byte `offset` within the contents of the literal
+    fn escape_err<'a>(
+        &self,
+        parser: &Parser<'a>,
+        offset: usize,
+        err: EscapeError,
+    ) -> DiagnosticBuilder<'a> {
+        // offset by one to account for the opening quote
+        let start = self.span.start().to_usize() + 1 + offset;
+        parser.build_err(parser.mk_span(start, start + 1), ParseError::InvalidEscape(err))
+    }
+}
+/// parses the length of an array type or a repeat expression
+/// for now, the length must be an unsuffixed integer literal
+pub struct ArrayLenParser;
+
+impl<'a> Parse<'a> for ArrayLenParser {
+    type Output = usize;
+
+    fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
+        let (kind, suffix_start, span) = parser.expect_literal()?;
+        let expr = LiteralParser { kind, suffix_start, span }.parse(parser)?;
+        match expr.kind {
+            ExprKind::Lit(Lit::Int(n, LitIntTy::Unsuffixed)) => Ok(n as usize),
+            _ => Err(parser.build_err(span, ParseError::InvalidArrayLen)),
+        }
+    }
+}
+
+/// <array> = '[' ( <expr> , )* <expr>? ']' | '[' <expr> ; <len> ']'
+pub struct ArrayExprParser {
+    pub open_bracket: Token,
+}
+
+impl<'a> Parse<'a> for ArrayExprParser {
+    type Output = P<Expr>;
+
+    fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
+        if let Some(close_bracket) = parser.accept(TokenKind::CloseBracket) {
+            let span = self.open_bracket.span.merge(close_bracket.span);
+            return Ok(parser.mk_expr(span, ExprKind::Array(vec![])));
+        }
+
+        let first = ExprParser.parse(parser)?;
+        if parser.accept(TokenKind::Semi).is_some() {
+            let len = ArrayLenParser.parse(parser)?;
+            let close_bracket = parser.expect(TokenKind::CloseBracket)?;
+            let span = self.open_bracket.span.merge(close_bracket.span);
+            return Ok(parser.mk_expr(span, ExprKind::Repeat(first, len)));
+        }
+
+        let mut xs = vec![first];
+        let close_bracket = loop {
+            if let Some(close_bracket) = parser.accept(TokenKind::CloseBracket) {
+                break close_bracket;
+            }
+            parser.expect(TokenKind::Comma)?;
+            // allow a trailing comma
+            if let Some(close_bracket) = parser.accept(TokenKind::CloseBracket) {
+                break close_bracket;
+            }
+            xs.push(ExprParser.parse(parser)?);
+        };
+        let span = self.open_bracket.span.merge(close_bracket.span);
+        Ok(parser.mk_expr(span, ExprKind::Array(xs)))
+    }
+}
+
 pub struct ArmParser;
 
 impl<'a> Parse<'a> for ArmParser {
     type Output = Arm;
 
     fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
+        let attrs = parser.parse_outer_attributes()?;
         let pat = parser.parse_pattern()?;
         let guard = parser.accept(TokenKind::If).map(|_| parser.parse_expr());
         parser.expect(TokenKind::RFArrow)?;
         let body = parser.parse_expr();
         let span = pat.span.merge(body.span);
-        Ok(Arm { id: parser.mk_id(), span, pat, body, guard })
+        Ok(Arm { id: parser.mk_id(), span, attrs, pat, body, guard })
     }
 }
 
@@ -817,7 +991,8 @@ impl<'a> Parse<'a> for GenericsParser {
         } else {
             vec![]
         };
-        Ok(Generics { params, span })
+        // the `where` clause is parsed separately as its position depends on the item
+        Ok(Generics { params, span, predicates: vec![] })
     }
 }
 
@@ -826,10 +1001,61 @@ pub struct TyParamParser;
 impl<'a> Parse<'a> for TyParamParser {
     type Output = TyParam;
 
+    /// <ident> (: <bounds>)? (= <ty>)?
     fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
         let ident = parser.expect_ident()?;
+        let bounds = match parser.accept(TokenKind::Colon) {
+            Some(_) => BoundsParser.parse(parser)?,
+            None => vec![],
+        };
         let default = parser.accept(TokenKind::Eq).map(|_| parser.parse_ty(false));
-        // eventually parse bounds here
-        Ok(TyParam { span: ident.span, id: parser.mk_id(), ident, default })
+        Ok(TyParam { span: ident.span, id: parser.mk_id(), ident, bounds, default })
+    }
+}
+
+/// <path> (+ <path>)*
+pub struct BoundsParser;
+
+impl<'a> Parse<'a> for BoundsParser {
+    type Output = Vec<Path>;
+
+    fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
+        let mut bounds = vec![parser.parse_type_path()?];
+        while parser.accept(TokenKind::Plus).is_some() {
+            bounds.push(parser.parse_type_path()?);
+        }
+        Ok(bounds)
+    }
+}
+
+/// parses an optional `where` clause into the predicates of `generics`
+/// where (<ty>: <bounds>),* ,?
+pub struct WhereClauseParser<'g> {
+    pub generics: &'g mut Generics,
+}
+
+impl<'a, 'g> Parse<'a> for WhereClauseParser<'g> {
+    type Output = ();
+
+    fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
+        let where_kw = match parser.accept(TokenKind::Where) {
+            Some(where_kw) => where_kw,
+            None => return Ok(()),
+        };
+        self.generics.span = self.generics.span.merge(where_kw.span);
+        loop {
+            let ty = parser.parse_ty(false);
+            parser.expect(TokenKind::Colon)?;
+            let bounds = BoundsParser.parse(parser)?;
+            let span = ty.span.merge(bounds.last().unwrap().span);
+            self.generics.span = self.generics.span.merge(span);
+            self.generics.predicates.push(WherePredicate { span, ty, bounds });
+            // allow a trailing comma before the item body
+            if parser.accept(TokenKind::Comma).is_none()
+                || matches!(parser.peek().kind, TokenKind::OpenBrace | TokenKind::Semi)
+            {
+                break Ok(());
+            }
+        }
     }
 }
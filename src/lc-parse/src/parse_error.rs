@@ -1,6 +1,6 @@
-use lc_ast::{Ident, ItemKind};
+use lc_ast::{Ident, ItemKind, LitIntTy};
 use lc_error::DiagnosticBuilder;
-use lc_lex::{DelimiterKind, Token, TokenKind};
+use lc_lex::{DelimiterKind, EscapeError, Token, TokenKind};
 use lc_span::Symbol;
 use std::path::PathBuf;
 use thiserror::Error;
@@ -25,6 +25,10 @@ pub enum ParseError {
     UnresolvedModule(PathBuf, Ident),
     #[error("modules declared as a file cannot have submodules")]
     FileModuleWithSubmodules(Ident),
+    #[error("module file `{}` not found", .0.display())]
+    ModuleFileNotFound(PathBuf),
+    #[error("malformed `path` attribute\nexpected `#[path = \"<file>\"]`")]
+    MalformedPathAttribute,
     #[error("expected uppercase identifier, found `{0}`")]
     ExpectUppercaseIdentifier(Symbol),
     #[error("expected lowercase identifier, found `{0}`")]
@@ -33,6 +37,8 @@ pub enum ParseError {
     ExpectedLiteral(TokenKind),
     #[error("unexpected <eof>")]
     Eof,
+    #[error("unexpected token `{}`", .0.kind)]
+    UnexpectedToken(Token),
     #[error("function signature requires explicit type annotations")]
     RequireTypeAnnotations,
     #[error("expected semicolon after expression statement")]
@@ -47,10 +53,28 @@ pub enum ParseError {
     ElidedTypeNotAllowedInThisContext,
     #[error("unterminated string literal")]
     UnterminatedStringLiteral,
+    #[error("unterminated character literal")]
+    UnterminatedCharLiteral,
+    #[error("{0}")]
+    InvalidEscape(EscapeError),
+    #[error("invalid suffix `{0}` for {1} literal")]
+    InvalidLiteralSuffix(String, &'static str),
+    #[error("integer literal is too large")]
+    IntLiteralTooLarge,
+    #[error("invalid float literal")]
+    InvalidFloatLiteral,
+    #[error("only decimal float literals are supported")]
+    NonDecimalFloatLiteral,
+    #[error("array length must be an unsuffixed integer literal")]
+    InvalidArrayLen,
+    #[error("literal out of range for `{0}`")]
+    LiteralOutOfRange(LitIntTy),
     #[error("missing fragment specifier")]
     MissingFragmentSpecifier,
     #[error("expected token `{0}` to close group, found `{1}`")]
     MismatchedTokenTreeDelimiter(TokenKind, TokenKind),
     #[error("unmatched opening delimiter `{0}`")]
     UnmatchedOpenTokenTreeDelimiter(DelimiterKind),
+    #[error("macro invocations are only supported in expression position, not as {0}")]
+    MacroInvocationOutsideExpr(&'static str),
 }
@@ -19,6 +19,8 @@ pub struct Parser<'a> {
 /// parser for a single source file
 pub struct FileParser {
     pub(crate) file: FileIdx,
+    /// the inline modules (outermost first) enclosing the current position within the file
+    pub(crate) inline_modules: Vec<Symbol>,
     tokens: Vec<Token>,
     idx: usize,
 }
@@ -26,7 +28,18 @@ pub struct FileParser {
 impl FileParser {
     pub fn new(file: FileIdx) -> Self {
         let tokens = Lexer::new().lex(file).collect();
-        Self { file, tokens, idx: 0 }
+        Self { file, inline_modules: vec![], tokens, idx: 0 }
+    }
+
+    /// creates a parser over an existing sequence of tokens (i.e. the output of a macro expansion)
+    /// `file` is the file that the tokens are considered to belong to
+    pub fn from_tokens(file: FileIdx, mut tokens: Vec<Token>) -> Self {
+        let eof_span = tokens.last().map_or_else(Span::default, |token| {
+            let end = token.span.end();
+            Span::new(token.span.file, end, end)
+        });
+        tokens.push(Token { span: eof_span, kind: TokenKind::Eof });
+        Self { file, inline_modules: vec![], tokens, idx: 0 }
     }
 }
 
@@ -45,10 +58,24 @@ impl<'a> Parser<'a> {
     }
 
     pub(crate) fn with_file<R>(&mut self, file: FileIdx, f: impl FnOnce(&mut Self) -> R) -> R {
-        let fparser = self.fparser.take();
-        self.fparser = Some(FileParser::new(file));
+        self.with_file_parser(FileParser::new(file), f)
+    }
+
+    /// runs `f` with the parser reading from `tokens` rather than from a source file
+    /// the node id counter is shared so any nodes created will not clash with existing ones
+    pub fn with_tokens<R>(
+        &mut self,
+        file: FileIdx,
+        tokens: Vec<Token>,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        self.with_file_parser(FileParser::from_tokens(file, tokens), f)
+    }
+
+    fn with_file_parser<R>(&mut self, fparser: FileParser, f: impl FnOnce(&mut Self) -> R) -> R {
+        let prev = self.fparser.replace(fparser);
         let ret = f(self);
-        self.fparser = fparser;
+        self.fparser = prev;
         ret
     }
 
@@ -65,7 +92,9 @@ impl<'a> Parser<'a> {
     }
 
     pub fn mk_span(&self, start: impl SpanIdx, end: impl SpanIdx) -> Span {
-        Span::new(self.file, start, end)
+        // tokens produced by macro expansion are not necessarily in source order
+        let (start, end) = (start.into(), end.into());
+        Span::new(self.file, start.min(end), start.max(end))
     }
 
     pub fn build_err(&self, span: impl Into<MultiSpan>, err: impl Error) -> DiagnosticBuilder<'a> {
@@ -106,13 +135,12 @@ impl<'a> Parser<'a> {
         mut parser: impl Parse<'a, Output = R>,
         include_prev: bool,
     ) -> ParseResult<'a, (Span, R)> {
-        let lo = if include_prev {
-            self.tokens[self.idx - 1].span.start()
-        } else {
-            self.curr_span_start()
-        };
+        let lo = self.tokens[if include_prev { self.idx - 1 } else { self.idx }].span;
         let p = parser.parse(self)?;
-        Ok((self.mk_span(lo, self.prev_span_end()), p))
+        // the span inherits the syntax context of its first token so that diagnostics on nodes
+        // produced by macro expansion can refer back to the expansion
+        let span = self.mk_span(lo.start(), self.prev_span_end()).with_ctxt(lo.ctxt());
+        Ok((span, p))
     }
 
     /// returns true if the current token is an ident
@@ -126,6 +154,22 @@ impl<'a> Parser<'a> {
         })
     }
 
+    /// returns true if the current token begins a path
+    /// (`self` only begins a path when followed by `::` as otherwise it is the `self` parameter)
+    pub fn is_path_start(&self) -> ParseResult<'a, bool> {
+        Ok(match self.safe_peek()?.kind {
+            TokenKind::Ident(..) | TokenKind::Super | TokenKind::Crate => true,
+            TokenKind::LSelf => self.tokens[self.idx + 1].kind == TokenKind::Dcolon,
+            _ => false,
+        })
+    }
+
+    /// returns true if the current tokens are `<ident>!`, the start of a macro invocation
+    pub(crate) fn is_macro_invocation(&self) -> bool {
+        matches!(self.safe_peek().map(|token| token.kind), Ok(TokenKind::Ident(..)))
+            && self.tokens[self.idx + 1].kind == TokenKind::Not
+    }
+
     /// separates float x.y into x . y
     /// assumes the float has been accepted already
     /// returns a pair of the components (x, y) to avoid modifying the token stream
@@ -193,10 +237,76 @@ impl<'a> Parser<'a> {
         MacroParser.parse(self)
     }
 
+    /// parses a single macro fragment of the given kind starting at the current token
+    /// returns the number of tokens that the fragment consists of
+    pub fn parse_fragment(&mut self, spec: FragmentSpecifier) -> ParseResult<'a, usize> {
+        let start = self.idx;
+        match spec {
+            FragmentSpecifier::Item => {
+                self.parse_item()?;
+            }
+            FragmentSpecifier::Block => {
+                let open_brace = self.expect(TokenKind::OpenBrace)?;
+                self.parse_block(open_brace)?;
+            }
+            FragmentSpecifier::Stmt => {
+                self.parse_stmt()?;
+            }
+            FragmentSpecifier::Pat => {
+                self.parse_pattern()?;
+            }
+            FragmentSpecifier::Expr => {
+                ExprParser.parse(self)?;
+            }
+            FragmentSpecifier::Ty => {
+                TyParser { allow_infer: false }.parse(self)?;
+            }
+            FragmentSpecifier::Ident => {
+                self.expect_ident()?;
+            }
+            FragmentSpecifier::Path => {
+                self.parse_type_path()?;
+            }
+            FragmentSpecifier::Lit =>
+                if self.accept_one_of([TokenKind::True, TokenKind::False]).is_none() {
+                    self.expect_literal()?;
+                },
+            FragmentSpecifier::Tt => {
+                let token = self.safe_peek()?;
+                match token.kind {
+                    TokenKind::OpenParen | TokenKind::OpenBrace | TokenKind::OpenBracket => {
+                        self.parse_tt_group();
+                    }
+                    TokenKind::CloseParen | TokenKind::CloseBrace | TokenKind::CloseBracket =>
+                        return Err(self.build_err(token.span, ParseError::UnexpectedToken(token))),
+                    _ => self.bump(),
+                }
+            }
+            FragmentSpecifier::Err => {
+                let err = ParseError::MissingFragmentSpecifier;
+                return Err(self.build_err(self.empty_span(), err));
+            }
+        };
+        Ok(self.idx - start)
+    }
+
+    /// parses any number of outer attributes `#[...]`
+    pub fn parse_outer_attributes(&mut self) -> ParseResult<'a, Vec<Attribute>> {
+        let mut attrs = vec![];
+        while self.peek().kind == TokenKind::Pound {
+            attrs.push(AttributeParser.parse(self)?);
+        }
+        Ok(attrs)
+    }
+
     pub fn parse_generics(&mut self) -> ParseResult<'a, Generics> {
         GenericsParser.parse(self)
     }
 
+    pub fn parse_where_clause(&mut self, generics: &mut Generics) -> ParseResult<'a, ()> {
+        WhereClauseParser { generics }.parse(self)
+    }
+
     pub fn parse_block(&mut self, open_brace: Token) -> ParseResult<'a, P<Block>> {
         BlockParser { open_brace, is_unsafe: false }.parse(self)
     }
@@ -300,7 +410,7 @@ impl<'a> Parser<'a> {
             _ => {}
         }
 
-        Box::new(Item { span, id: self.mk_id(), ident, vis, kind })
+        Box::new(Item { span, id: self.mk_id(), attrs: vec![], ident, vis, kind })
     }
 
     // same as next except the return value is suppressed
@@ -320,10 +430,18 @@ impl<'a> Parser<'a> {
         tok
     }
 
-    pub(crate) fn reached_eof(&self) -> bool {
+    pub fn reached_eof(&self) -> bool {
         self.tokens[self.idx].kind == TokenKind::Eof
     }
 
+    pub fn expect_eof(&self) -> ParseResult<'a, ()> {
+        let token = self.tokens[self.idx];
+        match token.kind {
+            TokenKind::Eof => Ok(()),
+            _ => Err(self.build_err(token.span, ParseError::UnexpectedToken(token))),
+        }
+    }
+
     pub(crate) fn safe_peek(&self) -> ParseResult<'a, Token> {
         if !self.reached_eof() {
             Ok(self.tokens[self.idx])
@@ -340,16 +458,17 @@ impl<'a> Parser<'a> {
         self.tokens[self.idx - 1]
     }
 
-    pub(crate) fn accept_literal(&mut self) -> Option<(LiteralKind, Span)> {
+    /// returns the kind of the literal, the offset of its suffix, and its span
+    pub(crate) fn accept_literal(&mut self) -> Option<(LiteralKind, usize, Span)> {
         self.expect_literal().ok()
     }
 
-    pub(crate) fn expect_literal(&mut self) -> ParseResult<'a, (LiteralKind, Span)> {
+    pub(crate) fn expect_literal(&mut self) -> ParseResult<'a, (LiteralKind, usize, Span)> {
         let Token { span, kind: ttype } = self.safe_peek()?;
         match ttype {
-            TokenKind::Literal { kind, .. } => {
+            TokenKind::Literal { kind, suffix_start } => {
                 self.idx += 1;
-                Ok((kind, span))
+                Ok((kind, suffix_start, span))
             }
             _ => Err(self.build_err(span, ParseError::ExpectedLiteral(ttype))),
         }
@@ -389,6 +508,20 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// expects an identifier or one of the keywords `self`, `super` and `crate` that may begin a
+    /// path (the resolver checks these keywords are only used at the start of the path)
+    pub(crate) fn expect_path_segment_ident(&mut self) -> ParseResult<'a, Ident> {
+        let token = self.safe_peek()?;
+        let symbol = match token.kind {
+            TokenKind::LSelf => kw::LSelf,
+            TokenKind::Super => kw::Super,
+            TokenKind::Crate => kw::Crate,
+            _ => return self.expect_ident(),
+        };
+        self.idx += 1;
+        Ok(Ident::new(token.span, symbol))
+    }
+
     pub(crate) fn accept_lident(&mut self) -> Option<Ident> {
         self.expect_lident().ok()
     }
@@ -412,6 +545,83 @@ impl<'a> Parser<'a> {
         ttypes.into_iter().fold(None, |acc, t| acc.or_else(|| self.accept(t)))
     }
 
+    /// accepts the binary operator at the current position if it is one of `ops`
+    /// multi-character operators are not glued together by the lexer (as `>>` may close two
+    /// generic argument lists and `&&` may be a double reference), so they are recognized here
+    /// as a sequence of adjacent single character tokens
+    pub(crate) fn accept_binop(
+        &mut self,
+        ops: impl IntoIterator<Item = BinOp>,
+    ) -> Option<(BinOp, Span)> {
+        let (op, n) = self.peek_binop()?;
+        if self.is_assign_op(op, n) || !ops.into_iter().any(|t| t == op) {
+            return None;
+        }
+        let span = self.tokens[self.idx].span.merge(self.tokens[self.idx + n - 1].span);
+        self.idx += n;
+        Some((op, span))
+    }
+
+    /// accepts a compound assignment operator such as `+=` or `<<=`
+    /// returns the underlying binary operator
+    pub(crate) fn accept_assign_op(&mut self) -> Option<(BinOp, Span)> {
+        let (op, n) = self.peek_binop()?;
+        if !self.is_assign_op(op, n) {
+            return None;
+        }
+        let span = self.tokens[self.idx].span.merge(self.tokens[self.idx + n].span);
+        self.idx += n + 1;
+        Some((op, span))
+    }
+
+    /// whether the `n` token binary operator `op` at the current position is immediately followed
+    /// by an `=`, making it a compound assignment operator
+    fn is_assign_op(&self, op: BinOp, n: usize) -> bool {
+        !op.is_comparison()
+            && !op.is_short_circuiting()
+            && self.is_joint(self.idx + n - 1)
+            && self.tokens[self.idx + n].kind == TokenKind::Eq
+    }
+
+    /// whether the token at index `i` is immediately followed by the next token (with no space)
+    fn is_joint(&self, i: usize) -> bool {
+        match (self.tokens.get(i), self.tokens.get(i + 1)) {
+            (Some(token), Some(next)) =>
+                next.span.file == token.span.file && next.span.start() == token.span.end(),
+            _ => false,
+        }
+    }
+
+    /// returns the (longest) binary operator at the current position along with the number of
+    /// tokens it consists of
+    fn peek_binop(&self) -> Option<(BinOp, usize)> {
+        let kind = self.tokens[self.idx].kind;
+        // the following token only forms part of the operator if there is no space in between
+        let joint = self.is_joint(self.idx).then(|| self.tokens[self.idx + 1].kind);
+        let op = match (kind, joint) {
+            (TokenKind::Lt, Some(TokenKind::Lt)) => (BinOp::Shl, 2),
+            (TokenKind::Lt, Some(TokenKind::Eq)) => (BinOp::Le, 2),
+            (TokenKind::Gt, Some(TokenKind::Gt)) => (BinOp::Shr, 2),
+            (TokenKind::Gt, Some(TokenKind::Eq)) => (BinOp::Ge, 2),
+            (TokenKind::Eq, Some(TokenKind::Eq)) => (BinOp::Eq, 2),
+            (TokenKind::Not, Some(TokenKind::Eq)) => (BinOp::Neq, 2),
+            (TokenKind::And, Some(TokenKind::And)) => (BinOp::AndAnd, 2),
+            (TokenKind::Or, Some(TokenKind::Or)) => (BinOp::OrOr, 2),
+            (TokenKind::Lt, _) => (BinOp::Lt, 1),
+            (TokenKind::Gt, _) => (BinOp::Gt, 1),
+            (TokenKind::And, _) => (BinOp::And, 1),
+            (TokenKind::Or, _) => (BinOp::Or, 1),
+            (TokenKind::Caret, _) => (BinOp::Xor, 1),
+            (TokenKind::Plus, _) => (BinOp::Add, 1),
+            (TokenKind::Minus, _) => (BinOp::Sub, 1),
+            (TokenKind::Star, _) => (BinOp::Mul, 1),
+            (TokenKind::Slash, _) => (BinOp::Div, 1),
+            (TokenKind::Percent, _) => (BinOp::Rem, 1),
+            _ => return None,
+        };
+        Some(op)
+    }
+
     pub(crate) fn parse_abi(&mut self) -> ParseResult<'a, Abi> {
         let symbol = self.accept_str();
         let symbol = match symbol {
@@ -429,7 +639,7 @@ impl<'a> Parser<'a> {
     }
 
     pub(crate) fn expect_str(&mut self) -> ParseResult<'a, Ident> {
-        let (kind, span) = self.expect_literal()?;
+        let (kind, _, span) = self.expect_literal()?;
         match kind {
             LiteralKind::Str { terminated } => {
                 if !terminated {
@@ -12,9 +12,12 @@ impl<'a> Parse<'a> for PatParser {
     fn parse(&mut self, parser: &mut Parser<'a>) -> ParseResult<'a, Self::Output> {
         if let Some(token) = parser.accept(TokenKind::Underscore) {
             Ok(parser.mk_pat(token.span, PatternKind::Wildcard))
-        } else if parser.is_ident()?.is_some() {
+        } else if parser.is_path_start()? {
             let path = parser.parse_expr_path()?;
-            if parser.accept(TokenKind::OpenBrace).is_some() {
+            if parser.accept(TokenKind::Not).is_some() {
+                let err = ParseError::MacroInvocationOutsideExpr("a pattern");
+                Err(parser.build_err(path.span, err))
+            } else if parser.accept(TokenKind::OpenBrace).is_some() {
                 let (span, fields) =
                     PunctuatedParser { inner: FieldPatParser, separator: TokenKind::Comma }
                         .spanned(false)
@@ -47,8 +50,8 @@ impl<'a> Parse<'a> for PatParser {
                 let (span, patterns) = parser.parse_tuple_pat()?;
                 Ok(parser.mk_pat(span, PatternKind::Tuple(patterns)))
             }
-        } else if let Some((kind, span)) = parser.accept_literal() {
-            let expr = LiteralParser { kind, span }.parse(parser)?;
+        } else if let Some((kind, suffix_start, span)) = parser.accept_literal() {
+            let expr = LiteralParser { kind, suffix_start, span }.parse(parser)?;
             Ok(parser.mk_pat(span, PatternKind::Lit(expr)))
         } else if let Some(false_kw) = parser.accept(TokenKind::False) {
             let expr = parser.mk_expr(false_kw.span, ExprKind::Lit(Lit::Bool(false)));
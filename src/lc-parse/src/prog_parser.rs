@@ -1,5 +1,9 @@
 use super::*;
-use lc_ast::{Ast};
+use lc_ast::Ast;
+
+/// the source of the prelude, which is compiled as a package that every other package implicitly
+/// depends on
+pub const PRELUDE_SRC: &str = include_str!("prelude.l");
 
 pub struct AstParser;
 
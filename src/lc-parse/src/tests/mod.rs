@@ -94,7 +94,7 @@ fn test_parser_span() {
         Box::new(Expr::new(
             Span::new(ROOT_FILE_IDX, 4, 5),
             NodeId::new(0),
-            ExprKind::Lit(Lit::Int(3))
+            ExprKind::Lit(Lit::Int(3, LitIntTy::Unsuffixed))
         ))
     );
 }
@@ -129,12 +129,12 @@ fn parse_tuple() {
                 Box::new(Expr::new(
                     Span::new(ROOT_FILE_IDX, 1, 2),
                     NodeId::new(0),
-                    ExprKind::Lit(Lit::Int(2)),
+                    ExprKind::Lit(Lit::Int(2, LitIntTy::Unsuffixed)),
                 )),
                 Box::new(Expr::new(
                     Span::new(ROOT_FILE_IDX, 4, 5),
                     NodeId::new(1),
-                    ExprKind::Lit(Lit::Int(3)),
+                    ExprKind::Lit(Lit::Int(3, LitIntTy::Unsuffixed)),
                 ))
             ],),
         ))
@@ -149,7 +149,7 @@ fn parse_int_literal() {
         Box::new(Expr::new(
             Span::new(ROOT_FILE_IDX, 0, 1),
             NodeId::new(0),
-            ExprKind::Lit(Lit::Int(2))
+            ExprKind::Lit(Lit::Int(2, LitIntTy::Unsuffixed))
         ))
     );
 }
@@ -167,12 +167,12 @@ fn parse_simple_binary_expr() {
                 Box::new(Expr::new(
                     Span::new(ROOT_FILE_IDX, 0, 1),
                     NodeId::new(0),
-                    ExprKind::Lit(Lit::Int(2)),
+                    ExprKind::Lit(Lit::Int(2, LitIntTy::Unsuffixed)),
                 )),
                 Box::new(Expr::new(
                     Span::new(ROOT_FILE_IDX, 4, 5),
                     NodeId::new(1),
-                    ExprKind::Lit(Lit::Int(3)),
+                    ExprKind::Lit(Lit::Int(3, LitIntTy::Unsuffixed)),
                 )),
             ),
         ))
@@ -212,7 +212,7 @@ fn parse_precedence_expr() {
                 Box::new(Expr::new(
                     Span::new(ROOT_FILE_IDX, 0, 1),
                     NodeId::new(0),
-                    ExprKind::Lit(Lit::Int(2)),
+                    ExprKind::Lit(Lit::Int(2, LitIntTy::Unsuffixed)),
                 )),
                 Box::new(Expr::new(
                     Span::new(ROOT_FILE_IDX, 4, 9),
@@ -222,12 +222,12 @@ fn parse_precedence_expr() {
                         Box::new(Expr::new(
                             Span::new(ROOT_FILE_IDX, 4, 5),
                             NodeId::new(1),
-                            ExprKind::Lit(Lit::Int(3)),
+                            ExprKind::Lit(Lit::Int(3, LitIntTy::Unsuffixed)),
                         )),
                         Box::new(Expr::new(
                             Span::new(ROOT_FILE_IDX, 8, 9),
                             NodeId::new(2),
-                            ExprKind::Lit(Lit::Int(4)),
+                            ExprKind::Lit(Lit::Int(4, LitIntTy::Unsuffixed)),
                         )),
                     ),
                 )),
@@ -235,3 +235,236 @@ fn parse_precedence_expr() {
         ))
     );
 }
+
+#[test]
+fn parse_string_literal_with_escapes() {
+    let expr = parse_expr!(r#""tab\tquote\"unicode\u{1F600}""#);
+    assert_eq!(expr.kind, ExprKind::Lit(Lit::Str(Symbol::intern("tab\tquote\"unicode\u{1F600}"))));
+}
+
+#[test]
+fn parse_string_literal_with_invalid_escape() {
+    let src = r#"fn main() -> int { "\q"; 0 }"#;
+    expect_parse_err!(src);
+}
+
+#[test]
+fn parse_unterminated_string_literal() {
+    let src = r#"fn main() -> int { "abc; 0 }"#;
+    expect_parse_err!(src);
+}
+
+#[test]
+fn parse_char_literals() {
+    assert_eq!(parse_expr!("'a'").kind, ExprKind::Lit(Lit::Char('a')));
+    assert_eq!(parse_expr!(r"'\n'").kind, ExprKind::Lit(Lit::Char('\n')));
+    assert_eq!(parse_expr!(r"'\''").kind, ExprKind::Lit(Lit::Char('\'')));
+    assert_eq!(parse_expr!(r"'\u{3bb}'").kind, ExprKind::Lit(Lit::Char('λ')));
+}
+
+#[test]
+fn parse_invalid_char_literals() {
+    expect_parse_err!("fn main() -> int { ''; 0 }");
+    expect_parse_err!("fn main() -> int { 'ab'; 0 }");
+    expect_parse_err!(r"fn main() -> int { '\u{110000}'; 0 }");
+}
+
+#[test]
+fn parse_int_literal_suffixes() {
+    let signed = |ty| ExprKind::Lit(Lit::Int(12, LitIntTy::Signed(ty)));
+    let unsigned = |ty| ExprKind::Lit(Lit::Int(12, LitIntTy::Unsigned(ty)));
+    assert_eq!(parse_expr!("12u8").kind, unsigned(UintTy::U8));
+    assert_eq!(parse_expr!("12_u64").kind, unsigned(UintTy::U64));
+    assert_eq!(parse_expr!("0xcu16").kind, unsigned(UintTy::U16));
+    assert_eq!(parse_expr!("12i32").kind, signed(IntTy::I32));
+    assert_eq!(parse_expr!("1_000").kind, ExprKind::Lit(Lit::Int(1000, LitIntTy::Unsuffixed)));
+}
+
+#[test]
+fn parse_float_literal_suffixes() {
+    let f32_lit = |f| ExprKind::Lit(Lit::Float(f, LitFloatTy::Suffixed(FloatTy::F32)));
+    assert_eq!(parse_expr!("1.5f32").kind, f32_lit(1.5));
+    assert_eq!(parse_expr!("2f32").kind, f32_lit(2.0));
+    assert_eq!(parse_expr!("2.5").kind, ExprKind::Lit(Lit::Float(2.5, LitFloatTy::Unsuffixed)));
+}
+
+#[test]
+fn parse_invalid_numeric_literals() {
+    expect_parse_err!("fn main() -> int { 5u7; 0 }");
+    expect_parse_err!("fn main() -> int { 5.0u8; 0 }");
+    expect_parse_err!("fn main() -> int { 256u8; 0 }");
+    expect_parse_err!("fn main() -> int { 18446744073709551616; 0 }");
+    expect_parse_err!(r#"fn main() -> int { "abc"foo; 0 }"#);
+}
+
+#[test]
+fn parse_binop_precedence() {
+    assert_eq!(fmt_expr!("a || b && c"), "(|| a (&& b c))");
+    assert_eq!(fmt_expr!("a == b && c != d"), "(&& (== a b) (!= c d))");
+    assert_eq!(fmt_expr!("a <= b | c"), "(<= a (| b c))");
+    assert_eq!(fmt_expr!("a | b ^ c & d"), "(| a (^ b (& c d)))");
+    assert_eq!(fmt_expr!("a & b << c"), "(& a (<< b c))");
+    assert_eq!(fmt_expr!("a >> b + c"), "(>> a (+ b c))");
+    assert_eq!(fmt_expr!("a + b % c"), "(+ a (% b c))");
+    assert_eq!(fmt_expr!("a >= b - c"), "(>= a (- b c))");
+}
+
+#[test]
+fn parse_multi_token_binops_require_adjacent_tokens() {
+    // `& &b` is a bitwise and with a reference rather than a logical and
+    assert_eq!(fmt_expr!("a & &b"), "(& a (&b))");
+    assert_eq!(fmt_expr!("a&&b"), "(&& a b)");
+    assert_eq!(fmt_expr!("a<=b"), "(<= a b)");
+}
+
+#[test]
+fn parse_compound_assignment() {
+    assert_eq!(fmt_expr!("x += 1"), "x += 1");
+    assert_eq!(fmt_expr!("x <<= y >> 2"), "x <<= (>> y 2)");
+    assert_eq!(fmt_expr!("x.y %= a <= b"), "x.y %= (<= a b)");
+    assert_eq!(fmt_expr!("x = y -= 2"), "x = y -= 2");
+}
+
+#[test]
+fn parse_index_expr() {
+    assert_eq!(fmt_expr!("xs[i]"), "xs[i]");
+    assert_eq!(fmt_expr!("xs[i][j + 1]"), "xs[i][(+ j 1)]");
+    assert_eq!(fmt_expr!("s.xs[0].y"), "s.xs[0].y");
+    assert_eq!(fmt_expr!("f(x)[0]"), "(f x)[0]");
+    assert_eq!(fmt_expr!("xs[i] += 1"), "xs[i] += 1");
+}
+
+#[test]
+fn parse_method_call() {
+    assert_eq!(fmt_expr!("x.f()"), "(x.f )");
+    assert_eq!(fmt_expr!("x.f(1, y)"), "(x.f 1 y)");
+    assert_eq!(fmt_expr!("x.f().g(2).h"), "((x.f ).g 2).h");
+    assert_eq!(fmt_expr!("s.xs.len()"), "(s.xs.len )");
+    assert_eq!(fmt_expr!("(s.f)(1)"), "((s.f) 1)");
+}
+
+#[test]
+fn parse_array_exprs() {
+    assert_eq!(fmt_expr!("[]"), "[]");
+    assert_eq!(fmt_expr!("[1, 2 + 3, x]"), "[1,(+ 2 3),x]");
+    assert_eq!(fmt_expr!("[1, 2,]"), "[1,2]");
+    assert_eq!(fmt_expr!("[[0; 2]; 3]"), "[[0; 2]; 3]");
+    assert_eq!(fmt_expr!("[1, 2][0]"), "[1,2][0]");
+}
+
+#[test]
+fn parse_array_ty() {
+    let src = "fn f(xs: [[int; 3]; 2]) -> [bool; 0] { [] }";
+    assert!(lc_driver::Driver::from_src(src).parse().is_ok());
+    expect_parse_err!("fn f(xs: [int]) -> int { 0 }");
+    expect_parse_err!("fn f(xs: [int; n]) -> int { 0 }");
+    expect_parse_err!("fn main() -> int { let xs = [0; 2u8]; 0 }");
+}
+
+#[test]
+fn parse_outer_attributes() {
+    let src = r#"
+    #[derive(Eq)] #[inline]
+    struct S { #[a] x: int, #[b(c, "d")] y: bool }
+    fn main() -> int { match 0 { #[arm] _ => 0 } }
+    "#;
+    let ast = lc_driver::Driver::from_src(src).parse().unwrap();
+    let item = |name| ast.module.items.iter().find(|item| item.ident.to_string() == name).unwrap();
+    let s = item("S");
+    let names = s.attrs.iter().map(|attr| attr.ident.to_string()).collect::<Vec<_>>();
+    assert_eq!(names, ["derive", "inline"]);
+    assert_eq!(s.attrs[1].args, AttrArgs::Empty);
+    match &s.kind {
+        ItemKind::Struct(_, VariantKind::Struct(fields)) => {
+            assert_eq!(fields[0].attrs[0].ident.to_string(), "a");
+            assert!(matches!(fields[1].attrs[0].args, AttrArgs::Delimited(..)));
+        }
+        _ => panic!(),
+    }
+    let body = match &item("main").kind {
+        ItemKind::Fn(_, _, Some(body)) => body,
+        _ => panic!(),
+    };
+    assert!(format!("{:?}", body).contains("attrs: [Attribute"));
+}
+
+#[test]
+fn parse_malformed_attributes() {
+    expect_parse_err!("#[derive struct S {}");
+    expect_parse_err!("#[] struct S {}");
+    expect_parse_err!("#derive struct S {}");
+}
+
+#[test]
+fn parse_use_trees() {
+    let src = "use a::b::{c as d, e::*, {f}}; pub use g; use *;";
+    let ast = lc_driver::Driver::from_src(src).parse().unwrap();
+    let trees = ast
+        .module
+        .items
+        .iter()
+        .filter_map(|item| match &item.kind {
+            ItemKind::Use(tree) => Some(tree.to_string()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(trees, ["a::b::{c as d, e::*, {f}}", "g", "*"]);
+}
+
+#[test]
+fn parse_malformed_use_trees() {
+    expect_parse_err!("use a::;");
+    expect_parse_err!("use a::{b;");
+    expect_parse_err!("use a as;");
+}
+
+#[test]
+fn parse_inline_modules() {
+    let src = "mod a { pub mod b { fn f() {} } struct S; } mod c {}";
+    let ast = lc_driver::Driver::from_src(src).parse().unwrap();
+    let items = match &ast.module.items[0].kind {
+        ItemKind::Mod(module) => &module.items,
+        _ => panic!(),
+    };
+    assert_eq!(items.len(), 2);
+    match &items[0].kind {
+        ItemKind::Mod(module) => assert_eq!(module.items.len(), 1),
+        _ => panic!(),
+    }
+    expect_parse_err!("mod a { fn f() {}");
+    expect_parse_err!("mod a { let x = 5; }");
+}
+
+#[test]
+fn parse_path_roots() {
+    let src = r#"
+    use super::a;
+    use crate::b::{self::c, *};
+    fn f(x: self::T) -> super::U {
+        let crate::V { x } = x;
+        self::g() + super::super::h()
+    }"#;
+    assert!(lc_driver::Driver::from_src(src).parse().is_ok());
+    // `self` alone is still the `self` parameter
+    assert!(lc_driver::Driver::from_src("impl S { fn f(self) -> S { self } }").parse().is_ok());
+}
+
+#[test]
+fn parse_path_attribute() {
+    let src = r#"#[path = "other.l"] mod m;"#;
+    let driver = lc_driver::Driver::from_src_with_modules(src, &[("other", "fn f() {}")]);
+    let ast = driver.parse().unwrap();
+    match &ast.module.items[0].kind {
+        ItemKind::Mod(module) => assert_eq!(module.items.len(), 1),
+        _ => panic!(),
+    }
+    expect_parse_err!(r#"#[path = "missing.l"] mod m;"#);
+    expect_parse_err!("#[path] mod m;");
+}
+
+#[test]
+fn parse_macro_invocation_outside_expr() {
+    expect_parse_err!("m!();");
+    expect_parse_err!("fn main() -> int { let m!() = 0; 0 }");
+    assert!(lc_driver::Driver::from_src("fn main() -> int { m!(); m!() }").parse().is_ok());
+}
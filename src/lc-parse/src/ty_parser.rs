@@ -32,14 +32,27 @@ impl<'a> Parse<'a> for TyParser {
         } else if let Some(amp) = parser.accept(TokenKind::And) {
             let ty = self.parse(parser)?;
             Ok(parser.mk_ty(amp.span.merge(ty.span), TyKind::Box(ty)))
+        } else if let Some(dyn_kw) = parser.accept(TokenKind::Dyn) {
+            let path = parser.parse_type_path()?;
+            Ok(parser.mk_ty(dyn_kw.span.merge(path.span), TyKind::Dyn(path)))
         } else if let Some(star) = parser.accept(TokenKind::Star) {
             let ty = self.parse(parser)?;
             Ok(parser.mk_ty(star.span.merge(ty.span), TyKind::Ptr(ty)))
         } else if let Some(lsq) = parser.accept(TokenKind::OpenBracket) {
             let ty = self.parse(parser)?;
+            parser.expect(TokenKind::Semi)?;
+            let len = ArrayLenParser.parse(parser)?;
             let rsq = parser.expect(TokenKind::CloseBracket)?;
-            Ok(parser.mk_ty(lsq.span.merge(rsq.span), TyKind::Array(ty)))
-        } else if parser.is_ident()?.is_some() {
+            Ok(parser.mk_ty(lsq.span.merge(rsq.span), TyKind::Array(ty, len)))
+        } else if let Some(lt) = parser.accept(TokenKind::Lt) {
+            let ty = self.parse(parser)?;
+            parser.expect(TokenKind::As)?;
+            let path = parser.parse_type_path()?;
+            parser.expect(TokenKind::Gt)?;
+            parser.expect(TokenKind::Dcolon)?;
+            let ident = parser.expect_ident()?;
+            Ok(parser.mk_ty(lt.span.merge(ident.span), TyKind::Projection(ty, path, ident)))
+        } else if parser.is_path_start()? {
             let path = parser.parse_type_path()?;
             Ok(parser.mk_ty(path.span, TyKind::Path(path)))
         } else {
@@ -31,6 +31,15 @@ impl<'a, 'r> DefCollector<'a, 'r> {
     pub fn def_module(&mut self, name: Ident) -> ModuleId {
         self.resolver.def_module(self.curr_mod, name)
     }
+
+    /// defines the module of the item (a module or an enum), recording it if it is private
+    fn def_item_module(&mut self, item: &Item) -> ModuleId {
+        if item.vis.node == VisibilityKind::Private {
+            let private_submodules = &self.resolver.modules[self.curr_mod].private_submodules;
+            private_submodules.borrow_mut().insert(item.ident, item.kind.def_kind());
+        }
+        self.def_module(item.ident)
+    }
 }
 
 impl<'ast, 'r> Visitor<'ast> for DefCollector<'ast, 'r> {
@@ -46,11 +55,11 @@ impl<'ast, 'r> Visitor<'ast> for DefCollector<'ast, 'r> {
             ItemKind::Enum(..) => {
                 // enums introduce a new namespace represented as a module
                 // where the variants are defined
-                let module = self.def_module(item.ident);
+                let module = self.def_item_module(item);
                 self.with_module(module, |this| lc_ast::walk_item(this, item));
             }
             ItemKind::Mod(module) => {
-                let module_id = self.def_module(item.ident);
+                let module_id = self.def_item_module(item);
                 self.with_module(module_id, |this| lc_ast::walk_module(this, module))
             }
             _ => lc_ast::walk_item(self, item),
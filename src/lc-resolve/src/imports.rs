@@ -1,70 +1,199 @@
-use crate::{ModuleId, ResResult, ResolutionError, Resolver, ROOT_MODULE};
-use lc_ast::{Ast, Item, ItemKind, Path, Visitor};
-use std::ops::{Deref, DerefMut};
+//! resolves `use` imports by binding the imported names in the importing module
+//! an import may depend on names bound by any other import, so the imports are resolved in rounds
+//! until no further progress is made (the imports that remain are unresolvable, e.g. due to cycles)
 
-struct ImportResolver<'a, 'r> {
-    resolver: &'a mut Resolver<'r>,
+use crate::{Lookup, ModuleId, ResolutionError, Resolver, ROOT_MODULE};
+use ir::Res;
+use lc_ast::{
+    Ast, Ident, Item, ItemKind, NodeId, PathSegment, UseTree, UseTreeKind, VisibilityKind, Visitor,
+};
+use lc_span::Span;
+use rustc_hash::FxHashMap;
+use std::cell::RefCell;
+use std::collections::hash_map::Entry;
+
+/// an import of a flattened use tree
+struct Import<'ast> {
+    /// the module the import binds names in
+    module: ModuleId,
+    /// the path of the module that is imported from
+    prefix: Vec<&'ast PathSegment>,
+    kind: ImportKind,
+    /// the names bound by an import that is not `pub` are only visible within `module`
+    vis: VisibilityKind,
+    span: Span,
 }
 
-impl<'a, 'r> ImportResolver<'a, 'r> {
-    pub fn new(resolver: &'a mut Resolver<'r>) -> Self {
-        Self { resolver }
-    }
+enum ImportKind {
+    /// `use <prefix>::<ident> as <binding>;`
+    /// the resolution of the import is recorded against the path of the use tree `id`
+    Single { ident: Ident, binding: Ident, id: NodeId },
+    /// `use <prefix>::*;`
+    Glob,
+}
 
-    /// resolves path to a local module
-    fn resolve_local_module(&mut self, path: &Path) -> ResResult<'a, ModuleId> {
-        let mut module = ROOT_MODULE;
-        for segment in &path.segments {
-            debug_assert!(segment.args.is_none());
-            match self.find_module(module, segment.ident) {
-                Some(m) => module = m,
-                None =>
-                    return Err(self.build_error(
-                        path.span,
-                        ResolutionError::UnresolvedModule(segment.clone(), path.clone()),
-                    )),
-            }
+impl Import<'_> {
+    fn path(&self) -> String {
+        let mut segments =
+            self.prefix.iter().map(|segment| segment.ident.to_string()).collect::<Vec<_>>();
+        match self.kind {
+            ImportKind::Single { ident, .. } => segments.push(ident.to_string()),
+            ImportKind::Glob => segments.push("*".to_owned()),
         }
-        Ok(module)
+        segments.join("::")
     }
+}
 
-    fn resolve_extern_module(&mut self, _path: &Path) -> ResResult<'a, ModuleId> {
-        todo!()
-    }
+/// collects the imports of each module
+struct ImportCollector<'a, 'r, 'ast> {
+    resolver: &'a Resolver<'r>,
+    curr_module: ModuleId,
+    imports: Vec<Import<'ast>>,
+}
 
-    fn resolve_use_path(&mut self, path: &Path) {
-        // we use the original error if both local and external resolution fails
-        let _module = self
-            .resolve_local_module(path)
-            .or_else(|err| self.resolve_extern_module(path).map_err(|_| err));
+impl<'ast> ImportCollector<'_, '_, 'ast> {
+    fn collect_use_tree(
+        &mut self,
+        vis: VisibilityKind,
+        mut prefix: Vec<&'ast PathSegment>,
+        tree: &'ast UseTree,
+    ) {
+        prefix.extend(&tree.prefix.segments);
+        let kind = match &tree.kind {
+            UseTreeKind::Simple(..) => {
+                let ident = prefix.pop().unwrap().ident;
+                ImportKind::Single { ident, binding: tree.ident().unwrap(), id: tree.prefix.id }
+            }
+            UseTreeKind::Glob => ImportKind::Glob,
+            UseTreeKind::Nested(trees) => {
+                trees.iter().for_each(|tree| self.collect_use_tree(vis, prefix.clone(), tree));
+                return;
+            }
+        };
+        let import = Import { module: self.curr_module, prefix, kind, vis, span: tree.span };
+        self.imports.push(import);
     }
 }
 
-impl<'a> Visitor<'a> for ImportResolver<'a, '_> {
-    fn visit_item(&mut self, item: &'a Item) {
-        if let ItemKind::Use(path) = &item.kind {
-            self.resolve_use_path(path)
+impl<'ast> Visitor<'ast> for ImportCollector<'_, '_, 'ast> {
+    fn visit_item(&mut self, item: &'ast Item) {
+        match &item.kind {
+            ItemKind::Use(tree) => self.collect_use_tree(item.vis.node, vec![], tree),
+            ItemKind::Mod(module) => {
+                let prev = self.curr_module;
+                self.curr_module = self.resolver.find_module(prev, item.ident).unwrap();
+                lc_ast::walk_module(self, module);
+                self.curr_module = prev;
+            }
+            _ => {}
         }
     }
 }
 
 impl<'r> Resolver<'r> {
     pub fn resolve_imports(&mut self, prog: &Ast) {
-        let mut visitor = ImportResolver::new(self);
-        visitor.visit_ast(prog);
+        let mut collector =
+            ImportCollector { resolver: self, curr_module: ROOT_MODULE, imports: vec![] };
+        collector.visit_ast(prog);
+        let mut imports = collector.imports;
+
+        loop {
+            let unresolved = imports.len();
+            imports.retain(|import| !self.try_resolve_import(import));
+            if imports.len() == unresolved {
+                break;
+            }
+        }
+
+        for import in imports {
+            self.emit_error(import.span, ResolutionError::UnresolvedImport(import.path()));
+            if let ImportKind::Single { id, .. } = import.kind {
+                self.resolve_node(id, Res::Err);
+            }
+        }
     }
-}
 
-impl<'a, 'r> Deref for ImportResolver<'a, 'r> {
-    type Target = Resolver<'r>;
+    /// returns whether the import was resolved
+    /// an import that can't be resolved yet may be resolvable once other imports are
+    fn try_resolve_import(&mut self, import: &Import<'_>) -> bool {
+        // paths are relative to the root module unless they begin with `self` or `super`
+        let idents = import.prefix.iter().map(|segment| segment.ident);
+        let (root, len) = match self.resolve_path_root(import.module, idents) {
+            Ok(root) => root.unwrap_or((ROOT_MODULE, 0)),
+            Err((span, err)) => {
+                // the error doesn't depend on other imports, so we give up on the import now
+                self.emit_error(span, err);
+                if let ImportKind::Single { id, .. } = import.kind {
+                    self.resolve_node(id, Res::Err);
+                }
+                return true;
+            }
+        };
+        // the modules the prefix passes through along with the name of the next segment
+        let mut traversed = vec![];
+        let module = match import.prefix[len..].iter().try_fold(root, |module, segment| {
+            traversed.push((module, segment.ident));
+            self.lookup_module(module, segment.ident, import.module).ok()
+        }) {
+            Some(module) => module,
+            None => return false,
+        };
+
+        let (ident, binding, id) = match import.kind {
+            ImportKind::Single { ident, binding, id } => (ident, binding, id),
+            ImportKind::Glob => {
+                self.check_import_privacy(import, &traversed);
+                self.modules[import.module].globs.borrow_mut().push((module, import.vis));
+                return true;
+            }
+        };
+
+        let res = self.lookup_item(module, ident, import.module);
+        let submodule = self.lookup_module(module, ident, import.module);
+        if res == Lookup::NotFound && submodule == Lookup::NotFound {
+            return false;
+        }
+        if matches!(res, Lookup::Ambiguous(..)) || matches!(submodule, Lookup::Ambiguous(..)) {
+            self.emit_error(ident.span, ResolutionError::AmbiguousName(ident));
+        }
 
-    fn deref(&self) -> &Self::Target {
-        self.resolver
+        // the name is bound as both an item and a module if it refers to a module (or an enum)
+        let importing_module = self.modules[import.module];
+        let mut is_unique = true;
+        if let Some(res) = res.ok() {
+            is_unique &= bind(&importing_module.items, binding, res);
+        }
+        if let Some(submodule) = submodule.ok() {
+            is_unique &= bind(&importing_module.submodules, binding, submodule);
+        }
+        if !is_unique {
+            self.emit_error(binding.span, ResolutionError::DuplicateImport(binding));
+        } else if import.vis == VisibilityKind::Private {
+            importing_module.private_imports.borrow_mut().insert(binding);
+        }
+        self.check_import_privacy(import, &traversed);
+        self.resolve_node(id, res.ok().unwrap_or(Res::Err));
+        true
+    }
+
+    /// reports the private modules that the prefix of the (resolved) import passes through from
+    /// outside of the module they are defined in (the imported name itself is checked by the
+    /// privacy checker like any other path)
+    /// this is only checked once the import is resolved so that each error is reported once
+    fn check_import_privacy(&self, import: &Import<'_>, traversed: &[(ModuleId, Ident)]) {
+        for &(module, ident) in traversed {
+            self.check_submodule_privacy(module, ident, import.module);
+        }
     }
 }
 
-impl<'a, 'r> DerefMut for ImportResolver<'a, 'r> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        self.resolver
+/// binds `ident` to `value` unless `ident` is already bound, returns whether it was bound
+fn bind<T>(bindings: &RefCell<FxHashMap<Ident, T>>, ident: Ident, value: T) -> bool {
+    match bindings.borrow_mut().entry(ident) {
+        Entry::Vacant(entry) => {
+            entry.insert(value);
+            true
+        }
+        Entry::Occupied(..) => false,
     }
 }
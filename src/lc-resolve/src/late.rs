@@ -10,6 +10,9 @@ pub struct LateResolver<'a, 'r, 'ast> {
     pub(crate) resolver: &'a mut Resolver<'r>,
     pub(crate) scopes: PerNS<Scopes<Res<NodeId>>>,
     pub(crate) current_module: Vec<ModuleId>,
+    /// the module that the code being resolved is written in
+    /// (unlike `current_module`, this does not change while resolving the segments of a path)
+    pub(crate) lexical_module: ModuleId,
     _pd: &'ast PhantomData<()>,
 }
 
@@ -17,15 +20,20 @@ impl<'a, 'r, 'ast> LateResolver<'a, 'r, 'ast> {
     pub fn new(resolver: &'a mut Resolver<'r>) -> Self {
         Self {
             resolver,
-            scopes: Default::default(),
+            // only local variables are hygienic, items and type parameters are not
+            scopes: PerNS { value: Scopes::hygienic(), ty: Scopes::default() },
             current_module: vec![ROOT_MODULE],
+            lexical_module: ROOT_MODULE,
             _pd: &PhantomData,
         }
     }
 
     pub(crate) fn with_module<R>(&mut self, name: Ident, f: impl FnOnce(&mut Self) -> R) -> R {
         let module_id = self.resolve_module(name).unwrap();
-        self.with_module_id(module_id, f)
+        let prev = std::mem::replace(&mut self.lexical_module, module_id);
+        let ret = self.with_module_id(module_id, f);
+        self.lexical_module = prev;
+        ret
     }
 
     pub(crate) fn with_module_id<R>(
@@ -53,17 +61,34 @@ impl<'a, 'r, 'ast> LateResolver<'a, 'r, 'ast> {
         ret
     }
 
-    fn with_generics<R>(&mut self, generics: &Generics, f: impl FnOnce(&mut Self) -> R) -> R {
+    fn with_generics<R>(
+        &mut self,
+        generics: &'ast Generics,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
         self.with_ty_scope(|this| {
             for param in &generics.params {
                 let index = this.scopes[NS::Type].def_ty_param();
                 let res = this.resolver.def_ty_param(param.id, ParamIdx::new(index));
                 this.scopes[NS::Type].def(param.ident, res)
             }
+            this.resolve_bounds(generics);
             f(this)
         })
     }
 
+    /// resolves the trait bounds of the generic parameters and the `where` clause
+    /// (after all the parameters are in scope as bounds may refer to any of them)
+    fn resolve_bounds(&mut self, generics: &'ast Generics) {
+        for param in &generics.params {
+            param.bounds.iter().for_each(|bound| self.resolve_path(bound, NS::Type));
+        }
+        for predicate in &generics.predicates {
+            self.visit_ty(&predicate.ty);
+            predicate.bounds.iter().for_each(|bound| self.resolve_path(bound, NS::Type));
+        }
+    }
+
     pub(crate) fn def_val(&mut self, ident: Ident, res: Res<NodeId>) {
         self.scopes[NS::Value].def(ident, res);
     }
@@ -76,9 +101,14 @@ impl<'a, 'r, 'ast> LateResolver<'a, 'r, 'ast> {
         self.current_module.last().copied().unwrap()
     }
 
-    /// searches for an item with name = `ident` in the current module
+    /// searches for an item with name = `ident` in the current module and then in the prelude
     pub(crate) fn try_resolve_item(&self, ident: Ident) -> Option<Res<NodeId>> {
-        self.resolver.resolve_item(self.curr_module(), ident)
+        match self.resolver.lookup_item(self.curr_module(), ident, self.lexical_module) {
+            Lookup::Found(res) => Some(res),
+            Lookup::Ambiguous(..) =>
+                Some(self.resolver.emit_error(ident.span, ResolutionError::AmbiguousName(ident))),
+            Lookup::NotFound => self.resolver.resolve_prelude_item(ident),
+        }
     }
 
     /// search for a local variable in scope otherwise look for a resolution to an item
@@ -98,16 +128,19 @@ impl<'a, 'r, 'ast> LateResolver<'a, 'r, 'ast> {
             ItemKind::Mod(module) =>
                 self.with_module(item.ident, |this| lc_ast::walk_module(this, module)),
             ItemKind::Use(..) => {}
-            ItemKind::Trait { generics, items } => self.with_generics(generics, |r| {
-                r.with_self(item.id, |r| {
+            ItemKind::Trait { generics, items } => self.with_self(item.id, |r| {
+                // `Self` is an implicit type parameter of the trait
+                // so we reserve the first index for it before the declared parameters
+                r.scopes[NS::Type].def_ty_param();
+                r.with_generics(generics, |r| {
                     for item in items {
-                        lc_ast::walk_assoc_item(r, item);
+                        r.resolve_assoc_item(item);
                     }
                 })
             }),
             ItemKind::Impl { generics, trait_path, self_ty, items } =>
                 self.resolve_impl(item, generics, trait_path.as_ref(), self_ty, items),
-            ItemKind::Macro(_) => todo!(),
+            ItemKind::Macro(_) => unreachable!("macro definitions are removed during expansion"),
         }
     }
 
@@ -163,7 +196,7 @@ impl<'a, 'r, 'ast> LateResolver<'a, 'r, 'ast> {
     fn resolve_assoc_item(&mut self, item: &'ast AssocItem) {
         match &item.kind {
             // TODO add the impls generics to the assoc fns generics
-            AssocItemKind::Fn(_, generics, _) =>
+            AssocItemKind::Fn(_, generics, _) | AssocItemKind::TyAlias(generics, _) =>
                 self.with_generics(generics, |this| lc_ast::walk_assoc_item(this, item)),
         }
     }
@@ -222,7 +255,8 @@ impl<'a, 'ast> lc_ast::Visitor<'ast> for LateResolver<'a, '_, 'ast> {
 
     fn visit_ty(&mut self, ty: &'ast Ty) {
         match &ty.kind {
-            TyKind::Path(path) => self.resolve_path(path, NS::Type),
+            TyKind::Path(path) | TyKind::Dyn(path) | TyKind::Projection(_, path, _) =>
+                self.resolve_path(path, NS::Type),
             TyKind::ImplicitSelf => {
                 let res = self.scopes[NS::Type]
                     .lookup(Ident::unspanned(kw::USelf))
@@ -18,15 +18,18 @@ extern crate log;
 extern crate lc_ir as ir;
 
 use late::LateResolver;
-use lc_meta::PkgMetadata;
-use module::Mod;
+use lc_meta::{Export, PkgMetadata};
+use module::{Lookup, Mod};
 use pat::PatternResolutionCtx;
 use resolution_error::{ResResult, ResolutionError};
 use scope::{Scope, Scopes};
 
-use ir::{DefId, DefKind, Definitions, ParamIdx, PartialRes, PkgId, PrimTy, Res, Resolutions};
+use ir::{
+    DefId, DefKind, Definitions, ExternDef, LangItem, LangItems, ParamIdx, PartialRes, PkgId,
+    PrimTy, Res, Resolutions,
+};
 use lc_arena::TypedArena;
-use lc_ast::{Ast, Ident, NodeId};
+use lc_ast::{Ast, FloatTy, Ident, IntTy, NodeId, UintTy};
 use lc_error::DiagnosticBuilder;
 use lc_index::{newtype_index, IndexVec};
 use lc_session::Session;
@@ -49,9 +52,15 @@ newtype_index!(
 pub struct Resolver<'a> {
     arenas: &'a ResolverArenas<'a>,
     pkgs: &'a IndexVec<PkgId, PkgMetadata>,
+    /// the packages of `pkgs` that are direct dependencies (and so can be referred to by name)
+    direct_pkgs: &'a [PkgId],
     sess: &'a Session,
     primitive_types: PrimitiveTypes,
     modules: IndexVec<ModuleId, &'a Mod<'a>>,
+    /// the module of the prelude package whose items are in scope in every module
+    prelude: Option<ModuleId>,
+    /// the module of each dependency, which are in scope in every module
+    extern_pkgs: FxHashMap<Symbol, ModuleId>,
     defs: Definitions<'a>,
     partial_resolutions: FxHashMap<NodeId, PartialRes>,
     node_id_to_def_id: FxHashMap<NodeId, DefId>,
@@ -63,12 +72,16 @@ impl<'a> Resolver<'a> {
         sess: &'a Session,
         arenas: &'a ResolverArenas<'a>,
         pkgs: &'a IndexVec<PkgId, PkgMetadata>,
+        direct_pkgs: &'a [PkgId],
     ) -> Self {
         Self {
             sess,
             arenas,
             pkgs,
+            direct_pkgs,
             modules: Default::default(),
+            prelude: None,
+            extern_pkgs: Default::default(),
             defs: Default::default(),
             partial_resolutions: Default::default(),
             node_id_to_def_id: Default::default(),
@@ -80,18 +93,96 @@ impl<'a> Resolver<'a> {
     /// top level function to run the resolver on the given prog
     pub fn resolve(&mut self, prog: &Ast) {
         self.collect_defs(prog);
+        self.def_extern_pkgs();
         self.resolve_imports(prog);
         self.late_resolve(prog);
     }
 
     pub fn complete(self) -> Resolutions<'a> {
+        let lang_items = self.collect_lang_items();
         let Resolver { defs, .. } = self;
-        Resolutions { defs }
+        Resolutions { defs, lang_items }
     }
 
-    pub fn find_module(&mut self, par: ModuleId, ident: Ident) -> Option<ModuleId> {
-        let par = self.modules[par];
-        par.submodules.borrow().get(&ident).copied()
+    /// finds the definition of each lang item amongst the items of the prelude
+    /// (the prelude package itself is the only package without a prelude, and its lang items are
+    /// the items of its own root module)
+    fn collect_lang_items(&self) -> LangItems {
+        let mut lang_items = LangItems::default();
+        let prelude = self.prelude.unwrap_or(ROOT_MODULE);
+        for &item in &LangItem::ALL {
+            if let Some(Res::Def(def_id, _)) =
+                self.resolve_item(prelude, Ident::unspanned(item.name()))
+            {
+                lang_items.insert(item, def_id);
+            }
+        }
+        lang_items
+    }
+
+    /// defines a module for each direct dependency (named after the package) holding its exports
+    /// every definition of every dependency is given a local `DefId` (not only the exports) as
+    /// the types and mir in their metadata may refer to any of them
+    fn def_extern_pkgs(&mut self) {
+        let pkgs = self.pkgs;
+        for (pkg, metadata) in pkgs.iter_enumerated() {
+            for entry in &metadata.defs {
+                let ident = Ident::unspanned(entry.name);
+                self.defs.mk_extern_def(ExternDef {
+                    pkg,
+                    def_id: entry.def_id,
+                    ident,
+                    def_kind: entry.def_kind,
+                    fn_kind: entry.fn_kind,
+                });
+            }
+        }
+        for &pkg in self.direct_pkgs {
+            let metadata = &pkgs[pkg];
+            let name = Ident::unspanned(Symbol::intern(&metadata.name));
+            let module = self.def_module(ROOT_MODULE, name);
+            self.extern_pkgs.insert(name.symbol, module);
+            self.def_exports(pkg, module, &metadata.exports);
+        }
+        // the prelude is a dependency of every package (other than the prelude itself)
+        self.prelude = self.find_extern_pkg(Ident::unspanned(sym::prelude));
+    }
+
+    fn def_exports(&mut self, pkg: PkgId, module: ModuleId, exports: &[Export]) {
+        for export in exports {
+            let ident = Ident::unspanned(Symbol::intern(&export.name));
+            // a re-export names the package that defines it (which is loaded as a dependency of
+            // the re-exporting package)
+            let def_pkg = match &export.pkg {
+                None => pkg,
+                Some(name) => self
+                    .pkgs
+                    .iter_enumerated()
+                    .find(|(_, metadata)| &metadata.name == name)
+                    .map(|(pkg, _)| pkg)
+                    .unwrap_or_else(|| panic!("the package `{}` is not loaded", name)),
+            };
+            let def_id = self.defs.extern_def_id(def_pkg, export.def_id).unwrap_or_else(|| {
+                panic!("the export `{}` has no definition in its package", ident)
+            });
+            let res = Res::Def(def_id, export.def_kind);
+            self.modules[module].items.borrow_mut().insert(ident, res);
+            // as with local items, modules and enums are also namespaces
+            if let DefKind::Mod | DefKind::Enum = export.def_kind {
+                let submodule = self.def_module(module, ident);
+                self.def_exports(pkg, submodule, &export.children);
+            }
+        }
+    }
+
+    /// the module of the dependency named `ident`
+    pub fn find_extern_pkg(&self, ident: Ident) -> Option<ModuleId> {
+        self.extern_pkgs.get(&ident.symbol).copied()
+    }
+
+    /// finds the submodule `ident` of `par` (as seen from within `par`)
+    pub fn find_module(&self, par: ModuleId, ident: Ident) -> Option<ModuleId> {
+        self.lookup_module(par, ident, par).ok()
     }
 
     pub fn root_module(&mut self) -> &Mod<'a> {
@@ -99,7 +190,9 @@ impl<'a> Resolver<'a> {
     }
 
     pub fn def_module(&mut self, par: ModuleId, name: Ident) -> ModuleId {
-        let module = self.arenas.modules.alloc(Mod::default());
+        // the root module is the first module defined and the only one without a parent
+        let parent = if self.modules.is_empty() { None } else { Some(par) };
+        let module = self.arenas.modules.alloc(Mod::new(parent));
         let id = self.modules.push(module);
         if self.modules[par].submodules.borrow_mut().insert(name, id).is_some() {
             self.emit_error(name.span, ResolutionError::DuplicateModuleDefinition(name));
@@ -161,7 +254,12 @@ impl<'a> Resolver<'a> {
     }
 
     pub fn resolve_item(&self, module: ModuleId, ident: Ident) -> Option<Res<NodeId>> {
-        self.modules[module].items.borrow().get(&ident).copied()
+        self.lookup_item(module, ident, module).ok()
+    }
+
+    /// items of the prelude are in scope everywhere unless shadowed by an item of the same name
+    pub fn resolve_prelude_item(&self, ident: Ident) -> Option<Res<NodeId>> {
+        self.prelude.and_then(|prelude| self.resolve_item(prelude, ident))
     }
 
     /// node_id -> def_id
@@ -204,9 +302,21 @@ impl Default for PrimitiveTypes {
     fn default() -> Self {
         let mut types = FxHashMap::default();
         types.insert(sym::bool, PrimTy::Bool);
-        types.insert(sym::float, PrimTy::Float);
-        types.insert(sym::int, PrimTy::Int);
         types.insert(sym::char, PrimTy::Char);
+        types.insert(sym::str, PrimTy::Str);
+        // `int` and `float` are aliases for `i64` and `f64` respectively
+        types.insert(sym::int, PrimTy::Int(IntTy::I64));
+        types.insert(sym::i8, PrimTy::Int(IntTy::I8));
+        types.insert(sym::i16, PrimTy::Int(IntTy::I16));
+        types.insert(sym::i32, PrimTy::Int(IntTy::I32));
+        types.insert(sym::i64, PrimTy::Int(IntTy::I64));
+        types.insert(sym::u8, PrimTy::Uint(UintTy::U8));
+        types.insert(sym::u16, PrimTy::Uint(UintTy::U16));
+        types.insert(sym::u32, PrimTy::Uint(UintTy::U32));
+        types.insert(sym::u64, PrimTy::Uint(UintTy::U64));
+        types.insert(sym::float, PrimTy::Float(FloatTy::F64));
+        types.insert(sym::f32, PrimTy::Float(FloatTy::F32));
+        types.insert(sym::f64, PrimTy::Float(FloatTy::F64));
         Self { types }
     }
 }
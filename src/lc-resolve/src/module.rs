@@ -1,13 +1,169 @@
 use crate::*;
-use lc_ast::{Ident, NodeId};
-use ir::Res;
-use rustc_hash::FxHashMap;
+use lc_ast::{Ident, NodeId, VisibilityKind};
+use ir::{DefKind, Res};
+use rustc_hash::{FxHashMap, FxHashSet};
 use std::cell::RefCell;
 use std::marker::PhantomData;
 
 #[derive(Default, Debug)]
 pub struct Mod<'a> {
+    /// the module this module is declared in (`None` for the root module)
+    pub parent: Option<ModuleId>,
     pub items: RefCell<FxHashMap<Ident, Res<NodeId>>>,
     pub submodules: RefCell<FxHashMap<Ident, ModuleId>>,
+    /// the kind of each submodule (i.e. a module or an enum) that is not `pub`
+    pub private_submodules: RefCell<FxHashMap<Ident, DefKind>>,
+    /// the names bound by imports that are not `pub`
+    /// these are only visible from within this module (and its descendants)
+    pub private_imports: RefCell<FxHashSet<Ident>>,
+    /// the modules whose names are brought into this module by `use <module>::*`
+    /// along with the visibility of the import
+    pub globs: RefCell<Vec<(ModuleId, VisibilityKind)>>,
     pd: PhantomData<&'a ()>,
 }
+
+impl Mod<'_> {
+    pub fn new(parent: Option<ModuleId>) -> Self {
+        Self { parent, ..Default::default() }
+    }
+}
+
+/// the result of looking up a name in a module
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Lookup<T> {
+    Found(T),
+    /// the name is not defined in the module itself and
+    /// multiple glob imports bring in different definitions for it
+    Ambiguous(T, T),
+    NotFound,
+}
+
+impl<T> Lookup<T> {
+    pub fn ok(self) -> Option<T> {
+        match self {
+            Lookup::Found(t) | Lookup::Ambiguous(t, _) => Some(t),
+            Lookup::NotFound => None,
+        }
+    }
+}
+
+impl<'a> Resolver<'a> {
+    /// resolves the leading `crate`, `self` and `super` segments of a path relative to `module`
+    /// returns the module they refer to along with the number of such segments
+    /// (or `None` if the path does not begin with any of them)
+    pub fn resolve_path_root(
+        &self,
+        module: ModuleId,
+        idents: impl IntoIterator<Item = Ident>,
+    ) -> Result<Option<(ModuleId, usize)>, (Span, ResolutionError)> {
+        let mut root = None;
+        let mut len = 0;
+        for (i, ident) in idents.into_iter().enumerate() {
+            let next = match ident.symbol {
+                kw::Crate if i == 0 => ROOT_MODULE,
+                kw::LSelf if i == 0 => module,
+                kw::Super if i == len => self.modules[root.unwrap_or(module)]
+                    .parent
+                    .ok_or((ident.span, ResolutionError::TooManySuperKeywords))?,
+                kw::Crate | kw::LSelf | kw::Super =>
+                    return Err((ident.span, ResolutionError::PathRootNotAtStart(ident))),
+                _ => continue,
+            };
+            root = Some(next);
+            len += 1;
+        }
+        Ok(root.map(|root| (root, len)))
+    }
+
+    /// looks up `ident` amongst the items of `module` (including the ones it imports)
+    /// from within the module `from`, which can only see the private imports of `module` if
+    /// it is `module` or one of its descendants
+    pub fn lookup_item(
+        &self,
+        module: ModuleId,
+        ident: Ident,
+        from: ModuleId,
+    ) -> Lookup<Res<NodeId>> {
+        self.lookup(module, ident, from, &|module| module.items.borrow().get(&ident).copied())
+    }
+
+    /// looks up `ident` amongst the submodules of `module` (including the ones it imports)
+    /// from within the module `from` (see `lookup_item`)
+    pub fn lookup_module(
+        &self,
+        module: ModuleId,
+        ident: Ident,
+        from: ModuleId,
+    ) -> Lookup<ModuleId> {
+        self.lookup(module, ident, from, &|module| module.submodules.borrow().get(&ident).copied())
+    }
+
+    fn lookup<T: Copy + PartialEq>(
+        &self,
+        module: ModuleId,
+        ident: Ident,
+        from: ModuleId,
+        get: &impl Fn(&Mod<'a>) -> Option<T>,
+    ) -> Lookup<T> {
+        self.lookup_inner(module, ident, from, get, &mut FxHashSet::default())
+    }
+
+    /// a name defined (or explicitly imported) in the module shadows any glob imported name
+    /// `visited` guards against cycles of glob imports
+    fn lookup_inner<T: Copy + PartialEq>(
+        &self,
+        module_id: ModuleId,
+        ident: Ident,
+        from: ModuleId,
+        get: &impl Fn(&Mod<'a>) -> Option<T>,
+        visited: &mut FxHashSet<ModuleId>,
+    ) -> Lookup<T> {
+        if !visited.insert(module_id) {
+            return Lookup::NotFound;
+        }
+        let module = self.modules[module_id];
+        let is_inside = self.is_descendant(from, module_id);
+        if let Some(t) = get(module) {
+            if is_inside || !module.private_imports.borrow().contains(&ident) {
+                return Lookup::Found(t);
+            }
+        }
+        let mut lookup = Lookup::NotFound;
+        for &(glob, vis) in module.globs.borrow().iter() {
+            if vis == VisibilityKind::Private && !is_inside {
+                continue;
+            }
+            match (lookup, self.lookup_inner(glob, ident, from, get, visited)) {
+                (_, Lookup::NotFound) => {}
+                (Lookup::NotFound, glob_lookup) => lookup = glob_lookup,
+                (Lookup::Found(t), Lookup::Found(u) | Lookup::Ambiguous(u, _)) if t != u =>
+                    lookup = Lookup::Ambiguous(t, u),
+                _ => {}
+            }
+        }
+        lookup
+    }
+
+    /// reports the submodule `ident` of `module` if it is private and `from` is outside `module`
+    /// (the private imports of `module` are not even visible from outside it)
+    pub fn check_submodule_privacy(&self, module: ModuleId, ident: Ident, from: ModuleId) {
+        let def_kind = self.modules[module].private_submodules.borrow().get(&ident).copied();
+        if let Some(def_kind) = def_kind {
+            if !self.is_descendant(from, module) {
+                self.emit_error(ident.span, ResolutionError::PrivateModule(def_kind, ident));
+            }
+        }
+    }
+
+    /// whether `module` is `ancestor` or is nested within it
+    pub fn is_descendant(&self, module: ModuleId, ancestor: ModuleId) -> bool {
+        let mut curr = Some(module);
+        while let Some(module) = curr {
+            if module == ancestor {
+                return true;
+            }
+            curr = self.modules[module].parent;
+        }
+        false
+    }
+}
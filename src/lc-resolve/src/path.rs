@@ -9,7 +9,7 @@ impl<'a, 'r, 'ast> LateResolver<'a, 'r, 'ast> {
     pub(crate) fn resolve_path(&mut self, path: &'ast Path, ns: NS) {
         let partial_res = match ns {
             NS::Value => self.resolve_val_path(path),
-            NS::Type => self.resolve_ty_path(path).map(PartialRes::resolved),
+            NS::Type => self.resolve_ty_path(path),
         }
         .unwrap_or_else(|err| {
             err.emit();
@@ -19,11 +19,44 @@ impl<'a, 'r, 'ast> LateResolver<'a, 'r, 'ast> {
     }
 
     fn resolve_val_path(&mut self, path: &'ast Path) -> ResResult<'a, PartialRes> {
-        self.resolve_val_path_segments(path, &path.segments)
+        match self.resolve_path_root(path, NS::Value)? {
+            Some((module, segments)) =>
+                self.with_module_id(module, |this| this.resolve_val_path_segments(path, segments)),
+            None => self.resolve_val_path_segments(path, &path.segments),
+        }
+    }
+
+    /// resolves the leading `crate`, `self` and `super` segments of `path`
+    /// returns the module they refer to along with the remaining segments
+    fn resolve_path_root(
+        &self,
+        path: &'ast Path,
+        ns: NS,
+    ) -> ResResult<'a, Option<(ModuleId, &'ast [PathSegment])>> {
+        // a lone `self` is the self parameter rather than the current module
+        if path.segments.len() == 1 {
+            return Ok(None);
+        }
+        let idents = path.segments.iter().map(|segment| segment.ident);
+        match self.resolver.resolve_path_root(self.curr_module(), idents) {
+            Ok(Some((_, len))) if len == path.segments.len() => {
+                let err = match ns {
+                    NS::Value => ResolutionError::InvalidValuePath(DefKind::Mod),
+                    NS::Type => ResolutionError::UnresolvedType(path.clone()),
+                };
+                Err(self.build_error(path.span, err))
+            }
+            Ok(root) => Ok(root.map(|(module, len)| (module, &path.segments[len..]))),
+            Err((span, err)) => Err(self.build_error(span, err)),
+        }
     }
 
     pub(crate) fn resolve_module(&mut self, ident: Ident) -> Option<ModuleId> {
-        self.resolver.find_module(self.curr_module(), ident)
+        let module = self.resolver.lookup_module(self.curr_module(), ident, self.lexical_module);
+        if module.ok().is_some() {
+            self.resolver.check_submodule_privacy(self.curr_module(), ident, self.lexical_module);
+        }
+        module.ok().or_else(|| self.resolver.find_extern_pkg(ident))
     }
 
     fn resolve_val_path_segments(
@@ -61,6 +94,12 @@ impl<'a, 'r, 'ast> LateResolver<'a, 'r, 'ast> {
                 ResolutionError::UnresolvedPath(segment.clone(), path.clone()),
             )
         })?;
+        // a module has no type relative items, so the next segment is not visible in it
+        // (e.g. it doesn't exist or is a private import)
+        if let Res::Def(_, DefKind::Mod) = base_res {
+            let err = ResolutionError::UnresolvedPath(remaining[0].clone(), path.clone());
+            return Err(self.build_error(path.span, err));
+        }
         Ok(PartialRes::new(base_res, remaining.len()))
     }
 
@@ -82,7 +121,11 @@ impl<'a, 'r, 'ast> LateResolver<'a, 'r, 'ast> {
                     return Err(
                         self.build_error(path.span, ResolutionError::InvalidValuePath(def_kind))
                     ),
-                DefKind::TyParam(..) | DefKind::Extern | DefKind::Use | DefKind::Impl => panic!(),
+                DefKind::TyParam(..)
+                | DefKind::AssocTy
+                | DefKind::Extern
+                | DefKind::Use
+                | DefKind::Impl => panic!(),
                 DefKind::Ctor(..)
                 | DefKind::Macro
                 | DefKind::Fn
@@ -97,11 +140,33 @@ impl<'a, 'r, 'ast> LateResolver<'a, 'r, 'ast> {
         Ok(res)
     }
 
-    fn resolve_ty_path(&mut self, path: &'ast Path) -> ResResult<Res<NodeId>> {
-        match path.segments.as_slice() {
+    fn resolve_ty_path(&mut self, path: &'ast Path) -> ResResult<'a, PartialRes> {
+        match self.resolve_path_root(path, NS::Type)? {
+            Some((module, segments)) =>
+                self.with_module_id(module, |this| this.resolve_ty_path_segments(path, segments)),
+            None => self.resolve_ty_path_segments(path, &path.segments),
+        }
+    }
+
+    fn resolve_ty_path_segments(
+        &mut self,
+        path: &'ast Path,
+        segments: &'ast [PathSegment],
+    ) -> ResResult<'a, PartialRes> {
+        match &segments {
+            [segment] =>
+                self.resolve_path_segment(path, segment, NS::Type).map(PartialRes::resolved),
+            [segment, remaining @ ..] =>
+                match self.resolve_module(segment.ident).and_then(|module_id| {
+                    self.with_module_id(module_id, |this| {
+                        this.resolve_ty_path_segments(path, remaining).ok()
+                    })
+                }) {
+                    Some(res) => Ok(res),
+                    // otherwise, it is an associated type such as `T::Item`
+                    None => self.resolve_type_relative(path, remaining, segment),
+                },
             [] => panic!("empty ty path"),
-            [segment] => self.resolve_path_segment(path, segment, NS::Type),
-            [_xs @ .., _segment] => todo!(),
         }
     }
 
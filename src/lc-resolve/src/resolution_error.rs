@@ -11,8 +11,6 @@ pub enum ResolutionError {
     UnresolvedPath(PathSegment, Path),
     #[error("unresolved type `{0}`")]
     UnresolvedType(Path),
-    #[error("unresolved module segment `{0}` in path `{1}`")]
-    UnresolvedModule(PathSegment, Path),
     #[error("expected value, found {0}")]
     InvalidValuePath(DefKind),
     #[error("let binding to named closure")]
@@ -25,4 +23,16 @@ pub enum ResolutionError {
     DuplicatePatternIdentifier(Ident),
     #[error("self parameter is only allowed in functions declared within an impl block")]
     SelfParameterInFreeFunction,
+    #[error("unresolved import `{0}`")]
+    UnresolvedImport(String),
+    #[error("the name `{0}` is defined multiple times")]
+    DuplicateImport(Ident),
+    #[error("`{0}` is ambiguous as multiple glob imports bring it into scope")]
+    AmbiguousName(Ident),
+    #[error("`{0}` in paths can only be used in start position")]
+    PathRootNotAtStart(Ident),
+    #[error("there are too many leading `super` keywords")]
+    TooManySuperKeywords,
+    #[error("{0} `{1}` is private")]
+    PrivateModule(DefKind, Ident),
 }
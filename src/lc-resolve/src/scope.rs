@@ -1,16 +1,21 @@
 use lc_ast::Ident;
+use lc_span::{Symbol, SyntaxContext};
 use rustc_hash::FxHashMap;
 use std::ops::{Deref, DerefMut};
 
 #[derive(Debug)]
 pub struct Scopes<T> {
     scopes: Vec<Scope<T>>,
+    /// whether names are compared hygienically (i.e. also by their syntax context)
+    /// this is the case for local variables, so a binding introduced by a macro expansion is
+    /// distinct from a binding of the same name at the invocation site
+    hygienic: bool,
 }
 
 impl<T> Default for Scopes<T> {
     fn default() -> Self {
         // start with one top-level scope
-        Self { scopes: vec![Default::default()] }
+        Self { scopes: vec![Default::default()], hygienic: false }
     }
 }
 
@@ -29,6 +34,15 @@ impl<T> DerefMut for Scopes<T> {
 }
 
 impl<T> Scopes<T> {
+    pub fn hygienic() -> Self {
+        Self { hygienic: true, ..Default::default() }
+    }
+
+    fn key(&self, ident: Ident) -> BindingKey {
+        let ctxt = if self.hygienic { ident.span.ctxt() } else { SyntaxContext::ROOT };
+        (ident.symbol, ctxt)
+    }
+
     /// returns index for next param
     pub fn def_ty_param(&mut self) -> usize {
         let index = self.paramc();
@@ -45,12 +59,14 @@ impl<T> Scopes<T> {
     }
 
     pub fn def(&mut self, ident: Ident, value: T) {
-        self.curr_scope_mut().def(ident, value);
+        let key = self.key(ident);
+        self.curr_scope_mut().def(key, value);
     }
 
     pub fn lookup(&self, ident: Ident) -> Option<&T> {
+        let key = self.key(ident);
         for scope in self.scopes.iter().rev() {
-            if let Some(x) = scope.lookup(&ident) {
+            if let Some(x) = scope.lookup(&key) {
                 return Some(x);
             }
         }
@@ -64,18 +80,20 @@ impl<T> Default for Scope<T> {
     }
 }
 
+type BindingKey = (Symbol, SyntaxContext);
+
 #[derive(Debug)]
 pub struct Scope<T> {
-    bindings: FxHashMap<Ident, T>,
+    bindings: FxHashMap<BindingKey, T>,
     paramc: usize,
 }
 
 impl<T> Scope<T> {
-    fn def(&mut self, ident: Ident, value: T) -> Option<T> {
-        self.bindings.insert(ident, value)
+    fn def(&mut self, key: BindingKey, value: T) -> Option<T> {
+        self.bindings.insert(key, value)
     }
 
-    fn lookup(&self, ident: &Ident) -> Option<&T> {
-        self.bindings.get(ident)
+    fn lookup(&self, key: &BindingKey) -> Option<&T> {
+        self.bindings.get(key)
     }
 }
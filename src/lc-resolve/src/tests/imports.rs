@@ -0,0 +1,208 @@
+use super::*;
+use crate::{expect_resolution_error, resolve};
+
+#[test]
+fn resolve_single_import() {
+    resolve!({
+        enum Color {
+            Red,
+            Green,
+        }
+
+        use Color::Red;
+        use Color::Green as G;
+
+        fn main() -> int {
+            let red = Red;
+            let green = G;
+            0
+        }
+    });
+}
+
+#[test]
+fn resolve_glob_and_nested_imports() {
+    resolve!({
+        enum Color {
+            Red,
+            Green,
+        }
+
+        enum Shape {
+            Circle(int),
+            Square(int),
+        }
+
+        use Color::*;
+        use Shape::{Circle, Square as Sq};
+
+        fn main() -> int {
+            let red = Red;
+            let green = Green;
+            let circle = Circle(1);
+            let square = Sq(2);
+            0
+        }
+    });
+}
+
+#[test]
+fn resolve_imports_out_of_order() {
+    // `R` can only be resolved once the import of `Red` is
+    resolve!({
+        use Red as R;
+        use Color::Red;
+
+        enum Color {
+            Red,
+        }
+
+        fn main() -> int {
+            let red = R;
+            0
+        }
+    });
+}
+
+#[test]
+fn resolve_glob_import_cycle() {
+    resolve!({
+        use *;
+
+        enum Color {
+            Red,
+        }
+
+        fn main() -> int {
+            let red = Color::Red;
+            0
+        }
+    });
+}
+
+#[test]
+fn unresolved_import() {
+    expect_resolution_error!({
+        enum Color {
+            Red,
+        }
+
+        use Color::Blue;
+
+        fn main() -> int {
+            0
+        }
+    });
+}
+
+#[test]
+fn unresolved_import_cycle() {
+    expect_resolution_error!({
+        use A as B;
+        use B as A;
+
+        fn main() -> int {
+            0
+        }
+    });
+}
+
+#[test]
+fn duplicate_import() {
+    expect_resolution_error!({
+        enum Color {
+            Red,
+        }
+
+        enum Light {
+            Red,
+        }
+
+        use Color::Red;
+        use Light::Red;
+
+        fn main() -> int {
+            0
+        }
+    });
+}
+
+#[test]
+fn ambiguous_glob_imports() {
+    expect_resolution_error!({
+        enum Color {
+            Red,
+        }
+
+        enum Light {
+            Red,
+        }
+
+        use Color::*;
+        use Light::*;
+
+        fn main() -> int {
+            let red = Red;
+            0
+        }
+    });
+}
+
+#[test]
+fn resolve_public_imports_from_other_modules() {
+    resolve!({
+        mod a {
+            pub mod b {
+                pub fn f() -> int {
+                    0
+                }
+            }
+
+            pub use self::b::f;
+            pub use self::b::*;
+            use self::b::f as g;
+
+            pub fn h() -> int {
+                g()
+            }
+        }
+
+        fn main() -> int {
+            a::f() + a::h()
+        }
+    });
+}
+
+#[test]
+fn private_imports_are_not_visible_from_other_modules() {
+    expect_resolution_error!({
+        mod a {
+            pub mod b {
+                pub fn f() -> int {
+                    0
+                }
+            }
+
+            use self::b::f as g;
+        }
+
+        fn main() -> int {
+            a::g()
+        }
+    });
+    expect_resolution_error!({
+        mod a {
+            pub mod b {
+                pub fn f() -> int {
+                    0
+                }
+            }
+
+            use self::b::*;
+        }
+
+        fn main() -> int {
+            a::f()
+        }
+    });
+}
@@ -1,19 +1,69 @@
-use crate::resolve;
-
-// #[test]
-// fn resolve_macro() {
-//     resolve!({
-//         macro marco {
-//             ($expr:expr) => {
-//                 $expr
-//             }
-//             ($tt:tt) => {
-//                 $tt
-//             }
-//         }
-//
-//         fn main() {
-//             marco!(0)
-//         }
-//     });
-// }
+use super::*;
+use crate::{expect_resolution_error, resolve};
+
+#[test]
+fn resolve_macro() {
+    resolve!({
+        macro marco {
+            ($expr:expr) => {
+                $expr
+            }
+            ($tt:tt) => {
+                $tt
+            }
+        }
+
+        fn main() -> int {
+            marco!(0)
+        }
+    });
+}
+
+#[test]
+fn resolve_hygienic_local_does_not_shadow_invocation_local() {
+    // the `x` bound in the macro body is distinct from the `x` passed in by the caller
+    resolve!({
+        macro plus_one {
+            ($e:expr) => {{
+                let x = 1;
+                $e + x
+            }}
+        }
+
+        fn main() -> int {
+            let x = 5;
+            plus_one!(x)
+        }
+    });
+}
+
+#[test]
+fn resolve_macro_body_cannot_refer_to_invocation_local() {
+    expect_resolution_error!({
+        macro get_x {
+            () => { x }
+        }
+
+        fn main() -> int {
+            let x = 5;
+            get_x!()
+        }
+    });
+}
+
+#[test]
+fn resolve_macro_body_can_refer_to_items() {
+    resolve!({
+        macro call_f {
+            () => { f() }
+        }
+
+        fn f() -> int {
+            5
+        }
+
+        fn main() -> int {
+            call_f!()
+        }
+    });
+}
@@ -1,6 +1,9 @@
 mod generics;
 mod impls;
+mod imports;
 mod macros;
+mod modules;
+mod packages;
 mod pattern;
 
 use lc_util::stringify_tt;
@@ -0,0 +1,167 @@
+use super::*;
+use crate::{expect_resolution_error, resolve};
+
+#[test]
+fn resolve_inline_modules() {
+    resolve!({
+        mod outer {
+            pub struct S {
+                pub x: int,
+            }
+
+            pub mod inner {
+                pub fn f() -> int {
+                    0
+                }
+            }
+        }
+
+        fn main() -> int {
+            let s = outer::S { x: outer::inner::f() };
+            s.x
+        }
+    });
+}
+
+#[test]
+fn resolve_path_roots() {
+    resolve!({
+        fn f() -> int {
+            0
+        }
+
+        mod a {
+            pub fn g() -> int {
+                super::f() + crate::f() + self::h()
+            }
+
+            fn h() -> int {
+                0
+            }
+
+            pub mod b {
+                pub fn k() -> super::super::S {
+                    super::super::S
+                }
+            }
+        }
+
+        struct S;
+
+        fn main() -> int {
+            let s: S = self::a::b::k();
+            crate::a::g()
+        }
+    });
+}
+
+#[test]
+fn resolve_imports_with_path_roots() {
+    resolve!({
+        fn f() -> int {
+            0
+        }
+
+        mod a {
+            use super::f;
+            use self::b::*;
+            use crate::a::b::g as h;
+
+            pub fn run() -> int {
+                f() + g() + h()
+            }
+
+            mod b {
+                pub fn g() -> int {
+                    0
+                }
+            }
+        }
+
+        fn main() -> int {
+            a::run()
+        }
+    });
+}
+
+#[test]
+fn resolve_too_many_super_keywords() {
+    expect_resolution_error!({
+        mod a {
+            pub fn f() -> int {
+                super::super::f()
+            }
+        }
+
+        fn main() -> int {
+            a::f()
+        }
+    });
+}
+
+#[test]
+fn resolve_path_root_not_at_start() {
+    expect_resolution_error!({
+        mod a {
+            pub fn f() -> int {
+                0
+            }
+        }
+
+        fn main() -> int {
+            a::super::a::f()
+        }
+    });
+}
+
+#[test]
+fn resolve_path_through_private_module() {
+    expect_resolution_error!({
+        mod a {
+            mod b {
+                pub fn f() -> int {
+                    0
+                }
+            }
+        }
+
+        fn main() -> int {
+            a::b::f()
+        }
+    });
+    expect_resolution_error!({
+        mod a {
+            mod b {
+                pub fn f() -> int {
+                    0
+                }
+            }
+        }
+
+        use a::b::f;
+
+        fn main() -> int {
+            0
+        }
+    });
+    // private modules are accessible from within their parent module
+    resolve!({
+        mod a {
+            mod b {
+                pub fn f() -> int {
+                    0
+                }
+            }
+
+            pub mod c {
+                pub fn g() -> int {
+                    super::b::f()
+                }
+            }
+        }
+
+        fn main() -> int {
+            a::c::g()
+        }
+    });
+}
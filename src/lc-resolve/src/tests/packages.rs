@@ -0,0 +1,105 @@
+/// the library of the dependency `dep` that the tests below refer to
+const DEP_SRC: &str = r#"
+pub fn f() -> int { secret() }
+
+fn secret() -> int { 2 }
+
+pub enum Color { Red, Green }
+
+pub mod shapes {
+    pub struct Square { pub side: int }
+
+    struct Hidden;
+}
+
+mod internal {
+    pub fn g() -> int { 3 }
+}
+
+pub use internal::g;
+pub use shapes::Square as Quad;
+pub use Color as Colour;
+"#;
+
+macro resolve_with_dep($src:expr) {{
+    let driver = lc_driver::Driver::from_src_with_dependencies($src, &[("dep", DEP_SRC)]);
+    driver.gen_ir().unwrap();
+    assert!(!driver.has_errors());
+}}
+
+macro expect_resolution_error_with_dep($src:expr) {{
+    let driver = lc_driver::Driver::from_src_with_dependencies($src, &[("dep", DEP_SRC)]);
+    let _ = driver.gen_ir();
+    assert!(driver.has_errors());
+}}
+
+#[test]
+fn resolve_dependency_imports() {
+    resolve_with_dep!(
+        r#"
+    use dep::f;
+    use dep::Color::Red;
+    use dep::shapes::Square;
+
+    mod inner {
+        use dep::Color;
+    }
+
+    fn main() -> int { 0 }"#
+    );
+}
+
+#[test]
+fn resolve_private_item_of_dependency() {
+    expect_resolution_error_with_dep!(
+        r#"
+    use dep::secret;
+
+    fn main() -> int { 0 }"#
+    );
+    expect_resolution_error_with_dep!(
+        r#"
+    use dep::shapes::Hidden;
+
+    fn main() -> int { 0 }"#
+    );
+}
+
+#[test]
+fn resolve_unknown_dependency() {
+    expect_resolution_error_with_dep!(
+        r#"
+    use other::f;
+
+    fn main() -> int { 0 }"#
+    );
+}
+
+#[test]
+fn resolve_use_of_dependency_item() {
+    resolve_with_dep!(
+        r#"
+    fn main() -> int {
+        let square = dep::shapes::Square { side: 2 };
+        dep::f() + square.side
+    }"#
+    );
+}
+
+#[test]
+fn resolve_reexports_of_dependency() {
+    resolve_with_dep!(
+        r#"
+    use dep::Quad;
+    use dep::Colour::Green;
+
+    fn main() -> int {
+        let quad = Quad { side: 2 };
+        dep::g() + quad.side
+    }"#
+    );
+    expect_resolution_error_with_dep!(
+        r#"
+    fn main() -> int { dep::internal::g() }"#
+    );
+}
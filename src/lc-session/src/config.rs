@@ -1,7 +1,9 @@
 use clap::Parser;
 use lc_error::ErrorFormat;
 use serde::Deserialize;
+use std::fmt::{self, Display, Formatter};
 use std::path::PathBuf;
+use std::str::FromStr;
 
 #[derive(Clone, Debug, Default, Parser, Deserialize)]
 pub struct CompilerOptions {
@@ -11,6 +13,10 @@ pub struct CompilerOptions {
     pub input_path: PathBuf,
     #[clap(long("error-format"), default_value = "text")]
     pub error_format: ErrorFormat,
+    /// enables the configuration option `name` or `name="value"` for `#[cfg(...)]`
+    #[clap(long("cfg"), number_of_values = 1)]
+    #[serde(default)]
+    pub cfg: Vec<CfgOption>,
     // TODO take optimization level as parameter (or debug/release)
 }
 
@@ -19,3 +25,47 @@ impl CompilerOptions {
         Self { input_path, ..Self::default() }
     }
 }
+
+/// a configuration option that `#[cfg(...)]` predicates are evaluated against
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize)]
+pub struct CfgOption {
+    pub name: String,
+    pub value: Option<String>,
+}
+
+impl CfgOption {
+    /// the option `feature="<feature>"` that enables a feature declared in `L.toml`
+    pub fn feature(feature: &str) -> Self {
+        Self { name: "feature".to_owned(), value: Some(feature.to_owned()) }
+    }
+}
+
+impl FromStr for CfgOption {
+    type Err = String;
+
+    /// parses `name` or `name="value"` (the quotes are optional as the shell may strip them)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, value) = match s.split_once('=') {
+            Some((name, value)) => {
+                let unquoted = value.strip_prefix('"').and_then(|value| value.strip_suffix('"'));
+                (name, Some(unquoted.unwrap_or(value).to_owned()))
+            }
+            None => (s, None),
+        };
+        let is_ident = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_ident {
+            return Err(format!("invalid cfg option `{}`, expected `name` or `name=\"value\"`", s));
+        }
+        Ok(Self { name: name.to_owned(), value })
+    }
+}
+
+impl Display for CfgOption {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.value {
+            Some(value) => write!(f, "{}=\"{}\"", self.name, value),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
@@ -4,7 +4,7 @@ extern crate log;
 mod config;
 mod profiling;
 
-pub use config::CompilerOptions;
+pub use config::{CfgOption, CompilerOptions};
 
 use lc_error::Diagnostics;
 use profiling::Profiler;
@@ -13,14 +13,17 @@ use std::ops::Deref;
 pub struct Session {
     pub prof: Profiler,
     pub opts: CompilerOptions,
+    /// the name of the package being compiled, which qualifies the symbols it exports
+    pub pkg_name: String,
     diagnostics: Diagnostics,
 }
 
 impl Session {
-    pub fn create(opts: CompilerOptions) -> Self {
+    pub fn create(opts: CompilerOptions, pkg_name: String) -> Self {
         Self {
             diagnostics: Diagnostics::with_error_format(opts.error_format),
             opts,
+            pkg_name,
             prof: Default::default(),
         }
     }
@@ -0,0 +1,54 @@
+use crate::{Span, Symbol, SPAN_GLOBALS};
+
+/// identifies the macro expansion (if any) that produced a span
+/// identifiers that are introduced by a macro transcriber are given a fresh context,
+/// which is used to prevent them from capturing (or being captured by)
+/// identifiers at the invocation site
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct SyntaxContext(u32);
+
+impl SyntaxContext {
+    /// the context of code that was written directly in the source
+    pub const ROOT: Self = Self(0);
+
+    /// creates a new context for a single expansion of a macro
+    pub fn fresh_expansion(expn_data: ExpnData) -> Self {
+        with_hygiene_data(|data| {
+            data.expansions.push(expn_data);
+            Self(data.expansions.len() as u32)
+        })
+    }
+
+    pub fn is_root(self) -> bool {
+        self == Self::ROOT
+    }
+
+    /// returns the data of the expansion that created this context,
+    /// or `None` if this is the root context
+    pub fn expn_data(self) -> Option<ExpnData> {
+        if self.is_root() {
+            return None;
+        }
+        with_hygiene_data(|data| Some(data.expansions[self.0 as usize - 1]))
+    }
+}
+
+/// information about a single macro expansion
+#[derive(Debug, Clone, Copy)]
+pub struct ExpnData {
+    pub macro_name: Symbol,
+    /// span of the macro invocation
+    pub call_site: Span,
+    /// span of the macro definition
+    pub def_site: Span,
+}
+
+#[derive(Debug, Default)]
+pub struct HygieneData {
+    /// the expansion data for each non-root syntax context (offset by one)
+    expansions: Vec<ExpnData>,
+}
+
+fn with_hygiene_data<R>(f: impl FnOnce(&mut HygieneData) -> R) -> R {
+    SPAN_GLOBALS.with(|globals| f(&mut globals.hygiene_data.borrow_mut()))
+}
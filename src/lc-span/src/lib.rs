@@ -7,10 +7,12 @@ extern crate lc_macros;
 #[macro_use]
 extern crate serde;
 
+mod hygiene;
 mod source_map;
 mod symbol;
 
 use codespan_reporting::diagnostic::Label;
+pub use hygiene::{ExpnData, SyntaxContext};
 pub use source_map::{FileIdx, ModuleKind, SourceMap, ROOT_FILE_IDX};
 pub use symbol::{kw, sym, Symbol};
 
@@ -23,6 +25,7 @@ use std::ops::{Deref, DerefMut, Range};
 pub struct SpanGlobals {
     pub symbol_interner: RefCell<symbol::Interner>,
     pub source_map: RefCell<SourceMap>,
+    pub hygiene_data: RefCell<hygiene::HygieneData>,
 }
 
 pub fn with_interner<R>(f: impl FnOnce(&mut symbol::Interner) -> R) -> R {
@@ -46,11 +49,12 @@ impl From<Span> for Label<FileIdx> {
 pub struct Span {
     pub file: FileIdx,
     span: codespan::Span,
+    ctxt: SyntaxContext,
 }
 
 impl Default for Span {
     fn default() -> Self {
-        Self { file: ROOT_FILE_IDX, span: Default::default() }
+        Self { file: ROOT_FILE_IDX, span: Default::default(), ctxt: SyntaxContext::ROOT }
     }
 }
 
@@ -72,7 +76,16 @@ impl SpanIdx for usize {
 
 impl Span {
     pub fn new(file: FileIdx, start: impl SpanIdx, end: impl SpanIdx) -> Self {
-        Self { file, span: codespan::Span::new(start.into(), end.into()) }
+        let span = codespan::Span::new(start.into(), end.into());
+        Self { file, span, ctxt: SyntaxContext::ROOT }
+    }
+
+    pub fn ctxt(self) -> SyntaxContext {
+        self.ctxt
+    }
+
+    pub fn with_ctxt(self, ctxt: SyntaxContext) -> Self {
+        Self { ctxt, ..self }
     }
 
     pub fn range(self) -> Range<usize> {
@@ -92,8 +105,12 @@ impl Span {
     }
 
     pub fn merge(self, other: Self) -> Self {
-        assert_eq!(self.file, other.file);
-        Self { file: self.file, span: self.span.merge(other.span) }
+        // tokens produced by a macro expansion may originate from a different file to the
+        // invocation (i.e. the macro definition), in which case there is no sensible merge
+        if self.file != other.file {
+            return self;
+        }
+        Self { span: self.span.merge(other.span), ..self }
     }
 }
 
@@ -35,13 +35,23 @@ impl SourceMap {
         self.modules.push(src_file)
     }
 
-    pub fn add_module(&mut self, current_file: FileIdx, sym: Symbol) -> Option<FileIdx> {
-        self.find_module_file(current_file, sym).map(|path| self.add_src_file(path))
+    /// adds a source file that does not exist on disk (i.e. the prelude)
+    pub fn add_virtual_file(&mut self, name: &str, src: &str) -> FileIdx {
+        self.modules.push(SourceFile::virtual_file(name, src))
     }
 
-    fn find_module_file(&mut self, current_file: FileIdx, sym: Symbol) -> Option<ModuleFile> {
-        let path = self.path_of(current_file).parent().unwrap();
+    /// adds the file of the module `sym` declared in a module whose submodules are found in `dir`
+    pub fn add_module(&mut self, dir: &Path, sym: Symbol) -> Option<FileIdx> {
+        self.find_module_file(dir, sym).map(|path| self.add_src_file(path))
+    }
+
+    /// adds the module file at `path` given by a `#[path]` attribute
+    /// the file is considered a `dir module` so it may declare submodules of its own
+    pub fn add_module_at_path(&mut self, path: &Path) -> Option<FileIdx> {
+        path.is_file().then(|| self.add_src_file(ModuleFile::new(path, ModuleKind::Dir)))
+    }
 
+    fn find_module_file(&mut self, dir: &Path, sym: Symbol) -> Option<ModuleFile> {
         let check_path = |p: &Path| p.is_file() && p.extension() == Some(&OsString::from("l"));
 
         // suppose we are in a module at `/path/to/file.l`
@@ -49,11 +59,11 @@ impl SourceMap {
         // then we check both `/path/to/foo.l` and `path/to/foo/foo.l`
         // we call these `module_file_path` and `module_dir_path` respectively
         // for the `foo` module file
-        let module_file_path = path.join(format!("{}.l", sym));
+        let module_file_path = dir.join(format!("{}.l", sym));
         if check_path(&module_file_path) {
             return Some(ModuleFile::new(module_file_path, ModuleKind::File));
         } else {
-            let module_dir_path = path.join(format!("{}/{}.l", sym, sym));
+            let module_dir_path = dir.join(format!("{}/{}.l", sym, sym));
             if check_path(&module_dir_path) {
                 return Some(ModuleFile::new(module_dir_path, ModuleKind::Dir));
             }
@@ -61,6 +71,15 @@ impl SourceMap {
         None
     }
 
+    /// the directory that the file submodules declared in `file` are found in
+    /// `inline_modules` are the inline modules (outermost first) enclosing the declarations,
+    /// i.e. a `mod bar;` within `mod foo { .. }` in `/path/to/file.l` is found in `/path/to/foo`
+    pub fn module_dir(&self, file: FileIdx, inline_modules: &[Symbol]) -> PathBuf {
+        let mut dir = self.dir_of(file).to_path_buf();
+        dir.extend(inline_modules.iter().map(|sym| sym.as_str()));
+        dir
+    }
+
     pub fn dir_of(&self, file: FileIdx) -> &Path {
         self.path_of(file).parent().unwrap()
     }
@@ -98,6 +117,13 @@ impl SourceFile {
         }
     }
 
+    fn virtual_file(name: &str, src: &str) -> Self {
+        let src = Symbol::intern_str(src);
+        // the path is only used for diagnostics and a virtual file can't declare submodules
+        let file = ModuleFile { path: PathBuf::from(name), kind: ModuleKind::File };
+        Self { name: name.to_owned(), line_starts: line_starts(src).collect(), file, src }
+    }
+
     pub fn source(&self) -> &'static str {
         self.src
     }
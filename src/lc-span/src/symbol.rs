@@ -8,18 +8,67 @@ symbols! {
         Empty: "",
         USelf: "Self",
         LSelf: "self",
+        Super: "super",
+        Crate: "crate",
     }
     // the following must be in alphabetical order
     Symbols {
+        Add,
+        Clone,
+        Debug,
+        Default,
+        Div,
+        Eq,
+        Index,
+        Mul,
+        Neg,
+        Not,
+        Ord,
+        Rem,
+        Sub,
+        add,
         addr,
+        all,
+        any,
         bool,
+        cfg,
         char,
+        derive,
+        div,
+        eq,
+        f32,
+        f64,
+        feature,
         float,
+        ge,
+        gt,
+        i16,
+        i32,
+        i64,
+        i8,
+        index,
         int,
         intrinsics,
+        le,
+        lt,
         main,
+        mul,
+        ne,
+        neg,
+        not,
+        path,
+        prelude,
         print,
+        print_str,
         rc,
+        rem,
+        str,
+        sub,
+        u16,
+        u32,
+        u64,
+        u8,
+        write,
     }
 }
 
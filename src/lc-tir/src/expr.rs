@@ -1,7 +1,7 @@
 use crate as tir;
 use fmt::Display;
 use ir::{DefId, FieldIdx, VariantIdx};
-use lc_core::ty::{AdtTy, Const, SubstsRef, Ty};
+use lc_core::ty::{AdtTy, Const, PointerCast, SubstsRef, Ty};
 use lc_span::Span;
 use std::fmt::{self, Formatter};
 
@@ -29,13 +29,21 @@ pub enum ExprKind<'tcx> {
     ItemRef(DefId, SubstsRef<'tcx>),
     /// (x, y)
     Tuple(Vec<tir::Expr<'tcx>>),
+    /// [x, y, z]
+    Array(Vec<tir::Expr<'tcx>>),
+    /// [x; n]
+    Repeat(Box<tir::Expr<'tcx>>, usize),
     /// f(x)
     Call(Box<tir::Expr<'tcx>>, Vec<tir::Expr<'tcx>>),
     Match(Box<tir::Expr<'tcx>>, Vec<tir::Arm<'tcx>>),
     /// x = y
     Assign(Box<tir::Expr<'tcx>>, Box<tir::Expr<'tcx>>),
+    /// x += y
+    AssignOp(lc_ast::BinOp, Box<tir::Expr<'tcx>>, Box<tir::Expr<'tcx>>),
     /// s.x
     Field(Box<tir::Expr<'tcx>>, FieldIdx),
+    /// xs[i]
+    Index(Box<tir::Expr<'tcx>>, Box<tir::Expr<'tcx>>),
     /// return x
     Ret(Option<Box<tir::Expr<'tcx>>>),
     /// &x
@@ -44,6 +52,8 @@ pub enum ExprKind<'tcx> {
     Deref(Box<tir::Expr<'tcx>>),
     /// box x
     Box(Box<tir::Expr<'tcx>>),
+    /// a pointer cast inserted by a coercion (e.g. `&S` to `&dyn Trait`)
+    Cast(Box<tir::Expr<'tcx>>, PointerCast),
     Closure {
         body: Box<tir::Body<'tcx>>,
         upvars: Vec<tir::Expr<'tcx>>,
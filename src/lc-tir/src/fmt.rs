@@ -88,6 +88,7 @@ where
     pub fn fmt_expr(&mut self, expr: &tir::Expr) -> fmt::Result {
         match &expr.kind {
             tir::ExprKind::Box(expr) => indent!(self, "(box {})", expr),
+            tir::ExprKind::Cast(expr, cast) => indent!(self, "({} as {:?})", expr, cast),
             tir::ExprKind::Loop(block) => indent!(self, "loop {}", block),
             tir::ExprKind::Const(c) => indent!(self, "{}", c),
             tir::ExprKind::Bin(op, l, r) => indent!(self, "({} {} {})", op, l, r),
@@ -95,7 +96,10 @@ where
             tir::ExprKind::Block(block) => self.fmt_block(block),
             tir::ExprKind::VarRef(_id) => indent!(self, "{}", expr.span.to_string()),
             tir::ExprKind::Field(base, field_idx) => indent!(self, "{}->{:?}", base, field_idx),
+            tir::ExprKind::Index(base, index) => indent!(self, "{}[{}]", base, index),
             tir::ExprKind::Tuple(xs) => indent!(self, "({})", lc_util::join2(xs.iter(), ",")),
+            tir::ExprKind::Array(xs) => indent!(self, "[{}]", lc_util::join2(xs.iter(), ",")),
+            tir::ExprKind::Repeat(x, n) => indent!(self, "[{}; {}]", x, n),
             tir::ExprKind::Ref(expr) => indent!(self, "(&{})", expr),
             tir::ExprKind::Deref(expr) => indent!(self, "(*{})", expr),
             tir::ExprKind::Ret(expr) => match expr {
@@ -107,6 +111,7 @@ where
                 indent!(self, "(λ({}) {})", lc_util::join2(body.params.iter(), ","), body),
             tir::ExprKind::Call(f, args) => self.fmt_call(f, args),
             tir::ExprKind::Assign(l, r) => indent!(self, "({} = {})", l, r),
+            tir::ExprKind::AssignOp(op, l, r) => indent!(self, "({} {}= {})", l, op, r),
             tir::ExprKind::ItemRef(_def_id, substs) =>
                 indent!(self, "{}<{}>", expr.span.to_string(), substs),
             tir::ExprKind::Adt { adt, fields, .. } => {
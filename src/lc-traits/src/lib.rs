@@ -1,61 +1,181 @@
-// mod interner;
-
-// use lc_core::ty::{Ty, TyCtx, TyKind};
-// use logic_ir::*;
-
-// #[derive(Copy, Clone)]
-// pub struct LInterner<'tcx> {
-//     tcx: TyCtx<'tcx>,
-// }
-
-// impl<'tcx> LInterner<'tcx> {
-//     fn tys_to_terms(self, ty: &[Ty<'tcx>]) -> Terms<Self> {
-//         Terms::intern(self, ty.iter().map(|ty| self.ty_to_term(ty)))
-//     }
-
-//     // this is probably a really bad idea, just experimenting
-//     fn ty_to_term(self, ty: Ty<'tcx>) -> Term<Self> {
-//         let mk_atom = |atom| Atom::new(Sym::intern(atom));
-//         let mk_term_atom = |atom| Term::intern(self, TermData::Atom(mk_atom(atom)));
-//         let mk_structure = |f, terms| Term::intern(self, TermData::Structure(mk_atom(f), terms));
-
-//         match ty.kind {
-//             TyKind::Box(inner) => mk_structure("box", self.tys_to_terms(&[inner])),
-//             TyKind::Tuple(tys) => mk_structure("tuple", self.tys_to_terms(tys)),
-//             TyKind::FnPtr(fnptr) => todo!(),
-//             TyKind::Array(_, _) => todo!(),
-//             TyKind::Infer(_) => todo!(),
-//             TyKind::Ptr(_) => todo!(),
-//             TyKind::Param(_) => todo!(),
-//             TyKind::Opaque(_, _) => todo!(),
-//             TyKind::Adt(adt, substs) => todo!(),
-//             TyKind::Bool => mk_term_atom("bool"),
-//             TyKind::Discr => mk_term_atom("discr"),
-//             TyKind::Char => mk_term_atom("char"),
-//             TyKind::Float => mk_term_atom("float"),
-//             TyKind::Int => mk_term_atom("int"),
-//             TyKind::Error => mk_term_atom("err"),
-//             TyKind::Never => mk_term_atom("never"),
-//         }
-//     }
-// }
-
-// #[cfg(test)]
-// mod tests {
-//     use super::*;
-//     use lc_driver::Driver;
-
-//     #[test]
-//     fn ty_to_term() {
-//         Driver::from_src("")
-//             .with_tcx(|tcx| {
-//                 let ty = tcx.mk_ty(TyKind::Int);
-//                 let ty = tcx.mk_tup_iter(vec![ty, ty, ty].into_iter());
-//                 dbg!(ty);
-//                 let interner = LInterner { tcx };
-//                 let term = interner.ty_to_term(ty);
-//                 dbg!(term);
-//             })
-//             .unwrap();
-//     }
-// }
+//! the trait solver
+//! proves (or refutes) that the obligations registered during typechecking hold
+//! using the impls of each trait and the bounds that are in scope
+
+#[macro_use]
+extern crate log;
+
+extern crate lc_ir as ir;
+
+mod trait_error;
+
+pub use trait_error::TraitError;
+
+use ir::DefId;
+use lc_core::ty::{HasTyFlags, Predicate, Predicates, Subst, TraitRef, TyKind};
+use lc_infer::{InferCtx, Obligation};
+use lc_span::Span;
+
+/// the maximum depth of nested obligations before the solver gives up
+/// (e.g. `impl<T: Trait> Trait for T {}` would otherwise never terminate)
+const RECURSION_LIMIT: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvaluationResult {
+    /// the predicate definitely holds
+    Proven,
+    /// there is no impl or bound in scope that satisfies the predicate
+    Refuted,
+    /// the recursion limit was reached before the predicate could be proven
+    Overflow,
+    /// the predicate may hold, but which impl or bound applies depends on an inference variable
+    /// that is not yet known
+    Ambiguous,
+}
+
+pub struct TraitSolver<'a, 'tcx> {
+    infcx: &'a InferCtx<'a, 'tcx>,
+    /// the predicates that are assumed to hold
+    /// i.e. the bounds of the item that is being typechecked
+    param_env: &'tcx Predicates<'tcx>,
+}
+
+impl<'a, 'tcx> TraitSolver<'a, 'tcx> {
+    /// creates a solver with the bounds of `def_id` in scope
+    pub fn new(infcx: &'a InferCtx<'a, 'tcx>, def_id: DefId) -> Self {
+        Self { infcx, param_env: infcx.predicates_of(def_id) }
+    }
+
+    /// creates a solver with no bounds in scope
+    pub fn without_param_env(infcx: &'a InferCtx<'a, 'tcx>) -> Self {
+        Self { infcx, param_env: infcx.tcx.alloc(Predicates::default()) }
+    }
+
+    /// attempts to prove each obligation, reporting an error for those that do not hold
+    /// this is deferred until the inference variables have been resolved (after fallback),
+    /// so any obligation that is still ambiguous at this point can never be proven
+    pub fn solve_obligations(&self, obligations: &[Obligation<'tcx>]) {
+        for obligation in obligations {
+            // don't report unsatisfied bounds involving types that have already errored
+            if obligation.predicate.contains_err() {
+                continue;
+            }
+            let err = match (self.evaluate(obligation.predicate), obligation.predicate) {
+                (EvaluationResult::Proven, _) => continue,
+                (EvaluationResult::Refuted, Predicate::Trait(trait_ref)) =>
+                    TraitError::Unsatisfied(trait_ref),
+                (EvaluationResult::Overflow, Predicate::Trait(trait_ref)) =>
+                    TraitError::Overflow(trait_ref),
+                (EvaluationResult::Ambiguous, Predicate::Trait(trait_ref)) =>
+                    TraitError::Ambiguous(trait_ref),
+            };
+            let mut diag = self.infcx.sess.build_error(obligation.span, err);
+            // obligations that don't originate from a bound (e.g. from an unsizing coercion)
+            // use the span of the expression as their bound span
+            if obligation.bound_span != obligation.span {
+                diag.labelled_span(obligation.bound_span, "required by this bound".to_owned());
+            }
+            diag.emit();
+        }
+    }
+
+    pub fn evaluate(&self, predicate: Predicate<'tcx>) -> EvaluationResult {
+        self.evaluate_predicate(predicate, 0)
+    }
+
+    fn evaluate_predicate(&self, predicate: Predicate<'tcx>, depth: usize) -> EvaluationResult {
+        debug!("evaluate predicate `{}` (depth {})", predicate, depth);
+        if depth > RECURSION_LIMIT {
+            return EvaluationResult::Overflow;
+        }
+        match predicate {
+            Predicate::Trait(trait_ref) => self.evaluate_trait_ref(trait_ref, depth),
+        }
+    }
+
+    fn evaluate_trait_ref(&self, trait_ref: TraitRef<'tcx>, depth: usize) -> EvaluationResult {
+        // if the self type is not yet known, then a matching impl or bound only shows that the
+        // predicate may hold (as the self type could still be inferred to be some other type)
+        let self_ty = self.infcx.shallow_resolve(trait_ref.self_ty);
+        let proven = match self_ty.kind {
+            TyKind::Infer(..) => EvaluationResult::Ambiguous,
+            _ => EvaluationResult::Proven,
+        };
+
+        // the bounds in scope are assumed to hold
+        let in_param_env =
+            self.param_env.predicates.iter().any(|&(predicate, _)| match predicate {
+                Predicate::Trait(assumption) =>
+                    self.infcx.probe(|_| self.unify(assumption, trait_ref)),
+            });
+        if in_param_env {
+            return proven;
+        }
+
+        // a trait object `dyn Trait` implements `Trait` through its vtable
+        if let TyKind::Dynamic(def_id, substs) = self_ty.kind {
+            let at = self.infcx.at(Span::default());
+            if def_id == trait_ref.def_id
+                && self.infcx.probe(|_| at.equate(substs, trait_ref.substs).is_ok())
+            {
+                return EvaluationResult::Proven;
+            }
+        }
+
+        let mut result = EvaluationResult::Refuted;
+        for &impl_def_id in self.infcx.trait_impls_of(trait_ref.def_id) {
+            match self.evaluate_impl(impl_def_id, trait_ref, depth) {
+                EvaluationResult::Proven if proven == EvaluationResult::Proven =>
+                    return EvaluationResult::Proven,
+                EvaluationResult::Proven | EvaluationResult::Ambiguous =>
+                    result = EvaluationResult::Ambiguous,
+                EvaluationResult::Overflow if result != EvaluationResult::Ambiguous =>
+                    result = EvaluationResult::Overflow,
+                EvaluationResult::Overflow | EvaluationResult::Refuted => {}
+            }
+        }
+        result
+    }
+
+    /// evaluates whether the impl with `impl_def_id` applies to `trait_ref`
+    /// that is, its header unifies with `trait_ref` and all of its own bounds hold
+    fn evaluate_impl(
+        &self,
+        impl_def_id: DefId,
+        trait_ref: TraitRef<'tcx>,
+        depth: usize,
+    ) -> EvaluationResult {
+        let infcx = self.infcx;
+        infcx.probe(|_| {
+            let substs = infcx.fresh_substs_for_item(impl_def_id);
+            let impl_trait_ref = infcx.impl_trait_ref(impl_def_id).unwrap();
+            let impl_trait_ref = impl_trait_ref.subst(infcx.tcx, substs);
+            if !self.unify(impl_trait_ref, trait_ref) {
+                return EvaluationResult::Refuted;
+            }
+
+            let mut result = EvaluationResult::Proven;
+            for &(predicate, _) in &infcx.predicates_of(impl_def_id).predicates {
+                let predicate = predicate.subst(infcx.tcx, substs);
+                match self.evaluate_predicate(predicate, depth + 1) {
+                    EvaluationResult::Proven => {}
+                    EvaluationResult::Refuted => return EvaluationResult::Refuted,
+                    EvaluationResult::Overflow => result = EvaluationResult::Overflow,
+                    EvaluationResult::Ambiguous if result == EvaluationResult::Proven =>
+                        result = EvaluationResult::Ambiguous,
+                    EvaluationResult::Ambiguous => {}
+                }
+            }
+            result
+        })
+    }
+
+    /// whether the two trait refs can be made equal
+    /// this should only be called within a `probe` as it may constrain inference variables
+    fn unify(&self, a: TraitRef<'tcx>, b: TraitRef<'tcx>) -> bool {
+        let at = self.infcx.at(Span::default());
+        a.def_id == b.def_id
+            && at.equate(a.self_ty, b.self_ty).is_ok()
+            && at.equate(a.substs, b.substs).is_ok()
+    }
+}
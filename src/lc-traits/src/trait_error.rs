@@ -0,0 +1,12 @@
+use lc_core::ty::TraitRef;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum TraitError<'tcx> {
+    #[error("the trait bound `{0}` is not satisfied")]
+    Unsatisfied(TraitRef<'tcx>),
+    #[error("overflow evaluating the requirement `{0}`")]
+    Overflow(TraitRef<'tcx>),
+    #[error("type annotations needed: cannot satisfy `{0}`")]
+    Ambiguous(TraitRef<'tcx>),
+}
@@ -1,5 +1,7 @@
 use crate::FnCtx;
+use ir::DefId;
 use lc_core::ty::{self, *};
+use lc_infer::Obligation;
 use lc_span::Span;
 use std::ops::Deref;
 
@@ -17,15 +19,19 @@ impl<'a, 'tcx> TypeRelation<'tcx> for Coercion<'a, 'tcx> {
     }
 
     fn relate_tys(&mut self, ty: Ty<'tcx>, target: Ty<'tcx>) -> TypeResult<'tcx, Ty<'tcx>> {
-        dbg!(ty);
-        dbg!(target);
         match (ty.kind, target.kind) {
             (ty::Never, _) => {
                 self.adjustments.push(Adjustment::new(target, AdjustmentKind::NeverToAny));
                 Ok(target)
             }
+            (ty::Boxed(inner), ty::Boxed(target_inner)) | (ty::Ptr(inner), ty::Ptr(target_inner)) =>
+                match self.shallow_resolve(target_inner).kind {
+                    ty::Dynamic(trait_def_id, substs) =>
+                        self.coerce_unsized(ty, inner, target, trait_def_id, substs),
+                    _ => self.at(self.span).equate(target, ty),
+                },
             // if it isn't one of the cases for coercion, fallback to `equate`
-            _ => self.at(self.span).equate(ty, target),
+            _ => self.at(self.span).equate(target, ty),
         }
     }
 }
@@ -39,9 +45,35 @@ impl<'a, 'tcx> Coercion<'a, 'tcx> {
         if ty.contains_err() || target.contains_err() {
             return Ok(vec![]);
         }
-        let ty = self.partially_resolve_ty(self.span, ty);
+        let ty = self.shallow_resolve(ty);
+        let target = self.shallow_resolve(target);
         self.relate_tys(ty, target)?;
-        Ok(vec![])
+        Ok(std::mem::take(&mut self.adjustments))
+    }
+
+    /// coerces the pointer `ty` to the trait object pointer `target`
+    /// (e.g. `&S` to `&dyn Trait`) which requires that the pointee implements the trait
+    fn coerce_unsized(
+        &mut self,
+        ty: Ty<'tcx>,
+        inner: Ty<'tcx>,
+        target: Ty<'tcx>,
+        trait_def_id: DefId,
+        substs: SubstsRef<'tcx>,
+    ) -> TypeResult<'tcx, Ty<'tcx>> {
+        let inner = self.shallow_resolve(inner);
+        // a pointer to a trait object (or to a type that is still unknown) is left as is
+        if let ty::Dynamic(..) | ty::Infer(TyVar(_)) = inner.kind {
+            return self.at(self.span).equate(target, ty);
+        }
+        let trait_ref = TraitRef::new(trait_def_id, inner, substs);
+        self.register_obligation(Obligation {
+            span: self.span,
+            bound_span: self.span,
+            predicate: Predicate::Trait(trait_ref),
+        });
+        self.adjustments.push(Adjustment::new(target, AdjustmentKind::Cast(PointerCast::Unsize)));
+        Ok(target)
     }
 }
 
@@ -65,7 +97,9 @@ impl<'a, 'tcx> FnCtx<'a, 'tcx> {
         target: Ty<'tcx>,
     ) -> TypeResult<'tcx, Ty<'tcx>> {
         Coercion::new(self, expr.span).coerce(ty, target).map(|adjustments| {
-            self.record_adjustments(expr.id, adjustments);
+            if !adjustments.is_empty() {
+                self.record_adjustments(expr.id, adjustments);
+            }
             if ty.contains_err() { self.set_ty_err() } else { target }
         })
     }
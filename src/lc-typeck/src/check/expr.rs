@@ -1,8 +1,11 @@
+use super::op::overloaded_binop;
 use super::FnCtx;
 use crate::{Autoderef, TyConv};
-use lc_ast::{BinOp, Ident, Lit, Mutability, UnaryOp};
+use ir::{DefKind, LangItem, Res};
+use lc_ast::{BinOp, Ident, Lit, LitFloatTy, LitIntTy, Mutability, UnaryOp};
 use itertools::Itertools;
 use lc_core::ty::{self, *};
+use lc_span::{sym, Span};
 use rustc_hash::FxHashMap;
 
 impl<'a, 'tcx> FnCtx<'a, 'tcx> {
@@ -11,18 +14,24 @@ impl<'a, 'tcx> FnCtx<'a, 'tcx> {
             ir::ExprKind::Box(expr) => self.check_expr_box(expr),
             ir::ExprKind::Loop(block) => self.check_expr_loop(expr, block),
             ir::ExprKind::Lit(lit) => self.check_lit(lit),
-            ir::ExprKind::Bin(op, l, r) => self.check_expr_binop(*op, l, r),
+            ir::ExprKind::Bin(op, l, r) => self.check_expr_binop(expr, *op, l, r),
             ir::ExprKind::Unary(op, operand) => self.check_expr_unary(expr, *op, operand),
             ir::ExprKind::Block(block) => self.check_block(block),
             ir::ExprKind::Path(qpath) => self.check_qpath(expr, qpath),
             ir::ExprKind::Tuple(xs) => self.check_expr_tuple(xs),
+            ir::ExprKind::Array(xs) => self.check_expr_array(expr, xs),
+            ir::ExprKind::Repeat(x, n) => self.check_expr_repeat(x, *n),
             ir::ExprKind::Closure(sig, body) => self.check_closure_expr(expr, sig, body),
             ir::ExprKind::Call(f, args) => self.check_call_expr(expr, f, args),
+            ir::ExprKind::MethodCall(receiver, ident, args) =>
+                self.check_method_call_expr(expr, receiver, *ident, args),
             ir::ExprKind::Match(expr, arms, src) => self.check_expr_match(expr, arms, src),
             ir::ExprKind::Struct(qpath, fields) => self.check_expr_struct(expr, qpath, fields),
             ir::ExprKind::Assign(l, r) => self.check_expr_assign(expr, l, r),
+            ir::ExprKind::AssignOp(op, l, r) => self.check_expr_assign_op(expr, *op, l, r),
             ir::ExprKind::Ret(ret) => self.check_expr_ret(expr, ret.as_deref()),
             ir::ExprKind::Field(base, ident) => self.check_expr_field(expr, base, *ident),
+            ir::ExprKind::Index(base, index) => self.check_expr_index(expr, base, index),
             ir::ExprKind::Break | ir::ExprKind::Continue => self.tcx.types.never,
             ir::ExprKind::Err => self.set_ty_err(),
         };
@@ -43,7 +52,11 @@ impl<'a, 'tcx> FnCtx<'a, 'tcx> {
     ) -> Ty<'tcx> {
         let operand_ty = self.check_expr(operand);
         match op {
-            UnaryOp::Neg => todo!(),
+            UnaryOp::Neg if self.is_overloaded_operand(operand_ty) =>
+                self.check_overloaded_op(expr, LangItem::Neg, sym::neg, &[(operand, operand_ty)]),
+            UnaryOp::Not if self.is_overloaded_operand(operand_ty) =>
+                self.check_overloaded_op(expr, LangItem::Not, sym::not, &[(operand, operand_ty)]),
+            UnaryOp::Neg => self.check_neg_operand(expr.span, operand_ty),
             UnaryOp::Not => {
                 self.unify(expr.span, self.types.bool, operand_ty);
                 self.types.bool
@@ -59,6 +72,21 @@ impl<'a, 'tcx> FnCtx<'a, 'tcx> {
         }
     }
 
+    /// unsigned integers can't be negated, operands of unknown type are assumed to be integers
+    fn check_neg_operand(&mut self, span: Span, ty: Ty<'tcx>) -> Ty<'tcx> {
+        let ty = self.shallow_resolve(ty);
+        if let ty::Infer(ty::TyVar(_)) = ty.kind {
+            let int_var = self.new_int_var();
+            self.unify(span, int_var, ty);
+            return int_var;
+        }
+        let is_negatable = ty.is_numeric() && !matches!(ty.kind, ty::Uint(_));
+        if !is_negatable && !ty.contains_err() {
+            return self.emit_ty_err(span, TypeError::InvalidUnaryOperand(UnaryOp::Neg, ty));
+        }
+        ty
+    }
+
     fn check_expr_box(&mut self, expr: &ir::Expr<'tcx>) -> Ty<'tcx> {
         let ty = self.check_expr(expr);
         self.mk_box_ty(ty)
@@ -123,6 +151,44 @@ impl<'a, 'tcx> FnCtx<'a, 'tcx> {
         (autoderef, self.emit_ty_err(expr.span, TypeError::BadFieldAccess(base_ty)))
     }
 
+    fn check_expr_index(
+        &mut self,
+        expr: &ir::Expr<'tcx>,
+        base: &ir::Expr<'tcx>,
+        index: &ir::Expr<'tcx>,
+    ) -> Ty<'tcx> {
+        let index_ty = self.check_expr(index);
+        let base_ty = self.check_expr(base);
+        if self.is_overloaded_operand(base_ty) {
+            let operands = [(base, base_ty), (index, index_ty)];
+            return self.check_overloaded_op(expr, LangItem::Index, sym::index, &operands);
+        }
+        // any integer type may be used as an index
+        let int_var = self.new_int_var();
+        self.unify(index.span, int_var, index_ty);
+        let (autoderef, ty) = self.check_index_expr_inner(expr, base_ty);
+        let adjustments = autoderef.get_adjustments();
+        self.record_adjustments(base.id, adjustments);
+        ty
+    }
+
+    fn check_index_expr_inner(
+        &mut self,
+        expr: &ir::Expr<'tcx>,
+        base_ty: Ty<'tcx>,
+    ) -> (Autoderef<'_, 'tcx>, Ty<'tcx>) {
+        let mut autoderef = self.autoderef(expr.span, base_ty);
+        if base_ty.contains_err() {
+            return (autoderef, base_ty);
+        }
+        for ty in &mut autoderef {
+            if let ty::Array(elem_ty, _) = ty.kind {
+                return (autoderef, elem_ty);
+            }
+        }
+        (autoderef, self.emit_ty_err(expr.span, TypeError::InvalidIndexBase(base_ty)))
+    }
+
     /// return expressions have the type of the expression that follows the return
     fn check_expr_ret(
         &mut self,
@@ -152,11 +218,29 @@ impl<'a, 'tcx> FnCtx<'a, 'tcx> {
     ) -> Ty<'tcx> {
         self.check_lvalue(l);
         let lty = self.check_expr(l);
+        self.check_overloaded_index_lvalue(l);
         let rty = self.check_expr(r);
         self.unify(expr.span, lty, rty);
         rty
     }
 
+    fn check_expr_assign_op(
+        &mut self,
+        expr: &ir::Expr<'tcx>,
+        op: BinOp,
+        l: &ir::Expr<'tcx>,
+        r: &ir::Expr<'tcx>,
+    ) -> Ty<'tcx> {
+        self.check_lvalue(l);
+        let tl = self.check_expr(l);
+        self.check_overloaded_index_lvalue(l);
+        let tr = self.check_expr(r);
+        // the result of `l op r` must be assignable back into `l`
+        let ty = self.check_binop(expr, op, (l, tl), (r, tr));
+        self.unify(expr.span, tl, ty);
+        self.tcx.types.unit
+    }
+
     fn check_expr_struct(
         &mut self,
         expr: &ir::Expr<'tcx>,
@@ -269,12 +353,68 @@ impl<'a, 'tcx> FnCtx<'a, 'tcx> {
         f: &ir::Expr<'tcx>,
         args: &[ir::Expr<'tcx>],
     ) -> Ty<'tcx> {
-        let ret = self.new_infer_var(expr.span);
         let f_ty = self.check_expr(f);
-        let params = self.check_expr_list(args);
-        let ty = self.tcx.mk_fn_ptr(FnSig { params, ret });
-        self.unify(expr.span, ty, f_ty);
-        ret
+        match self.shallow_resolve(f_ty).kind {
+            // when the signature is known, each argument is coerced to its parameter type
+            ty::FnPtr(sig) if sig.params.len() == args.len() => {
+                args.iter().zip(sig.params).for_each(|(arg, param)| {
+                    self.check_expr_coercible_to(arg, param);
+                });
+                sig.ret
+            }
+            _ => {
+                let ret = self.new_infer_var(expr.span);
+                let params = self.check_expr_list(args);
+                let ty = self.tcx.mk_fn_ptr(FnSig { params, ret });
+                self.unify(expr.span, ty, f_ty);
+                ret
+            }
+        }
+    }
+
+    fn check_method_call_expr(
+        &mut self,
+        expr: &ir::Expr<'tcx>,
+        receiver: &ir::Expr<'tcx>,
+        ident: Ident,
+        args: &[ir::Expr<'tcx>],
+    ) -> Ty<'tcx> {
+        let receiver_ty = self.check_expr(receiver);
+        if receiver_ty.contains_err() {
+            self.check_expr_list(args);
+            return receiver_ty;
+        }
+
+        let pick = match self.lookup_method(expr, receiver_ty, ident) {
+            Ok(pick) => pick,
+            Err(err) => {
+                self.emit_method_error(expr.span, err);
+                self.check_expr_list(args);
+                return self.set_ty_err();
+            }
+        };
+        self.record_adjustments(receiver.id, pick.adjustments);
+        self.record_type_relative_res(expr.id, Res::Def(pick.def_id, DefKind::AssocFn));
+
+        // the (adjusted) receiver is passed as the first argument
+        let f_ty = self.instantiate(expr, pick.def_id);
+        match self.shallow_resolve(f_ty).kind {
+            ty::FnPtr(sig) if sig.params.len() == args.len() + 1 => {
+                self.unify(expr.span, sig.params[0], pick.self_ty);
+                args.iter().zip(&sig.params[1..]).for_each(|(arg, &param)| {
+                    self.check_expr_coercible_to(arg, param);
+                });
+                sig.ret
+            }
+            _ => {
+                let ret = self.new_infer_var(expr.span);
+                let arg_tys = args.iter().map(|arg| self.check_expr(arg)).collect_vec();
+                let params = self.tcx.mk_substs(std::iter::once(pick.self_ty).chain(arg_tys));
+                let ty = self.tcx.mk_fn_ptr(FnSig { params, ret });
+                self.unify(expr.span, ty, f_ty);
+                ret
+            }
+        }
     }
 
     fn check_closure_expr(
@@ -316,6 +456,46 @@ impl<'a, 'tcx> FnCtx<'a, 'tcx> {
         self.tcx.mk_tup(self.check_expr_list(xs))
     }
 
+    /// all the elements must have the same type as the first element
+    /// the element type of an empty array is left to be inferred
+    fn check_expr_array(&mut self, expr: &ir::Expr<'tcx>, xs: &[ir::Expr<'tcx>]) -> Ty<'tcx> {
+        let elem_ty = match xs {
+            [] => self.new_infer_var(expr.span),
+            [x, rest @ ..] => {
+                let elem_ty = self.check_expr(x);
+                for x in rest {
+                    let ty = self.check_expr(x);
+                    self.unify(x.span, elem_ty, ty);
+                }
+                elem_ty
+            }
+        };
+        self.tcx.mk_array_ty(elem_ty, xs.len())
+    }
+
+    /// checks `expr` and coerces it to `target`
+    /// the elements of an array expression are each coerced to the element type of `target`
+    /// (e.g. so an array of trait objects can be built from values of different types)
+    pub fn check_expr_coercible_to(&mut self, expr: &ir::Expr<'tcx>, target: Ty<'tcx>) -> Ty<'tcx> {
+        if let (ir::ExprKind::Array(xs), ty::Array(elem_ty, n)) =
+            (&expr.kind, self.shallow_resolve(target).kind)
+        {
+            if xs.len() == n {
+                xs.iter().for_each(|x| {
+                    self.check_expr_coercible_to(x, elem_ty);
+                });
+                return self.record_ty(expr.id, target);
+            }
+        }
+        let ty = self.check_expr(expr);
+        self.coerce(expr, ty, target)
+    }
+
+    fn check_expr_repeat(&mut self, x: &ir::Expr<'tcx>, n: usize) -> Ty<'tcx> {
+        let ty = self.check_expr(x);
+        self.tcx.mk_array_ty(ty, n)
+    }
+
     fn check_block(&mut self, block: &ir::Block<'tcx>) -> Ty<'tcx> {
         if block.is_unsafe {
             self.with_unsafe_ctx(|fcx| fcx.check_block_inner(block))
@@ -355,40 +535,111 @@ impl<'a, 'tcx> FnCtx<'a, 'tcx> {
 
     fn check_expr_binop(
         &mut self,
+        expr: &ir::Expr<'tcx>,
         op: lc_ast::BinOp,
         l: &ir::Expr<'tcx>,
         r: &ir::Expr<'tcx>,
     ) -> Ty<'tcx> {
         let tl = self.check_expr(l);
         let tr = self.check_expr(r);
+        self.check_binop(expr, op, (l, tl), (r, tr))
+    }
+
+    /// checks `l op r` as a call to the overloaded operator if the left operand is not a
+    /// primitive, and as the builtin operator otherwise
+    fn check_binop(
+        &mut self,
+        expr: &ir::Expr<'tcx>,
+        op: BinOp,
+        (l, tl): (&ir::Expr<'tcx>, Ty<'tcx>),
+        (r, tr): (&ir::Expr<'tcx>, Ty<'tcx>),
+    ) -> Ty<'tcx> {
+        match overloaded_binop(op) {
+            Some((lang_item, method)) if self.is_overloaded_operand(tl) =>
+                self.check_overloaded_op(expr, lang_item, method, &[(l, tl), (r, tr)]),
+            _ => self.check_binop_operands(op, l, r, tl, tr),
+        }
+    }
+
+    /// checks the (already computed) operand types of a binary operator and returns the type of
+    /// the result
+    fn check_binop_operands(
+        &mut self,
+        op: BinOp,
+        l: &ir::Expr<'tcx>,
+        r: &ir::Expr<'tcx>,
+        tl: Ty<'tcx>,
+        tr: Ty<'tcx>,
+    ) -> Ty<'tcx> {
         match op {
-            BinOp::Eq => todo!(),
-            // TODO deal with floats
-            BinOp::Mul | BinOp::Div | BinOp::Add | BinOp::Sub => {
-                self.unify(l.span, self.tcx.types.int, tl);
+            BinOp::Mul | BinOp::Div | BinOp::Rem | BinOp::Add | BinOp::Sub => {
+                self.unify(r.span, tl, tr);
+                self.check_numeric_operand(l.span, op, tl)
+            }
+            BinOp::Shl | BinOp::Shr => {
+                self.unify(r.span, tl, tr);
+                self.check_operand(l.span, op, tl, Type::is_integral)
+            }
+            BinOp::Lt | BinOp::Gt | BinOp::Le | BinOp::Ge => {
                 self.unify(r.span, tl, tr);
-                tl
+                self.check_numeric_operand(l.span, op, tl);
+                self.tcx.types.bool
             }
-            BinOp::Lt | BinOp::Gt => {
-                // TODO deal with floats
-                self.unify(l.span, self.tcx.types.int, tl);
+            BinOp::Eq | BinOp::Neq => {
                 self.unify(r.span, tl, tr);
+                // strings are compared by value
+                self.check_operand(l.span, op, tl, |t| t.is_scalar() || matches!(t.kind, ty::Str));
                 self.tcx.types.bool
             }
-            BinOp::Neq => todo!(),
-            BinOp::And | BinOp::Or => {
-                self.unify(l.span, self.tcx.types.int, tl);
+            // the bitwise operators are also defined on `bool` (without short-circuiting)
+            BinOp::And | BinOp::Or | BinOp::Xor => {
                 self.unify(r.span, tl, tr);
-                tl
+                self.check_operand(l.span, op, tl, |ty| ty.is_integral() || ty.is_bool())
+            }
+            BinOp::AndAnd | BinOp::OrOr => {
+                let bool_ty = self.tcx.types.bool;
+                self.unify(l.span, bool_ty, tl);
+                self.unify(r.span, bool_ty, tr);
+                bool_ty
             }
         }
     }
 
+    fn check_numeric_operand(&mut self, span: Span, op: BinOp, ty: Ty<'tcx>) -> Ty<'tcx> {
+        self.check_operand(span, op, ty, Type::is_numeric)
+    }
+
+    /// checks that the (already unified) type of the operands of `op` satisfies `predicate`
+    /// operands of unknown type are assumed to be integers
+    fn check_operand(
+        &mut self,
+        span: Span,
+        op: BinOp,
+        ty: Ty<'tcx>,
+        predicate: impl FnOnce(&Type<'tcx>) -> bool,
+    ) -> Ty<'tcx> {
+        let ty = self.shallow_resolve(ty);
+        if let ty::Infer(ty::TyVar(_)) = ty.kind {
+            let int_var = self.new_int_var();
+            self.unify(span, int_var, ty);
+            return int_var;
+        }
+        if !predicate(ty) && !ty.contains_err() {
+            return self.emit_ty_err(span, TypeError::InvalidBinaryOperand(op, ty));
+        }
+        ty
+    }
+
     fn check_lit(&self, lit: &lc_ast::Lit) -> Ty<'tcx> {
-        match lit {
+        match *lit {
             Lit::Bool(..) => self.tcx.types.bool,
-            Lit::Float(..) => self.tcx.types.float,
-            Lit::Int(..) => self.tcx.types.int,
+            Lit::Float(_, LitFloatTy::Suffixed(float_ty)) => self.mk_ty(TyKind::Float(float_ty)),
+            Lit::Float(_, LitFloatTy::Unsuffixed) => self.new_float_var(),
+            Lit::Int(_, LitIntTy::Signed(int_ty)) => self.mk_ty(TyKind::Int(int_ty)),
+            Lit::Int(_, LitIntTy::Unsigned(uint_ty)) => self.mk_ty(TyKind::Uint(uint_ty)),
+            Lit::Int(_, LitIntTy::Unsuffixed) => self.new_int_var(),
+            Lit::Char(..) => self.tcx.types.char,
+            Lit::Str(..) => self.tcx.types.str,
         }
     }
 }
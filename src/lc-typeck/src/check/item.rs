@@ -1,5 +1,6 @@
 use ir::DefId;
 use lc_core::queries::Queries;
+use lc_core::ty::TypeError;
 use lc_core::TyCtx;
 
 pub fn provide(queries: &mut Queries) {
@@ -7,7 +8,15 @@ pub fn provide(queries: &mut Queries) {
 }
 
 fn validate_item_type(tcx: TyCtx<'_>, def_id: DefId) {
-    match tcx.defs().get(def_id) {
+    let def_node = tcx.defs().get(def_id);
+    // lowering the bounds reports the bounds that are not traits
+    if let ir::DefNode::Item(item) = def_node {
+        if item.generics().is_some() {
+            tcx.predicates_of(def_id);
+        }
+    }
+
+    match def_node {
         ir::DefNode::Item(item) => match item.kind {
             ir::ItemKind::Fn(..) => {}
             ir::ItemKind::Enum(..) | ir::ItemKind::Struct(..) => self::validate_adt(tcx, def_id),
@@ -15,9 +24,22 @@ fn validate_item_type(tcx: TyCtx<'_>, def_id: DefId) {
             ir::ItemKind::Extern(..) => {}
             ir::ItemKind::TypeAlias(..) => {}
             ir::ItemKind::Mod(..) => {}
-            ir::ItemKind::Trait { .. } => {}
-            ir::ItemKind::Impl { .. } => {}
+            ir::ItemKind::Trait { trait_item_refs, .. } =>
+                trait_item_refs.iter().for_each(|item_ref| tcx.validate_item_type(item_ref.id.0)),
+            ir::ItemKind::Impl { trait_path: Some(_), impl_item_refs, .. } => {
+                impl_item_refs.iter().for_each(|item_ref| tcx.validate_item_type(item_ref.id.0));
+                self::validate_trait_impl(tcx, item, impl_item_refs)
+            }
+            ir::ItemKind::Impl { trait_path: None, .. } => {}
         },
+        // lowering the type of an associated type reports any errors within it
+        ir::DefNode::ImplItem(ir::ImplItem { kind: ir::ImplItemKind::TyAlias(..), .. })
+        | ir::DefNode::TraitItem(ir::TraitItem {
+            kind: ir::TraitItemKind::TyAlias(Some(_)),
+            ..
+        }) => {
+            tcx.type_of(def_id);
+        }
         ir::DefNode::ImplItem(..) => {}
         ir::DefNode::TraitItem(..) => {}
         ir::DefNode::ForeignItem(..) => {}
@@ -28,6 +50,37 @@ fn validate_item_type(tcx: TyCtx<'_>, def_id: DefId) {
     }
 }
 
+/// checks that every item of the trait that has no default is provided by the impl
+fn validate_trait_impl<'tcx>(
+    tcx: TyCtx<'tcx>,
+    item: &ir::Item<'tcx>,
+    impl_item_refs: &[ir::ImplItemRef],
+) {
+    // the trait path failed to resolve to a trait which has already been reported
+    let trait_ref = match tcx.impl_trait_ref(item.id.def) {
+        Some(trait_ref) => trait_ref,
+        None => return,
+    };
+    // the trait may be of another package so its items are found through `assoc_items`
+    for trait_item in tcx.assoc_items(trait_ref.def_id) {
+        let is_implemented = impl_item_refs
+            .iter()
+            .any(|impl_item_ref| tcx.defs().ident(impl_item_ref.id.0) == trait_item.ident);
+        if trait_item.has_default() || is_implemented {
+            continue;
+        }
+        let trait_ident = tcx.defs().ident(trait_ref.def_id);
+        let err = TypeError::MissingTraitItem(trait_item.ident, trait_ident);
+        let mut diag = tcx.sess.build_error(item.span, err);
+        // the items of other packages have no span to point at
+        if !tcx.defs().is_extern(trait_item.def_id) {
+            let span = tcx.defs().span(trait_item.def_id);
+            diag.labelled_span(span, format!("`{}` declared here", trait_item.ident));
+        }
+        diag.emit();
+    }
+}
+
 /// we check for things such as the following
 /// incorrect number of generic arguments of the fields in the adt decl
 /// unrepresentable adts (i.e. infinite sized) (todo)
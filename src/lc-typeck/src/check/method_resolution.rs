@@ -1,7 +1,12 @@
 use crate::FnCtx;
-use ir::{DefId, DefKind, ImplItemRef, Res};
+use ir::{DefId, DefKind, Res};
+use itertools::Itertools;
 use lc_ast::Ident;
-use lc_core::ty::{self, Subst, Ty};
+use lc_core::ty::{
+    self, Adjuster, Adjustment, AssocItem, AssocItemKind, Predicate, Subst, TraitRef, Ty,
+};
+use lc_span::Span;
+use lc_traits::{EvaluationResult, TraitSolver};
 use std::ops::Deref;
 use thiserror::Error;
 
@@ -9,24 +14,15 @@ pub type MethodResult<'tcx, T> = Result<T, MethodError<'tcx>>;
 
 #[derive(Error, Debug)]
 pub enum MethodError<'tcx> {
+    /// holds the spans of each of the applicable candidates
     #[error("multiple applicable methods found")]
-    Ambiguous,
+    Ambiguous(Vec<Span>),
     #[error("no applicable method found")]
     None,
     #[error("")]
     _Marker(std::marker::PhantomData<&'tcx ()>),
 }
 
-// some ideas for dealing with overlapping trait impls
-// enum Specificity {
-//     // impl<V> Trait for V {}
-//     Generic,
-//     // impl<V> Trait for V where V : Bounds {}
-//     BoundedGeneric,
-//     // impl Trait for T {}
-//     Type,
-// }
-
 impl<'a, 'tcx> FnCtx<'a, 'tcx> {
     pub(crate) fn resolve_type_relative_path(
         &mut self,
@@ -37,22 +33,121 @@ impl<'a, 'tcx> FnCtx<'a, 'tcx> {
         // TODO maybe require the generic args in `segment` later?
         let rcx = MethodResolutionCtx::new(self, xpat, self_ty, segment.ident);
         rcx.resolve().unwrap_or_else(|err| {
-            self.sess.emit_error(xpat.span(), err);
+            self.emit_method_error(xpat.span(), err);
             Res::Err
         })
     }
+
+    /// resolves `Trait::ident` to the item named `ident` of the trait with `trait_def_id`
+    pub(crate) fn resolve_trait_relative_path(
+        &self,
+        xpat: &dyn ir::ExprOrPat<'tcx>,
+        trait_def_id: DefId,
+        segment: &ir::PathSegment<'tcx>,
+    ) -> Res {
+        self.assoc_items(trait_def_id)
+            .iter()
+            .find(|trait_item| {
+                trait_item.ident == segment.ident
+                    && matches!(trait_item.kind, AssocItemKind::Fn { .. })
+            })
+            .map(|trait_item| Res::Def(trait_item.def_id, trait_item.def_kind()))
+            .unwrap_or_else(|| {
+                self.emit_method_error(xpat.span(), MethodError::None);
+                Res::Err
+            })
+    }
+
+    /// looks up the method `ident` for a method call with a receiver of type `receiver_ty`
+    /// each autoderef step of the receiver is searched in turn for an inherent method
+    /// and then for a method of a trait that the step's type implements
+    pub(crate) fn lookup_method(
+        &self,
+        expr: &ir::Expr<'tcx>,
+        receiver_ty: Ty<'tcx>,
+        ident: Ident,
+    ) -> MethodResult<'tcx, MethodPick<'tcx>> {
+        let mut autoderef = self.autoderef(expr.span, receiver_ty);
+        let steps = (&mut autoderef).collect_vec();
+        // the `i`th step is reached by applying the first `i` derefs
+        let derefs = autoderef.get_adjustments();
+
+        for (i, &ty) in steps.iter().enumerate() {
+            let mut rcx = MethodResolutionCtx::new(self, expr, ty, ident);
+            rcx.collect_candidates();
+            // associated functions without a `self` parameter can't be called as methods
+            rcx.inherent_candidates.retain(|candidate| candidate.receiver.is_some());
+            rcx.trait_candidates.retain(|candidate| candidate.receiver.is_some());
+            let candidate = match rcx.select_candidate() {
+                Ok(candidate) => candidate,
+                Err(MethodError::None) => continue,
+                Err(err) => return Err(err),
+            };
+
+            let (adjustments, self_ty) = match candidate.receiver.unwrap() {
+                ReceiverKind::ByValue => (derefs[..i].to_vec(), ty),
+                // if this step was reached by dereferencing a box, we just use that box
+                ReceiverKind::ByRef if i > 0 => (derefs[..i - 1].to_vec(), steps[i - 1]),
+                ReceiverKind::ByRef => {
+                    let self_ty = self.mk_box_ty(ty);
+                    (vec![Adjustment::new_borrow(self_ty)], self_ty)
+                }
+            };
+            return Ok(MethodPick { def_id: candidate.def_id, adjustments, self_ty });
+        }
+
+        Err(MethodError::None)
+    }
+
+    pub(crate) fn emit_method_error(&self, span: Span, err: MethodError<'tcx>) {
+        let candidate_spans = match &err {
+            MethodError::Ambiguous(spans) => spans.clone(),
+            _ => vec![],
+        };
+        let mut diag = self.sess.build_error(span, err);
+        for span in candidate_spans {
+            diag.labelled_span(span, "candidate defined here".to_owned());
+        }
+        diag.emit();
+    }
+}
+
+/// the method selected for a method call expression
+pub(crate) struct MethodPick<'tcx> {
+    pub def_id: DefId,
+    /// the adjustments to apply to the receiver
+    pub adjustments: Vec<Adjustment<'tcx>>,
+    /// the type of the receiver after adjustment
+    pub self_ty: Ty<'tcx>,
+}
+
+/// how a method takes its receiver
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReceiverKind {
+    /// `self`
+    ByValue,
+    /// `&self`
+    ByRef,
 }
 
 #[derive(Debug)]
 struct Candidate<'tcx> {
     def_id: DefId,
     def_kind: DefKind,
+    span: Span,
+    /// `None` if the candidate has no `self` parameter
+    receiver: Option<ReceiverKind>,
     _pd: std::marker::PhantomData<&'tcx ()>,
 }
 
 impl<'tcx> Candidate<'tcx> {
-    pub fn new(def_id: DefId, def_kind: DefKind) -> Candidate<'tcx> {
-        Self { def_id, def_kind, _pd: Default::default() }
+    pub fn new(
+        def_id: DefId,
+        def_kind: DefKind,
+        span: Span,
+        receiver: Option<ReceiverKind>,
+    ) -> Candidate<'tcx> {
+        Self { def_id, def_kind, span, receiver, _pd: Default::default() }
     }
 }
 
@@ -64,6 +159,7 @@ pub(crate) struct MethodResolutionCtx<'a, 'tcx> {
     self_ty: Ty<'tcx>,
     ident: Ident,
     inherent_candidates: Vec<Candidate<'tcx>>,
+    trait_candidates: Vec<Candidate<'tcx>>,
 }
 
 trait InherentCandidates<'tcx> {
@@ -77,28 +173,109 @@ impl<'a, 'tcx> MethodResolutionCtx<'a, 'tcx> {
         self_ty: Ty<'tcx>,
         ident: Ident,
     ) -> Self {
-        Self { fcx, self_ty, xpat, ident, inherent_candidates: Default::default() }
+        Self {
+            fcx,
+            self_ty,
+            xpat,
+            ident,
+            inherent_candidates: Default::default(),
+            trait_candidates: Default::default(),
+        }
     }
 
-    fn collect_inherent_candidates(&mut self) {
+    fn collect_candidates(&mut self) {
         let ty = self.self_ty;
-        ty.inherent_candidates(self)
+        ty.inherent_candidates(self);
+        self.collect_trait_candidates();
     }
 
     fn resolve(mut self) -> MethodResult<'tcx, Res> {
-        self.collect_inherent_candidates();
-        self.resolve_candidates()
+        self.collect_candidates();
+        let selected = self.select_candidate()?;
+        Ok(Res::Def(selected.def_id, selected.def_kind))
     }
 
-    /// chooses a single candidate from the possibilities and returns a resolution to it
-    fn resolve_candidates(mut self) -> MethodResult<'tcx, Res> {
-        if self.inherent_candidates.len() == 1 {
-            let selected = self.inherent_candidates.pop().unwrap();
-            Ok(Res::Def(selected.def_id, selected.def_kind))
-        } else if self.inherent_candidates.is_empty() {
+    /// chooses a single candidate from the possibilities
+    /// inherent candidates take precedence over trait candidates
+    fn select_candidate(mut self) -> MethodResult<'tcx, Candidate<'tcx>> {
+        let candidates = if self.inherent_candidates.is_empty() {
+            &mut self.trait_candidates
+        } else {
+            &mut self.inherent_candidates
+        };
+        if candidates.len() == 1 {
+            Ok(candidates.pop().unwrap())
+        } else if candidates.is_empty() {
             Err(MethodError::None)
         } else {
-            Err(MethodError::Ambiguous)
+            let spans = candidates.iter().map(|candidate| candidate.span).collect();
+            Err(MethodError::Ambiguous(spans))
+        }
+    }
+
+    /// collects the items named `ident` of each trait (of this package or of another) that
+    /// `self_ty` may implement
+    fn collect_trait_candidates(&mut self) {
+        // an unresolved type could implement any trait so there is nothing to choose from
+        if let ty::Infer(ty::TyVar(..)) = self.self_ty.kind {
+            return;
+        }
+        let local_traits = self.tcx.ir.items.values().filter_map(|item| match item.kind {
+            ir::ItemKind::Trait { .. } => Some(item.id.def),
+            _ => None,
+        });
+        let traits = local_traits.chain(self.tcx.defs().extern_defs_of_kind(DefKind::Trait));
+        for trait_def_id in traits.collect_vec() {
+            for &trait_item in self.tcx.assoc_items(trait_def_id) {
+                if trait_item.ident != self.ident || !self.may_implement(trait_def_id) {
+                    continue;
+                }
+                let receiver = match trait_item.kind {
+                    AssocItemKind::Fn { has_self, .. } =>
+                        self.receiver_kind(trait_item.def_id, trait_def_id, has_self),
+                    AssocItemKind::Ty { .. } => continue,
+                };
+                let def_kind = trait_item.def_kind();
+                let candidate =
+                    Candidate::new(trait_item.def_id, def_kind, trait_item.ident.span, receiver);
+                self.trait_candidates.push(candidate);
+            }
+        }
+    }
+
+    /// whether `self_ty` may implement the trait with `trait_def_id` for some choice of the
+    /// trait's generic arguments (the obligation itself is checked after inference)
+    fn may_implement(&self, trait_def_id: DefId) -> bool {
+        self.probe(|_| {
+            // the first of the fresh substitutions is for `Self` which we already know
+            let substs = self.fresh_substs_for_item(trait_def_id);
+            let substs = self.mk_substs(substs[1..].iter().copied());
+            let trait_ref = TraitRef::new(trait_def_id, self.self_ty, substs);
+            let param_env_def_id = self.tables.borrow().def_id();
+            let solver = TraitSolver::new(self.infcx, param_env_def_id);
+            solver.evaluate(Predicate::Trait(trait_ref)) != EvaluationResult::Refuted
+        })
+    }
+
+    /// determines how the associated function `def_id` takes its `self` parameter (if it has one)
+    /// `parent_def_id` is the `DefId` of the impl or trait that the function belongs to
+    fn receiver_kind(
+        &self,
+        def_id: DefId,
+        parent_def_id: DefId,
+        has_self: bool,
+    ) -> Option<ReceiverKind> {
+        if !has_self {
+            return None;
+        }
+        let self_ty = self.type_of(parent_def_id);
+        let param_ty = self.type_of(def_id).expect_fn_ptr().params[0];
+        if param_ty == self_ty {
+            Some(ReceiverKind::ByValue)
+        } else if param_ty == self.mk_box_ty(self_ty) {
+            Some(ReceiverKind::ByRef)
+        } else {
+            None
         }
     }
 
@@ -118,7 +295,7 @@ impl<'tcx> InherentCandidates<'tcx> for Ty<'tcx> {
     fn inherent_candidates(&self, rcx: &mut MethodResolutionCtx) {
         match self.kind {
             ty::Adt(adt, _) => adt.def_id.inherent_candidates(rcx),
-            _ => todo!(),
+            _ => {}
         }
     }
 }
@@ -128,13 +305,7 @@ impl<'tcx> InherentCandidates<'tcx> for DefId {
         let inherent_impls = rcx.inherent_impls_of(*self);
 
         for &impl_def_id in inherent_impls {
-            let impl_block = rcx.ir.items[&impl_def_id];
-
-            match impl_block.kind {
-                ir::ItemKind::Impl { impl_item_refs, .. } =>
-                    impl_item_refs.inherent_candidates(rcx),
-                _ => unreachable!(),
-            }
+            rcx.assoc_items(impl_def_id).inherent_candidates(rcx);
         }
     }
 }
@@ -148,14 +319,17 @@ where
     }
 }
 
-impl<'tcx> InherentCandidates<'tcx> for ImplItemRef {
+impl<'tcx> InherentCandidates<'tcx> for AssocItem {
     fn inherent_candidates(&self, rcx: &mut MethodResolutionCtx) {
-        let impl_item = rcx.ir.impl_items[&self.id];
-        if impl_item.ident != rcx.ident {
+        if self.ident != rcx.ident {
             return;
         }
-        let def_kind = impl_item.kind.def_kind();
-        rcx.add_candidate(Candidate::new(self.id.0, def_kind));
+        let receiver = match self.kind {
+            AssocItemKind::Fn { has_self, .. } =>
+                rcx.receiver_kind(self.def_id, self.container.def_id(), has_self),
+            AssocItemKind::Ty { .. } => return,
+        };
+        rcx.add_candidate(Candidate::new(self.def_id, self.def_kind(), self.ident.span, receiver));
     }
 }
 
@@ -2,6 +2,7 @@ mod coerce;
 mod expr;
 mod item;
 mod method_resolution;
+mod op;
 mod pat;
 mod path;
 mod stmt;
@@ -136,7 +137,7 @@ impl<'a, 'tcx> InheritedCtx<'a, 'tcx> {
 
     /// top level entry point for typechecking a function item
     pub fn check_fn_item(&'a self, def_id: DefId, body: &ir::Body<'tcx>) -> FnCtx<'a, 'tcx> {
-        let sig = self.tcx.fn_sig(def_id);
+        let sig = self.infcx.normalize(self.tcx.fn_sig(def_id));
         // don't instantiate anything and typeck the body using the param tys
         self.check_fn(sig, body)
     }
@@ -0,0 +1,87 @@
+//! operators applied to types other than primitives are overloaded through the lang item traits
+//! declared in the prelude, e.g. `a + b` is checked (and later lowered) as `Add::add(a, b)`
+
+use super::FnCtx;
+use ir::{DefId, DefKind, LangItem, Res};
+use lc_ast::BinOp;
+use lc_core::ty::{self, *};
+use lc_span::{sym, Symbol};
+
+/// the lang item and method that the binary operator `op` is overloaded through
+/// (the bitwise, shift and short-circuiting operators can't be overloaded)
+pub(super) fn overloaded_binop(op: BinOp) -> Option<(LangItem, Symbol)> {
+    let overload = match op {
+        BinOp::Add => (LangItem::Add, sym::add),
+        BinOp::Sub => (LangItem::Sub, sym::sub),
+        BinOp::Mul => (LangItem::Mul, sym::mul),
+        BinOp::Div => (LangItem::Div, sym::div),
+        BinOp::Rem => (LangItem::Rem, sym::rem),
+        BinOp::Eq => (LangItem::Eq, sym::eq),
+        BinOp::Neq => (LangItem::Eq, sym::ne),
+        BinOp::Lt => (LangItem::Ord, sym::lt),
+        BinOp::Le => (LangItem::Ord, sym::le),
+        BinOp::Gt => (LangItem::Ord, sym::gt),
+        BinOp::Ge => (LangItem::Ord, sym::ge),
+        BinOp::Shl
+        | BinOp::Shr
+        | BinOp::And
+        | BinOp::Or
+        | BinOp::Xor
+        | BinOp::AndAnd
+        | BinOp::OrOr => return None,
+    };
+    Some(overload)
+}
+
+impl<'a, 'tcx> FnCtx<'a, 'tcx> {
+    /// whether an operator applied to an operand of type `ty` is overloaded rather than builtin
+    pub(super) fn is_overloaded_operand(&self, ty: Ty<'tcx>) -> bool {
+        matches!(self.shallow_resolve(ty).kind, ty::Adt(..) | ty::Param(..))
+    }
+
+    /// checks the operator expression `expr` as a call to `method` of the trait `lang_item`
+    /// with `operands` as the arguments (where the first operand is `self`)
+    pub(super) fn check_overloaded_op(
+        &mut self,
+        expr: &ir::Expr<'tcx>,
+        lang_item: LangItem,
+        method: Symbol,
+        operands: &[(&ir::Expr<'tcx>, Ty<'tcx>)],
+    ) -> Ty<'tcx> {
+        let def_id = self.lang_item_method(lang_item, method);
+        self.record_type_relative_res(expr.id, Res::Def(def_id, DefKind::AssocFn));
+        // as the substitutions of a trait method include `Self`, this also registers the
+        // obligation that the first operand implements the trait
+        let sig = match self.instantiate(expr, def_id).kind {
+            ty::FnPtr(sig) => sig,
+            _ => unreachable!(),
+        };
+        debug_assert_eq!(sig.params.len(), operands.len());
+        for (&(operand, ty), param) in operands.iter().zip(sig.params) {
+            self.unify(operand.span, param, ty);
+        }
+        self.normalize(sig.ret)
+    }
+
+    /// an overloaded index expression evaluates to a temporary so it can't be assigned to
+    pub(super) fn check_overloaded_index_lvalue(&self, l: &ir::Expr<'tcx>) {
+        let is_overloaded = self.tables.borrow().type_relative_resolutions().get(l.id).is_some();
+        if matches!(l.kind, ir::ExprKind::Index(..)) && is_overloaded {
+            self.emit_ty_err(l.span, TypeError::AssignToOverloadedIndex);
+        }
+    }
+
+    fn lang_item_method(&self, lang_item: LangItem, method: Symbol) -> DefId {
+        let trait_def_id = self.tcx.lang_item(lang_item);
+        let trait_item_refs = match self.ir.items[&trait_def_id].kind {
+            ir::ItemKind::Trait { trait_item_refs, .. } => trait_item_refs,
+            _ => unreachable!(),
+        };
+        trait_item_refs
+            .iter()
+            .map(|trait_item_ref| self.ir.trait_items[&trait_item_ref.id])
+            .find(|trait_item| trait_item.ident.symbol == method)
+            .map(|trait_item| trait_item.id.def)
+            .unwrap_or_else(|| panic!("lang item `{}` has no method `{}`", lang_item, method))
+    }
+}
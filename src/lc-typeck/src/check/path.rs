@@ -50,6 +50,21 @@ impl<'a, 'tcx> FnCtx<'a, 'tcx> {
     ) -> (Res, Ty<'tcx>) {
         match qpath {
             QPath::Resolved(path) => (path.res, self.check_expr_path(xpat, path)),
+            // `Trait::item` refers directly to the trait item
+            QPath::TypeRelative(
+                ir::Ty {
+                    kind:
+                        ir::TyKind::Path(QPath::Resolved(ir::Path {
+                            res: Res::Def(trait_def_id, DefKind::Trait),
+                            ..
+                        })),
+                    ..
+                },
+                segment,
+            ) => {
+                let res = self.resolve_trait_relative_path(xpat, *trait_def_id, segment);
+                self.check_type_relative_res(xpat, res)
+            }
             QPath::TypeRelative(self_ty, segment) =>
                 self.check_type_relative_path(xpat, self.ir_ty_to_ty(self_ty), segment),
         }
@@ -62,6 +77,23 @@ impl<'a, 'tcx> FnCtx<'a, 'tcx> {
         segment: &ir::PathSegment<'tcx>,
     ) -> (Res, Ty<'tcx>) {
         let res = self.resolve_type_relative_path(xpat, self_ty, segment);
+        let (res, ty) = self.check_type_relative_res(xpat, res);
+        // the `Self` of a trait item is the type the path is relative to
+        if let Res::Def(def_id, _) = res {
+            let container = self.assoc_item(def_id).map(|item| item.container);
+            if let Some(AssocContainer::Trait(..)) = container {
+                let substs = self.tables.borrow().node_substs_opt(xpat.id()).unwrap();
+                self.unify(xpat.span(), self_ty, substs[0]);
+            }
+        }
+        (res, ty)
+    }
+
+    fn check_type_relative_res(
+        &mut self,
+        xpat: &dyn ir::ExprOrPat<'tcx>,
+        res: Res,
+    ) -> (Res, Ty<'tcx>) {
         if let Res::Err = res {
             return (res, self.mk_ty_err());
         }
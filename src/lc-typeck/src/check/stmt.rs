@@ -11,9 +11,20 @@ impl<'a, 'tcx> FnCtx<'a, 'tcx> {
     }
 
     pub fn check_let_stmt(&mut self, l: &ir::Let<'tcx>) {
-        let ty =
-            l.init.map(|expr| self.check_expr(expr)).unwrap_or_else(|| self.new_infer_var(l.span));
-        l.ty.iter().for_each(|t| self.unify(l.span, self.ir_ty_to_ty(t), ty));
+        let ty = match l.ty {
+            // the initializer is coerced to the annotated type
+            Some(ty) => {
+                let ty = self.ir_ty_to_ty(ty);
+                if let Some(init) = l.init {
+                    self.check_expr_coercible_to(init, ty);
+                }
+                ty
+            }
+            None => l
+                .init
+                .map(|expr| self.check_expr(expr))
+                .unwrap_or_else(|| self.new_infer_var(l.span)),
+        };
         let pat_ty = self.check_pat(l.pat, ty);
         self.unify(l.span, ty, pat_ty);
     }
@@ -1 +1,101 @@
+//! coherence checking
+//! ensures there is at most one impl that applies to any given type, i.e.
+//! no two impls of a trait overlap and no two inherent impls define a method of the same name
+//! for overlapping types
+//! it also ensures that each impl is in a package where it can be checked against every other
+//! impl that may apply (the orphan rule)
 
+mod orphan;
+mod overlap;
+
+use ir::{DefId, DefKind};
+use lc_core::queries::Queries;
+use lc_core::ty::{TyCtx, TypeError};
+use orphan::check_orphan;
+use overlap::impls_overlap;
+
+pub(crate) fn provide(queries: &mut Queries) {
+    *queries = Queries { check_coherence: |tcx, ()| check_coherence(tcx), ..*queries }
+}
+
+fn check_coherence(tcx: TyCtx<'_>) {
+    for item in tcx.ir.items.values() {
+        match item.kind {
+            ir::ItemKind::Trait { .. } => check_trait_impls(tcx, item.id.def),
+            ir::ItemKind::Struct(..) | ir::ItemKind::Enum(..) =>
+                check_inherent_impls(tcx, item.id.def),
+            ir::ItemKind::Impl { .. } => check_orphan(tcx, item.id.def),
+            _ => {}
+        }
+    }
+    // the impls of the traits of other packages may overlap with the impls of this package
+    for trait_def_id in tcx.defs().extern_defs_of_kind(DefKind::Trait) {
+        check_trait_impls(tcx, trait_def_id);
+    }
+}
+
+/// reports each pair of impls of the trait with `trait_def_id` that overlap
+/// (the impls of other packages are collected after those of this package and a pair of them is
+/// not checked again here)
+fn check_trait_impls(tcx: TyCtx<'_>, trait_def_id: DefId) {
+    let impls = tcx.trait_impls_of(trait_def_id);
+    for (i, &impl_def_id) in impls.iter().enumerate() {
+        if tcx.defs().is_extern(impl_def_id) {
+            break;
+        }
+        for &other_def_id in &impls[i + 1..] {
+            if !impls_overlap(tcx, impl_def_id, other_def_id) {
+                continue;
+            }
+            let err = TypeError::ConflictingImpls(tcx.defs().ident(trait_def_id));
+            // the impl of this package is reported when the other impl has no span to point at
+            if tcx.defs().is_extern(other_def_id) {
+                tcx.sess.build_error(tcx.defs().span(impl_def_id), err).emit();
+                continue;
+            }
+            tcx.sess
+                .build_error(tcx.defs().span(other_def_id), err)
+                .labelled_span(tcx.defs().span(impl_def_id), "first implementation here".to_owned())
+                .emit();
+        }
+    }
+}
+
+/// reports the methods with the same name in inherent impls of the adt with `adt_def_id`
+/// that apply to overlapping types
+fn check_inherent_impls(tcx: TyCtx<'_>, adt_def_id: DefId) {
+    let impls = tcx.inherent_impls_of(adt_def_id);
+    for (i, &impl_def_id) in impls.iter().enumerate() {
+        for &other_def_id in &impls[i + 1..] {
+            let impl_items = inherent_impl_items(tcx, impl_def_id);
+            let duplicates = inherent_impl_items(tcx, other_def_id)
+                .into_iter()
+                .filter_map(|other_item| {
+                    let ident = tcx.defs().ident(other_item);
+                    let item = impl_items.iter().find(|&&item| tcx.defs().ident(item) == ident)?;
+                    Some((*item, other_item))
+                })
+                .collect::<Vec<_>>();
+            if duplicates.is_empty() || !impls_overlap(tcx, impl_def_id, other_def_id) {
+                continue;
+            }
+            for (item, other_item) in duplicates {
+                let ident = tcx.defs().ident(item);
+                let label = format!("other definition for `{}`", ident);
+                let err = TypeError::DuplicateDefinitions(ident);
+                tcx.sess
+                    .build_error(tcx.defs().span(other_item), err)
+                    .labelled_span(tcx.defs().span(item), label)
+                    .emit();
+            }
+        }
+    }
+}
+
+fn inherent_impl_items(tcx: TyCtx<'_>, impl_def_id: DefId) -> Vec<DefId> {
+    match tcx.ir.items[&impl_def_id].kind {
+        ir::ItemKind::Impl { impl_item_refs, .. } =>
+            impl_item_refs.iter().map(|impl_item_ref| impl_item_ref.id.0).collect(),
+        _ => unreachable!(),
+    }
+}
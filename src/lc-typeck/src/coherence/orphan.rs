@@ -0,0 +1,30 @@
+use ir::DefId;
+use lc_core::ty::{Ty, TyCtx, TyKind, TypeError};
+
+/// reports the impl with `impl_def_id` if it is an orphan
+/// an impl of a trait must be in the package of the trait or of the self type,
+/// and an inherent impl must be in the package of the self type
+/// (otherwise two packages could each provide an impl that is never checked against the other)
+pub(super) fn check_orphan(tcx: TyCtx<'_>, impl_def_id: DefId) {
+    let self_ty = tcx.type_of(impl_def_id);
+    if is_local(tcx, self_ty) {
+        return;
+    }
+    let err = match tcx.impl_trait_ref(impl_def_id) {
+        Some(trait_ref) if !tcx.defs().is_extern(trait_ref.def_id) => return,
+        Some(..) => TypeError::OrphanImpl,
+        None => TypeError::ForeignInherentImpl(self_ty),
+    };
+    tcx.sess.emit_error(tcx.defs().span(impl_def_id), err);
+}
+
+/// whether the type `ty` is defined in this package
+fn is_local(tcx: TyCtx<'_>, ty: Ty<'_>) -> bool {
+    match ty.kind {
+        TyKind::Adt(adt, _) => !tcx.defs().is_extern(adt.def_id),
+        TyKind::Dynamic(trait_def_id, _) => !tcx.defs().is_extern(trait_def_id),
+        // don't report an error for a type that is already erroneous
+        TyKind::Error => true,
+        _ => false,
+    }
+}
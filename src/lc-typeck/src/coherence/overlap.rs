@@ -0,0 +1,40 @@
+use ir::DefId;
+use lc_core::ty::{Subst, TyCtx};
+use lc_infer::TyCtxtInferExt;
+use lc_span::Span;
+use lc_traits::{EvaluationResult, TraitSolver};
+
+/// whether there is some type that both impls apply to
+/// the headers of the impls are unified after replacing their generic parameters with fresh
+/// inference variables, and the impls are considered disjoint if the bounds of either can
+/// never hold for the unified types
+pub(super) fn impls_overlap(tcx: TyCtx<'_>, impl_def_id: DefId, other_def_id: DefId) -> bool {
+    tcx.infer_ctx(impl_def_id).enter(|infcx| {
+        let at = infcx.at(Span::default());
+        let substs = infcx.fresh_substs_for_item(impl_def_id);
+        let other_substs = infcx.fresh_substs_for_item(other_def_id);
+
+        let self_ty = tcx.type_of(impl_def_id).subst(tcx, substs);
+        let other_self_ty = tcx.type_of(other_def_id).subst(tcx, other_substs);
+        if at.equate(self_ty, other_self_ty).is_err() {
+            return false;
+        }
+        // for impls of a trait, the generic arguments of the trait must unify too
+        if let (Some(trait_ref), Some(other_trait_ref)) =
+            (tcx.impl_trait_ref(impl_def_id), tcx.impl_trait_ref(other_def_id))
+        {
+            let trait_substs = trait_ref.substs.subst(tcx, substs);
+            let other_trait_substs = other_trait_ref.substs.subst(tcx, other_substs);
+            if at.equate(trait_substs, other_trait_substs).is_err() {
+                return false;
+            }
+        }
+
+        let solver = TraitSolver::without_param_env(&infcx);
+        [(impl_def_id, substs), (other_def_id, other_substs)].iter().all(|&(def_id, substs)| {
+            tcx.predicates_of(def_id).predicates.iter().all(|&(predicate, _)| {
+                solver.evaluate(predicate.subst(tcx, substs)) != EvaluationResult::Refuted
+            })
+        })
+    })
+}
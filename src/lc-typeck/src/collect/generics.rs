@@ -1,7 +1,10 @@
 use crate::TyConv;
-use ir::{DefId, DefNode};
+use ir::{DefId, DefNode, ParamIdx};
+use lc_ast::Ident;
 use lc_core::queries::Queries;
 use lc_core::ty::{self, TyCtx, TyParam};
+use lc_index::Idx;
+use lc_span::kw;
 
 pub(crate) fn provide(queries: &mut Queries) {
     *queries = Queries { generics_of, ..*queries }
@@ -10,14 +13,26 @@ pub(crate) fn provide(queries: &mut Queries) {
 pub fn generics_of(tcx: TyCtx<'_>, def_id: DefId) -> &ty::Generics<'_> {
     let generics = tcx.defs().generics(def_id);
 
-    // impl item is the only kind of defnode that may possibly have outer generics
-    let impl_generic_params = match tcx.defs().get(def_id) {
+    // impl items and trait items are the only kinds of defnode that may have outer generics
+    // a trait itself has the implicit `Self` parameter preceding its declared parameters
+    let parent_generic_params = match tcx.defs().get(def_id) {
         DefNode::ImplItem(item) => generics_of(tcx, item.impl_def_id).params,
+        DefNode::TraitItem(item) => generics_of(tcx, item.trait_def_id).params,
+        DefNode::Item(item) if matches!(item.kind, ir::ItemKind::Trait { .. }) => {
+            let self_param = TyParam {
+                id: item.id,
+                span: item.span,
+                ident: Ident::new(item.span, kw::USelf),
+                index: ParamIdx::new(0),
+                default: None,
+            };
+            tcx.alloc_iter(std::iter::once(self_param))
+        }
         _ => &[],
     };
 
     let generic_params = tcx.alloc_iter(generics.params.iter().map(
-        |&ir::TyParam { id, index, ident, span, default }| TyParam {
+        |&ir::TyParam { id, index, ident, span, default, .. }| TyParam {
             id,
             span,
             ident,
@@ -26,7 +41,7 @@ pub fn generics_of(tcx: TyCtx<'_>, def_id: DefId) -> &ty::Generics<'_> {
         },
     ));
 
-    let mut params = impl_generic_params.to_vec();
+    let mut params = parent_generic_params.to_vec();
     params.extend(generic_params);
     let params = tcx.alloc_iter(params);
 
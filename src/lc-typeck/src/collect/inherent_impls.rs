@@ -1,9 +1,9 @@
 //! collect inherent impls
 
 use crate::TyConv;
-use ir::{DefId, Visitor};
+use ir::{DefId, DefKind, Visitor};
 use lc_core::queries::Queries;
-use lc_core::ty::{self, InherentImpls, TyCtx};
+use lc_core::ty::{self, InherentImpls, TyCtx, TypeError};
 use rustc_hash::FxHashMap;
 
 pub(crate) fn provide(queries: &mut Queries) {
@@ -41,18 +41,12 @@ impl<'tcx> ir::Visitor<'tcx> for InherentCollector<'tcx> {
         debug_assert_eq!(self_ty, tcx.type_of(item.id.def));
 
         match self_ty.kind {
-            ty::Boxed(..) => todo!(),
-            ty::Array(..) => todo!(),
-            ty::FnPtr(..) => todo!(),
-            ty::Tuple(..) => todo!(),
-            ty::Infer(..) => todo!(),
-            ty::Ptr(..) => todo!(),
-            ty::Param(..) => todo!(),
-            ty::Opaque(..) => todo!(),
             ty::Adt(adt, _) => self.visit_def(adt.def_id, item.id.def),
-            ty::Bool | ty::Discr | ty::Char | ty::Float | ty::Int => todo!(),
-            ty::Never => todo!(),
-            ty::Error => (),
+            ty::Dynamic(trait_def_id, _) if !tcx.defs().is_extern(trait_def_id) =>
+                tcx.sess.emit_error(item.span, TypeError::DynamicInherentImpl(self_ty)),
+            // inherent impls for any other type are orphans, which are reported by the
+            // coherence check
+            _ => {}
         }
     }
 }
@@ -64,6 +58,15 @@ impl<'tcx> InherentCollector<'tcx> {
 
     fn collect(mut self) -> InherentImpls {
         self.visit_ir(self.tcx.ir);
+        // the inherent impls of other packages (which the orphan rule restricts to adts)
+        for impl_def_id in self.tcx.defs().extern_defs_of_kind(DefKind::Impl) {
+            if self.tcx.impl_trait_ref(impl_def_id).is_some() {
+                continue;
+            }
+            if let ty::Adt(adt, _) = self.tcx.type_of(impl_def_id).kind {
+                self.visit_def(adt.def_id, impl_def_id)
+            }
+        }
         InherentImpls { inherent_impls: self.inherent_impls }
     }
 
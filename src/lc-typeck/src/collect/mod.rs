@@ -2,6 +2,7 @@ use lc_core::queries::Queries;
 
 mod generics;
 mod inherent_impls;
+mod predicates;
 mod trait_impls;
 mod tys;
 
@@ -9,5 +10,6 @@ pub fn provide(queries: &mut Queries) {
     inherent_impls::provide(queries);
     trait_impls::provide(queries);
     generics::provide(queries);
+    predicates::provide(queries);
     tys::provide(queries);
 }
@@ -0,0 +1,47 @@
+use crate::TyConv;
+use ir::{DefId, DefNode};
+use lc_core::queries::Queries;
+use lc_core::ty::{self, Predicate, Substs, TraitRef, TyCtx};
+
+pub(crate) fn provide(queries: &mut Queries) {
+    *queries = Queries { predicates_of, ..*queries }
+}
+
+/// collects the trait bounds on the generic parameters and in the `where` clause of `def_id`
+pub fn predicates_of(tcx: TyCtx<'_>, def_id: DefId) -> &ty::Predicates<'_> {
+    // similarly to `generics_of`, impl items and trait items also have the predicates of their
+    // parent and constructors and variants have the predicates of their adt
+    let mut predicates = match tcx.defs().get(def_id) {
+        DefNode::ImplItem(item) => tcx.predicates_of(item.impl_def_id).predicates.clone(),
+        DefNode::TraitItem(item) => tcx.predicates_of(item.trait_def_id).predicates.clone(),
+        DefNode::Ctor(variant) | DefNode::Variant(variant) =>
+            return tcx.predicates_of(variant.adt_def_id),
+        // within a trait it is assumed that `Self` implements the trait
+        DefNode::Item(item) if matches!(item.kind, ir::ItemKind::Trait { .. }) => {
+            let trait_ref = TraitRef::from_substs(tcx, def_id, Substs::id_for_def(tcx, def_id));
+            vec![(Predicate::Trait(trait_ref), item.ident.span)]
+        }
+        _ => vec![],
+    };
+
+    let generics = tcx.defs().generics(def_id);
+    for param in generics.params {
+        let self_ty = tcx.mk_ty_param(param.id.def, param.index, param.ident);
+        for bound in param.bounds {
+            if let Some(trait_ref) = tcx.path_to_trait_ref(self_ty, bound) {
+                predicates.push((Predicate::Trait(trait_ref), bound.span));
+            }
+        }
+    }
+
+    for predicate in generics.predicates {
+        let self_ty = tcx.ir_ty_to_ty(predicate.ty);
+        for bound in predicate.bounds {
+            if let Some(trait_ref) = tcx.path_to_trait_ref(self_ty, bound) {
+                predicates.push((Predicate::Trait(trait_ref), bound.span));
+            }
+        }
+    }
+
+    tcx.alloc(ty::Predicates { predicates })
+}
@@ -1,18 +1,21 @@
 //! collect trait impls
 
-// TODO straight copied from inherent_impls
-// the current purpose of this is to validate trait impls (by running ir_ty_to_ty)
-
 use crate::TyConv;
-use ir::{DefId, Visitor};
+use ir::{DefId, DefKind, Visitor};
 use lc_core::queries::Queries;
-use lc_core::ty::{self, TraitImpls, TyCtx};
+use lc_core::ty::{TraitImpls, TraitRef, TyCtx};
 use rustc_hash::FxHashMap;
 
 pub(crate) fn provide(queries: &mut Queries) {
-    *queries = Queries { trait_impls: |tcx, ()| trait_impls(tcx), trait_impls_of, ..*queries }
+    *queries = Queries {
+        trait_impls: |tcx, ()| trait_impls(tcx),
+        trait_impls_of,
+        impl_trait_ref,
+        ..*queries
+    }
 }
 
+/// returns the impls of the trait with `def_id`
 fn trait_impls_of(tcx: TyCtx<'_>, def_id: DefId) -> &[DefId] {
     tcx.trait_impls(()).trait_impls.get(&def_id).map_or(&[], |xs| xs)
 }
@@ -21,10 +24,23 @@ fn trait_impls(tcx: TyCtx<'_>) -> &TraitImpls {
     tcx.alloc(TraitImplCollector::new(tcx).collect())
 }
 
-/// collects inherent impls
-/// i.e. implementation blocks on a type (not as part of a trait)
-/// e.g. impl S { ...  }
-/// the `DefId` of the `impl` item itself will be recorded
+/// returns the trait that is implemented by the impl with `def_id`
+/// (or `None` if it is an inherent impl)
+fn impl_trait_ref(tcx: TyCtx<'_>, def_id: DefId) -> Option<TraitRef<'_>> {
+    match tcx.defs().get(def_id) {
+        ir::DefNode::Item(item) => match item.kind {
+            ir::ItemKind::Impl { trait_path, .. } =>
+                tcx.path_to_trait_ref(tcx.type_of(def_id), trait_path?),
+            _ => panic!("`{}` is not an impl", item.ident),
+        },
+        node => panic!("`{}` is not an impl", node.descr()),
+    }
+}
+
+/// collects trait impls
+/// i.e. implementations of a trait for a type
+/// e.g. impl Trait for S { ...  }
+/// the `DefId` of the `impl` item itself will be recorded against the `DefId` of the trait
 struct TraitImplCollector<'tcx> {
     tcx: TyCtx<'tcx>,
     trait_impls: FxHashMap<DefId, Vec<DefId>>,
@@ -32,28 +48,12 @@ struct TraitImplCollector<'tcx> {
 
 impl<'tcx> ir::Visitor<'tcx> for TraitImplCollector<'tcx> {
     fn visit_item(&mut self, item: &'tcx ir::Item<'tcx>) {
-        let tcx = self.tcx;
-        let self_ty = match item.kind {
-            ir::ItemKind::Impl { self_ty, trait_path: Some(_), .. } => tcx.ir_ty_to_ty(self_ty),
-            _ => return,
-        };
-
-        // sanity check that these types are consistent
-        debug_assert_eq!(self_ty, tcx.type_of(item.id.def));
-
-        match self_ty.kind {
-            ty::Boxed(..) => todo!(),
-            ty::Array(..) => todo!(),
-            ty::FnPtr(..) => todo!(),
-            ty::Tuple(..) => todo!(),
-            ty::Infer(..) => todo!(),
-            ty::Ptr(..) => todo!(),
-            ty::Param(..) => todo!(),
-            ty::Opaque(..) => todo!(),
-            ty::Adt(adt, _) => self.visit_def(adt.def_id, item.id.def),
-            ty::Bool | ty::Discr | ty::Char | ty::Float | ty::Int => todo!(),
-            ty::Never => todo!(),
-            ty::Error => (),
+        if let ir::ItemKind::Impl { self_ty, trait_path: Some(_), .. } = item.kind {
+            // sanity check that these types are consistent
+            debug_assert_eq!(self.tcx.ir_ty_to_ty(self_ty), self.tcx.type_of(item.id.def));
+            if let Some(trait_ref) = self.tcx.impl_trait_ref(item.id.def) {
+                self.visit_def(trait_ref.def_id, item.id.def)
+            }
         }
     }
 }
@@ -65,10 +65,16 @@ impl<'tcx> TraitImplCollector<'tcx> {
 
     fn collect(mut self) -> TraitImpls {
         self.visit_ir(self.tcx.ir);
+        // the trait impls of other packages (whose trait refs are provided by their metadata)
+        for impl_def_id in self.tcx.defs().extern_defs_of_kind(DefKind::Impl) {
+            if let Some(trait_ref) = self.tcx.impl_trait_ref(impl_def_id) {
+                self.visit_def(trait_ref.def_id, impl_def_id)
+            }
+        }
         TraitImpls { trait_impls: self.trait_impls }
     }
 
-    fn visit_def(&mut self, type_def_id: DefId, impl_def_id: DefId) {
-        self.trait_impls.entry(type_def_id).or_insert_with(Default::default).push(impl_def_id);
+    fn visit_def(&mut self, trait_def_id: DefId, impl_def_id: DefId) {
+        self.trait_impls.entry(trait_def_id).or_insert_with(Default::default).push(impl_def_id);
     }
 }
@@ -15,6 +15,8 @@ mod autoderef;
 mod check;
 mod coherence;
 pub mod collect;
+mod object_safety;
+mod privacy;
 mod tyconv;
 mod type_of;
 mod upvars;
@@ -27,7 +29,9 @@ use autoderef::Autoderef;
 use lc_core::queries::Queries;
 
 pub fn provide(queries: &mut Queries) {
+    coherence::provide(queries);
     collect::provide(queries);
     check::provide(queries);
+    privacy::provide(queries);
     type_of::provide(queries);
 }
@@ -0,0 +1,78 @@
+//! a trait is object safe if each of its methods can be called through a vtable
+//! i.e. it has no associated types, and each method takes `&self`, is not generic,
+//! and does not otherwise mention `Self` in its signature
+//! (this only inspects the ir so that it may be used while lowering types)
+
+use ir::{DefId, QPath, Res, Visitor};
+use lc_core::ty::TyCtx;
+use lc_span::Span;
+
+/// returns the reasons the trait with `trait_def_id` cannot be made into an object
+/// along with the span of each offending trait item
+pub(crate) fn object_safety_violations(tcx: TyCtx<'_>, trait_def_id: DefId) -> Vec<(Span, String)> {
+    let trait_item_refs = match tcx.ir.items[&trait_def_id].kind {
+        ir::ItemKind::Trait { trait_item_refs, .. } => trait_item_refs,
+        _ => unreachable!(),
+    };
+
+    let mut violations = vec![];
+    for trait_item_ref in trait_item_refs {
+        let trait_item = tcx.ir.trait_items[&trait_item_ref.id];
+        let ident = trait_item.ident;
+        let sig = match trait_item.kind {
+            ir::TraitItemKind::Fn(sig, _) => sig,
+            ir::TraitItemKind::TyAlias(..) => {
+                violations.push((
+                    trait_item.span,
+                    format!("the trait has the associated type `{}`", ident),
+                ));
+                continue;
+            }
+        };
+
+        if !trait_item.generics.params.is_empty() {
+            violations.push((
+                trait_item.generics.span,
+                format!("method `{}` has generic type parameters", ident),
+            ));
+        }
+
+        let mut inputs = sig.inputs.iter();
+        if !sig.has_self || !is_self_box(inputs.next().unwrap()) {
+            violations.push((trait_item.span, format!("method `{}` does not take `&self`", ident)));
+        }
+
+        let mut visitor = SelfTyVisitor { found: false };
+        inputs.chain(sig.output).for_each(|ty| visitor.visit_ty(ty));
+        if visitor.found {
+            violations.push((
+                trait_item.span,
+                format!("method `{}` references the `Self` type in its signature", ident),
+            ));
+        }
+    }
+    violations
+}
+
+/// whether `ty` is `&Self`
+fn is_self_box(ty: &ir::Ty<'_>) -> bool {
+    match ty.kind {
+        ir::TyKind::Box(ty) => matches!(
+            ty.kind,
+            ir::TyKind::Path(QPath::Resolved(ir::Path { res: Res::SelfTy { .. }, .. }))
+        ),
+        _ => false,
+    }
+}
+
+/// finds any occurence of `Self` in a type
+struct SelfTyVisitor {
+    found: bool,
+}
+
+impl<'ir> Visitor<'ir> for SelfTyVisitor {
+    fn visit_path(&mut self, path: &'ir ir::Path<'ir>) {
+        self.found |= matches!(path.res, Res::SelfTy { .. });
+        ir::walk_path(self, path);
+    }
+}
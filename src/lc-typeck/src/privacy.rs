@@ -0,0 +1,259 @@
+//! privacy checking
+//! a private item is only accessible from within the module it is defined in (and the
+//! descendants of that module), similarly for the private fields of a struct
+//! this runs after typechecking as method calls and field accesses are only resolved then
+
+use ir::{DefId, DefKind, DefNode, FieldIdx, Res, Visitor};
+use lc_ast::VisibilityKind;
+use lc_core::queries::Queries;
+use lc_core::ty::{self, AdtTy, TyCtx, TypeError, TypeckTables};
+use lc_index::Idx;
+use lc_span::Span;
+use rustc_hash::FxHashMap;
+
+pub(crate) fn provide(queries: &mut Queries) {
+    *queries = Queries { check_privacy: |tcx, ()| check_privacy(tcx), ..*queries }
+}
+
+fn check_privacy(tcx: TyCtx<'_>) {
+    PrivacyChecker::new(tcx).visit_ir(tcx.ir);
+}
+
+struct PrivacyChecker<'tcx> {
+    tcx: TyCtx<'tcx>,
+    /// the module each item is defined in, the items of the root module are absent
+    parents: FxHashMap<DefId, DefId>,
+    /// the module of the item currently being checked (`None` is the root module)
+    curr_module: Option<DefId>,
+    /// the typeck tables of the body currently being checked
+    tables: Option<&'tcx TypeckTables<'tcx>>,
+}
+
+impl<'tcx> PrivacyChecker<'tcx> {
+    fn new(tcx: TyCtx<'tcx>) -> Self {
+        let mut parents = FxHashMap::default();
+        for item in tcx.ir.items.values() {
+            if let ir::ItemKind::Mod(module) = &item.kind {
+                parents.extend(module.items.iter().map(|&child| (child, item.id.def)));
+            }
+        }
+        // foreign items are defined in the module of their extern block
+        for item in tcx.ir.items.values() {
+            if let (ir::ItemKind::Extern(_, foreign_items), Some(&module)) =
+                (&item.kind, parents.get(&item.id.def))
+            {
+                parents.extend(foreign_items.iter().map(|foreign| (foreign.id.def, module)));
+            }
+        }
+        Self { tcx, parents, curr_module: None, tables: None }
+    }
+
+    fn tables(&self) -> &'tcx TypeckTables<'tcx> {
+        self.tables.expect("expressions and patterns are only checked within a body")
+    }
+
+    fn with_item(&mut self, def_id: DefId, f: impl FnOnce(&mut Self)) {
+        let prev = self.curr_module;
+        self.curr_module = self.module_of(def_id);
+        f(self);
+        self.curr_module = prev;
+    }
+
+    /// the module that `def_id` is defined in (`None` is the root module)
+    fn module_of(&self, def_id: DefId) -> Option<DefId> {
+        match self.tcx.defs().get(def_id) {
+            DefNode::Item(..) | DefNode::ForeignItem(..) => self.parents.get(&def_id).copied(),
+            DefNode::ImplItem(impl_item) => self.module_of(impl_item.impl_def_id),
+            DefNode::TraitItem(trait_item) => self.module_of(trait_item.trait_def_id),
+            DefNode::Ctor(variant) | DefNode::Variant(variant) =>
+                self.module_of(variant.adt_def_id),
+            node @ (DefNode::TyParam(..) | DefNode::Field(..)) =>
+                unreachable!("{} is not defined directly within a module", node.descr()),
+        }
+    }
+
+    /// the items of traits and trait impls and the variants of enums are always public
+    fn vis_of(&self, def_id: DefId) -> VisibilityKind {
+        match self.tcx.defs().get(def_id) {
+            DefNode::Item(item) => item.vis.node,
+            DefNode::ForeignItem(foreign_item) => foreign_item.vis.node,
+            DefNode::ImplItem(impl_item) => match self.tcx.ir.items[&impl_item.impl_def_id].kind {
+                ir::ItemKind::Impl { trait_path: Some(..), .. } => VisibilityKind::Public,
+                _ => impl_item.vis.node,
+            },
+            DefNode::TraitItem(..)
+            | DefNode::Ctor(..)
+            | DefNode::Variant(..)
+            | DefNode::TyParam(..)
+            | DefNode::Field(..) => VisibilityKind::Public,
+        }
+    }
+
+    /// whether something defined in `module` with visibility `vis` is accessible from the
+    /// current module
+    fn is_accessible(&self, vis: VisibilityKind, module: Option<DefId>) -> bool {
+        if vis == VisibilityKind::Public {
+            return true;
+        }
+        let mut curr = self.curr_module;
+        loop {
+            if curr == module {
+                return true;
+            }
+            match curr {
+                Some(def_id) => curr = self.module_of(def_id),
+                None => return false,
+            }
+        }
+    }
+
+    fn check_res(&self, span: Span, res: Res) {
+        let (def_id, def_kind) = match res {
+            Res::Def(def_id, def_kind) if !matches!(def_kind, DefKind::TyParam(..)) =>
+                (def_id, def_kind),
+            _ => return,
+        };
+        // only public items are exported by other packages, but the items of their impls are
+        // found by type relative paths and method calls regardless
+        let is_extern = self.tcx.defs().is_extern(def_id);
+        let is_accessible = if is_extern {
+            self.tcx.assoc_item(def_id).map_or(true, |item| item.vis == VisibilityKind::Public)
+        } else {
+            self.is_accessible(self.vis_of(def_id), self.module_of(def_id))
+        };
+        if is_accessible {
+            return;
+        }
+        let ident = self.tcx.defs().ident(def_id);
+        let mut diag = self.tcx.sess.build_error(span, TypeError::PrivateItem(def_kind, ident));
+        if !is_extern {
+            diag.labelled_span(self.tcx.defs().span(def_id), format!("`{}` defined here", ident));
+        }
+        diag.emit();
+    }
+
+    /// checks the field `idx` of `adt` is accessible (the fields of enum variants always are)
+    fn check_field(&self, span: Span, adt: &AdtTy, idx: FieldIdx) {
+        if adt.is_enum() {
+            return;
+        }
+        let field = &adt.single_variant().fields[idx.index()];
+        let is_extern = self.tcx.defs().is_extern(adt.def_id);
+        let is_accessible = if is_extern {
+            // only the public fields of the structs of other packages are accessible
+            field.vis.node == VisibilityKind::Public
+        } else {
+            self.is_accessible(field.vis.node, self.module_of(adt.def_id))
+        };
+        if is_accessible {
+            return;
+        }
+        let err = TypeError::PrivateField(field.ident, adt.ident);
+        let mut diag = self.tcx.sess.build_error(span, err);
+        if !is_extern {
+            diag.labelled_span(self.tcx.defs().span(field.def_id), "field defined here".to_owned());
+        }
+        diag.emit();
+    }
+
+    /// checks the resolution of a type relative path (e.g. `S::new`) in an expression or pattern
+    /// (the resolved paths are checked by `visit_path`)
+    fn check_type_relative(&self, xpat: &dyn ir::ExprOrPat<'tcx>, qpath: &ir::QPath<'tcx>) {
+        if let ir::QPath::TypeRelative(..) = qpath {
+            if let Some(&res) = self.tables().type_relative_resolutions().get(xpat.id()) {
+                self.check_res(xpat.span(), res);
+            }
+        }
+    }
+
+    fn check_field_expr(&self, expr: &ir::Expr<'tcx>, base: &ir::Expr<'tcx>) {
+        let tables = self.tables();
+        // the field belongs to the type of the base after autoderef
+        let base_ty = tables
+            .adjustments_for_expr(base)
+            .last()
+            .map_or_else(|| tables.node_type(base.id), |adjustment| adjustment.ty);
+        if let (ty::Adt(adt, _), Some(idx)) = (base_ty.kind, tables.field_index_opt(expr.id)) {
+            self.check_field(expr.span, adt, idx);
+        }
+    }
+
+    fn check_struct_expr(&self, expr: &ir::Expr<'tcx>, fields: &[ir::Field<'tcx>]) {
+        let tables = self.tables();
+        if let ty::Adt(adt, _) = tables.node_type(expr.id).kind {
+            for field in fields {
+                if let Some(idx) = tables.field_index_opt(field.id) {
+                    self.check_field(field.span, adt, idx);
+                }
+            }
+        }
+    }
+
+    fn check_struct_pat(&self, pat: &ir::Pattern<'tcx>, fields: &[ir::FieldPat<'tcx>]) {
+        let tables = self.tables();
+        if let ty::Adt(adt, _) = tables.node_type(pat.id).kind {
+            for field in fields {
+                if let Some(idx) = tables.field_index_opt(field.pat.id) {
+                    self.check_field(field.span, adt, idx);
+                }
+            }
+        }
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for PrivacyChecker<'tcx> {
+    fn visit_item(&mut self, item: &'tcx ir::Item<'tcx>) {
+        self.with_item(item.id.def, |this| ir::walk_item(this, item));
+    }
+
+    fn visit_impl_item(&mut self, impl_item: &'tcx ir::ImplItem<'tcx>) {
+        self.with_item(impl_item.id.def, |this| ir::walk_impl_item(this, impl_item));
+    }
+
+    fn visit_trait_item(&mut self, trait_item: &'tcx ir::TraitItem<'tcx>) {
+        self.with_item(trait_item.id.def, |this| ir::walk_trait_item(this, trait_item));
+    }
+
+    fn visit_body(&mut self, body: &'tcx ir::Body<'tcx>) {
+        // closures are typechecked along with their enclosing function
+        let prev = self.tables.replace(self.tcx.typeck(body.expr.id.def));
+        ir::walk_body(self, body);
+        self.tables = prev;
+    }
+
+    fn visit_path(&mut self, path: &'tcx ir::Path<'tcx>) {
+        self.check_res(path.span, path.res);
+        ir::walk_path(self, path);
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx ir::Expr<'tcx>) {
+        match expr.kind {
+            ir::ExprKind::Path(qpath) => self.check_type_relative(expr, qpath),
+            ir::ExprKind::Struct(qpath, fields) => {
+                self.check_type_relative(expr, qpath);
+                self.check_struct_expr(expr, fields);
+            }
+            ir::ExprKind::MethodCall(..) => {
+                if let Some(&res) = self.tables().type_relative_resolutions().get(expr.id) {
+                    self.check_res(expr.span, res);
+                }
+            }
+            ir::ExprKind::Field(base, _) => self.check_field_expr(expr, base),
+            _ => {}
+        }
+        ir::walk_expr(self, expr);
+    }
+
+    fn visit_pat(&mut self, pat: &'tcx ir::Pattern<'tcx>) {
+        match pat.kind {
+            ir::PatternKind::Variant(qpath, _) | ir::PatternKind::Path(qpath) =>
+                self.check_type_relative(pat, qpath),
+            ir::PatternKind::Struct(qpath, fields) => {
+                self.check_type_relative(pat, qpath);
+                self.check_struct_pat(pat, fields);
+            }
+            _ => {}
+        }
+        ir::walk_pat(self, pat);
+    }
+}
@@ -10,3 +10,125 @@ fn check_box_expr() {
     typeck!("fn main() -> int { let x = 5; let boxed = box x; 5 }");
     // dbg!(_tir);
 }
+
+#[test]
+fn check_string_literal() {
+    typeck!(r#"fn main() -> int { let s: str = "hello"; 0 }"#);
+    expect_type_error!(r#"fn main() -> int { let s: int = "hello"; 0 }"#);
+}
+
+#[test]
+fn check_char_literal() {
+    typeck!("fn main() -> int { let c: char = 'c'; 0 }");
+    expect_type_error!("fn main() -> int { let c: int = 'c'; 0 }");
+}
+
+#[test]
+fn check_suffixed_int_literal() {
+    typeck!("fn main() -> int { let x: u8 = 5u8; let y: i32 = 5i32; 0 }");
+    expect_type_error!("fn main() -> int { let x: i32 = 5u8; 0 }");
+}
+
+#[test]
+fn check_unsuffixed_int_literal_range() {
+    typeck!("fn main() -> int { let x: u8 = 255; let y: i8 = -128; 0 }");
+    typeck!("fn main() -> int { let x: u64 = 18446744073709551615; 0 }");
+    expect_type_error!("fn main() -> int { let x: u8 = 300; 0 }");
+    expect_type_error!("fn main() -> int { let x: i8 = 129; 0 }");
+    // only negated signed literals may be one larger than the maximum
+    expect_type_error!("fn main() -> int { let x: i8 = 128; 0 }");
+    expect_type_error!("fn main() -> int { let x = 128i8; 0 }");
+    typeck!("fn main() -> int { let x = -128i8; let y: i64 = -9223372036854775808; 0 }");
+    // unconstrained literals fall back to `int` before they are checked
+    expect_type_error!("fn main() -> int { let x = 9223372036854775809; 0 }");
+}
+
+#[test]
+fn check_unsuffixed_int_literal_is_inferred() {
+    let lines = lines!(typeck_expr!("let x = 5u8; let y = x + 1; 0"));
+    assert_eq!(lines[0], "let x:u8 = 5:u8;");
+    assert_eq!(lines[1], "let y:u8 = (+ x:u8 1:u8):u8;");
+}
+
+#[test]
+fn check_unconstrained_numeric_literals_fallback() {
+    let lines = lines!(typeck_expr!("let x = 5; let y = 2.5; 0"));
+    assert_eq!(lines[0], "let x:int = 5:int;");
+    assert_eq!(lines[1], "let y:float = 2.5:float;");
+}
+
+#[test]
+fn check_numeric_literal_kind_mismatch() {
+    expect_type_error!("fn main() -> int { let x: float = 5; 0 }");
+    expect_type_error!("fn main() -> int { let x: u16 = 5.0; 0 }");
+    expect_type_error!("fn main() -> int { let x = 1 + 1.0; 0 }");
+}
+
+#[test]
+fn check_binop_on_non_numeric_type() {
+    expect_type_error!("fn main() -> int { let x = true + false; 0 }");
+    expect_type_error!(r#"fn main() -> int { let x = "a" < "b"; 0 }"#);
+}
+
+#[test]
+fn check_comparison_operators() {
+    let lines = lines!(typeck_expr!("let x = 5u8 <= 6; let y = 'a' == 'b'; 0"));
+    assert_eq!(lines[0], "let x:bool = (<= 5:u8 6:u8):bool;");
+    assert_eq!(lines[1], "let y:bool = (== 'a':char 'b':char):bool;");
+}
+
+#[test]
+fn check_bitwise_operators() {
+    let lines = lines!(typeck_expr!("let x = 5u16 << 2 ^ 3; let y = true & false; 0"));
+    assert_eq!(lines[0], "let x:u16 = (^ (<< 5:u16 2:u16):u16 3:u16):u16;");
+    assert_eq!(lines[1], "let y:bool = (& true:bool false:bool):bool;");
+    expect_type_error!("fn main() -> int { let x = 1.0 % 2.0 >> 1; 0 }");
+    expect_type_error!("fn main() -> int { let x = 1.0 | 2.0; 0 }");
+}
+
+#[test]
+fn check_short_circuiting_operators_require_bool() {
+    typeck!("fn main() -> int { let x = true && false || 1 < 2; 0 }");
+    expect_type_error!("fn main() -> int { let x = 1 && 2; 0 }");
+}
+
+#[test]
+fn check_compound_assignment() {
+    let lines = lines!(typeck_expr!("let mut x = 5u8; x += 1; x <<= 2; 0"));
+    assert!(lines[1].starts_with("(x:u8 += 1:u8)"));
+    assert!(lines[2].starts_with("(x:u8 <<= 2:u8)"));
+    expect_type_error!("fn main() -> int { let mut x = 5u8; x += 1i32; 0 }");
+    expect_type_error!("fn main() -> int { let mut b = true; b += false; 0 }");
+    expect_type_error!("fn main() -> int { 5 += 1; 0 }");
+}
+
+#[test]
+fn check_index_non_array() {
+    expect_type_error!("fn main() -> int { let x = 5; x[0] }");
+    expect_type_error!("fn main() -> int { let t = (1, 2); t[0] }");
+    expect_type_error!(r#"fn main() -> int { let s = "abc"; s[0]; 0 }"#);
+}
+
+#[test]
+fn check_index_with_integer_types() {
+    typeck!("fn main() -> int { let xs = [1, 2]; let i: u8 = 1; xs[i] + xs[0u64] }");
+    expect_type_error!("fn main() -> int { let xs = [1, 2]; xs[true] }");
+    expect_type_error!("fn main() -> int { let xs = [1, 2]; xs[1.0] }");
+}
+
+#[test]
+fn check_array_exprs() {
+    let lines = lines!(typeck_expr!("let xs = [1u8, 2, 3]; let ys = [true; 4]; 0"));
+    assert_eq!(lines[0], "let xs:[u8;3] = [1:u8,2:u8,3:u8]:[u8;3];");
+    assert_eq!(lines[1], "let ys:[bool;4] = [true:bool; 4]:[bool;4];");
+    typeck!("fn main() -> int { let xs: [int; 0] = []; let ys: [[int; 2]; 1] = [[1, 2]]; 0 }");
+    expect_type_error!("fn main() -> int { let xs = [1, 'a']; 0 }");
+    expect_type_error!("fn main() -> int { let xs: [int; 2] = [0; 3]; 0 }");
+}
+
+#[test]
+fn check_array_index() {
+    let lines = lines!(typeck_expr!("let xs = [1u8, 2]; let x = xs[1]; 0"));
+    assert_eq!(lines[1], "let x:u8 = xs:[u8;2][1:int]:u8;");
+    expect_type_error!("fn main() -> int { let xs = [1, 2]; xs[true] }");
+}
@@ -0,0 +1,128 @@
+use super::*;
+
+#[test]
+fn check_method_call() {
+    let src = r#"
+    struct S { x: int };
+
+    impl S {
+        fn by_value(self, y: int) -> int { self.x + y }
+        fn by_ref(&self) -> int { self.x }
+    }
+
+    fn main() -> int {
+        let s = S { x: 5 };
+        s.by_value(2) + s.by_ref()
+    }
+    "#;
+    typeck!(src);
+}
+
+#[test]
+fn check_method_call_receiver_autoderef() {
+    let src = r#"
+    struct S { x: int };
+
+    impl S {
+        fn by_value(self) -> int { self.x }
+        fn by_ref(&self) -> int { self.x }
+    }
+
+    fn main() -> int {
+        let s = box box S { x: 5 };
+        s.by_value() + s.by_ref()
+    }
+    "#;
+    typeck!(src);
+}
+
+#[test]
+fn check_generic_method_call() {
+    let src = r#"
+    struct S<T> { t: T };
+
+    impl<T> S<T> {
+        fn get(&self) -> T { self.t }
+    }
+
+    fn main() -> int {
+        let s = S { t: false };
+        let b: bool = s.get();
+        0
+    }
+    "#;
+    typeck!(src);
+}
+
+#[test]
+fn check_method_call_arg_mismatch() {
+    let src = r#"
+    struct S;
+
+    impl S {
+        fn f(self, x: int) -> int { x }
+    }
+
+    fn main() -> int {
+        S.f(false)
+    }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_assoc_fn_is_not_a_method() {
+    let src = r#"
+    struct S;
+
+    impl S {
+        fn new() -> S { S }
+    }
+
+    fn main() -> int {
+        S.new();
+        0
+    }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_ambiguous_method_call() {
+    let src = r#"
+    struct S;
+
+    impl S {
+        fn f(self) -> int { 0 }
+    }
+
+    impl S {
+        fn f(&self) -> int { 1 }
+    }
+
+    fn main() -> int {
+        S.f()
+    }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_inherent_impl_of_primitive() {
+    expect_type_error!("impl int { fn f(self) -> int { self } } fn main() -> int { 0 }");
+    expect_type_error!("impl (int, bool) {} fn main() -> int { 0 }");
+}
+
+#[test]
+fn check_inherent_impl_of_trait_object() {
+    let src = r#"
+    trait T {}
+
+    impl dyn T {
+        fn f(&self) -> int { 0 }
+    }
+
+    fn main() -> int { 0 }
+    "#;
+    expect_type_error!(src);
+}
@@ -5,9 +5,13 @@ mod enum_tests;
 mod fn_tests;
 mod general_tests;
 mod generics_tests;
+mod method_tests;
 mod parametric_tests;
 mod pattern_tests;
+mod package_tests;
+mod privacy_tests;
 mod struct_tests;
+mod trait_tests;
 
 use itertools::Itertools;
 
@@ -0,0 +1,125 @@
+/// the library of the dependency `dep` that the tests below refer to
+const DEP_SRC: &str = r#"
+pub trait Trait {}
+
+pub trait Required {
+    fn required(self) -> int;
+}
+
+pub struct S;
+
+pub fn s() -> S { S }
+
+impl S {
+    pub fn public(self) -> int { 0 }
+    fn private(self) -> int { 0 }
+}
+"#;
+
+macro typeck_with_dep($src:expr) {{
+    let driver = lc_driver::Driver::from_src_with_dependencies($src, &[("dep", DEP_SRC)]);
+    driver.check().unwrap();
+}}
+
+macro expect_type_error_with_dep($src:expr) {{
+    let driver = lc_driver::Driver::from_src_with_dependencies($src, &[("dep", DEP_SRC)]);
+    driver.check().unwrap_err();
+}}
+
+#[test]
+fn impl_dependency_trait_for_local_type() {
+    typeck_with_dep!(
+        r#"
+    struct T;
+
+    impl dep::Trait for T {}
+
+    fn main() -> int { 0 }"#
+    );
+}
+
+#[test]
+fn impl_local_trait_for_dependency_type() {
+    typeck_with_dep!(
+        r#"
+    trait Local {}
+
+    impl Local for dep::S {}
+    impl Local for int {}
+
+    fn main() -> int { 0 }"#
+    );
+}
+
+#[test]
+fn orphan_impl_of_dependency_trait() {
+    expect_type_error_with_dep!(
+        r#"
+    impl dep::Trait for dep::S {}
+
+    fn main() -> int { 0 }"#
+    );
+    expect_type_error_with_dep!(
+        r#"
+    impl dep::Trait for int {}
+
+    fn main() -> int { 0 }"#
+    );
+}
+
+#[test]
+fn inherent_impl_of_dependency_type() {
+    expect_type_error_with_dep!(
+        r#"
+    impl dep::S {
+        fn f(self) -> int { 0 }
+    }
+
+    fn main() -> int { 0 }"#
+    );
+}
+
+#[test]
+fn call_dependency_inherent_methods() {
+    typeck_with_dep!(
+        r#"
+    fn main() -> int { dep::s().public() + dep::S::public(dep::s()) }"#
+    );
+    expect_type_error_with_dep!(
+        r#"
+    fn main() -> int { dep::s().private() }"#
+    );
+}
+
+#[test]
+fn impl_of_dependency_trait_missing_item() {
+    expect_type_error_with_dep!(
+        r#"
+    struct T;
+
+    impl dep::Required for T {}
+
+    fn main() -> int { 0 }"#
+    );
+}
+
+#[test]
+fn prelude_traits_across_packages() {
+    // the derived impls of `dep` implement the same traits as those of this package
+    let dep_src = r#"
+    #[derive(Eq, Clone)]
+    pub struct P;
+
+    pub fn dup<T: Clone>(t: T) -> T { t.clone() }"#;
+
+    let src = r#"
+    fn eq<T: Eq>(t: T, u: T) -> bool { t == u }
+
+    fn main() -> int {
+        let p = dep::dup(dep::P);
+        if eq(p, dep::P) && p == p.clone() { 0 } else { 1 }
+    }"#;
+
+    let driver = lc_driver::Driver::from_src_with_dependencies(src, &[("dep", dep_src)]);
+    driver.check().unwrap();
+}
@@ -0,0 +1,71 @@
+/// the source of the module `m` that the tests below access
+const MODULE_SRC: &str = r#"
+pub fn f() -> int { g() }
+
+fn g() -> int { 2 }
+
+pub struct S { pub x: int, y: int }
+
+impl S {
+    pub fn new() -> S { S { x: 1, y: 2 } }
+
+    fn secret(self) -> int { self.y }
+
+    pub fn reveal(self) -> int { self.secret() }
+}
+"#;
+
+macro check_privacy($src:expr) {{
+    let driver = lc_driver::Driver::from_src_with_modules($src, &[("m", MODULE_SRC)]);
+    driver.check().unwrap();
+}}
+
+macro expect_privacy_error($src:expr) {{
+    let driver = lc_driver::Driver::from_src_with_modules($src, &[("m", MODULE_SRC)]);
+    driver.check().unwrap_err();
+}}
+
+#[test]
+fn check_public_items_and_fields() {
+    check_privacy!(
+        r#"
+    mod m;
+
+    fn main() -> int {
+        let s = m::S::new();
+        let m::S { x } = s;
+        m::f() + s.x + x + s.reveal()
+    }"#
+    );
+}
+
+#[test]
+fn check_private_fn() {
+    expect_privacy_error!("mod m; fn main() -> int { m::g() }");
+}
+
+#[test]
+fn check_private_import() {
+    expect_privacy_error!("mod m; use m::g; fn main() -> int { 0 }");
+}
+
+#[test]
+fn check_private_method() {
+    expect_privacy_error!("mod m; fn main() -> int { m::S::new().secret() }");
+    expect_privacy_error!("mod m; fn main() -> int { m::S::secret(m::S::new()) }");
+}
+
+#[test]
+fn check_private_field_access() {
+    expect_privacy_error!("mod m; fn main() -> int { m::S::new().y }");
+}
+
+#[test]
+fn check_private_field_in_struct_expr() {
+    expect_privacy_error!("mod m; fn main() -> int { let s = m::S { x: 1, y: 2 }; 0 }");
+}
+
+#[test]
+fn check_private_field_in_struct_pattern() {
+    expect_privacy_error!("mod m; fn main() -> int { let m::S { y } = m::S::new(); y }");
+}
@@ -0,0 +1,744 @@
+use super::*;
+
+#[test]
+fn check_bound_satisfied_by_impl() {
+    let src = r#"
+    trait Foo {}
+    struct S;
+    impl Foo for S {}
+
+    fn foo<T: Foo>(t: T) -> T { t }
+
+    fn main() -> int {
+        foo(S);
+        0
+    }
+    "#;
+    typeck!(src);
+}
+
+#[test]
+fn check_unsatisfied_bound() {
+    let src = r#"
+    trait Foo {}
+    fn foo<T: Foo>(t: T) -> T { t }
+    fn main() -> int { foo(5) }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_where_clause() {
+    let src = r#"
+    trait Foo {}
+    impl Foo for int {}
+
+    fn foo<T>(t: T) -> T where T: Foo { t }
+
+    fn main() -> int { foo(5) }
+    "#;
+    typeck!(src);
+}
+
+#[test]
+fn check_unsatisfied_where_clause() {
+    let src = r#"
+    trait Foo {}
+    impl Foo for int {}
+
+    fn foo<T>(t: T) -> T where T: Foo { t }
+
+    fn main() -> int {
+        foo(false);
+        0
+    }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_bound_satisfied_by_param_env() {
+    let src = r#"
+    trait Foo {}
+    fn foo<T: Foo>(t: T) -> T { t }
+    fn bar<U: Foo>(u: U) -> U { foo(u) }
+    fn main() -> int { 0 }
+    "#;
+    typeck!(src);
+}
+
+#[test]
+fn check_bound_not_in_param_env() {
+    let src = r#"
+    trait Foo {}
+    fn foo<T: Foo>(t: T) -> T { t }
+    fn bar<U>(u: U) -> U { foo(u) }
+    fn main() -> int { 0 }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_multiple_bounds() {
+    let src = r#"
+    trait Foo {}
+    trait Bar {}
+    impl Foo for int {}
+
+    fn foo<T: Foo + Bar>(t: T) -> T { t }
+
+    fn main() -> int { foo(5) }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_nested_impl_bounds() {
+    let src = r#"
+    trait Foo {}
+    struct W<T> { t: T }
+    impl Foo for int {}
+    impl<T: Foo> Foo for W<T> {}
+
+    fn foo<T: Foo>(t: T) -> T { t }
+
+    fn main() -> int {
+        foo(W { t: W { t: 5 } });
+        0
+    }
+    "#;
+    typeck!(src);
+}
+
+#[test]
+fn check_unsatisfied_nested_impl_bounds() {
+    let src = r#"
+    trait Foo {}
+    struct W<T> { t: T }
+    impl<T: Foo> Foo for W<T> {}
+
+    fn foo<T: Foo>(t: T) -> T { t }
+
+    fn main() -> int {
+        foo(W { t: false });
+        0
+    }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_generic_trait_bound() {
+    let src = r#"
+    trait Into<T> {}
+    impl Into<bool> for int {}
+
+    fn into<T: Into<bool>>(t: T) -> T { t }
+    fn not_into<T: Into<int>>(t: T) -> T { t }
+
+    fn main() -> int {
+        into(5);
+        not_into(5)
+    }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_adt_bounds() {
+    let src = r#"
+    trait Foo {}
+    struct S<T: Foo> { t: T }
+
+    fn main() -> int {
+        S { t: 5 };
+        0
+    }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_impl_bounds_on_method_call() {
+    let src = r#"
+    trait Foo {}
+    struct W<T> { t: T }
+    impl<T: Foo> W<T> {
+        fn get(self) -> T { self.t }
+    }
+
+    fn main() -> int {
+        let w = W { t: 5 };
+        w.get()
+    }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_bound_is_not_trait() {
+    let src = r#"
+    struct S;
+    fn foo<T: S>(t: T) -> T { t }
+    fn main() -> int { 0 }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_bound_overflow() {
+    let src = r#"
+    trait Foo {}
+    impl<T: Foo> Foo for T {}
+
+    fn foo<T: Foo>(t: T) -> T { t }
+
+    fn main() -> int { foo(5) }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_trait_method_call() {
+    let src = r#"
+    trait Foo {
+        fn foo(&self) -> int;
+    }
+    struct S;
+    impl Foo for S {
+        fn foo(&self) -> int { 5 }
+    }
+
+    fn main() -> int { S.foo() }
+    "#;
+    typeck!(src);
+}
+
+#[test]
+fn check_trait_method_call_without_impl() {
+    let src = r#"
+    trait Foo {
+        fn foo(&self) -> int;
+    }
+    struct S;
+
+    fn main() -> int { S.foo() }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_trait_method_call_on_bounded_param() {
+    let src = r#"
+    trait Foo {
+        fn foo(self) -> int;
+    }
+
+    fn foo<T: Foo>(t: T) -> int { t.foo() }
+    fn main() -> int { 0 }
+    "#;
+    typeck!(src);
+}
+
+#[test]
+fn check_trait_method_call_on_unbounded_param() {
+    let src = r#"
+    trait Foo {
+        fn foo(self) -> int;
+    }
+
+    fn foo<T>(t: T) -> int { t.foo() }
+    fn main() -> int { 0 }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_ambiguous_trait_method_call() {
+    let src = r#"
+    trait Foo {
+        fn get(self) -> int;
+    }
+    trait Bar {
+        fn get(self) -> int;
+    }
+    impl Foo for int {
+        fn get(self) -> int { self }
+    }
+    impl Bar for int {
+        fn get(self) -> int { self }
+    }
+
+    fn main() -> int { 5.get() }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_inherent_method_preferred_over_trait_method() {
+    let src = r#"
+    trait Foo {
+        fn get(self) -> bool;
+    }
+    struct S;
+    impl S {
+        fn get(self) -> int { 5 }
+    }
+    impl Foo for S {
+        fn get(self) -> bool { false }
+    }
+
+    fn main() -> int { S.get() }
+    "#;
+    typeck!(src);
+}
+
+#[test]
+fn check_trait_relative_path() {
+    let src = r#"
+    trait Foo {
+        fn foo(self) -> int;
+    }
+    impl Foo for bool {
+        fn foo(self) -> int { 5 }
+    }
+
+    fn main() -> int { Foo::foo(false) }
+    "#;
+    typeck!(src);
+}
+
+#[test]
+fn check_trait_relative_path_unsatisfied() {
+    let src = r#"
+    trait Foo {
+        fn foo(self) -> int;
+    }
+    impl Foo for bool {
+        fn foo(self) -> int { 5 }
+    }
+
+    fn main() -> int { Foo::foo(5) }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_type_relative_path_to_trait_method() {
+    let src = r#"
+    trait Foo {
+        fn foo(self) -> int;
+    }
+    struct S;
+    impl Foo for S {
+        fn foo(self) -> int { 5 }
+    }
+
+    fn main() -> int { S::foo(S) }
+    "#;
+    typeck!(src);
+}
+
+#[test]
+fn check_trait_method_uses_self_type() {
+    let src = r#"
+    trait Foo {
+        fn foo(self) -> int;
+    }
+    struct S;
+    struct T;
+    impl Foo for S {
+        fn foo(self) -> int { 5 }
+    }
+    impl Foo for T {
+        fn foo(self) -> int { 5 }
+    }
+
+    fn main() -> int { S::foo(T) }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_default_method_call() {
+    let src = r#"
+    trait Foo {
+        fn foo(self) -> int;
+        fn bar(self) -> int { self.foo() + 1 }
+    }
+    struct S;
+    impl Foo for S {
+        fn foo(self) -> int { 5 }
+    }
+
+    fn main() -> int { S.bar() }
+    "#;
+    typeck!(src);
+}
+
+#[test]
+fn check_default_method_body_uses_self_type() {
+    let src = r#"
+    trait Foo {
+        fn foo(self) -> int { self }
+    }
+
+    fn main() -> int { 0 }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_missing_required_trait_method() {
+    let src = r#"
+    trait Foo {
+        fn foo(self) -> int;
+        fn bar(self) -> int { 5 }
+    }
+    struct S;
+    impl Foo for S {
+        fn bar(self) -> int { 6 }
+    }
+
+    fn main() -> int { 0 }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_assoc_ty_projection_normalizes() {
+    let src = r#"
+    trait Container {
+        type Item;
+        fn get(self) -> Self::Item;
+    }
+    struct S;
+    impl Container for S {
+        type Item = int;
+        fn get(self) -> Self::Item { 5 }
+    }
+
+    fn main() -> int {
+        let x: <S as Container>::Item = S.get();
+        x + 1
+    }
+    "#;
+    typeck!(src);
+}
+
+#[test]
+fn check_assoc_ty_through_bound() {
+    let src = r#"
+    trait Container {
+        type Item;
+        fn get(self) -> Self::Item;
+    }
+    struct S;
+    impl Container for S {
+        type Item = int;
+        fn get(self) -> int { 5 }
+    }
+
+    fn get<C: Container>(c: C) -> C::Item { c.get() }
+
+    fn main() -> int { get(S) + 1 }
+    "#;
+    typeck!(src);
+}
+
+#[test]
+fn check_assoc_ty_of_param_is_opaque() {
+    let src = r#"
+    trait Container {
+        type Item;
+        fn get(self) -> Self::Item;
+    }
+
+    fn get<C: Container>(c: C) -> int { c.get() }
+
+    fn main() -> int { 0 }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_assoc_ty_mismatch() {
+    let src = r#"
+    trait Container {
+        type Item;
+    }
+    struct S;
+    impl Container for S {
+        type Item = int;
+    }
+
+    fn main() -> int {
+        let x: <S as Container>::Item = false;
+        0
+    }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_missing_assoc_ty() {
+    let src = r#"
+    trait Container {
+        type Item;
+    }
+    struct S;
+    impl Container for S {}
+
+    fn main() -> int { 0 }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_unknown_assoc_ty() {
+    let src = r#"
+    trait Container {
+        type Item;
+    }
+
+    fn get<C: Container>(c: C) -> C::Elem { loop {} }
+
+    fn main() -> int { 0 }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_projection_without_impl() {
+    let src = r#"
+    trait Container {
+        type Item;
+    }
+    struct S;
+
+    fn main() -> int {
+        let x: <S as Container>::Item = 5;
+        x
+    }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_trait_object_coercion() {
+    let src = r#"
+    trait Shape {
+        fn area(&self) -> int;
+    }
+    struct Square { side: int }
+    struct Circle { r: int }
+    impl Shape for Square {
+        fn area(&self) -> int { self.side * self.side }
+    }
+    impl Shape for Circle {
+        fn area(&self) -> int { 3 * self.r * self.r }
+    }
+
+    fn area(shape: &dyn Shape) -> int { shape.area() }
+
+    fn main() -> int {
+        let shapes: [&dyn Shape; 2] = [box Square { side: 2 }, box Circle { r: 1 }];
+        let square: &dyn Shape = box Square { side: 3 };
+        area(box Circle { r: 2 }) + square.area()
+    }
+    "#;
+    typeck!(src);
+}
+
+#[test]
+fn check_trait_object_unsatisfied() {
+    let src = r#"
+    trait Shape {
+        fn area(&self) -> int;
+    }
+    struct S;
+
+    fn main() -> int {
+        let shape: &dyn Shape = box S;
+        0
+    }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_object_unsafe_trait() {
+    let src = r#"
+    trait Scale {
+        fn scale(self, k: int) -> Self;
+    }
+
+    fn f(x: &dyn Scale) {}
+
+    fn main() -> int { 0 }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_overlapping_trait_impls() {
+    let src = r#"
+    trait Trait {}
+    struct S<T> { t: T }
+
+    impl<T> Trait for S<T> {}
+    impl Trait for S<int> {}
+
+    fn main() -> int { 0 }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_disjoint_trait_impls() {
+    let src = r#"
+    trait Trait<U> {}
+    trait Bound {}
+    struct S<T> { t: T }
+
+    impl Bound for int {}
+
+    impl Trait<int> for S<int> {}
+    impl Trait<bool> for S<int> {}
+    impl<T: Bound> Trait<char> for S<T> {}
+    impl Trait<char> for S<bool> {}
+
+    fn main() -> int { 0 }
+    "#;
+    typeck!(src);
+}
+
+#[test]
+fn check_duplicate_inherent_methods() {
+    let src = r#"
+    struct S<T> { t: T }
+
+    impl<T> S<T> {
+        fn f(&self) {}
+    }
+
+    impl S<int> {
+        fn f(&self) {}
+    }
+
+    fn main() -> int { 0 }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_inherent_methods_on_disjoint_impls() {
+    let src = r#"
+    struct S<T> { t: T }
+
+    impl S<int> {
+        fn f(&self) {}
+    }
+
+    impl S<bool> {
+        fn f(&self) {}
+    }
+
+    fn main() -> int { 0 }
+    "#;
+    typeck!(src);
+}
+
+#[test]
+fn check_overloaded_binop() {
+    let src = r#"
+    struct V { x: int, y: int }
+
+    impl Add<V> for V {
+        type Output = V;
+
+        fn add(self, rhs: V) -> V {
+            V { x: self.x + rhs.x, y: self.y + rhs.y }
+        }
+    }
+
+    fn main() -> int {
+        let v = V { x: 1, y: 2 } + V { x: 3, y: 4 };
+        v.x
+    }
+    "#;
+    typeck!(src);
+}
+
+#[test]
+fn check_overloaded_binop_without_impl() {
+    let src = r#"
+    struct S;
+
+    fn main() -> int {
+        S + S;
+        0
+    }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_overloaded_comparison_on_bounded_param() {
+    let src = r#"
+    fn max<T: Ord>(x: T, y: T) -> T {
+        if x < y { y } else { x }
+    }
+
+    fn same<T>(x: T, y: T) -> bool where T: Eq {
+        x == y && !(x != y)
+    }
+
+    fn main() -> int { 0 }
+    "#;
+    typeck!(src);
+}
+
+#[test]
+fn check_overloaded_comparison_on_unbounded_param() {
+    let src = r#"
+    fn same<T>(x: T, y: T) -> bool {
+        x == y
+    }
+
+    fn main() -> int { 0 }
+    "#;
+    expect_type_error!(src);
+}
+
+#[test]
+fn check_bound_on_int_var_after_fallback() {
+    // `{integer}: Trait` is only solved once the literal falls back to `int`
+    let src = r#"
+    trait Trait {}
+    impl Trait for int {}
+
+    fn f<T: Trait>(t: T) -> T { t }
+
+    fn main() -> int { f(5) }
+    "#;
+    typeck!(src);
+}
+
+#[test]
+fn check_bound_on_int_var_inferred_later() {
+    // the impl for `int` must not be taken as proof while the type of `5` is unknown
+    let src = r#"
+    trait Trait {}
+    impl Trait for int {}
+
+    fn f<T: Trait>(t: T) -> T { t }
+
+    fn main() -> int {
+        let x = f(5);
+        let y: i8 = x;
+        0
+    }
+    "#;
+    expect_type_error!(src);
+}
@@ -2,9 +2,15 @@
 //! there are two primary lowering contexts `TyCtx` and `InferCtx`
 //! `InferCtx` allows inference variables, one does not
 
-use ir::{DefId, DefKind, QPath, Res};
-use lc_core::ty::{FnSig, Generics, Subst, Substs, Ty, TyCtx, TyParam, TypeError};
-use lc_span::Span;
+use crate::object_safety::object_safety_violations;
+use ir::{DefId, DefKind, DefNode, QPath, Res};
+use lc_ast::Ident;
+use lc_core::ty::{
+    AssocItemKind, FnSig, Generics, HasTyFlags, Subst, Substs, SubstsRef, TraitRef, Ty, TyCtx,
+    TyParam, TypeError,
+};
+use lc_span::{kw, Span};
+use lc_traits::TraitError;
 
 /// refer to module comments
 pub trait TyConv<'tcx> {
@@ -27,12 +33,17 @@ pub trait TyConv<'tcx> {
                 ret: ret.map(|ty| self.ir_ty_to_ty(ty)).unwrap_or(tcx.types.unit),
             }),
             ir::TyKind::Path(qpath) => self.qpath_to_ty(qpath),
+            ir::TyKind::Dyn(path) => self.path_to_dyn_ty(path),
+            ir::TyKind::Projection(ty, path, ident) => {
+                let self_ty = self.ir_ty_to_ty(ty);
+                match self.path_to_trait_ref(self_ty, path) {
+                    Some(trait_ref) => self.trait_ref_to_projection(trait_ref, *ident),
+                    None => tcx.mk_ty_err(),
+                }
+            }
             ir::TyKind::Tuple(tys) => tcx.mk_tup_iter(tys.iter().map(|ty| self.ir_ty_to_ty(ty))),
             ir::TyKind::Ptr(ty) => tcx.mk_ptr_ty(self.ir_ty_to_ty(ty)),
-            ir::TyKind::Array(_ty) => {
-                // tcx.mk_array_ty(self.ir_ty_to_ty(ty), todo!()),
-                todo!();
-            }
+            ir::TyKind::Array(ty, n) => tcx.mk_array_ty(self.ir_ty_to_ty(ty), *n),
             ir::TyKind::Infer => self.infer_ty(ir_ty.span),
             ir::TyKind::Err => tcx.mk_ty_err(),
         }
@@ -41,8 +52,103 @@ pub trait TyConv<'tcx> {
     fn qpath_to_ty(&self, qpath: &ir::QPath<'tcx>) -> Ty<'tcx> {
         match qpath {
             QPath::Resolved(path) => self.path_to_ty(path),
-            QPath::TypeRelative(_, _) => todo!(),
+            QPath::TypeRelative(ty, segment) => self.assoc_ty_path_to_ty(ty, segment.ident),
+        }
+    }
+
+    /// lowers a type relative path such as `T::Item` to an associated type
+    /// the trait it belongs to is found through the bounds on `T`
+    /// (or the trait or impl that `Self` refers to)
+    fn assoc_ty_path_to_ty(&self, base: &ir::Ty<'tcx>, ident: Ident) -> Ty<'tcx> {
+        let tcx = self.tcx();
+        let self_ty = self.ir_ty_to_ty(base);
+        let res = match base.kind {
+            ir::TyKind::Path(QPath::Resolved(path)) => path.res,
+            _ => return self.emit_ty_err(base.span, TypeError::AmbiguousAssocTy(ident)),
+        };
+
+        let trait_refs = match res {
+            Res::SelfTy { impl_def } => match tcx.defs().get(impl_def) {
+                // within a trait, `Self` is assumed to implement the trait
+                DefNode::Item(ir::Item { kind: ir::ItemKind::Trait { .. }, .. }) => {
+                    let substs = Substs::id_for_def(tcx, impl_def);
+                    vec![TraitRef::from_substs(tcx, impl_def, substs)]
+                }
+                _ => match tcx.impl_trait_ref(impl_def) {
+                    Some(trait_ref) => {
+                        // within an impl, the associated type can be read directly off the impl
+                        if let Some(impl_item) = self
+                            .find_assoc_ty(trait_ref.def_id, ident)
+                            .and_then(|assoc_ty| tcx.impl_item_for_trait_item(impl_def, assoc_ty))
+                        {
+                            return tcx.type_of(impl_item);
+                        }
+                        vec![trait_ref]
+                    }
+                    None => vec![],
+                },
+            },
+            Res::Def(def_id, DefKind::TyParam(..)) => match tcx.defs().get(def_id) {
+                // bounds that are not traits are reported when collecting the predicates
+                DefNode::TyParam(param) => param
+                    .bounds
+                    .iter()
+                    .filter(|bound| matches!(bound.res, Res::Def(_, DefKind::Trait)))
+                    .filter_map(|bound| self.path_to_trait_ref(self_ty, bound))
+                    .collect(),
+                _ => unreachable!(),
+            },
+            Res::Err => return tcx.mk_ty_err(),
+            _ => return self.emit_ty_err(base.span, TypeError::AmbiguousAssocTy(ident)),
+        };
+
+        let mut candidates = trait_refs
+            .into_iter()
+            .filter(|trait_ref| self.find_assoc_ty(trait_ref.def_id, ident).is_some());
+        match (candidates.next(), candidates.next()) {
+            (Some(trait_ref), None) => self.trait_ref_to_projection(trait_ref, ident),
+            (Some(_), Some(_)) =>
+                self.emit_ty_err(ident.span, TypeError::AmbiguousAssocTy(ident)),
+            (None, _) => self.emit_ty_err(ident.span, TypeError::UnknownAssocTy(self_ty, ident)),
+        }
+    }
+
+    /// constructs the projection `<T as Trait>::ident` given the trait ref `T: Trait`
+    fn trait_ref_to_projection(&self, trait_ref: TraitRef<'tcx>, ident: Ident) -> Ty<'tcx> {
+        let tcx = self.tcx();
+        let def_id = match self.find_assoc_ty(trait_ref.def_id, ident) {
+            Some(def_id) => def_id,
+            None =>
+                return self.emit_ty_err(
+                    ident.span,
+                    TypeError::UnknownAssocTy(trait_ref.self_ty, ident),
+                ),
+        };
+        // report projections that can never be normalized as no impl applies
+        let is_concrete = !trait_ref.has_ty_params() && !trait_ref.has_infer_vars();
+        if is_concrete && !trait_ref.contains_err() && tcx.select_impl(trait_ref).is_none() {
+            tcx.sess.emit_error(ident.span, TraitError::Unsatisfied(trait_ref));
+            return tcx.mk_ty_err();
         }
+        let substs = std::iter::once(trait_ref.self_ty).chain(trait_ref.substs);
+        tcx.mk_projection_ty(def_id, tcx.mk_substs(substs))
+    }
+
+    /// finds the associated type named `ident` in the trait with `trait_def_id`
+    fn find_assoc_ty(&self, trait_def_id: DefId, ident: Ident) -> Option<DefId> {
+        self.tcx()
+            .assoc_items(trait_def_id)
+            .iter()
+            .find(|trait_item| {
+                matches!(trait_item.kind, AssocItemKind::Ty { .. }) && trait_item.ident == ident
+            })
+            .map(|trait_item| trait_item.def_id)
+    }
+
+    fn emit_ty_err(&self, span: Span, err: TypeError<'tcx>) -> Ty<'tcx> {
+        let tcx = self.tcx();
+        tcx.sess.emit_error(span, err);
+        tcx.mk_ty_err()
     }
 
     fn def_to_ty(&self, path: &ir::Path<'tcx>, def_id: DefId, def_kind: DefKind) -> Ty<'tcx> {
@@ -50,66 +156,131 @@ pub trait TyConv<'tcx> {
         match def_kind {
             DefKind::TyParam(idx) => tcx.mk_ty_param(def_id, idx, tcx.defs().ident(def_id)),
             DefKind::Struct | DefKind::Enum | DefKind::TypeAlias => {
-                let expected_argc = tcx.generics_of(def_id).params.len();
-                // there should only be generic args in the very last position.
-                // the preceding segments should be a module path
-                // the segments afterwards are type relative
-                let (last, segs) = path.segments.split_last().unwrap();
-                self.ensure_no_generic_args(segs);
-                let generic_args = last.args;
-
-                let emit_err = |argc, err| {
-                    tcx.sess
-                        .build_error(path.span, err)
-                        .labelled_span(
-                            tcx.defs().generics(def_id).span,
-                            format!(
-                                "{} generic parameter{} declared here",
-                                expected_argc,
-                                pluralize!(expected_argc)
-                            ),
-                        )
-                        .labelled_span(
-                            generic_args.map(|args| args.span).unwrap_or(last.ident.span),
-                            format!(
-                                "but {} generic argument{} provided here",
-                                argc,
-                                pluralize!(argc)
-                            ),
-                        )
-                        .emit();
-                    tcx.mk_ty_err()
-                };
-
-                // replace each generic parameter with either the specified
-                // type argument or id generics
-                let substs = match generic_args {
-                    Some(args) => {
-                        let argc = args.args.len();
-                        if argc != expected_argc {
-                            return emit_err(
-                                argc,
-                                TypeError::GenericArgCount(expected_argc, args.args.len()),
-                            );
-                        } else {
-                            tcx.mk_substs(args.args.iter().map(|ty| self.ir_ty_to_ty(ty)))
-                        }
-                    }
-                    // TODO this case below is probably not correct
-                    None if self.allow_infer() => Substs::id_for_def(tcx, def_id),
-                    None if expected_argc == 0 => Substs::empty(),
-                    None => return emit_err(0, TypeError::GenericArgCount(expected_argc, 0)),
+                let substs = match self.path_substs(path, def_id) {
+                    Some(substs) => substs,
+                    None => return tcx.mk_ty_err(),
                 };
                 let ty = tcx.type_of(def_id);
                 ty.subst(tcx, substs)
             }
-            DefKind::Ctor(..) | DefKind::Trait | DefKind::Fn | DefKind::AssocFn | DefKind::Impl =>
-                todo!(),
+            DefKind::Ctor(..)
+            | DefKind::Trait
+            | DefKind::Fn
+            | DefKind::AssocFn
+            | DefKind::AssocTy
+            | DefKind::Impl => todo!(),
             DefKind::Macro | DefKind::Mod | DefKind::Extern | DefKind::Use =>
                 unreachable!("unexpected defkind `{}`", def_kind),
         }
     }
 
+    /// computes the substitutions for the generic parameters of `def_id` using the generic
+    /// arguments of the last segment of `path` (excluding the implicit `Self` of a trait)
+    /// returns `None` if the number of arguments is incorrect (after reporting an error)
+    fn path_substs(&self, path: &ir::Path<'tcx>, def_id: DefId) -> Option<SubstsRef<'tcx>> {
+        let tcx = self.tcx();
+        let is_extern = tcx.defs().is_extern(def_id);
+        // the generics of other packages are only known through `generics_of`
+        let expected_argc = if is_extern {
+            let params = tcx.generics_of(def_id).params;
+            params.iter().filter(|param| param.ident.symbol != kw::USelf).count()
+        } else {
+            tcx.defs().generics(def_id).params.len()
+        };
+        // there should only be generic args in the very last position.
+        // the preceding segments should be a module path
+        // the segments afterwards are type relative
+        let (last, segs) = path.segments.split_last().unwrap();
+        self.ensure_no_generic_args(segs);
+        let generic_args = last.args;
+
+        let emit_err = |argc, err| {
+            let mut diag = tcx.sess.build_error(path.span, err);
+            // the definitions of other packages have no span to point at
+            if !is_extern {
+                diag.labelled_span(
+                    tcx.defs().generics(def_id).span,
+                    format!(
+                        "{} generic parameter{} declared here",
+                        expected_argc,
+                        pluralize!(expected_argc)
+                    ),
+                );
+            }
+            diag.labelled_span(
+                generic_args.map(|args| args.span).unwrap_or(last.ident.span),
+                format!("but {} generic argument{} provided here", argc, pluralize!(argc)),
+            )
+            .emit();
+            None
+        };
+
+        // replace each generic parameter with either the specified
+        // type argument or id generics
+        match generic_args {
+            Some(args) => {
+                let argc = args.args.len();
+                if argc != expected_argc {
+                    emit_err(argc, TypeError::GenericArgCount(expected_argc, args.args.len()))
+                } else {
+                    Some(tcx.mk_substs(args.args.iter().map(|ty| self.ir_ty_to_ty(ty))))
+                }
+            }
+            None if expected_argc == 0 => Some(Substs::empty()),
+            // TODO this case below is probably not correct
+            None if self.allow_infer() => Some(Substs::id_for_def(tcx, def_id)),
+            None => emit_err(0, TypeError::GenericArgCount(expected_argc, 0)),
+        }
+    }
+
+    /// lowers the bound `path` on `self_ty` into a `TraitRef`
+    /// returns `None` if `path` does not refer to a trait (after reporting an error)
+    fn path_to_trait_ref(
+        &self,
+        self_ty: Ty<'tcx>,
+        path: &ir::Path<'tcx>,
+    ) -> Option<TraitRef<'tcx>> {
+        let tcx = self.tcx();
+        match path.res {
+            Res::Def(def_id, DefKind::Trait) => {
+                let substs = self.path_substs(path, def_id)?;
+                Some(TraitRef::new(def_id, self_ty, substs))
+            }
+            Res::Err => None,
+            res => {
+                tcx.sess.emit_error(path.span, TypeError::ExpectedTrait(res));
+                None
+            }
+        }
+    }
+
+    /// lowers the trait `path` of `dyn <path>` into a trait object type
+    /// reporting an error if the trait is not object safe
+    fn path_to_dyn_ty(&self, path: &ir::Path<'tcx>) -> Ty<'tcx> {
+        let tcx = self.tcx();
+        let def_id = match path.res {
+            Res::Def(def_id, DefKind::Trait) => def_id,
+            Res::Err => return tcx.mk_ty_err(),
+            res => return self.emit_ty_err(path.span, TypeError::ExpectedTrait(res)),
+        };
+        let substs = match self.path_substs(path, def_id) {
+            Some(substs) => substs,
+            None => return tcx.mk_ty_err(),
+        };
+
+        let violations = object_safety_violations(tcx, def_id);
+        if !violations.is_empty() {
+            let mut diag =
+                tcx.sess.build_error(path.span, TypeError::ObjectUnsafe(tcx.defs().ident(def_id)));
+            for (span, reason) in violations {
+                diag.labelled_span(span, reason);
+            }
+            diag.emit();
+            return tcx.mk_ty_err();
+        }
+        tcx.mk_dynamic_ty(def_id, substs)
+    }
+
     fn path_to_ty(&self, path: &ir::Path<'tcx>) -> Ty<'tcx> {
         let tcx = self.tcx();
         match path.res {
@@ -128,7 +299,7 @@ pub trait TyConv<'tcx> {
     fn lower_generics(&self, generics: &ir::Generics<'tcx>) -> &'tcx Generics<'tcx> {
         let tcx = self.tcx();
         let params =
-            generics.params.iter().map(|&ir::TyParam { id, index, ident, span, default }| {
+            generics.params.iter().map(|&ir::TyParam { id, index, ident, span, default, .. }| {
                 TyParam { id, span, ident, index, default: default.map(|ty| self.ir_ty_to_ty(ty)) }
             });
         tcx.alloc(Generics { params: tcx.alloc_iter(params) })
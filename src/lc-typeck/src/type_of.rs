@@ -1,7 +1,10 @@
 use crate::TyConv;
-use ir::{DefId, DefNode};
+use ir::{DefId, DefNode, ParamIdx};
+use lc_ast::Ident;
 use lc_core::queries::Queries;
 use lc_core::ty::*;
+use lc_index::Idx;
+use lc_span::kw;
 
 pub fn provide(queries: &mut Queries) {
     *queries = Queries { type_of, fn_sig, ..*queries }
@@ -15,7 +18,9 @@ fn type_of(tcx: TyCtx<'_>, def_id: DefId) -> Ty<'_> {
             ir::ItemKind::Enum(..) | ir::ItemKind::Struct(..) => self::type_of_adt(tcx, def_id),
             ir::ItemKind::TypeAlias(_, ty) => tcx.ir_ty_to_ty(ty),
             ir::ItemKind::Mod(..) | ir::ItemKind::Use(..) | ir::ItemKind::Extern(..) => panic!(),
-            ir::ItemKind::Trait { generics: _, trait_item_refs: _ } => todo!(),
+            // within a trait, `Self` refers to the implicit type parameter of the trait
+            ir::ItemKind::Trait { .. } =>
+                tcx.mk_ty_param(def_id, ParamIdx::new(0), Ident::new(item.span, kw::USelf)),
             ir::ItemKind::Impl { generics: _, trait_path: _, self_ty, impl_item_refs: _ } =>
                 tcx.ir_ty_to_ty(self_ty),
         },
@@ -23,9 +28,15 @@ fn type_of(tcx: TyCtx<'_>, def_id: DefId) -> Ty<'_> {
             self::type_of_variant(tcx, variant),
         ir::DefNode::ImplItem(item) => match item.kind {
             ir::ImplItemKind::Fn(..) => tcx.mk_fn_ptr(tcx.fn_sig(def_id)),
+            ir::ImplItemKind::TyAlias(ty) => tcx.ir_ty_to_ty(ty),
         },
         ir::DefNode::TraitItem(item) => match item.kind {
             ir::TraitItemKind::Fn(..) => tcx.mk_fn_ptr(tcx.fn_sig(def_id)),
+            // the type of an associated type is its default
+            // or otherwise the (unnormalizable) projection `<Self as Trait>::Item`
+            ir::TraitItemKind::TyAlias(Some(ty)) => tcx.ir_ty_to_ty(ty),
+            ir::TraitItemKind::TyAlias(None) =>
+                tcx.mk_projection_ty(def_id, Substs::id_for_def(tcx, item.trait_def_id)),
         },
         ir::DefNode::ForeignItem(item) => match item.kind {
             ir::ForeignItemKind::Fn(..) => tcx.mk_fn_ptr(tcx.fn_sig(def_id)),
@@ -44,9 +55,11 @@ fn fn_sig(tcx: TyCtx<'_>, def_id: DefId) -> FnSig<'_> {
         },
         DefNode::ImplItem(impl_item) => match impl_item.kind {
             ir::ImplItemKind::Fn(sig, ..) => tcx.lower_fn_sig(sig),
+            ir::ImplItemKind::TyAlias(..) => panic!("associated types have no fn sig"),
         },
         DefNode::TraitItem(trait_item) => match trait_item.kind {
             ir::TraitItemKind::Fn(sig, _) => tcx.lower_fn_sig(sig),
+            ir::TraitItemKind::TyAlias(..) => panic!("associated types have no fn sig"),
         },
         DefNode::ForeignItem(foreign_item) => match foreign_item.kind {
             ir::ForeignItemKind::Fn(sig, ..) => tcx.lower_fn_sig(sig),
@@ -2,22 +2,32 @@
 //! variables with their actual values
 
 use crate::FnCtx;
-use lc_core::ty::{InferVarSubstsFolder, TypeFoldable, TypeckTables};
+use lc_ast::{Lit, UnaryOp};
+use lc_core::ty::{
+    InferSubsts, InferVarSubstsFolder, TyKind, TypeError, TypeFoldable, TypeckTables,
+};
+use lc_infer::Obligation;
+use lc_traits::TraitSolver;
 
 impl<'a, 'tcx> FnCtx<'a, 'tcx> {
     /// constructs a new typeck table with all inference variables replaced by their actual types
     pub fn resolve_inference_variables(
         &self,
-        _body: &'tcx ir::Body<'tcx>,
+        body: &'tcx ir::Body<'tcx>,
     ) -> &'tcx TypeckTables<'tcx> {
         let wbctx = WritebackCtx::new(self);
+        // unsuffixed literals can only be checked to fit their type once it is known
+        ir::Visitor::visit_body(&mut LiteralChecker { wbctx: &wbctx }, body);
+        // the obligations are only solved once the types they refer to are fully known
+        let obligations = wbctx.resolve_obligations();
+        TraitSolver::new(self.infcx, wbctx.tables.def_id()).solve_obligations(&obligations);
         self.tcx.arena.alloc(wbctx.tables)
     }
 }
 
 struct WritebackCtx<'a, 'tcx> {
-    #[allow(unused)]
     fcx: &'a FnCtx<'a, 'tcx>,
+    substs: InferSubsts<'tcx>,
     tables: TypeckTables<'tcx>,
 }
 
@@ -27,7 +37,58 @@ impl<'a, 'tcx> WritebackCtx<'a, 'tcx> {
         let mut subst_folder = InferVarSubstsFolder::new(fcx.tcx, substs);
 
         let tables = fcx.tables.borrow();
-        let tables = tables.fold_with(&mut subst_folder);
-        Self { fcx, tables }
+        // projections that only became concrete once their inference variables were resolved
+        // are normalized now
+        let tables = fcx.tcx.normalize(tables.fold_with(&mut subst_folder));
+        Self { fcx, substs, tables }
+    }
+
+    /// takes the obligations registered during typechecking and substitutes their inference
+    /// variables
+    fn resolve_obligations(&self) -> Vec<Obligation<'tcx>> {
+        let mut subst_folder = InferVarSubstsFolder::new(self.fcx.tcx, self.substs);
+        self.fcx
+            .take_obligations()
+            .into_iter()
+            .map(|obligation| Obligation {
+                predicate: obligation.predicate.fold_with(&mut subst_folder),
+                ..obligation
+            })
+            .collect()
+    }
+}
+
+struct LiteralChecker<'a, 'b, 'tcx> {
+    wbctx: &'b WritebackCtx<'a, 'tcx>,
+}
+
+impl<'a, 'b, 'tcx> LiteralChecker<'a, 'b, 'tcx> {
+    /// signed literals may only be one larger than the maximum of their type if they are negated
+    /// as in `-128i8` (this cannot be checked during parsing for suffixed literals as the `-` is
+    /// parsed separately)
+    fn check_int_lit(&self, expr: &'tcx ir::Expr<'tcx>, value: u64, negated: bool) {
+        let ty = self.wbctx.tables.node_type(expr.id);
+        let max = match ty.kind {
+            TyKind::Int(int_ty) => (1 << (int_ty.bit_width() - 1)) - !negated as u64,
+            TyKind::Uint(uint_ty) => u64::MAX >> (64 - uint_ty.bit_width()),
+            _ => u64::MAX,
+        };
+        if value > max {
+            self.wbctx.fcx.tcx.sess.emit_error(expr.span, TypeError::LiteralOutOfRange(ty));
+        }
+    }
+}
+
+impl<'a, 'b, 'tcx> ir::Visitor<'tcx> for LiteralChecker<'a, 'b, 'tcx> {
+    fn visit_expr(&mut self, expr: &'tcx ir::Expr<'tcx>) {
+        match expr.kind {
+            ir::ExprKind::Unary(UnaryOp::Neg, operand) =>
+                if let ir::ExprKind::Lit(Lit::Int(value, _)) = operand.kind {
+                    return self.check_int_lit(operand, value, true);
+                },
+            ir::ExprKind::Lit(Lit::Int(value, _)) => self.check_int_lit(expr, value, false),
+            _ => {}
+        }
+        ir::walk_expr(self, expr)
     }
 }